@@ -1,3 +1,5 @@
 fn main() {
+    // 창 없는 빌드(`--no-default-features`)에는 Tauri 설정이 필요 없습니다.
+    #[cfg(feature = "gui")]
     tauri_build::build()
 }
@@ -0,0 +1,157 @@
+use crate::i18n::Message;
+use crate::image_engine::StampSettingsInput;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+pub const CONFIG_FILE_NAME: &str = "cornerbrand.config.json";
+
+/// 프론트엔드가 보내는 설정 객체(camelCase)입니다. 빠진 필드는 앱 설정의 기본값으로 채웁니다.
+pub type PartialSettings = Map<String, Value>;
+
+static CURRENT_CONFIG: OnceLock<Mutex<AppConfig>> = OnceLock::new();
+
+/// 명령마다 설정을 다 보내지 않아도 되도록 앱 설정 폴더의 `cornerbrand.config.json`에 두는 기본값입니다.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AppConfig {
+    pub position: Option<String>,
+    pub size_preset: Option<String>,
+    pub size_percent: Option<f32>,
+    pub margin_percent: Option<f32>,
+    /// 명령에서 `output_dir`을 비웠을 때 쓸 출력 기준 폴더입니다.
+    pub output_dir: Option<String>,
+}
+
+impl AppConfig {
+    fn setting_defaults(&self) -> PartialSettings {
+        let mut defaults = Map::new();
+        let mut put = |key: &str, value: Option<Value>| {
+            if let Some(value) = value {
+                defaults.insert(key.to_string(), value);
+            }
+        };
+        put("position", self.position.clone().map(Value::from));
+        put("sizePreset", self.size_preset.clone().map(Value::from));
+        put("sizePercent", self.size_percent.map(Value::from));
+        put("marginPercent", self.margin_percent.map(Value::from));
+        defaults
+    }
+}
+
+/// 설정 파일을 읽습니다. 파일이 없으면 빈 설정입니다.
+pub fn load_config(config_dir: &Path) -> Result<AppConfig, Message> {
+    let path = config_path(config_dir);
+    if !path.exists() {
+        return Ok(AppConfig::default());
+    }
+    let bytes =
+        fs::read(&path).map_err(|e| Message::new("CONFIG_READ_FAILED").arg(e.to_string()))?;
+    serde_json::from_slice(&bytes).map_err(|e| Message::new("CONFIG_MALFORMED").arg(e.to_string()))
+}
+
+pub fn save_config(config_dir: &Path, config: &AppConfig) -> Result<(), Message> {
+    fs::create_dir_all(config_dir)
+        .map_err(|e| Message::new("SETTINGS_DIR_CREATE_FAILED").arg(e.to_string()))?;
+    let payload = serde_json::to_vec_pretty(config)
+        .map_err(|e| Message::new("CONFIG_SERIALIZE_FAILED").arg(e.to_string()))?;
+    fs::write(config_path(config_dir), payload)
+        .map_err(|e| Message::new("CONFIG_SAVE_FAILED").arg(e.to_string()))
+}
+
+/// 명령이 기본값으로 쓰는 설정입니다. 시작할 때 읽고 `save_config`로 바뀝니다.
+pub fn current() -> AppConfig {
+    current_slot()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+pub fn set_current(config: AppConfig) {
+    *current_slot()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = config;
+}
+
+/// `partial`에 있는 필드는 그대로 두고 빠진 필드만 `config`로 채웁니다. 둘 다 없는 필수 필드(`position` 등)는 오류입니다.
+pub fn merge_settings(
+    config: &AppConfig,
+    partial: PartialSettings,
+) -> Result<StampSettingsInput, Message> {
+    let mut merged = config.setting_defaults();
+    merged.extend(partial);
+    serde_json::from_value(Value::Object(merged))
+        .map_err(|e| Message::new("SETTINGS_INVALID").arg(e.to_string()))
+}
+
+/// 명령에서 비운 출력 폴더를 설정의 `output_dir`로 채웁니다.
+pub fn output_dir_or_default(config: &AppConfig, output_dir: Option<String>) -> Option<String> {
+    output_dir
+        .filter(|dir| !dir.trim().is_empty())
+        .or_else(|| config.output_dir.clone())
+}
+
+fn current_slot() -> &'static Mutex<AppConfig> {
+    CURRENT_CONFIG.get_or_init(|| Mutex::new(AppConfig::default()))
+}
+
+fn config_path(config_dir: &Path) -> PathBuf {
+    config_dir.join(CONFIG_FILE_NAME)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn partial_settings_inherit_defaults_from_written_config() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-app-config-{nonce}"));
+
+        assert_eq!(
+            load_config(&root).expect("missing config"),
+            AppConfig::default()
+        );
+        let written = AppConfig {
+            position: Some("좌상단".to_string()),
+            size_preset: Some("큼".to_string()),
+            margin_percent: Some(4.0),
+            output_dir: Some("/exports".to_string()),
+            ..Default::default()
+        };
+        save_config(&root, &written).expect("save config");
+        let config = load_config(&root).expect("load config");
+        assert_eq!(config, written);
+
+        let partial = serde_json::json!({ "sizePercent": 20.0 });
+        let settings = merge_settings(&config, partial.as_object().cloned().expect("object"))
+            .expect("merged settings");
+        assert_eq!(settings.position, "좌상단");
+        assert_eq!(settings.size_preset, "큼");
+        assert_eq!(settings.size_percent, Some(20.0));
+        assert_eq!(settings.margin_percent, 4.0);
+
+        let explicit = serde_json::json!({ "position": "우하단" });
+        let settings = merge_settings(&config, explicit.as_object().cloned().expect("object"))
+            .expect("explicit settings");
+        assert_eq!(settings.position, "우하단");
+
+        assert!(merge_settings(&AppConfig::default(), PartialSettings::new()).is_err());
+        assert_eq!(
+            output_dir_or_default(&config, Some(" ".to_string())).as_deref(),
+            Some("/exports")
+        );
+        assert_eq!(
+            output_dir_or_default(&config, Some("/other".to_string())).as_deref(),
+            Some("/other")
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,76 @@
+use image::{Rgba, RgbaImage};
+
+/// 배너 로고를 기울이는 각도(도). 왼쪽 아래에서 오른쪽 위로 올라가도록 반시계 방향으로 돌립니다.
+pub(crate) const BANNER_ANGLE_DEGREES: f64 = 45.0;
+/// `opacity`를 비워 두었을 때 배너에 쓰는 불투명도입니다.
+pub(crate) const BANNER_OPACITY: f32 = 0.3;
+/// 돌린 로고의 외곽 상자가 캔버스(페이지)의 가로·세로 중 이만큼을 넘지 않도록 맞춥니다.
+pub(crate) const BANNER_SPAN: f64 = 0.9;
+
+/// `BANNER_ANGLE_DEGREES`만큼 돌린 로고를 감싸는 상자의 크기입니다.
+pub(crate) fn rotated_size((width, height): (u32, u32)) -> (u32, u32) {
+    let (sin, cos) = BANNER_ANGLE_DEGREES.to_radians().sin_cos();
+    let (width, height) = (f64::from(width), f64::from(height));
+    (
+        ((width * cos + height * sin).ceil() as u32).max(1),
+        ((width * sin + height * cos).ceil() as u32).max(1),
+    )
+}
+
+/// 캔버스 가운데에 놓을 때 `(box_width, box_height)` 상자가 `BANNER_SPAN` 안에 들어가는 배율입니다.
+pub(crate) fn fit_scale((width, height): (f64, f64), (box_width, box_height): (f64, f64)) -> f64 {
+    BANNER_SPAN * (width / box_width.max(1.0)).min(height / box_height.max(1.0))
+}
+
+/// 로고를 돌려 `rotated_size` 크기의 새 이미지에 담습니다. 바깥 영역은 투명하고,
+/// 가장자리가 어둡게 번지지 않도록 알파를 곱한 값으로 이중선형 보간합니다.
+pub(crate) fn rotate_logo(logo: &RgbaImage) -> RgbaImage {
+    let (width, height) = logo.dimensions();
+    let (out_width, out_height) = rotated_size((width, height));
+    let (sin, cos) = BANNER_ANGLE_DEGREES.to_radians().sin_cos();
+    let (src_cx, src_cy) = (f64::from(width) / 2.0, f64::from(height) / 2.0);
+    let (dst_cx, dst_cy) = (f64::from(out_width) / 2.0, f64::from(out_height) / 2.0);
+
+    RgbaImage::from_fn(out_width, out_height, |x, y| {
+        // 화면 좌표(아래로 갈수록 y 증가)에서 반시계 회전의 역변환으로 원본 위치를 찾습니다.
+        let dx = f64::from(x) + 0.5 - dst_cx;
+        let dy = f64::from(y) + 0.5 - dst_cy;
+        let sx = dx * cos - dy * sin + src_cx - 0.5;
+        let sy = dx * sin + dy * cos + src_cy - 0.5;
+        sample_bilinear(logo, sx, sy)
+    })
+}
+
+fn sample_bilinear(image: &RgbaImage, x: f64, y: f64) -> Rgba<u8> {
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+    let mut sum = [0.0f64; 4];
+    for (ox, oy, weight) in [
+        (0, 0, (1.0 - fx) * (1.0 - fy)),
+        (1, 0, fx * (1.0 - fy)),
+        (0, 1, (1.0 - fx) * fy),
+        (1, 1, fx * fy),
+    ] {
+        let (px, py) = (x0 as i64 + ox, y0 as i64 + oy);
+        if px < 0 || py < 0 || px >= i64::from(image.width()) || py >= i64::from(image.height()) {
+            continue;
+        }
+        let pixel = image.get_pixel(px as u32, py as u32);
+        let alpha = f64::from(pixel[3]) / 255.0 * weight;
+        for (total, value) in sum.iter_mut().zip(pixel.0).take(3) {
+            *total += f64::from(value) * alpha;
+        }
+        sum[3] += alpha;
+    }
+
+    if sum[3] <= 0.0 {
+        return Rgba([0, 0, 0, 0]);
+    }
+    let color = |channel: usize| (sum[channel] / sum[3]).round().clamp(0.0, 255.0) as u8;
+    Rgba([
+        color(0),
+        color(1),
+        color(2),
+        (sum[3] * 255.0).round().clamp(0.0, 255.0) as u8,
+    ])
+}
@@ -1,25 +1,302 @@
-use crate::image_engine::{self, StampFileResult, StampSettingsInput};
-use crate::path_policy;
+use crate::cancellation::CancellationToken;
+use crate::i18n::{self, Message};
+use crate::image_engine::{
+    self, StampFileResult, StampSettingsInput, CODE_UNSUPPORTED_FORMAT, CODE_UNSUPPORTED_OFFICE,
+    CODE_UNSUPPORTED_VIDEO,
+};
+use crate::memory_gate::{self, MemoryGate, MemoryPermit};
+use crate::path_policy::{self, ExpandedInput, UnsupportedKind};
 use crate::pdf_engine;
-use serde::Serialize;
-use std::collections::BTreeMap;
+use crate::run_log::RunLog;
+use crate::sheet::{self, SheetOptions};
+use crate::sidecar;
+use crate::verify;
+use crate::zip_output;
+use serde::{Deserialize, Serialize};
+use std::collections::{btree_map, BTreeMap, BTreeSet};
+use std::io::{self, BufRead, Read};
 use std::path::{Path, PathBuf};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+pub const CODE_TIMEOUT: &str = "TIMEOUT";
+pub const CODE_OUTPUT_NOT_WRITABLE: &str = "OUTPUT_NOT_WRITABLE";
+pub const CODE_NO_MATCH: &str = "NO_MATCH";
+pub const CODE_CANCELLED: &str = "CANCELLED";
+pub const CODE_DUPLICATE: &str = "DUPLICATE";
+/// `resume_from_report`의 이전 보고서에서 이미 성공한 파일이라 다시 처리하지 않았습니다. 결과는 성공으로 셉니다.
+pub const CODE_RESUMED: &str = "RESUMED";
+/// `resume_from_report`의 보고서를 읽지 못해 배치의 모든 파일을 처리하지 않았습니다.
+pub const CODE_RESUME_FAILED: &str = "RESUME_FAILED";
+/// `cornerbrand_report.json` 형식 버전. 필드 의미가 바뀌거나 빠지면 올립니다. (필드 추가만으로는 올리지 않습니다.)
+pub const REPORT_SCHEMA_VERSION: u32 = 1;
+/// 동시 처리할 때 `memory_budget_mb`를 비워 두면 쓰는 예산입니다.
+pub const DEFAULT_MEMORY_BUDGET_MB: u64 = 1024;
+
+/// 파일 하나가 아니라 배치 전체의 진행 방식을 정하는 옵션입니다.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BatchOptions {
+    /// 파일 하나의 처리 제한 시간(ms). 넘기면 `TIMEOUT`으로 기록하고 다음 파일로 넘어갑니다.
+    pub per_file_timeout_ms: Option<u64>,
+    /// 성공한 파일의 입력→출력 경로만 담은 `cornerbrand_manifest.json`을 보고서 옆에 씁니다.
+    /// ZIP 모드에서는 압축 안의 `cornerbrand_manifest.json` 항목으로 넣습니다.
+    pub write_manifest: bool,
+    /// 실행 기록을 남길 폴더. 프론트엔드가 아니라 앱이 앱 데이터 폴더로 채웁니다.
+    #[serde(skip)]
+    pub log_dir: Option<PathBuf>,
+    /// 입력 경로별 출력 기준 폴더. 지정된 파일은 전역 `output_dir` 대신 이 폴더에 저장되고 보고서도 따라갑니다.
+    pub output_dir_by_path: BTreeMap<String, String>,
+    /// 입력 경로별로 전역 설정 위에 덮어쓸 설정 필드(camelCase). 예: `{"position": "좌상단", "marginPercent": 2}`.
+    /// 적지 않은 필드는 전역 설정을 따르고, 합친 설정이 잘못되면 그 파일만 실패로 기록합니다.
+    pub settings_by_path: BTreeMap<String, serde_json::Map<String, serde_json::Value>>,
+    /// 파일을 이만큼 처리할 때마다 지금까지의 결과로 보고서를 덮어씁니다. 도중에 앱이 꺼져도 기록이 남습니다.
+    pub report_flush_interval: Option<usize>,
+    /// 지정하면 폴더별 `cornerbrand_report.json` 대신 모든 결과를 담은 보고서 하나를 정확히 이 경로에 씁니다.
+    pub report_path_override: Option<String>,
+    /// 보고서를 들여쓰기 없이 한 줄 JSON으로 씁니다. 큰 배치에서 파일 크기를 줄입니다.
+    pub report_compact: bool,
+    /// 취소되면 아직 시작하지 않은 파일을 `CANCELLED`로 기록하고 끝냅니다. 앱은 취소 등록부의 토큰을 넣고,
+    /// 라이브러리로 쓸 때는 직접 만든 토큰의 복제본을 넣어 두고 원본으로 취소합니다.
+    #[serde(skip)]
+    pub cancel_token: Option<CancellationToken>,
+    /// 표기만 다른 같은 파일(심볼릭 링크, `./a.png`와 절대 경로 등)은 처음 나온 것만 처리하고 나머지는 `DUPLICATE`로 건너뜁니다.
+    pub dedupe_inputs: bool,
+    /// `dedupe_inputs`에 더해, 경로가 달라도 내용이 같은 파일을 중복으로 봅니다. 파일을 한 번씩 더 읽습니다.
+    pub dedupe_by_content: bool,
+    /// 동시에 처리할 파일 수. 비우거나 1이면 한 파일씩 차례로 처리합니다.
+    pub max_concurrency: Option<usize>,
+    /// 동시 처리 중인 파일들의 어림 메모리(헤더 크기 기준 RGBA) 합의 상한(MB). 넘으면 앞 파일이 끝날 때까지 기다리며,
+    /// 혼자서도 넘는 파일은 하나씩 처리합니다.
+    pub memory_budget_mb: Option<u64>,
+    /// 결과와 보고서의 오류·경고 메시지 언어("ko" 기본, "en", "ja"). 경로나 숫자 같은 자세한 내용은 그대로 살립니다.
+    pub locale: Option<String>,
+    /// 끄면 보고서(`cornerbrand_report.json`)를 쓰지 않습니다. 기본은 켜짐입니다. 매니페스트는 `write_manifest`만 따릅니다.
+    pub write_report: bool,
+    /// 이전 실행의 `cornerbrand_report.json` 경로. 그 보고서에서 성공했고 결과 파일이 남아 있는 입력은
+    /// 다시 처리하지 않고 `RESUMED`로 기록합니다. 실패했거나 빠진 파일만 처리합니다.
+    /// 보고서를 읽지 못하면 모든 파일을 `RESUME_FAILED`로 남깁니다. ZIP 모드(`output_zip`)와는 함께 쓸 수 없습니다.
+    pub resume_from_report: Option<String>,
+    /// 지정하면 결과를 폴더에 흩어 두지 않고 이 경로의 ZIP 하나로 묶습니다. 입력 폴더 구조를 유지하고
+    /// 보고서를 `cornerbrand_report.json` 항목으로 넣으며, 결과의 `outputPath`와 `webOutputPath`는 압축 안의 항목 이름이 됩니다.
+    /// 이때 `output_dir`과 `output_dir_by_path`는 쓰지 않습니다. 그 경로에 파일이 이미 있으면 덮어쓰지 않고
+    /// 모든 파일을 `OUTPUT_NOT_WRITABLE`로 기록합니다. 매니페스트·사이드카·확인용 시트도 켜져 있으면 압축 안의
+    /// 항목으로 넣습니다. 보고서의 결과 경로가 항목 이름이라 `resume_from_report`와 함께 지정하면
+    /// 처리하지 않고 모든 파일을 `RESUME_FAILED`로 기록합니다.
+    pub output_zip: Option<String>,
+    /// 지정하면 배치가 끝난 뒤 출력 폴더마다 성공한 이미지 결과를 바둑판으로 모은 `sheet_1.png` 등을 씁니다.
+    /// 같은 이름의 파일은 덮어쓰지 않고 번호를 붙입니다. PDF 결과는 넣지 않으며, ZIP 모드에서는 압축 안에 넣습니다.
+    /// 시트를 쓰지 못하면 그 폴더의 성공한 결과에 경고를 남깁니다.
+    pub contact_sheet: Option<SheetOptions>,
+    /// 켜면 파일마다 저장한 결과를 다시 열어(이미지 디코딩, PDF `Document::load`) 읽히는지 확인하고,
+    /// 읽지 못하면 그 파일을 `VERIFY_FAILED` 실패로 기록합니다. 디스크/인코더의 조용한 손상을 잡습니다.
+    pub verify_output: bool,
+    /// 켜면 성공한 결과마다 옆에 `<결과 파일 이름>.json` 사이드카를 써서, 그 파일에 쓴 설정·시각·입력 경로·경고를 남깁니다.
+    /// 보고서와 별개이며 ZIP 모드에서는 결과 항목 옆에 `<항목 이름>.json` 항목으로 넣습니다. 이어서 처리(`RESUMED`)한
+    /// 파일에는 쓰지 않습니다. 이전 실행이 남긴 사이드카가 그대로 있기 때문입니다.
+    pub write_sidecars: bool,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            per_file_timeout_ms: None,
+            write_manifest: false,
+            log_dir: None,
+            output_dir_by_path: BTreeMap::new(),
+            settings_by_path: BTreeMap::new(),
+            report_flush_interval: None,
+            report_path_override: None,
+            report_compact: false,
+            cancel_token: None,
+            dedupe_inputs: false,
+            dedupe_by_content: false,
+            max_concurrency: None,
+            memory_budget_mb: None,
+            locale: None,
+            write_report: true,
+            resume_from_report: None,
+            output_zip: None,
+            contact_sheet: None,
+            verify_output: false,
+            write_sidecars: false,
+        }
+    }
+}
+
+impl BatchOptions {
+    fn output_dir_for<'a>(&'a self, input: &str, default: Option<&'a Path>) -> Option<&'a Path> {
+        self.output_dir_by_path
+            .get(input)
+            .map(|dir| dir.trim())
+            .filter(|dir| !dir.is_empty())
+            .map(Path::new)
+            .or(default)
+    }
+
+    fn settings_for(
+        &self,
+        input: &str,
+        global: &StampSettingsInput,
+    ) -> Result<StampSettingsInput, Message> {
+        let Some(overrides) = self.settings_by_path.get(input) else {
+            return Ok(global.clone());
+        };
+        let mut merged = match serde_json::to_value(global) {
+            Ok(serde_json::Value::Object(fields)) => fields,
+            Ok(_) => return Err(Message::new("GLOBAL_SETTINGS_NOT_OBJECT")),
+            Err(e) => return Err(Message::new("GLOBAL_SETTINGS_INVALID").arg(e.to_string())),
+        };
+        merged.extend(overrides.clone());
+        serde_json::from_value(serde_json::Value::Object(merged))
+            .map_err(|e| Message::new("FILE_SETTINGS_INVALID").arg(e.to_string()))
+    }
+
+    fn output_zip(&self) -> Option<&Path> {
+        self.output_zip
+            .as_deref()
+            .map(str::trim)
+            .filter(|path| !path.is_empty())
+            .map(Path::new)
+    }
+
+    fn report_path_override(&self) -> Option<&Path> {
+        self.report_path_override
+            .as_deref()
+            .map(str::trim)
+            .filter(|path| !path.is_empty())
+            .map(Path::new)
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancel_token
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    }
+}
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 struct BatchReport {
+    schema_version: u32,
     timestamp: u64,
+    /// 중간 저장본이면 `false`입니다. 배치가 끝나면 같은 파일을 `true`로 덮어씁니다.
+    complete: bool,
     settings: StampSettingsInput,
     results: Vec<StampFileResult>,
 }
 
+/// 완료 알림 등에 바로 쓰는 배치 집계입니다. 건너뜀/취소는 실패 수에 넣지 않습니다.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchSummary {
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub cancelled: usize,
+    pub skipped: usize,
+    pub total_duration_ms: u64,
+    /// 결과들의 경고를 처음 나온 순서대로 중복 없이 모은 목록입니다. 시트를 쓰지 못한 경우처럼 여러 파일에 걸친 경고도 한 번만 나옵니다.
+    pub warnings: Vec<String>,
+}
+
+impl BatchSummary {
+    pub fn from_results(results: &[StampFileResult], duration: Duration) -> Self {
+        let mut summary = Self {
+            total: results.len(),
+            total_duration_ms: u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+            ..Default::default()
+        };
+        for result in results {
+            match ProgressStatus::of(result) {
+                ProgressStatus::Ok => summary.succeeded += 1,
+                ProgressStatus::Skipped => summary.skipped += 1,
+                ProgressStatus::Cancelled => summary.cancelled += 1,
+                ProgressStatus::Failed => summary.failed += 1,
+            }
+            for warning in result.warnings.iter().map(Message::to_string) {
+                if !summary.warnings.contains(&warning) {
+                    summary.warnings.push(warning);
+                }
+            }
+        }
+        summary
+    }
+}
+
+/// 파일별 결과와 집계를 함께 돌려주는 형태입니다.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchOutcome {
+    pub summary: BatchSummary,
+    pub results: Vec<StampFileResult>,
+}
+
+impl BatchOutcome {
+    pub fn new(results: Vec<StampFileResult>, duration: Duration) -> Self {
+        Self {
+            summary: BatchSummary::from_results(&results, duration),
+            results,
+        }
+    }
+}
+
+/// 진행 알림 한 건의 결과 분류입니다. 화면이 건너뜀과 실패를 따로 셀 수 있도록 문자열로 보냅니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProgressStatus {
+    Ok,
+    Failed,
+    Skipped,
+    Cancelled,
+}
+
+impl ProgressStatus {
+    /// `BatchSummary`와 같은 기준으로 나눕니다.
+    pub fn of(result: &StampFileResult) -> Self {
+        if result.ok {
+            Self::Ok
+        } else if result.skipped {
+            Self::Skipped
+        } else if result.code.as_deref() == Some(CODE_CANCELLED) {
+            Self::Cancelled
+        } else {
+            Self::Failed
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ProgressUpdate {
     pub total: usize,
     pub done: usize,
     pub input_path: String,
-    pub ok: bool,
+    /// 페이지 단위 알림은 아직 처리 중이므로 항상 `Ok`입니다.
+    pub status: ProgressStatus,
+    /// PDF 페이지 단위 진행 알림일 때만 채워집니다. 이때 `done`은 아직 늘어나지 않습니다.
+    pub current_page: Option<u32>,
+    pub total_pages: Option<u32>,
+}
+
+/// 배치가 끝났을 때 한 번 보내는 알림입니다. 마지막 진행 알림 뒤에 옵니다.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchComplete {
+    pub summary: BatchSummary,
+    /// 취소로 처리하지 않은 파일(`CANCELLED`)이 있는지 여부입니다. `done == total`만으로는 알 수 없습니다.
+    pub cancelled: bool,
+}
+
+impl BatchComplete {
+    /// 취소 여부도 결과에서 셉니다. 마지막 파일을 마친 뒤에 온 취소 요청은 취소로 보지 않습니다.
+    pub fn from_results(results: &[StampFileResult], duration: Duration) -> Self {
+        let summary = BatchSummary::from_results(results, duration);
+        Self {
+            cancelled: summary.cancelled > 0,
+            summary,
+        }
+    }
 }
 
 pub fn stamp_batch(
@@ -27,104 +304,971 @@ pub fn stamp_batch(
     settings: StampSettingsInput,
     logo_path: &Path,
     output_base_dir: Option<&Path>,
+    options: &BatchOptions,
 ) -> Vec<StampFileResult> {
-    stamp_batch_with_progress(paths, settings, logo_path, output_base_dir, &mut |_| {})
+    stamp_batch_with_progress(
+        paths,
+        settings,
+        logo_path,
+        output_base_dir,
+        options,
+        &mut |_| {},
+    )
 }
 
+/// 결과는 항상 입력 순서를 따릅니다. 패턴이 없는 목록이면 `results[i]`가 `paths[i]`의 결과이며,
+/// 프론트엔드는 이 순서로 결과를 입력 목록과 맞춥니다. 처리 방식이 바뀌어도 지켜야 하는 약속입니다.
 pub fn stamp_batch_with_progress(
     paths: &[String],
     settings: StampSettingsInput,
     logo_path: &Path,
     output_base_dir: Option<&Path>,
+    options: &BatchOptions,
     on_progress: &mut dyn FnMut(ProgressUpdate),
 ) -> Vec<StampFileResult> {
-    let total = paths.len();
-    let mut results = Vec::with_capacity(total);
-
-    for (index, input) in paths.iter().enumerate() {
-        let input_path = Path::new(input);
-        let result = if path_policy::detect_supported_image(input_path).is_some() {
-            image_engine::stamp_images(
-                std::slice::from_ref(input),
-                settings.clone(),
-                logo_path,
-                output_base_dir,
-            )
-            .into_iter()
-            .next()
-            .unwrap_or_else(|| unsupported_type_result(input.clone()))
-        } else if path_policy::is_supported_pdf(input_path) {
-            pdf_engine::stamp_pdfs(
-                std::slice::from_ref(input),
-                settings.clone(),
-                logo_path,
-                output_base_dir,
-            )
-            .into_iter()
-            .next()
-            .unwrap_or_else(|| unsupported_type_result(input.clone()))
-        } else {
-            unsupported_type_result(input.clone())
+    stamp_batch_with_events(
+        paths,
+        settings,
+        logo_path,
+        output_base_dir,
+        options,
+        on_progress,
+        &mut |_| {},
+    )
+}
+
+/// `stamp_batch_with_progress`와 같고, 보고서·시트·ZIP까지 마친 뒤 `on_complete`로 집계를 한 번 알립니다.
+pub fn stamp_batch_with_events(
+    paths: &[String],
+    settings: StampSettingsInput,
+    logo_path: &Path,
+    output_base_dir: Option<&Path>,
+    options: &BatchOptions,
+    on_progress: &mut dyn FnMut(ProgressUpdate),
+    on_complete: &mut dyn FnMut(BatchComplete),
+) -> Vec<StampFileResult> {
+    let started = Instant::now();
+    let results = run_batch(
+        paths,
+        settings,
+        logo_path,
+        output_base_dir,
+        options,
+        on_progress,
+    );
+    on_complete(BatchComplete::from_results(&results, started.elapsed()));
+    results
+}
+
+fn run_batch(
+    paths: &[String],
+    settings: StampSettingsInput,
+    logo_path: &Path,
+    output_base_dir: Option<&Path>,
+    options: &BatchOptions,
+    on_progress: &mut dyn FnMut(ProgressUpdate),
+) -> Vec<StampFileResult> {
+    if let Some(zip_path) = options.output_zip() {
+        return stamp_batch_to_zip(paths, settings, logo_path, zip_path, options, on_progress);
+    }
+
+    let entries = path_policy::expand_globs(paths);
+    let files: Vec<String> = entries
+        .iter()
+        .filter_map(|entry| match entry {
+            ExpandedInput::Path(path) => Some(path.clone()),
+            ExpandedInput::Unmatched { .. } => None,
+        })
+        .collect();
+    let timeout = options
+        .per_file_timeout_ms
+        .filter(|ms| *ms > 0)
+        .map(Duration::from_millis);
+    let unwritable = probe_destinations(&files, output_base_dir, options);
+    let duplicates = find_duplicates(&entries, options);
+    let (resumed, resume_error) = match load_resume_report(options) {
+        Ok(done) => (find_resumed(&entries, &done), None),
+        Err(error) => (BTreeMap::new(), Some(error)),
+    };
+    let mut recorder = BatchRecorder {
+        total: entries.len(),
+        slots: vec![None; entries.len()],
+        done: 0,
+        run_log: options.log_dir.as_deref().map(RunLog::new),
+        flush_interval: options.report_flush_interval.filter(|n| *n > 0),
+        report_paths: BTreeMap::new(),
+        settings: &settings,
+        output_base_dir,
+        options,
+        locale: i18n::parse_locale(options.locale.as_deref()),
+        on_progress,
+    };
+
+    // 메모리 몫은 파일 처리가 실제로 끝날 때 돌려줍니다. 제한 시간을 넘긴 작업은 버려진 스레드가 끝날 때까지 쥡니다.
+    let stamp_entry =
+        |index: usize, permit: Option<MemoryPermit>, on_page: &mut dyn FnMut(u32, u32)| {
+            let entry = &entries[index];
+            let input = entry.input();
+            let file_output_dir = options.output_dir_for(input, output_base_dir);
+            let file_settings = options.settings_for(input, &settings);
+            let destination = destination_key(input, file_output_dir);
+            let unwritable = destination.and_then(|key| unwritable.get(&key));
+            match (entry, unwritable, timeout) {
+                _ if options.is_cancelled() => StampFileResult {
+                    input_path: input.clone(),
+                    ok: false,
+                    output_path: None,
+                    error: Some(Message::new(CODE_CANCELLED)),
+                    code: Some(CODE_CANCELLED.to_string()),
+                    ..Default::default()
+                },
+                _ if resume_error.is_some() => StampFileResult {
+                    input_path: input.clone(),
+                    ok: false,
+                    output_path: None,
+                    error: resume_error.clone(),
+                    code: Some(CODE_RESUME_FAILED.to_string()),
+                    ..Default::default()
+                },
+                _ if resumed.contains_key(&index) => StampFileResult {
+                    input_path: input.clone(),
+                    ok: true,
+                    output_path: Some(resumed[&index].clone()),
+                    code: Some(CODE_RESUMED.to_string()),
+                    ..Default::default()
+                },
+                _ if duplicates.contains_key(&index) => StampFileResult {
+                    input_path: input.clone(),
+                    ok: false,
+                    output_path: None,
+                    error: Some(Message::new(CODE_DUPLICATE).arg(duplicates[&index].clone())),
+                    code: Some(CODE_DUPLICATE.to_string()),
+                    skipped: true,
+                    ..Default::default()
+                },
+                (ExpandedInput::Unmatched { reason, .. }, _, _) => StampFileResult {
+                    input_path: input.clone(),
+                    ok: false,
+                    output_path: None,
+                    error: Some(reason.clone()),
+                    code: Some(CODE_NO_MATCH.to_string()),
+                    ..Default::default()
+                },
+                (_, Some(error), _) => StampFileResult {
+                    input_path: input.clone(),
+                    ok: false,
+                    output_path: None,
+                    error: Some(error.clone()),
+                    code: Some(CODE_OUTPUT_NOT_WRITABLE.to_string()),
+                    ..Default::default()
+                },
+                (_, None, timeout) => match (file_settings, timeout) {
+                    (Err(error), _) => StampFileResult {
+                        input_path: input.clone(),
+                        ok: false,
+                        output_path: None,
+                        error: Some(error),
+                        ..Default::default()
+                    },
+                    (Ok(settings), Some(timeout)) => {
+                        let input_owned = input.clone();
+                        let logo_path = logo_path.to_path_buf();
+                        let output_base_dir = file_output_dir.map(Path::to_path_buf);
+                        let abandon = CancellationToken::new();
+                        let cancel = abandon.clone();
+                        run_with_timeout(input, timeout, abandon, on_page, move |on_page| {
+                            let _permit = permit;
+                            stamp_single_with_page_progress(
+                                &input_owned,
+                                settings,
+                                &logo_path,
+                                output_base_dir.as_deref(),
+                                Some(&cancel),
+                                on_page,
+                            )
+                        })
+                    }
+                    (Ok(settings), None) => stamp_single_with_page_progress(
+                        input,
+                        settings,
+                        logo_path,
+                        file_output_dir,
+                        None,
+                        on_page,
+                    ),
+                },
+            }
         };
 
-        on_progress(ProgressUpdate {
-            total,
-            done: index + 1,
-            input_path: input.clone(),
-            ok: result.ok,
+    let workers = options.max_concurrency.unwrap_or(1).max(1);
+    if workers == 1 {
+        for (index, entry) in entries.iter().enumerate() {
+            let started = Instant::now();
+            let input = entry.input();
+            let result = stamp_entry(index, None, &mut |current, total_pages| {
+                recorder.page(input, current, total_pages)
+            });
+            recorder.finish(index, result, started.elapsed());
+        }
+    } else {
+        let gate = MemoryGate::new(
+            options
+                .memory_budget_mb
+                .unwrap_or(DEFAULT_MEMORY_BUDGET_MB)
+                .saturating_mul(1024 * 1024),
+        );
+        let next_index = AtomicUsize::new(0);
+        let (tx, rx) = mpsc::channel();
+        std::thread::scope(|scope| {
+            for _ in 0..workers.min(entries.len()) {
+                let tx = tx.clone();
+                let (entries, gate, next_index, stamp_entry) =
+                    (&entries, &gate, &next_index, &stamp_entry);
+                scope.spawn(move || loop {
+                    let index = next_index.fetch_add(1, Ordering::SeqCst);
+                    let Some(entry) = entries.get(index) else {
+                        break;
+                    };
+                    let cost = match entry {
+                        ExpandedInput::Path(path) => {
+                            memory_gate::estimate_file_bytes(Path::new(path))
+                        }
+                        ExpandedInput::Unmatched { .. } => 0,
+                    };
+                    let permit = gate.acquire(cost);
+                    let started = Instant::now();
+                    let page_tx = tx.clone();
+                    let result = stamp_entry(index, Some(permit), &mut |current, total_pages| {
+                        let _ = page_tx.send(BatchEvent::Page(index, current, total_pages));
+                    });
+                    let _ = tx.send(BatchEvent::Done(index, Box::new(result), started.elapsed()));
+                });
+            }
+            drop(tx);
+
+            for event in rx {
+                match event {
+                    BatchEvent::Page(index, current, total_pages) => {
+                        recorder.page(entries[index].input(), current, total_pages)
+                    }
+                    BatchEvent::Done(index, result, elapsed) => {
+                        recorder.finish(index, *result, elapsed)
+                    }
+                }
+            }
         });
+    }
+
+    let mut results = recorder.into_results();
+    if let Some(sheet_options) = &options.contact_sheet {
+        write_contact_sheets(
+            &mut results,
+            sheet_options,
+            i18n::parse_locale(options.locale.as_deref()),
+        );
+    }
+    results
+}
+
+/// 성공한 결과를 출력 폴더별로 묶어 폴더마다 시트를 쓰고, 쓴 시트 경로를 돌려줍니다.
+/// 시트를 못 써도 성공/실패는 바꾸지 않고, 그 폴더의 성공한 결과에 경고를 남깁니다.
+/// 결과는 이미 언어를 맞췄으므로 경고도 같은 언어로 씁니다.
+fn write_contact_sheets(
+    results: &mut [StampFileResult],
+    options: &SheetOptions,
+    locale: i18n::Locale,
+) -> Vec<PathBuf> {
+    let mut written = Vec::new();
+    let mut by_dir = BTreeMap::<PathBuf, Vec<usize>>::new();
+    for (index, result) in results.iter().enumerate().filter(|(_, result)| result.ok) {
+        if let Some(dir) = result
+            .output_path
+            .as_deref()
+            .and_then(|output| Path::new(output).parent())
+        {
+            by_dir.entry(dir.to_path_buf()).or_default().push(index);
+        }
+    }
+    for (dir, indices) in by_dir {
+        let outputs: Vec<&Path> = indices
+            .iter()
+            .filter_map(|index| results[*index].output_path.as_deref().map(Path::new))
+            .collect();
+        match sheet::write_sheets(&dir, &outputs, options) {
+            Ok(sheets) => written.extend(sheets),
+            Err(error) => {
+                let warning = Message::from(
+                    Message::new("CONTACT_SHEET_FAILED")
+                        .arg(error)
+                        .render(locale),
+                );
+                for index in indices {
+                    results[index].warnings.push(warning.clone());
+                }
+            }
+        }
+    }
+    written
+}
+
+/// 입력 폴더 구조대로 임시 폴더에 처리한 뒤 성공한 결과와 시트·사이드카·매니페스트·보고서를 ZIP 하나로 묶고
+/// 임시 폴더를 지웁니다. 압축에 실패하면 성공했던 파일도 실패로 바꿉니다.
+fn stamp_batch_to_zip(
+    paths: &[String],
+    settings: StampSettingsInput,
+    logo_path: &Path,
+    zip_path: &Path,
+    options: &BatchOptions,
+    on_progress: &mut dyn FnMut(ProgressUpdate),
+) -> Vec<StampFileResult> {
+    let locale = i18n::parse_locale(options.locale.as_deref());
+    let fail_all = |error: Message, code: &str| -> Vec<StampFileResult> {
+        let error = Message::from(error.render(locale));
+        path_policy::expand_globs(paths)
+            .iter()
+            .map(|entry| StampFileResult {
+                input_path: entry.input().clone(),
+                ok: false,
+                error: Some(error.clone()),
+                code: Some(code.to_string()),
+                ..Default::default()
+            })
+            .collect()
+    };
+    // 보고서의 결과 경로가 압축 안의 항목 이름이라 디스크의 결과 파일과 맞춰 볼 수 없습니다.
+    if options.resume_from_report.is_some() {
+        return fail_all(Message::new("RESUME_IN_ZIP"), CODE_RESUME_FAILED);
+    }
+    let zip_file = match zip_output::create_zip(zip_path) {
+        Ok(file) => file,
+        Err(error) => return fail_all(error, CODE_OUTPUT_NOT_WRITABLE),
+    };
+    let staging = zip_output::staging_dir(zip_path);
+    let files: Vec<String> = path_policy::expand_globs(paths)
+        .into_iter()
+        .filter_map(|entry| match entry {
+            ExpandedInput::Path(path) => Some(path),
+            ExpandedInput::Unmatched { .. } => None,
+        })
+        .collect();
+    let root = zip_output::common_input_root(&files);
+
+    // 시트·사이드카·매니페스트·보고서는 압축 안의 경로로 아래에서 직접 만들어 넣습니다.
+    let staged_options = BatchOptions {
+        output_zip: None,
+        contact_sheet: None,
+        write_sidecars: false,
+        write_report: false,
+        write_manifest: false,
+        report_path_override: None,
+        output_dir_by_path: files
+            .iter()
+            .map(|input| {
+                let dir = staging.join(zip_output::relative_input_dir(input, &root));
+                (input.clone(), dir.to_string_lossy().to_string())
+            })
+            .collect(),
+        ..options.clone()
+    };
+    let mut results = stamp_batch_with_progress(
+        paths,
+        settings.clone(),
+        logo_path,
+        None,
+        &staged_options,
+        on_progress,
+    );
+
+    let sheets = match &options.contact_sheet {
+        Some(sheet_options) => write_contact_sheets(&mut results, sheet_options, locale),
+        None => Vec::new(),
+    };
 
-        results.push(result);
+    let mut entries = Vec::new();
+    let mut documents = Vec::new();
+    for result in results.iter_mut().filter(|result| result.ok) {
+        let Some(staged) = result.output_path.as_deref().map(PathBuf::from) else {
+            continue;
+        };
+        if let Some(name) = zip_output::entry_name(&staged, &staging) {
+            result.output_path = Some(name.clone());
+            if let Some(sidecar) = options
+                .write_sidecars
+                .then(|| options.settings_for(&result.input_path, &settings).ok())
+                .flatten()
+            {
+                match sidecar::sidecar_payload(result, &name, &sidecar) {
+                    Ok(payload) => documents.push((format!("{name}.json"), payload)),
+                    Err(e) => result.warnings.push(Message::from(
+                        Message::new("SIDECAR_WRITE_FAILED").arg(e).render(locale),
+                    )),
+                }
+            }
+            entries.push((name, staged));
+        }
+        // 웹용 사본도 임시 폴더와 함께 지워지므로 압축에 넣고 항목 이름으로 바꿉니다.
+        let staged_web = result.web_output_path.take().map(PathBuf::from);
+        if let Some((name, staged_web)) = staged_web.and_then(|staged_web| {
+            zip_output::entry_name(&staged_web, &staging).map(|name| (name, staged_web))
+        }) {
+            result.web_output_path = Some(name.clone());
+            entries.push((name, staged_web));
+        }
     }
 
-    write_reports(settings, &results, output_base_dir);
+    entries.extend(
+        sheets
+            .into_iter()
+            .filter_map(|sheet| zip_output::entry_name(&sheet, &staging).map(|name| (name, sheet))),
+    );
+    if let Some(manifest) = options
+        .write_manifest
+        .then(|| manifest_payload(&root, &results))
+        .flatten()
+    {
+        documents.push((zip_output::MANIFEST_ENTRY_NAME.to_string(), manifest));
+    }
+    if let Some(report) = options
+        .write_report
+        .then(|| report_payload(settings, results.clone(), true, options.report_compact))
+        .flatten()
+    {
+        documents.push((zip_output::REPORT_ENTRY_NAME.to_string(), report));
+    }
+    if let Err(error) = zip_output::write_zip(zip_file, &entries, &documents) {
+        let error = Message::from(error.render(locale));
+        let _ = std::fs::remove_file(zip_path);
+        for result in results.iter_mut().filter(|result| result.ok) {
+            result.ok = false;
+            result.output_path = None;
+            result.web_output_path = None;
+            result.error = Some(error.clone());
+        }
+    }
+    let _ = std::fs::remove_dir_all(&staging);
     results
 }
 
+/// 동시 처리 중 작업 스레드가 진행 상황을 호출 스레드로 보내는 알림입니다.
+enum BatchEvent {
+    Page(usize, u32, u32),
+    Done(usize, Box<StampFileResult>, Duration),
+}
+
+/// 파일별 결과를 입력 순서 자리에 모으고, 실행 기록·중간 보고서·진행 알림을 호출 스레드에서 처리합니다.
+struct BatchRecorder<'a> {
+    total: usize,
+    slots: Vec<Option<StampFileResult>>,
+    done: usize,
+    run_log: Option<RunLog>,
+    flush_interval: Option<usize>,
+    report_paths: BTreeMap<(PathBuf, bool), PathBuf>,
+    settings: &'a StampSettingsInput,
+    output_base_dir: Option<&'a Path>,
+    options: &'a BatchOptions,
+    locale: i18n::Locale,
+    on_progress: &'a mut dyn FnMut(ProgressUpdate),
+}
+
+impl BatchRecorder<'_> {
+    fn page(&mut self, input: &str, current_page: u32, total_pages: u32) {
+        (self.on_progress)(ProgressUpdate {
+            total: self.total,
+            done: self.done,
+            input_path: input.to_string(),
+            status: ProgressStatus::Ok,
+            current_page: Some(current_page),
+            total_pages: Some(total_pages),
+        });
+    }
+
+    fn finish(&mut self, index: usize, mut result: StampFileResult, elapsed: Duration) {
+        if self.options.verify_output {
+            verify::verify_result(&mut result);
+        }
+        // 사이드카·실행 기록·보고서가 모두 요청한 언어로 남도록 먼저 바꿉니다.
+        i18n::localize(&mut result, self.locale);
+        // 이어서 처리한 파일의 사이드카는 이전 실행이 이미 썼습니다.
+        if self.options.write_sidecars && result.code.as_deref() != Some(CODE_RESUMED) {
+            // 파일별 설정이 잘못된 파일은 이미 실패했으므로 여기서는 합친 설정이 있습니다.
+            if let Ok(settings) = self.options.settings_for(&result.input_path, self.settings) {
+                sidecar::write_sidecar(&mut result, &settings);
+                // 사이드카를 쓰지 못했다는 경고도 같은 언어로 바꿉니다.
+                i18n::localize(&mut result, self.locale);
+            }
+        }
+        if let Some(run_log) = &self.run_log {
+            run_log.append(&result, elapsed);
+        }
+
+        let status = ProgressStatus::of(&result);
+        let input_path = result.input_path.clone();
+        self.slots[index] = Some(result);
+        self.done += 1;
+
+        if self
+            .flush_interval
+            .is_some_and(|n| self.done < self.total && self.done.is_multiple_of(n))
+        {
+            self.write_reports(false);
+        }
+
+        (self.on_progress)(ProgressUpdate {
+            total: self.total,
+            done: self.done,
+            input_path,
+            status,
+            current_page: None,
+            total_pages: None,
+        });
+    }
+
+    /// 끝난 파일만 입력 순서대로 보고서에 씁니다.
+    fn write_reports(&mut self, complete: bool) {
+        let manifest_due = complete && self.options.write_manifest;
+        if !self.options.write_report && !manifest_due {
+            return;
+        }
+        let results: Vec<StampFileResult> = self.slots.iter().flatten().cloned().collect();
+        write_reports(
+            self.settings,
+            &results,
+            self.output_base_dir,
+            self.options,
+            &mut self.report_paths,
+            complete,
+        );
+    }
+
+    fn into_results(mut self) -> Vec<StampFileResult> {
+        self.write_reports(true);
+        self.slots.into_iter().flatten().collect()
+    }
+}
+
+/// 중복 입력의 위치와 처음 나온 같은 파일의 입력 경로를 돌려줍니다.
+/// 경로를 정규화하지 못하거나 읽지 못한 파일은 중복으로 보지 않고 평소처럼 처리해 오류를 드러냅니다.
+fn find_duplicates(entries: &[ExpandedInput], options: &BatchOptions) -> BTreeMap<usize, String> {
+    let mut duplicates = BTreeMap::new();
+    if !options.dedupe_inputs && !options.dedupe_by_content {
+        return duplicates;
+    }
+
+    let mut seen_paths: BTreeMap<PathBuf, &String> = BTreeMap::new();
+    let mut seen_contents: BTreeMap<(u64, u64), Vec<&String>> = BTreeMap::new();
+    for (index, entry) in entries.iter().enumerate() {
+        let ExpandedInput::Path(input) = entry else {
+            continue;
+        };
+        let Ok(canonical) = std::fs::canonicalize(input) else {
+            continue;
+        };
+        if let Some(first) = seen_paths.get(&canonical) {
+            duplicates.insert(index, (*first).clone());
+            continue;
+        }
+        seen_paths.insert(canonical, input);
+
+        if options.dedupe_by_content {
+            let Some(key) = content_key(Path::new(input)) else {
+                continue;
+            };
+            // 해시가 같아도 내용을 끝까지 비교해 확인합니다. 같지 않으면 따로 처리할 파일로 남깁니다.
+            let candidates = seen_contents.entry(key).or_default();
+            match candidates
+                .iter()
+                .find(|first| same_contents(Path::new(first.as_str()), Path::new(input)))
+            {
+                Some(first) => {
+                    duplicates.insert(index, (*first).clone());
+                }
+                None => candidates.push(input),
+            }
+        }
+    }
+    duplicates
+}
+
+/// 이전 보고서에서 읽는 필드만 담은 형태입니다. 나머지 필드는 무시합니다.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ResumeReport {
+    results: Vec<ResumeEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ResumeEntry {
+    input_path: String,
+    ok: bool,
+    output_path: Option<String>,
+}
+
+/// 이전 보고서에서 성공한 입력의 정규화한 경로와 결과 파일 경로를 읽습니다. 지정하지 않았으면 빈 목록입니다.
+fn load_resume_report(options: &BatchOptions) -> Result<BTreeMap<PathBuf, String>, Message> {
+    let Some(path) = options
+        .resume_from_report
+        .as_deref()
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+    else {
+        return Ok(BTreeMap::new());
+    };
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| Message::new(CODE_RESUME_FAILED).arg(e.to_string()))?;
+    let report: ResumeReport = serde_json::from_str(&content)
+        .map_err(|e| Message::new("RESUME_REPORT_MALFORMED").arg(e.to_string()))?;
+    Ok(report
+        .results
+        .into_iter()
+        .filter(|entry| entry.ok)
+        .filter_map(|entry| {
+            let canonical = std::fs::canonicalize(&entry.input_path).ok()?;
+            Some((canonical, entry.output_path?))
+        })
+        .collect())
+}
+
+/// 이전에 성공했고 결과 파일이 아직 남아 있는 입력의 위치와 그 결과 파일 경로입니다.
+fn find_resumed(
+    entries: &[ExpandedInput],
+    done: &BTreeMap<PathBuf, String>,
+) -> BTreeMap<usize, String> {
+    if done.is_empty() {
+        return BTreeMap::new();
+    }
+    entries
+        .iter()
+        .enumerate()
+        .filter_map(|(index, entry)| {
+            let ExpandedInput::Path(input) = entry else {
+                return None;
+            };
+            let canonical = std::fs::canonicalize(input).ok()?;
+            let output = done.get(&canonical)?;
+            Path::new(output).is_file().then(|| (index, output.clone()))
+        })
+        .collect()
+}
+
+/// (파일 크기, 내용 해시)입니다. 큰 파일도 통째로 올리지 않도록 조금씩 읽으며 해시합니다.
+/// 64비트 해시라 충돌할 수 있으므로 같은 키는 `same_contents`로 다시 확인합니다.
+fn content_key(path: &Path) -> Option<(u64, u64)> {
+    use std::hash::{DefaultHasher, Hasher};
+
+    let mut file = std::fs::File::open(path).ok()?;
+    let mut hasher = DefaultHasher::new();
+    let mut buffer = vec![0; 64 * 1024];
+    let mut len = 0u64;
+    loop {
+        let read = file.read(&mut buffer).ok()?;
+        if read == 0 {
+            return Some((len, hasher.finish()));
+        }
+        hasher.write(&buffer[..read]);
+        len += read as u64;
+    }
+}
+
+/// 두 파일의 내용을 조금씩 읽어 바이트 단위로 비교합니다. 읽지 못하면 다른 파일로 봅니다.
+fn same_contents(left: &Path, right: &Path) -> bool {
+    let (Ok(left), Ok(right)) = (std::fs::File::open(left), std::fs::File::open(right)) else {
+        return false;
+    };
+    let (mut left, mut right) = (io::BufReader::new(left), io::BufReader::new(right));
+    loop {
+        let (Ok(a), Ok(b)) = (left.fill_buf(), right.fill_buf()) else {
+            return false;
+        };
+        let len = a.len().min(b.len());
+        if a[..len] != b[..len] {
+            return false;
+        }
+        if len == 0 {
+            return a.is_empty() && b.is_empty();
+        }
+        left.consume(len);
+        right.consume(len);
+    }
+}
+
+/// 파일이 저장될 (기준 폴더, 출력 폴더 지정 여부)입니다.
+fn destination_key(input: &str, output_base_dir: Option<&Path>) -> Option<(PathBuf, bool)> {
+    match output_base_dir {
+        Some(dir) => Some((dir.to_path_buf(), true)),
+        None => Path::new(input)
+            .parent()
+            .map(|parent| (parent.to_path_buf(), false)),
+    }
+}
+
+/// 작업을 시작하기 전에 모든 출력 폴더에 쓸 수 있는지 확인하고, 쓸 수 없는 폴더와 사유를 돌려줍니다.
+fn probe_destinations(
+    paths: &[String],
+    output_base_dir: Option<&Path>,
+    options: &BatchOptions,
+) -> BTreeMap<(PathBuf, bool), Message> {
+    let destinations: BTreeSet<(PathBuf, bool)> = paths
+        .iter()
+        .filter_map(|input| destination_key(input, options.output_dir_for(input, output_base_dir)))
+        .collect();
+
+    destinations
+        .into_iter()
+        .filter_map(|(dir, overridden)| {
+            let probe = path_policy::probe_output_dir(&dir, overridden.then_some(dir.as_path()));
+            probe.err().map(|error| ((dir, overridden), error))
+        })
+        .collect()
+}
+
+/// 확장자에 따라 이미지/PDF 엔진으로 한 파일을 처리합니다.
+pub fn stamp_single(
+    input: &str,
+    settings: StampSettingsInput,
+    logo_path: &Path,
+    output_base_dir: Option<&Path>,
+) -> StampFileResult {
+    stamp_single_with_page_progress(
+        input,
+        settings,
+        logo_path,
+        output_base_dir,
+        None,
+        &mut |_, _| {},
+    )
+}
+
+/// `stamp_single`과 같지만 PDF라면 페이지마다 `(현재 페이지, 전체 페이지)`를 알립니다.
+/// `cancel`이 취소되면 결과 파일을 쓰지 않고 실패합니다.
+pub fn stamp_single_with_page_progress(
+    input: &str,
+    settings: StampSettingsInput,
+    logo_path: &Path,
+    output_base_dir: Option<&Path>,
+    cancel: Option<&CancellationToken>,
+    on_page: &mut dyn FnMut(u32, u32),
+) -> StampFileResult {
+    let input_path = Path::new(input);
+    let input = input.to_string();
+    if path_policy::detect_supported_image(input_path).is_some() {
+        image_engine::stamp_images_with_cancel(
+            std::slice::from_ref(&input),
+            settings,
+            logo_path,
+            output_base_dir,
+            cancel,
+        )
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| unsupported_type_result(input))
+    } else if path_policy::is_supported_pdf(input_path) {
+        pdf_engine::stamp_pdfs_with_page_progress(
+            std::slice::from_ref(&input),
+            settings,
+            logo_path,
+            output_base_dir,
+            cancel,
+            &mut |_, current, total| on_page(current, total),
+        )
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| unsupported_type_result(input))
+    } else {
+        unsupported_type_result(input)
+    }
+}
+
+/// 작업을 별도 스레드에서 돌리고 제한 시간 안에 끝나지 않으면 `TIMEOUT` 결과를 돌려줍니다.
+/// 시간이 지난 작업은 멈출 수 없으므로 버려 둔 채 다음 파일로 넘어가되, `abandon`을 취소해
+/// 그 작업이 나중에 결과 파일을 쓰지 못하게 합니다. 작업은 같은 토큰을 엔진에 넘겨야 합니다.
+/// 작업 스레드의 페이지 진행 알림은 채널로 받아 호출한 스레드에서 `on_page`로 전달합니다.
+fn run_with_timeout(
+    input: &str,
+    timeout: Duration,
+    abandon: CancellationToken,
+    on_page: &mut dyn FnMut(u32, u32),
+    work: impl FnOnce(&mut dyn FnMut(u32, u32)) -> StampFileResult + Send + 'static,
+) -> StampFileResult {
+    enum WorkerEvent {
+        Page(u32, u32),
+        Done(StampFileResult),
+    }
+
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let page_tx = tx.clone();
+        let result = work(&mut |current, total| {
+            let _ = page_tx.send(WorkerEvent::Page(current, total));
+        });
+        let _ = tx.send(WorkerEvent::Done(result));
+    });
+
+    let deadline = Instant::now() + timeout;
+    let outcome = loop {
+        match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok(WorkerEvent::Page(current, total)) => on_page(current, total),
+            Ok(WorkerEvent::Done(result)) => break Ok(result),
+            Err(error) => break Err(error),
+        }
+    };
+
+    match outcome {
+        Ok(result) => result,
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            abandon.cancel();
+            // 취소하는 사이에 작업이 끝났다면 그 결과를 씁니다.
+            if let Ok(WorkerEvent::Done(result)) = rx.try_recv() {
+                return result;
+            }
+            StampFileResult {
+                input_path: input.to_string(),
+                ok: false,
+                output_path: None,
+                error: Some(Message::new(CODE_TIMEOUT).arg(timeout.as_millis().to_string())),
+                code: Some(CODE_TIMEOUT.to_string()),
+                ..Default::default()
+            }
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => StampFileResult {
+            input_path: input.to_string(),
+            ok: false,
+            output_path: None,
+            error: Some(Message::new("TASK_PANICKED")),
+            ..Default::default()
+        },
+    }
+}
+
+/// 동영상/오피스 문서처럼 종류를 알 수 있으면 그에 맞는 안내와 코드를 씁니다.
 fn unsupported_type_result(input_path: String) -> StampFileResult {
+    let code = match path_policy::classify_unsupported(Path::new(&input_path)) {
+        Some(UnsupportedKind::Video) => CODE_UNSUPPORTED_VIDEO,
+        Some(UnsupportedKind::Office) => CODE_UNSUPPORTED_OFFICE,
+        None => CODE_UNSUPPORTED_FORMAT,
+    };
     StampFileResult {
         input_path,
         ok: false,
         output_path: None,
-        error: Some("지원하지 않는 파일 형식입니다. (jpg/jpeg/png/webp/pdf)".to_string()),
+        error: Some(Message::new(code)),
+        code: Some(code.to_string()),
+        ..Default::default()
     }
 }
 
+/// 보고서 폴더마다 보고서를 씁니다. `report_paths`에 한 번 정한 경로를 기억해 중간 저장과 최종 저장이 같은 파일을 덮어쓰게 합니다.
+/// 보고서는 `write_report`, 매니페스트는 `write_manifest`를 따로 따르며, 매니페스트는 배치가 끝났을 때(`complete`)만 씁니다.
 fn write_reports(
-    settings: StampSettingsInput,
+    settings: &StampSettingsInput,
     results: &[StampFileResult],
     output_base_dir: Option<&Path>,
+    options: &BatchOptions,
+    report_paths: &mut BTreeMap<(PathBuf, bool), PathBuf>,
+    complete: bool,
 ) {
-    if let Some(output_base_dir) = output_base_dir {
-        let report_path =
-            match path_policy::build_report_path(output_base_dir, Some(output_base_dir)) {
-                Ok(path) => path,
-                Err(_) => return,
-            };
+    let report_override = options.report_path_override();
 
-        write_report_file(settings, results.to_vec(), report_path);
-        return;
+    for (key, group_results) in group_by_report_dir(results, output_base_dir, options) {
+        let (report_dir, overridden) = &key;
+        let output_base_dir = overridden.then_some(report_dir.as_path());
+        if complete && options.write_manifest {
+            if let Ok(manifest_path) = path_policy::build_manifest_path(report_dir, output_base_dir)
+            {
+                write_manifest_file(report_dir, &group_results, manifest_path);
+            }
+        }
+        if !options.write_report || report_override.is_some() {
+            continue;
+        }
+
+        let report_path = match report_paths.entry(key.clone()) {
+            btree_map::Entry::Occupied(entry) => entry.get().clone(),
+            btree_map::Entry::Vacant(entry) => {
+                match path_policy::build_report_path(report_dir, output_base_dir) {
+                    Ok(path) => entry.insert(path).clone(),
+                    Err(_) => continue,
+                }
+            }
+        };
+
+        write_report_file(
+            settings.clone(),
+            group_results,
+            report_path,
+            complete,
+            options.report_compact,
+        );
     }
 
-    let mut grouped = BTreeMap::<PathBuf, Vec<StampFileResult>>::new();
+    if let Some(report_path) = report_override.filter(|_| options.write_report) {
+        if let Some(parent) = report_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            if std::fs::create_dir_all(parent).is_err() {
+                return;
+            }
+        }
+        write_report_file(
+            settings.clone(),
+            results.to_vec(),
+            report_path.to_path_buf(),
+            complete,
+            options.report_compact,
+        );
+    }
+}
+
+/// 파일마다 실제 출력 기준 폴더(경로별 지정 > 전역 지정 > 입력 파일의 상위 폴더)로 결과를 묶습니다.
+/// 키의 `bool`은 출력 폴더를 따로 지정했는지 여부입니다.
+fn group_by_report_dir(
+    results: &[StampFileResult],
+    output_base_dir: Option<&Path>,
+    options: &BatchOptions,
+) -> BTreeMap<(PathBuf, bool), Vec<StampFileResult>> {
+    let mut grouped = BTreeMap::<(PathBuf, bool), Vec<StampFileResult>>::new();
 
     for result in results {
-        let input_path = Path::new(&result.input_path);
-        let parent = match input_path.parent() {
-            Some(parent) => parent.to_path_buf(),
-            None => continue,
+        let output_base_dir = options.output_dir_for(&result.input_path, output_base_dir);
+        let Some(key) = destination_key(&result.input_path, output_base_dir) else {
+            continue;
         };
 
-        grouped.entry(parent).or_default().push(result.clone());
+        grouped.entry(key).or_default().push(result.clone());
     }
 
-    for (parent_dir, group_results) in grouped {
-        let report_path = match path_policy::build_report_path(&parent_dir, None) {
-            Ok(path) => path,
-            Err(_) => continue,
-        };
+    grouped
+}
+
+fn write_manifest_file(base_dir: &Path, results: &[StampFileResult], manifest_path: PathBuf) {
+    if let Some(payload) = manifest_payload(base_dir, results) {
+        let _ = std::fs::write(manifest_path, payload);
+    }
+}
+
+/// 성공한 파일만 `{ 입력: 출력 }`으로 적은 매니페스트입니다. `base_dir` 아래 경로는 상대 경로(`/` 구분)로 적습니다.
+fn manifest_payload(base_dir: &Path, results: &[StampFileResult]) -> Option<Vec<u8>> {
+    let manifest: BTreeMap<String, String> = results
+        .iter()
+        .filter(|result| result.ok)
+        .filter_map(|result| {
+            let output_path = result.output_path.as_deref()?;
+            Some((
+                relative_to(Path::new(&result.input_path), base_dir),
+                relative_to(Path::new(output_path), base_dir),
+            ))
+        })
+        .collect();
+
+    serde_json::to_vec_pretty(&manifest).ok()
+}
 
-        write_report_file(settings.clone(), group_results, report_path);
+fn relative_to(path: &Path, base_dir: &Path) -> String {
+    match path.strip_prefix(base_dir) {
+        Ok(relative) => relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+        Err(_) => path.to_string_lossy().to_string(),
     }
 }
 
@@ -132,19 +1276,34 @@ fn write_report_file(
     settings: StampSettingsInput,
     results: Vec<StampFileResult>,
     report_path: PathBuf,
+    complete: bool,
+    compact: bool,
 ) {
+    if let Some(payload) = report_payload(settings, results, complete, compact) {
+        let _ = std::fs::write(report_path, payload);
+    }
+}
+
+fn report_payload(
+    settings: StampSettingsInput,
+    results: Vec<StampFileResult>,
+    complete: bool,
+    compact: bool,
+) -> Option<Vec<u8>> {
     let report = BatchReport {
+        schema_version: REPORT_SCHEMA_VERSION,
         timestamp: unix_timestamp_seconds(),
+        complete,
         settings,
         results,
     };
 
-    let payload = match serde_json::to_vec_pretty(&report) {
-        Ok(content) => content,
-        Err(_) => return,
+    let payload = if compact {
+        serde_json::to_vec(&report)
+    } else {
+        serde_json::to_vec_pretty(&report)
     };
-
-    let _ = std::fs::write(report_path, payload);
+    payload.ok()
 }
 
 fn unix_timestamp_seconds() -> u64 {
@@ -157,9 +1316,11 @@ fn unix_timestamp_seconds() -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::retry::RetryPolicy;
     use image::RgbaImage;
     use lopdf::{dictionary, Document, Object, Stream};
     use serde_json::Value;
+    use std::cell::RefCell;
     use std::fs;
     use std::path::Path;
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -248,13 +1409,14 @@ mod tests {
             size_preset: "보통".to_string(),
             size_percent: None,
             margin_percent: 2.0,
+            ..Default::default()
         };
 
         let paths = vec![
             input_png.to_string_lossy().to_string(),
             input_pdf.to_string_lossy().to_string(),
         ];
-        let results = stamp_batch(&paths, settings, &logo_png, None);
+        let results = stamp_batch(&paths, settings, &logo_png, None, &BatchOptions::default());
 
         assert_eq!(results.len(), 2);
         assert!(results.iter().all(|r| r.ok), "expected all success");
@@ -303,13 +1465,20 @@ mod tests {
             size_preset: "보통".to_string(),
             size_percent: None,
             margin_percent: 2.0,
+            ..Default::default()
         };
 
         let paths = vec![
             input_png.to_string_lossy().to_string(),
             input_pdf.to_string_lossy().to_string(),
         ];
-        let results = stamp_batch(&paths, settings, &logo_png, Some(&output_override));
+        let results = stamp_batch(
+            &paths,
+            settings,
+            &logo_png,
+            Some(&output_override),
+            &BatchOptions::default(),
+        );
 
         assert_eq!(results.len(), 2);
         assert!(results.iter().all(|r| r.ok), "expected all success");
@@ -354,4 +1523,1639 @@ mod tests {
 
         let _ = fs::remove_dir_all(&root);
     }
+
+    #[test]
+    fn summary_counts_mixed_batch_results() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-batch-summary-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let good_png = root.join("good.png");
+        let good_pdf = root.join("good.pdf");
+        let broken_png = root.join("broken.png");
+        let notes = root.join("notes.txt");
+        let logo_png = root.join("logo.png");
+        write_test_png(&good_png, 32, 32, [200, 200, 200, 255]);
+        write_minimal_two_page_pdf(&good_pdf);
+        fs::write(&broken_png, b"not a png").expect("write broken");
+        fs::write(&notes, b"text").expect("write notes");
+        write_test_png(&logo_png, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            ..Default::default()
+        };
+        let paths: Vec<String> = [&good_png, &good_pdf, &broken_png, &notes]
+            .iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+        let mut results = stamp_batch(&paths, settings, &logo_png, None, &BatchOptions::default());
+        results.push(StampFileResult {
+            input_path: "later.png".to_string(),
+            code: Some(CODE_CANCELLED.to_string()),
+            ..Default::default()
+        });
+        results.push(StampFileResult {
+            input_path: "marked.png".to_string(),
+            skipped: true,
+            ..Default::default()
+        });
+
+        let outcome = BatchOutcome::new(results, Duration::from_millis(1234));
+        assert_eq!(
+            outcome.summary,
+            BatchSummary {
+                total: 6,
+                succeeded: 2,
+                failed: 2,
+                cancelled: 1,
+                skipped: 1,
+                total_duration_ms: 1234,
+                warnings: Vec::new(),
+            }
+        );
+        assert_eq!(outcome.results.len(), 6);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn completion_event_follows_last_progress_event() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-batch-complete-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let logo_png = root.join("logo.png");
+        write_test_png(&logo_png, 8, 8, [255, 0, 0, 255]);
+        let paths: Vec<String> = (0..3)
+            .map(|i| {
+                let path = root.join(format!("photo-{i}.png"));
+                write_test_png(&path, 32, 32, [200, 200, 200, 255]);
+                path.to_string_lossy().to_string()
+            })
+            .collect();
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            ..Default::default()
+        };
+
+        for cancel_after in [None, Some(1)] {
+            let token = CancellationToken::new();
+            let options = BatchOptions {
+                cancel_token: Some(token.clone()),
+                write_report: false,
+                ..Default::default()
+            };
+            let events = RefCell::new(Vec::new());
+            let mut completions = Vec::new();
+            stamp_batch_with_events(
+                &paths,
+                settings.clone(),
+                &logo_png,
+                Some(&root.join("out")),
+                &options,
+                &mut |progress| {
+                    if Some(progress.done) == cancel_after {
+                        token.cancel();
+                    }
+                    events
+                        .borrow_mut()
+                        .push(format!("progress {}", progress.done));
+                },
+                &mut |complete| {
+                    events.borrow_mut().push("complete".to_string());
+                    completions.push(complete);
+                },
+            );
+
+            let events = events.into_inner();
+            assert_eq!(events.last().map(String::as_str), Some("complete"));
+            assert_eq!(events.iter().filter(|e| *e == "complete").count(), 1);
+            assert_eq!(events[events.len() - 2], "progress 3");
+            let complete = &completions[0];
+            assert_eq!(complete.summary.total, 3);
+            match cancel_after {
+                None => {
+                    assert!(!complete.cancelled);
+                    assert_eq!(complete.summary.succeeded, 3);
+                }
+                Some(_) => {
+                    assert!(complete.cancelled);
+                    assert_eq!(complete.summary.succeeded, 1);
+                    assert_eq!(complete.summary.cancelled, 2);
+                }
+            }
+        }
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn cancelled_batch_marks_remaining_files() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-batch-cancel-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let logo_png = root.join("logo.png");
+        write_test_png(&logo_png, 8, 8, [255, 0, 0, 255]);
+        let paths: Vec<String> = (0..3)
+            .map(|i| {
+                let path = root.join(format!("photo-{i}.png"));
+                write_test_png(&path, 32, 32, [200, 200, 200, 255]);
+                path.to_string_lossy().to_string()
+            })
+            .collect();
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            ..Default::default()
+        };
+        let token = CancellationToken::new();
+        let options = BatchOptions {
+            cancel_token: Some(token.clone()),
+            ..Default::default()
+        };
+        let results = stamp_batch_with_progress(
+            &paths,
+            settings,
+            &logo_png,
+            None,
+            &options,
+            &mut |progress| {
+                if progress.current_page.is_none() && progress.done == 1 {
+                    token.cancel();
+                }
+            },
+        );
+
+        assert!(
+            results[0].ok,
+            "first file runs before cancel: {:?}",
+            results[0].error
+        );
+        for result in &results[1..] {
+            assert_eq!(result.code.as_deref(), Some(CODE_CANCELLED));
+            assert!(result.output_path.is_none());
+        }
+        let outcome = BatchOutcome::new(results, Duration::ZERO);
+        assert_eq!(outcome.summary.cancelled, 2);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn library_token_cancelled_mid_batch_marks_the_rest_cancelled() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-batch-token-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let logo_png = root.join("logo.png");
+        write_test_png(&logo_png, 8, 8, [255, 0, 0, 255]);
+        let paths: Vec<String> = (0..4)
+            .map(|i| {
+                let path = root.join(format!("photo-{i}.png"));
+                write_test_png(&path, 32, 32, [200, 200, 200, 255]);
+                path.to_string_lossy().to_string()
+            })
+            .collect();
+
+        // 라이브러리 사용자가 보는 공개 경로(`crate::`의 재수출)로 호출합니다.
+        let token = crate::CancellationToken::new();
+        let options = crate::BatchOptions {
+            cancel_token: Some(token.clone()),
+            write_report: false,
+            ..Default::default()
+        };
+        let results = crate::stamp_batch_with_progress(
+            &paths,
+            StampSettingsInput {
+                position: "우하단".to_string(),
+                size_preset: "보통".to_string(),
+                ..Default::default()
+            },
+            &logo_png,
+            None,
+            &options,
+            &mut |progress| {
+                if progress.current_page.is_none() && progress.done == 2 {
+                    token.cancel();
+                }
+            },
+        );
+
+        assert_eq!(results.len(), 4);
+        assert!(results[..2].iter().all(|result| result.ok));
+        for result in &results[2..] {
+            assert_eq!(result.code.as_deref(), Some(CODE_CANCELLED));
+            assert!(result.output_path.is_none());
+        }
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn complete_event_counts_cancelled_results_not_late_cancel_requests() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-batch-late-cancel-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let logo_png = root.join("logo.png");
+        write_test_png(&logo_png, 8, 8, [255, 0, 0, 255]);
+        let input = root.join("photo.png");
+        write_test_png(&input, 32, 32, [200, 200, 200, 255]);
+
+        let token = CancellationToken::new();
+        let options = BatchOptions {
+            cancel_token: Some(token.clone()),
+            write_report: false,
+            ..Default::default()
+        };
+        let mut complete = None;
+        let results = stamp_batch_with_events(
+            &[input.to_string_lossy().to_string()],
+            StampSettingsInput {
+                position: "우하단".to_string(),
+                size_preset: "보통".to_string(),
+                ..Default::default()
+            },
+            &logo_png,
+            None,
+            &options,
+            // 마지막 파일을 마친 뒤에 온 취소 요청입니다.
+            &mut |progress| {
+                if progress.current_page.is_none() && progress.done == progress.total {
+                    token.cancel();
+                }
+            },
+            &mut |event| complete = Some(event),
+        );
+
+        assert!(results[0].ok, "{:?}", results[0].error);
+        let complete = complete.expect("complete event");
+        assert!(!complete.cancelled);
+        assert_eq!(complete.summary.cancelled, 0);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn resume_from_report_skips_files_that_already_succeeded() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-batch-resume-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let logo_png = root.join("logo.png");
+        write_test_png(&logo_png, 8, 8, [255, 0, 0, 255]);
+        let paths: Vec<String> = ["done.png", "retry.png"]
+            .iter()
+            .map(|name| {
+                let path = root.join(name);
+                write_test_png(&path, 32, 32, [200, 200, 200, 255]);
+                path.to_string_lossy().to_string()
+            })
+            .collect();
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            ..Default::default()
+        };
+        let report_path = root.join("first_report.json");
+        let options = BatchOptions {
+            report_path_override: Some(report_path.to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        let first = stamp_batch(&paths, settings.clone(), &logo_png, None, &options);
+        assert!(first.iter().all(|r| r.ok));
+
+        // 두 번째 파일은 실패한 것으로 고쳐 써서 중단된 배치를 흉내 냅니다.
+        let mut report: Value =
+            serde_json::from_str(&fs::read_to_string(&report_path).expect("report"))
+                .expect("parse report");
+        report["results"][1]["ok"] = Value::Bool(false);
+        fs::write(&report_path, report.to_string()).expect("rewrite report");
+
+        let options = BatchOptions {
+            resume_from_report: Some(report_path.to_string_lossy().to_string()),
+            write_report: false,
+            ..Default::default()
+        };
+        let resumed = stamp_batch(&paths, settings.clone(), &logo_png, None, &options);
+        assert!(resumed[0].ok);
+        assert_eq!(resumed[0].code.as_deref(), Some(CODE_RESUMED));
+        assert_eq!(resumed[0].output_path, first[0].output_path);
+        assert!(resumed[1].ok);
+        assert!(resumed[1].code.is_none());
+        assert_ne!(resumed[1].output_path, first[1].output_path);
+
+        let stamped_done = fs::read_dir(root.join(path_policy::OUTPUT_DIR_NAME))
+            .expect("read dir")
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with("done_"))
+            .count();
+        assert_eq!(stamped_done, 1);
+
+        let options = BatchOptions {
+            resume_from_report: Some(root.join("missing.json").to_string_lossy().to_string()),
+            write_report: false,
+            ..Default::default()
+        };
+        let missing = stamp_batch(&paths, settings, &logo_png, None, &options);
+        assert!(missing
+            .iter()
+            .all(|r| !r.ok && r.error.is_some() && r.code.as_deref() == Some(CODE_RESUME_FAILED)));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn dedupe_inputs_stamps_same_file_once() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-batch-dedupe-{nonce}"));
+        fs::create_dir_all(root.join("sub")).expect("temp dir");
+
+        let logo_png = root.join("logo.png");
+        let photo = root.join("photo.png");
+        let copy = root.join("copy.png");
+        write_test_png(&logo_png, 8, 8, [255, 0, 0, 255]);
+        write_test_png(&photo, 32, 32, [200, 200, 200, 255]);
+        fs::copy(&photo, &copy).expect("copy");
+
+        let paths = vec![
+            photo.to_string_lossy().to_string(),
+            root.join("sub")
+                .join("..")
+                .join(".")
+                .join("photo.png")
+                .to_string_lossy()
+                .to_string(),
+            copy.to_string_lossy().to_string(),
+        ];
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            ..Default::default()
+        };
+        let options = BatchOptions {
+            dedupe_inputs: true,
+            ..Default::default()
+        };
+        let mut statuses = Vec::new();
+        let results = stamp_batch_with_progress(
+            &paths,
+            settings.clone(),
+            &logo_png,
+            None,
+            &options,
+            &mut |update| {
+                if update.current_page.is_none() {
+                    statuses.push(update.status);
+                }
+            },
+        );
+
+        assert!(results[0].ok, "first spelling runs: {:?}", results[0].error);
+        assert!(results[1].skipped);
+        assert_eq!(results[1].code.as_deref(), Some(CODE_DUPLICATE));
+        assert!(results[1].output_path.is_none());
+        assert_eq!(
+            statuses,
+            [
+                ProgressStatus::Ok,
+                ProgressStatus::Skipped,
+                ProgressStatus::Ok
+            ]
+        );
+        assert!(
+            results[2].ok,
+            "copies differ by path only without content dedupe"
+        );
+        let stamped_photos = fs::read_dir(root.join(path_policy::OUTPUT_DIR_NAME))
+            .expect("read dir")
+            .filter_map(Result::ok)
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with("photo_cornerbrand")
+            })
+            .count();
+        assert_eq!(stamped_photos, 1);
+
+        let output_dir = root.join("out");
+        let options = BatchOptions {
+            dedupe_by_content: true,
+            ..Default::default()
+        };
+        let results = stamp_batch(&paths, settings, &logo_png, Some(&output_dir), &options);
+        assert!(results[0].ok);
+        assert_eq!(results[1].code.as_deref(), Some(CODE_DUPLICATE));
+        assert_eq!(results[2].code.as_deref(), Some(CODE_DUPLICATE));
+        assert!(results[2]
+            .error
+            .as_ref()
+            .map(Message::to_string)
+            .unwrap_or_default()
+            .contains("photo.png"));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn same_contents_compares_every_byte() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-batch-same-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let large = vec![7u8; 200 * 1024];
+        let mut changed_tail = large.clone();
+        *changed_tail.last_mut().expect("byte") = 8;
+        let write = |name: &str, bytes: &[u8]| {
+            let path = root.join(name);
+            fs::write(&path, bytes).expect("write");
+            path
+        };
+        let original = write("a.bin", &large);
+        let copy = write("b.bin", &large);
+        let tail = write("c.bin", &changed_tail);
+        let prefix = write("d.bin", &large[..large.len() - 1]);
+
+        assert!(same_contents(&original, &copy));
+        assert_eq!(content_key(&original), content_key(&copy));
+        assert!(!same_contents(&original, &tail));
+        assert!(!same_contents(&original, &prefix));
+        assert!(!same_contents(&original, &root.join("missing.bin")));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn concurrent_batch_with_low_memory_budget_keeps_input_order() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-batch-budget-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let logo_png = root.join("logo.png");
+        write_test_png(&logo_png, 8, 8, [255, 0, 0, 255]);
+        let mut paths: Vec<String> = (0..4)
+            .map(|i| {
+                let path = root.join(format!("photo-{i}.png"));
+                write_test_png(&path, 64 + i * 16, 64, [200, 200, 200, 255]);
+                path.to_string_lossy().to_string()
+            })
+            .collect();
+        paths.insert(2, root.join("missing-*.png").to_string_lossy().to_string());
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            ..Default::default()
+        };
+        // 예산 0MB면 모든 파일이 예산을 넘으므로 작업 스레드가 여럿이어도 하나씩 처리됩니다.
+        let options = BatchOptions {
+            max_concurrency: Some(4),
+            memory_budget_mb: Some(0),
+            ..Default::default()
+        };
+        let mut progress_done = Vec::new();
+        let results = stamp_batch_with_progress(
+            &paths,
+            settings,
+            &logo_png,
+            None,
+            &options,
+            &mut |progress| {
+                if progress.current_page.is_none() {
+                    progress_done.push(progress.done);
+                }
+            },
+        );
+
+        assert_eq!(results.len(), 5);
+        assert_eq!(progress_done, vec![1, 2, 3, 4, 5]);
+        for (result, input) in results.iter().zip(&paths) {
+            assert_eq!(&result.input_path, input);
+        }
+        assert_eq!(results[2].code.as_deref(), Some(CODE_NO_MATCH));
+        assert!(results
+            .iter()
+            .enumerate()
+            .all(|(i, result)| i == 2 || result.ok));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn locale_option_translates_coded_errors_in_results() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-batch-locale-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let logo_png = root.join("logo.png");
+        let notes = root.join("notes.txt");
+        write_test_png(&logo_png, 8, 8, [255, 0, 0, 255]);
+        fs::write(&notes, b"not an image").expect("write txt");
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            ..Default::default()
+        };
+        let paths = vec![notes.to_string_lossy().to_string()];
+        let options = BatchOptions {
+            locale: Some("en".to_string()),
+            ..Default::default()
+        };
+        let results = stamp_batch(&paths, settings, &logo_png, None, &options);
+
+        assert_eq!(results[0].code.as_deref(), Some(CODE_UNSUPPORTED_FORMAT));
+        assert_eq!(
+            results[0].error.as_ref().map(Message::to_string).as_deref(),
+            Some("Unsupported file type. (jpg/jpeg/png/webp/tiff/pdf)")
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn video_and_office_inputs_get_specific_unsupported_messages() {
+        let logo_png = std::env::temp_dir().join("cornerbrand-unused-logo.png");
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            ..Default::default()
+        };
+
+        let video = stamp_single("clips/Trip.MP4", settings.clone(), &logo_png, None);
+        assert!(!video.ok);
+        assert_eq!(video.code.as_deref(), Some(CODE_UNSUPPORTED_VIDEO));
+        assert!(video
+            .error
+            .as_ref()
+            .is_some_and(|e| e.to_string().starts_with("동영상은 지원하지 않습니다")));
+
+        let office = stamp_single("report.docx", settings.clone(), &logo_png, None);
+        assert_eq!(office.code.as_deref(), Some(CODE_UNSUPPORTED_OFFICE));
+
+        let other = stamp_single("notes.txt", settings, &logo_png, None);
+        assert_eq!(other.code.as_deref(), Some(CODE_UNSUPPORTED_FORMAT));
+    }
+
+    #[test]
+    fn results_follow_input_order_for_mixed_sizes() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-batch-order-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let large = root.join("large.png");
+        let small = root.join("small.png");
+        let bracketed = root.join("photo[1].png");
+        let pdf = root.join("doc.pdf");
+        let logo_png = root.join("logo.png");
+        write_test_png(&large, 900, 900, [180, 180, 180, 255]);
+        write_test_png(&small, 8, 8, [180, 180, 180, 255]);
+        write_test_png(&bracketed, 16, 16, [180, 180, 180, 255]);
+        write_minimal_two_page_pdf(&pdf);
+        write_test_png(&logo_png, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            ..Default::default()
+        };
+        // 같은 파일을 두 번 넣어도, 없는 파일이 섞여도 자리는 그대로여야 합니다.
+        let paths: Vec<String> = [
+            &large,
+            &small,
+            &root.join("missing.png"),
+            &pdf,
+            &small,
+            &bracketed,
+        ]
+        .iter()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect();
+
+        for options in [
+            BatchOptions::default(),
+            BatchOptions {
+                per_file_timeout_ms: Some(60_000),
+                ..Default::default()
+            },
+        ] {
+            let results = stamp_batch(&paths, settings.clone(), &logo_png, None, &options);
+            assert_eq!(results.len(), paths.len());
+            for (result, path) in results.iter().zip(&paths) {
+                assert_eq!(&result.input_path, path);
+            }
+            assert!(!results[2].ok);
+            assert!(results[5].ok, "bracketed literal path should be stamped");
+        }
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn report_is_written_only_to_override_path() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-batch-report-path-{nonce}"));
+        let dir_a = root.join("a");
+        let dir_b = root.join("b");
+        fs::create_dir_all(&dir_a).expect("dir a");
+        fs::create_dir_all(&dir_b).expect("dir b");
+
+        let input_a = dir_a.join("one.png");
+        let input_b = dir_b.join("two.png");
+        let logo_png = root.join("logo.png");
+        write_test_png(&input_a, 32, 32, [200, 200, 200, 255]);
+        write_test_png(&input_b, 32, 32, [200, 200, 200, 255]);
+        write_test_png(&logo_png, 8, 8, [255, 0, 0, 255]);
+
+        let report_path = root.join("ci").join("run-123.json");
+        let options = BatchOptions {
+            report_path_override: Some(report_path.to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            ..Default::default()
+        };
+        let paths = vec![
+            input_a.to_string_lossy().to_string(),
+            input_b.to_string_lossy().to_string(),
+        ];
+        let results = stamp_batch(&paths, settings, &logo_png, None, &options);
+        assert!(results.iter().all(|r| r.ok));
+
+        let report: Value =
+            serde_json::from_str(&fs::read_to_string(&report_path).expect("override report"))
+                .expect("parse report");
+        assert_eq!(report["results"].as_array().map(Vec::len), Some(2));
+        for dir in [&dir_a, &dir_b] {
+            assert!(!dir
+                .join(path_policy::OUTPUT_DIR_NAME)
+                .join("cornerbrand_report.json")
+                .exists());
+        }
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn report_carries_schema_version_and_can_be_compact() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-batch-compact-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input = root.join("one.png");
+        let logo_png = root.join("logo.png");
+        write_test_png(&input, 32, 32, [200, 200, 200, 255]);
+        write_test_png(&logo_png, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            ..Default::default()
+        };
+        let paths = vec![input.to_string_lossy().to_string()];
+        let report_path = root
+            .join(path_policy::OUTPUT_DIR_NAME)
+            .join("cornerbrand_report.json");
+
+        for compact in [false, true] {
+            let options = BatchOptions {
+                report_compact: compact,
+                ..Default::default()
+            };
+            stamp_batch(&paths, settings.clone(), &logo_png, None, &options);
+
+            let content = fs::read_to_string(&report_path).expect("report");
+            let report: Value = serde_json::from_str(&content).expect("parse report");
+            assert_eq!(
+                report["schemaVersion"].as_u64(),
+                Some(u64::from(REPORT_SCHEMA_VERSION))
+            );
+            assert_eq!(!content.contains('\n'), compact, "compact={compact}");
+            assert_eq!(!content.contains(": "), compact, "compact={compact}");
+            fs::remove_dir_all(root.join(path_policy::OUTPUT_DIR_NAME)).expect("reset output");
+        }
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn slow_file_is_reported_as_timeout() {
+        let slow = run_with_timeout(
+            "slow.pdf",
+            Duration::from_millis(50),
+            CancellationToken::new(),
+            &mut |_, _| {},
+            |_| {
+                std::thread::sleep(Duration::from_secs(2));
+                StampFileResult {
+                    input_path: "slow.pdf".to_string(),
+                    ok: true,
+                    ..Default::default()
+                }
+            },
+        );
+        assert!(!slow.ok);
+        assert_eq!(slow.input_path, "slow.pdf");
+        assert_eq!(slow.code.as_deref(), Some(CODE_TIMEOUT));
+
+        let fast = run_with_timeout(
+            "fast.png",
+            Duration::from_secs(5),
+            CancellationToken::new(),
+            &mut |_, _| {},
+            |_| StampFileResult {
+                input_path: "fast.png".to_string(),
+                ok: true,
+                ..Default::default()
+            },
+        );
+        assert!(fast.ok);
+        assert!(fast.code.is_none());
+    }
+
+    #[test]
+    fn timed_out_worker_does_not_write_output_afterwards() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-batch-abandon-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+        let output = root.join("late.png");
+
+        let abandon = CancellationToken::new();
+        let retry = RetryPolicy::default().with_cancel(Some(abandon.clone()));
+        let (done_tx, done_rx) = mpsc::channel();
+        let worker_output = output.clone();
+        let result = run_with_timeout(
+            "late.png",
+            Duration::from_millis(20),
+            abandon,
+            &mut |_, _| {},
+            move |_| {
+                std::thread::sleep(Duration::from_millis(200));
+                let _ = done_tx.send(retry.write(&worker_output, b"late"));
+                StampFileResult::default()
+            },
+        );
+        assert_eq!(result.code.as_deref(), Some(CODE_TIMEOUT));
+
+        let written = done_rx
+            .recv_timeout(Duration::from_secs(5))
+            .expect("worker finishes");
+        assert!(written.is_err(), "abandoned worker must not write");
+        assert!(!output.exists());
+        assert_eq!(fs::read_dir(&root).expect("read dir").count(), 0);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn stamp_batch_reports_page_progress_for_pdf_inputs() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-batch-pages-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_pdf = root.join("input.pdf");
+        let logo_png = root.join("logo.png");
+        write_minimal_two_page_pdf(&input_pdf);
+        write_test_png(&logo_png, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            ..Default::default()
+        };
+        let paths = vec![input_pdf.to_string_lossy().to_string()];
+
+        for options in [
+            BatchOptions::default(),
+            BatchOptions {
+                per_file_timeout_ms: Some(30_000),
+                ..Default::default()
+            },
+        ] {
+            let mut updates = Vec::new();
+            let results = stamp_batch_with_progress(
+                &paths,
+                settings.clone(),
+                &logo_png,
+                Some(&root.join("out")),
+                &options,
+                &mut |update| updates.push(update),
+            );
+            assert!(results[0].ok, "unexpected failure: {:?}", results[0].error);
+
+            let pages: Vec<_> = updates
+                .iter()
+                .map(|u| (u.done, u.current_page, u.total_pages))
+                .collect();
+            assert_eq!(
+                pages,
+                vec![
+                    (0, Some(1), Some(2)),
+                    (0, Some(2), Some(2)),
+                    (1, None, None)
+                ]
+            );
+        }
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn interrupted_batch_leaves_partial_report() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-batch-flush-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let logo_png = root.join("logo.png");
+        write_test_png(&logo_png, 8, 8, [255, 0, 0, 255]);
+        let paths: Vec<String> = ["a.png", "b.png", "c.png"]
+            .iter()
+            .map(|name| {
+                let path = root.join(name);
+                write_test_png(&path, 32, 32, [200, 200, 200, 255]);
+                path.to_string_lossy().to_string()
+            })
+            .collect();
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            ..Default::default()
+        };
+        let options = BatchOptions {
+            report_flush_interval: Some(1),
+            ..Default::default()
+        };
+
+        // 첫 파일을 마친 직후 앱이 꺼진 상황을 진행 콜백의 패닉으로 흉내 냅니다.
+        let interrupted = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            stamp_batch_with_progress(
+                &paths,
+                settings.clone(),
+                &logo_png,
+                None,
+                &options,
+                &mut |update| {
+                    if update.done == 1 {
+                        panic!("interrupted");
+                    }
+                },
+            )
+        }));
+        assert!(interrupted.is_err());
+
+        let out_dir = root.join(path_policy::OUTPUT_DIR_NAME);
+        let report_path = out_dir.join("cornerbrand_report.json");
+        let report: Value =
+            serde_json::from_str(&fs::read_to_string(&report_path).expect("partial report"))
+                .expect("parse partial report");
+        assert_eq!(report["complete"], false);
+        assert_eq!(report["results"].as_array().map(Vec::len), Some(1));
+        assert_eq!(report["results"][0]["inputPath"], paths[0].as_str());
+
+        // 끝까지 돌면 중간 저장본을 같은 파일에 덮어써 완료된 보고서 하나만 남습니다.
+        let _ = fs::remove_dir_all(&out_dir);
+        stamp_batch(&paths, settings, &logo_png, None, &options);
+        let reports: Vec<_> = fs::read_dir(&out_dir)
+            .expect("read output dir")
+            .filter_map(Result::ok)
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with("cornerbrand_report")
+            })
+            .collect();
+        assert_eq!(reports.len(), 1);
+        let report: Value =
+            serde_json::from_str(&fs::read_to_string(&report_path).expect("final report"))
+                .expect("parse final report");
+        assert_eq!(report["complete"], true);
+        assert_eq!(report["results"].as_array().map(Vec::len), Some(3));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn stamp_batch_writes_manifest_of_successful_outputs() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-batch-manifest-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_png = root.join("input.png");
+        let broken_png = root.join("broken.png");
+        let logo_png = root.join("logo.png");
+        write_test_png(&input_png, 32, 32, [200, 200, 200, 255]);
+        fs::write(&broken_png, b"not a png").expect("broken input");
+        write_test_png(&logo_png, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            margin_percent: 2.0,
+            ..Default::default()
+        };
+        let options = BatchOptions {
+            write_manifest: true,
+            ..Default::default()
+        };
+
+        let paths = vec![
+            input_png.to_string_lossy().to_string(),
+            broken_png.to_string_lossy().to_string(),
+        ];
+        let results = stamp_batch(&paths, settings, &logo_png, None, &options);
+        assert!(results[0].ok && !results[1].ok);
+
+        let manifest_path = root
+            .join(path_policy::OUTPUT_DIR_NAME)
+            .join("cornerbrand_manifest.json");
+        let manifest: BTreeMap<String, String> =
+            serde_json::from_slice(&fs::read(&manifest_path).expect("read manifest"))
+                .expect("parse manifest");
+
+        let mut expected = BTreeMap::new();
+        expected.insert(
+            "input.png".to_string(),
+            format!("{}/input_cornerbrand.png", path_policy::OUTPUT_DIR_NAME),
+        );
+        assert_eq!(manifest, expected);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn stamp_batch_appends_one_log_line_per_file() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-batch-log-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_png = root.join("input.png");
+        let logo_png = root.join("logo.png");
+        write_test_png(&input_png, 32, 32, [200, 200, 200, 255]);
+        write_test_png(&logo_png, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            margin_percent: 2.0,
+            ..Default::default()
+        };
+        let log_dir = root.join("logs");
+        let options = BatchOptions {
+            log_dir: Some(log_dir.clone()),
+            ..Default::default()
+        };
+
+        let paths = vec![
+            input_png.to_string_lossy().to_string(),
+            root.join("notes.txt").to_string_lossy().to_string(),
+        ];
+        stamp_batch(&paths, settings.clone(), &logo_png, None, &options);
+        stamp_batch(&paths[..1], settings, &logo_png, None, &options);
+
+        let log = fs::read_to_string(log_dir.join(crate::run_log::LOG_FILE_NAME)).expect("log");
+        let lines: Vec<&str> = log.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].contains(&paths[0]) && lines[0].contains("\ttrue\t"));
+        assert!(lines[1].contains(&paths[1]) && lines[1].contains("\tfalse\t"));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn stamp_batch_routes_files_to_per_path_output_dirs() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-batch-routes-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_png = root.join("input.png");
+        let input_pdf = root.join("input.pdf");
+        let logo_png = root.join("logo.png");
+        write_test_png(&input_png, 32, 32, [200, 200, 200, 255]);
+        write_minimal_two_page_pdf(&input_pdf);
+        write_test_png(&logo_png, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            margin_percent: 2.0,
+            ..Default::default()
+        };
+
+        let paths = vec![
+            input_png.to_string_lossy().to_string(),
+            input_pdf.to_string_lossy().to_string(),
+        ];
+        let job_a = root.join("job-a");
+        let job_b = root.join("job-b");
+        let options = BatchOptions {
+            output_dir_by_path: BTreeMap::from([
+                (paths[0].clone(), job_a.to_string_lossy().to_string()),
+                (paths[1].clone(), job_b.to_string_lossy().to_string()),
+            ]),
+            ..Default::default()
+        };
+        let global = root.join("global");
+        let results = stamp_batch(&paths, settings, &logo_png, Some(&global), &options);
+        assert!(results.iter().all(|r| r.ok), "expected all success");
+
+        for (result, dir) in results.iter().zip([&job_a, &job_b]) {
+            let output_root = dir.join(path_policy::OUTPUT_DIR_NAME);
+            let output = PathBuf::from(result.output_path.as_ref().expect("output path"));
+            assert!(output.starts_with(&output_root));
+
+            let report: Value = serde_json::from_slice(
+                &fs::read(output_root.join("cornerbrand_report.json")).expect("report"),
+            )
+            .expect("parse report");
+            assert_eq!(report["results"].as_array().map(Vec::len), Some(1));
+        }
+        assert!(!global.exists(), "global output dir should be unused");
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn settings_by_path_overrides_position_for_one_file() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-batch-overrides-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let logo_png = root.join("logo.png");
+        write_test_png(&logo_png, 8, 8, [255, 0, 0, 255]);
+        let paths: Vec<String> = ["a.png", "b.png", "c.png"]
+            .iter()
+            .map(|name| {
+                let path = root.join(name);
+                write_test_png(&path, 64, 64, [200, 200, 200, 255]);
+                path.to_string_lossy().to_string()
+            })
+            .collect();
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            margin_percent: 0.0,
+            ..Default::default()
+        };
+        let overrides = |value: Value| value.as_object().cloned().expect("object");
+        let options = BatchOptions {
+            settings_by_path: BTreeMap::from([
+                (
+                    paths[1].clone(),
+                    overrides(serde_json::json!({ "position": "좌상단" })),
+                ),
+                (
+                    paths[2].clone(),
+                    overrides(serde_json::json!({ "marginPercent": "넓게" })),
+                ),
+            ]),
+            write_report: false,
+            ..Default::default()
+        };
+        let results = stamp_batch(&paths, settings, &logo_png, None, &options);
+        assert!(results[0].ok && results[1].ok, "{results:?}");
+        assert!(
+            !results[2].ok,
+            "invalid override should fail only that file"
+        );
+
+        let red = |result: &StampFileResult, x: u32, y: u32| {
+            let output = result.output_path.as_ref().expect("output path");
+            image::open(output)
+                .expect("open output")
+                .to_rgba8()
+                .get_pixel(x, y)
+                .0
+                == [255, 0, 0, 255]
+        };
+        assert!(red(&results[0], 63, 63) && !red(&results[0], 0, 0));
+        assert!(red(&results[1], 0, 0) && !red(&results[1], 63, 63));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn output_zip_holds_one_entry_per_success_plus_report() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-batch-zip-{nonce}"));
+        fs::create_dir_all(root.join("sub")).expect("temp dir");
+
+        let logo_png = root.join("logo.png");
+        write_test_png(&logo_png, 8, 8, [255, 0, 0, 255]);
+        write_test_png(&root.join("a.png"), 32, 32, [200, 200, 200, 255]);
+        write_test_png(
+            &root.join("sub").join("b.png"),
+            32,
+            32,
+            [200, 200, 200, 255],
+        );
+        let paths: Vec<String> = [
+            root.join("a.png"),
+            root.join("sub").join("b.png"),
+            root.join("missing.png"),
+        ]
+        .iter()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect();
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            margin_percent: 2.0,
+            web_copy: Some(crate::web_copy::WebCopySettings {
+                max_dimension: 16,
+                format: None,
+                quality: None,
+            }),
+            ..Default::default()
+        };
+        let zip_path = root.join("delivery").join("stamped.zip");
+        let options = BatchOptions {
+            output_zip: Some(zip_path.to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        let results = stamp_batch(&paths, settings.clone(), &logo_png, None, &options);
+        assert!(
+            results[0].ok && results[1].ok && !results[2].ok,
+            "{results:?}"
+        );
+        assert_eq!(
+            results[1]
+                .output_path
+                .as_deref()
+                .map(|p| p.starts_with("sub/")),
+            Some(true)
+        );
+
+        let mut archive =
+            zip::ZipArchive::new(fs::File::open(&zip_path).expect("open zip")).expect("read zip");
+        let mut names: Vec<String> = archive.file_names().map(str::to_string).collect();
+        names.sort();
+        let mut expected: Vec<String> = results
+            .iter()
+            .flat_map(|result| [result.output_path.clone(), result.web_output_path.clone()])
+            .flatten()
+            .chain([zip_output::REPORT_ENTRY_NAME.to_string()])
+            .collect();
+        expected.sort();
+        assert_eq!(names, expected);
+
+        let report: Value = serde_json::from_reader(
+            archive
+                .by_name(zip_output::REPORT_ENTRY_NAME)
+                .expect("report entry"),
+        )
+        .expect("parse report");
+        assert_eq!(report["results"].as_array().map(Vec::len), Some(3));
+
+        let leftovers: Vec<_> = fs::read_dir(zip_path.parent().expect("zip dir"))
+            .expect("list")
+            .flatten()
+            .filter(|entry| entry.file_name() != "stamped.zip")
+            .collect();
+        assert!(leftovers.is_empty(), "staging dir should be removed");
+        assert!(!root.join(path_policy::OUTPUT_DIR_NAME).exists());
+        assert!(results[0]
+            .web_output_path
+            .as_deref()
+            .is_some_and(|name| name.contains(path_policy::WEB_COPY_DIR_NAME)));
+
+        let written = fs::read(&zip_path).expect("zip bytes");
+        let again = stamp_batch(&paths, settings, &logo_png, None, &options);
+        assert!(again
+            .iter()
+            .all(|result| !result.ok && result.code.as_deref() == Some(CODE_OUTPUT_NOT_WRITABLE)));
+        assert_eq!(fs::read(&zip_path).expect("zip bytes"), written);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn output_zip_includes_manifest_sidecars_and_sheets_and_rejects_resume() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-batch-zip-extras-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let logo_png = root.join("logo.png");
+        write_test_png(&logo_png, 8, 8, [255, 0, 0, 255]);
+        write_test_png(&root.join("a.png"), 32, 32, [200, 200, 200, 255]);
+        let paths = vec![root.join("a.png").to_string_lossy().to_string()];
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            ..Default::default()
+        };
+        let zip_path = root.join("stamped.zip");
+        let options = BatchOptions {
+            output_zip: Some(zip_path.to_string_lossy().to_string()),
+            write_manifest: true,
+            write_sidecars: true,
+            contact_sheet: Some(SheetOptions::default()),
+            ..Default::default()
+        };
+        let results = stamp_batch(&paths, settings.clone(), &logo_png, None, &options);
+        assert!(results[0].ok, "{results:?}");
+        let output = results[0].output_path.clone().expect("entry name");
+
+        let mut archive =
+            zip::ZipArchive::new(fs::File::open(&zip_path).expect("open zip")).expect("read zip");
+        let mut names: Vec<String> = archive.file_names().map(str::to_string).collect();
+        names.sort();
+        let mut expected = vec![
+            output.clone(),
+            format!("{output}.json"),
+            "sheet_1.png".to_string(),
+            zip_output::MANIFEST_ENTRY_NAME.to_string(),
+            zip_output::REPORT_ENTRY_NAME.to_string(),
+        ];
+        expected.sort();
+        assert_eq!(names, expected);
+
+        let manifest: BTreeMap<String, String> = serde_json::from_reader(
+            archive
+                .by_name(zip_output::MANIFEST_ENTRY_NAME)
+                .expect("manifest entry"),
+        )
+        .expect("parse manifest");
+        assert_eq!(manifest.get("a.png"), Some(&output));
+        let sidecar: Value = serde_json::from_reader(
+            archive
+                .by_name(&format!("{output}.json"))
+                .expect("sidecar entry"),
+        )
+        .expect("parse sidecar");
+        assert_eq!(sidecar["outputPath"].as_str(), Some(output.as_str()));
+
+        let resumed = stamp_batch(
+            &paths,
+            settings,
+            &logo_png,
+            None,
+            &BatchOptions {
+                output_zip: Some(root.join("again.zip").to_string_lossy().to_string()),
+                resume_from_report: Some(root.join("report.json").to_string_lossy().to_string()),
+                ..Default::default()
+            },
+        );
+        assert!(resumed
+            .iter()
+            .all(|r| !r.ok && r.code.as_deref() == Some(CODE_RESUME_FAILED)));
+        assert!(!root.join("again.zip").exists());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn contact_sheet_places_one_cell_per_stamped_image() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-batch-sheet-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let logo_png = root.join("logo.png");
+        write_test_png(&logo_png, 8, 8, [255, 0, 0, 255]);
+        let paths: Vec<String> = ["a.png", "b.png", "c.png"]
+            .iter()
+            .map(|name| {
+                let path = root.join(name);
+                write_test_png(&path, 32, 32, [120, 120, 120, 255]);
+                path.to_string_lossy().to_string()
+            })
+            .collect();
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            margin_percent: 2.0,
+            ..Default::default()
+        };
+        let options = BatchOptions {
+            contact_sheet: Some(SheetOptions {
+                columns: Some(2),
+                cell_size: Some(32),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let results = stamp_batch(&paths, settings, &logo_png, None, &options);
+        assert!(results.iter().all(|r| r.ok), "{results:?}");
+
+        let output_dir = root.join(path_policy::OUTPUT_DIR_NAME);
+        let sheet = image::open(output_dir.join("sheet_1.png"))
+            .expect("sheet")
+            .to_rgba8();
+        assert_eq!(sheet.dimensions(), (88, 88));
+        assert!(!output_dir.join("sheet_2.png").exists());
+        assert!(results.iter().all(|r| r.warnings.is_empty()), "{results:?}");
+
+        // 칸은 (8, 8)부터 40px 간격이고, 네 번째 칸은 비어 있습니다.
+        let filled: Vec<bool> = [(8, 8), (48, 8), (8, 48), (48, 48)]
+            .iter()
+            .map(|(x, y)| sheet.get_pixel(x + 4, y + 4).0 != [255, 255, 255, 255])
+            .collect();
+        assert_eq!(filled, [true, true, true, false]);
+
+        // 다시 실행해도 앞의 시트를 덮어쓰지 않습니다.
+        let sheet_bytes = fs::read(output_dir.join("sheet_1.png")).expect("sheet bytes");
+        let rerun_settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            margin_percent: 2.0,
+            ..Default::default()
+        };
+        stamp_batch(&paths, rerun_settings, &logo_png, None, &options);
+        assert_eq!(
+            fs::read(output_dir.join("sheet_1.png")).expect("sheet bytes"),
+            sheet_bytes
+        );
+        assert!(output_dir.join("sheet_1(1).png").is_file());
+
+        // 글꼴을 읽지 못하면 시트 대신 경고가 남고, 집계에도 한 번만 나옵니다.
+        let broken_font = BatchOptions {
+            contact_sheet: Some(SheetOptions {
+                label_font: Some(root.join("missing.ttf").to_string_lossy().to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let results = stamp_batch(
+            &paths,
+            StampSettingsInput {
+                position: "우하단".to_string(),
+                size_preset: "보통".to_string(),
+                margin_percent: 2.0,
+                ..Default::default()
+            },
+            &logo_png,
+            None,
+            &broken_font,
+        );
+        assert!(results.iter().all(|r| r.ok), "{results:?}");
+        assert!(results.iter().all(|r| r
+            .warnings
+            .iter()
+            .any(|w| w.to_string().contains("확인용 시트"))));
+        let summary = BatchSummary::from_results(&results, Duration::ZERO);
+        assert_eq!(
+            summary
+                .warnings
+                .iter()
+                .filter(|w| w.contains("확인용 시트"))
+                .count(),
+            1
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn stamp_batch_rejects_unwritable_output_dir_before_processing() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-batch-unwritable-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_png = root.join("input.png");
+        let logo_png = root.join("logo.png");
+        write_test_png(&input_png, 32, 32, [200, 200, 200, 255]);
+        write_test_png(&logo_png, 8, 8, [255, 0, 0, 255]);
+        let blocker = root.join("not-a-dir");
+        fs::write(&blocker, b"x").expect("blocker file");
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            margin_percent: 2.0,
+            ..Default::default()
+        };
+        let paths = vec![
+            input_png.to_string_lossy().to_string(),
+            root.join("other.png").to_string_lossy().to_string(),
+        ];
+        let results = stamp_batch(
+            &paths,
+            settings,
+            &logo_png,
+            Some(&blocker.join("exports")),
+            &BatchOptions::default(),
+        );
+
+        assert_eq!(results.len(), 2);
+        for result in &results {
+            assert!(!result.ok);
+            assert_eq!(result.code.as_deref(), Some(CODE_OUTPUT_NOT_WRITABLE));
+        }
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn write_report_false_skips_report_but_keeps_manifest() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-batch-no-report-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let logo_png = root.join("logo.png");
+        let input_png = root.join("photo.png");
+        write_test_png(&logo_png, 8, 8, [255, 0, 0, 255]);
+        write_test_png(&input_png, 32, 32, [200, 200, 200, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            ..Default::default()
+        };
+        let options = BatchOptions {
+            write_report: false,
+            write_manifest: true,
+            ..Default::default()
+        };
+        let paths = vec![input_png.to_string_lossy().to_string()];
+        let results = stamp_batch(&paths, settings, &logo_png, None, &options);
+
+        assert!(results[0].ok);
+        let out_dir = root.join(path_policy::OUTPUT_DIR_NAME);
+        assert!(Path::new(results[0].output_path.as_deref().expect("output")).exists());
+        assert!(!out_dir.join("cornerbrand_report.json").exists());
+        let manifest: Value = serde_json::from_str(
+            &fs::read_to_string(out_dir.join("cornerbrand_manifest.json"))
+                .expect("manifest is written without a report"),
+        )
+        .expect("parse manifest");
+        assert_eq!(manifest.as_object().map(|m| m.len()), Some(1));
+        assert!(BatchOptions::default().write_report);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn verify_output_keeps_readable_png_and_pdf_results() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-batch-verify-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let logo_png = root.join("logo.png");
+        let input_png = root.join("photo.png");
+        let input_pdf = root.join("doc.pdf");
+        write_test_png(&logo_png, 8, 8, [255, 0, 0, 255]);
+        write_test_png(&input_png, 32, 32, [200, 200, 200, 255]);
+        write_minimal_two_page_pdf(&input_pdf);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            ..Default::default()
+        };
+        let options = BatchOptions {
+            verify_output: true,
+            write_report: false,
+            ..Default::default()
+        };
+        let paths = vec![
+            input_png.to_string_lossy().to_string(),
+            input_pdf.to_string_lossy().to_string(),
+        ];
+        let results = stamp_batch(&paths, settings, &logo_png, None, &options);
+
+        assert!(results.iter().all(|r| r.ok), "{results:?}");
+        assert!(results.iter().all(|r| r.code.is_none()));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn write_sidecars_leaves_json_next_to_stamped_image() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-batch-sidecar-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let logo_png = root.join("logo.png");
+        let input_png = root.join("photo.png");
+        write_test_png(&logo_png, 8, 8, [255, 0, 0, 255]);
+        write_test_png(&input_png, 32, 32, [200, 200, 200, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            ..Default::default()
+        };
+        let options = BatchOptions {
+            write_sidecars: true,
+            write_report: false,
+            ..Default::default()
+        };
+        let input = input_png.to_string_lossy().to_string();
+        let results = stamp_batch(
+            std::slice::from_ref(&input),
+            settings,
+            &logo_png,
+            None,
+            &options,
+        );
+        assert!(results[0].ok, "{results:?}");
+
+        let output = results[0].output_path.as_deref().expect("output");
+        let sidecar_path = sidecar::sidecar_path(Path::new(output));
+        assert_eq!(
+            sidecar_path.file_name().and_then(|name| name.to_str()),
+            Some("photo_cornerbrand.png.json")
+        );
+        let sidecar: Value =
+            serde_json::from_slice(&fs::read(&sidecar_path).expect("sidecar")).expect("json");
+        assert_eq!(sidecar["inputPath"], input.as_str());
+        assert_eq!(sidecar["outputPath"], output);
+        assert_eq!(sidecar["settings"]["position"], "우하단");
+        assert!(sidecar["timestamp"].as_u64().is_some_and(|t| t > 0));
+        assert!(sidecar["warnings"].is_array());
+
+        let _ = fs::remove_dir_all(&root);
+    }
 }
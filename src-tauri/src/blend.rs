@@ -0,0 +1,98 @@
+use crate::i18n::Message;
+use image::imageops::overlay;
+use image::{ImageBuffer, Pixel, Primitive};
+
+/// 로고를 배경에 합성하는 방식입니다. 이미지에만 적용되며 PDF는 설정과 관계없이 일반 합성으로 그립니다.
+/// 다만 PDF 배너(`banner_mode`)는 흰 바탕으로 평탄화한 로고가 본문을 가리지 않도록 벡터·평탄화 경로 모두 `Multiply`로 그립니다.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum BlendMode {
+    /// 일반 알파 합성입니다.
+    #[default]
+    Over,
+    /// 배경과 로고 색을 곱합니다. 흰 부분은 배경을 그대로 두고 어두운 부분만 눌러 찍힌 듯 보입니다.
+    Multiply,
+    /// 반전한 두 색을 곱해 다시 반전합니다. 검은 부분은 배경을 그대로 두고 밝은 부분만 밝힙니다.
+    Screen,
+}
+
+pub(crate) fn parse_blend_mode(value: Option<&str>) -> Result<BlendMode, Message> {
+    match value.map(str::trim) {
+        None | Some("") | Some("over") => Ok(BlendMode::Over),
+        Some("multiply") => Ok(BlendMode::Multiply),
+        Some("screen") => Ok(BlendMode::Screen),
+        Some(_) => Err(Message::new("INVALID_BLEND_MODE")),
+    }
+}
+
+impl BlendMode {
+    /// 0.0~1.0으로 맞춘 배경/로고 채널 값을 섞습니다.
+    fn mix(self, backdrop: f32, source: f32) -> f32 {
+        match self {
+            Self::Over => source,
+            Self::Multiply => backdrop * source,
+            Self::Screen => backdrop + source - backdrop * source,
+        }
+    }
+}
+
+/// 합성 계산 결과(0.0~1.0)를 채널 값으로 되돌립니다. 합성하는 캔버스는 8/16비트입니다.
+pub(crate) trait UnitChannel {
+    fn from_unit(value: f32) -> Self;
+}
+
+impl UnitChannel for u8 {
+    fn from_unit(value: f32) -> Self {
+        (value.clamp(0.0, 1.0) * f32::from(u8::MAX)).round() as u8
+    }
+}
+
+impl UnitChannel for u16 {
+    fn from_unit(value: f32) -> Self {
+        (value.clamp(0.0, 1.0) * f32::from(u16::MAX)).round() as u16
+    }
+}
+
+/// `imageops::overlay`처럼 `(x, y)`에 로고를 놓되 색은 `mode`로 섞습니다.
+/// 로고 알파만큼만 섞인 색을 쓰고, 배경이 투명한 곳은 로고 색을 그대로 씁니다(W3C 합성 규칙).
+pub(crate) fn blend_overlay<P>(
+    bottom: &mut ImageBuffer<P, Vec<P::Subpixel>>,
+    top: &ImageBuffer<P, Vec<P::Subpixel>>,
+    x: i64,
+    y: i64,
+    mode: BlendMode,
+) where
+    P: Pixel + 'static,
+    P::Subpixel: Into<f32> + UnitChannel,
+{
+    if mode == BlendMode::Over || !P::HAS_ALPHA {
+        overlay(bottom, top, x, y);
+        return;
+    }
+
+    let max: f32 = <P::Subpixel as Primitive>::DEFAULT_MAX_VALUE.into();
+    let alpha_index = usize::from(P::CHANNEL_COUNT) - 1;
+    for (tx, ty, top_px) in top.enumerate_pixels() {
+        let (bx, by) = (x + i64::from(tx), y + i64::from(ty));
+        if bx < 0 || by < 0 || bx >= i64::from(bottom.width()) || by >= i64::from(bottom.height()) {
+            continue;
+        }
+        let source = top_px.channels();
+        let source_alpha = source[alpha_index].into() / max;
+        if source_alpha <= 0.0 {
+            continue;
+        }
+
+        let backdrop = bottom.get_pixel_mut(bx as u32, by as u32).channels_mut();
+        let backdrop_alpha = backdrop[alpha_index].into() / max;
+        let out_alpha = source_alpha + backdrop_alpha * (1.0 - source_alpha);
+        for channel in 0..alpha_index {
+            let b = backdrop[channel].into() / max;
+            let s = source[channel].into() / max;
+            let mixed = (1.0 - backdrop_alpha) * s + backdrop_alpha * mode.mix(b, s);
+            let out =
+                (source_alpha * mixed + backdrop_alpha * b * (1.0 - source_alpha)) / out_alpha;
+            backdrop[channel] = UnitChannel::from_unit(out);
+        }
+        backdrop[alpha_index] = UnitChannel::from_unit(out_alpha);
+    }
+}
@@ -0,0 +1,65 @@
+use crate::i18n::Message;
+use std::path::PathBuf;
+
+/// 프론트엔드가 짧은 이름으로 고를 수 있는 번들 로고입니다. 이 목록 밖의 리소스는 읽지 않습니다.
+pub const BUILTIN_LOGOS: &[(&str, &str)] = &[
+    ("default", "logo.png"),
+    ("dark", "logo_dark.png"),
+    ("mono", "logo_mono.png"),
+];
+
+pub fn builtin_logo_names() -> Vec<String> {
+    BUILTIN_LOGOS
+        .iter()
+        .map(|(name, _)| name.to_string())
+        .collect()
+}
+
+/// 허용 목록의 이름을 리소스 파일 이름으로 바꾼 뒤 `resolve`로 실제 경로를 찾습니다.
+/// `resolve`는 앱에서는 `app.path().resolve(.., BaseDirectory::Resource)`입니다.
+pub fn resolve_builtin_logo(
+    name: &str,
+    resolve: impl Fn(&str) -> Result<PathBuf, Message>,
+) -> Result<PathBuf, Message> {
+    let name = name.trim();
+    let (_, resource) = BUILTIN_LOGOS
+        .iter()
+        .find(|(builtin, _)| *builtin == name)
+        .ok_or_else(|| Message::new("UNKNOWN_BUILTIN_LOGO").arg(name))?;
+
+    let path = resolve(resource)?;
+    if path.is_file() {
+        Ok(path)
+    } else {
+        Err(Message::new("BUILTIN_LOGO_NOT_FOUND").arg(resource.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn known_builtin_resolves_and_unknown_name_errors() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-builtin-logo-{nonce}"));
+        fs::create_dir_all(&root).expect("create root");
+        fs::write(root.join("logo_mono.png"), b"png").expect("write logo");
+
+        let resolver = |resource: &str| Ok(root.join(resource));
+        assert_eq!(
+            resolve_builtin_logo(" mono ", resolver).expect("mono"),
+            root.join("logo_mono.png")
+        );
+        assert!(resolve_builtin_logo("dark", resolver).is_err());
+        assert!(resolve_builtin_logo("../secret.png", resolver).is_err());
+        assert!(resolve_builtin_logo("logo_mono.png", resolver).is_err());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+
+static CANCELLATION_REGISTRY: OnceLock<Registry> = OnceLock::new();
+
+/// 배치 하나를 취소하는 표시입니다. 복제본끼리 같은 표시를 나눠 가지므로, 하나를 `BatchOptions`에 넘기고
+/// 다른 복제본으로 다른 스레드에서 취소할 수 있습니다. 라이브러리로 쓸 때는 등록부 없이 이것만 쓰면 됩니다.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 취소를 요청합니다. 처리 중인 파일은 마저 끝내고 아직 시작하지 않은 파일부터 멈춥니다.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// 취소를 요청하고, 이번 호출로 처음 취소되었으면 `true`입니다.
+    fn cancel_once(&self) -> bool {
+        !self.0.swap(true, Ordering::SeqCst)
+    }
+
+    fn same_as(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// `request_id`별 취소 표시를 모아 둔 등록부입니다. 앱은 전역 등록부 하나를 쓰고, 테스트는 저마다 따로 만듭니다.
+#[derive(Default)]
+struct Registry(Mutex<HashMap<String, CancellationToken>>);
+
+impl Registry {
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, CancellationToken>> {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn begin(&self, request_id: &str) -> CancellationGuard<'_> {
+        let token = CancellationToken::new();
+        self.lock().insert(request_id.to_string(), token.clone());
+        CancellationGuard {
+            registry: self,
+            request_id: request_id.to_string(),
+            token,
+        }
+    }
+
+    fn cancel(&self, request_id: &str) -> bool {
+        self.lock()
+            .get(request_id)
+            .map(CancellationToken::cancel)
+            .is_some()
+    }
+
+    fn cancel_all(&self) -> usize {
+        self.lock()
+            .values()
+            .filter(|token| token.cancel_once())
+            .count()
+    }
+
+    fn active_requests(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.lock().keys().cloned().collect();
+        ids.sort();
+        ids
+    }
+}
+
+/// 등록부에 올린 배치 하나의 취소 표시입니다. 배치가 끝나 guard가 사라지면 등록도 풀립니다.
+pub struct CancellationGuard<'a> {
+    registry: &'a Registry,
+    request_id: String,
+    token: CancellationToken,
+}
+
+impl CancellationGuard<'_> {
+    pub fn token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+}
+
+impl Drop for CancellationGuard<'_> {
+    fn drop(&mut self) {
+        let mut registry = self.registry.lock();
+        // 같은 ID로 새 배치가 등록되었다면 그 항목은 건드리지 않습니다.
+        if registry
+            .get(&self.request_id)
+            .is_some_and(|token| token.same_as(&self.token))
+        {
+            registry.remove(&self.request_id);
+        }
+    }
+}
+
+/// `request_id`로 취소할 수 있도록 새 토큰을 만들어 등록합니다. 앱 명령용 얇은 감싸개입니다.
+pub fn begin(request_id: &str) -> CancellationGuard<'static> {
+    registry().begin(request_id)
+}
+
+/// 해당 배치에 취소를 요청합니다. 등록된 배치가 없으면 `false`입니다.
+pub fn cancel(request_id: &str) -> bool {
+    registry().cancel(request_id)
+}
+
+/// 진행 중인 모든 배치에 취소를 요청하고, 이번에 새로 취소된 배치 수를 돌려줍니다.
+pub fn cancel_all() -> usize {
+    registry().cancel_all()
+}
+
+/// 지금 등록되어 있는 배치의 `request_id` 목록입니다. 순서는 이름순입니다.
+pub fn active_requests() -> Vec<String> {
+    registry().active_requests()
+}
+
+fn registry() -> &'static Registry {
+    CANCELLATION_REGISTRY.get_or_init(Registry::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn unique_id(label: &str) -> String {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        format!("{label}-{nonce}")
+    }
+
+    #[test]
+    fn cancel_all_marks_every_active_request() {
+        // 전역 등록부를 쓰면 동시에 도는 다른 테스트의 배치까지 취소하므로 이 테스트만의 등록부를 씁니다.
+        let registry = Registry::default();
+        let ids: Vec<String> = (0..3).map(|i| format!("cancel-all-{i}")).collect();
+        let guards: Vec<CancellationGuard> = ids.iter().map(|id| registry.begin(id)).collect();
+
+        assert_eq!(registry.cancel_all(), guards.len());
+        assert!(guards.iter().all(|guard| guard.token().is_cancelled()));
+        assert_eq!(registry.cancel_all(), 0, "already cancelled");
+
+        drop(guards);
+        assert!(
+            ids.iter().all(|id| !registry.cancel(id)),
+            "dropped guards must unregister"
+        );
+        assert!(registry.active_requests().is_empty());
+    }
+
+    #[test]
+    fn token_clones_share_cancellation_without_registry() {
+        let token = CancellationToken::new();
+        let handle = token.clone();
+        assert!(!token.is_cancelled());
+
+        handle.cancel();
+        assert!(token.is_cancelled());
+        assert!(!handle.cancel_once(), "already cancelled");
+    }
+
+    #[test]
+    fn active_requests_tracks_guard_lifetime() {
+        let id = unique_id("active");
+        let guard = begin(&id);
+        assert!(active_requests().contains(&id));
+
+        drop(guard);
+        assert!(!active_requests().contains(&id));
+    }
+}
@@ -0,0 +1,195 @@
+use crate::app_config::{self, AppConfig, PartialSettings};
+use crate::batch::{self, BatchOptions};
+use crate::image_engine::{StampFileResult, StampSettingsInput};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+const USAGE: &str = "사용법: cornerbrand-cli --paths <파일 또는 패턴>... --logo <로고 파일>
+  [--position 좌상단|우상단|좌하단|우하단|가운데] [--size <프리셋 또는 %>]
+  [--margin <%>] [--output-dir <폴더>] [--config <설정 폴더>]
+
+--config를 주면 그 폴더의 cornerbrand.config.json을 기본값으로 씁니다.
+앱의 설정 폴더는 자동으로 읽지 않습니다. 명령줄 옵션이 설정 파일보다 우선합니다.";
+
+/// 명령줄에서 받은 배치 한 번의 입력입니다.
+#[derive(Debug, Clone)]
+struct CliArgs {
+    paths: Vec<String>,
+    logo: PathBuf,
+    settings: StampSettingsInput,
+    output_dir: Option<PathBuf>,
+}
+
+/// 창 없이 `batch::stamp_batch`를 돌리고 결과를 JSON 배열로 표준 출력에 씁니다.
+/// 인자가 잘못되면 2, 실패한 파일이 있으면 1, 모두 성공하거나 건너뛰었으면 0으로 끝납니다.
+pub fn run(args: impl IntoIterator<Item = String>) -> ExitCode {
+    let args = match parse_args(args) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(message) => {
+            eprintln!("{message}\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let results = batch::stamp_batch(
+        &args.paths,
+        args.settings,
+        &args.logo,
+        args.output_dir.as_deref(),
+        &BatchOptions::default(),
+    );
+    match serde_json::to_string_pretty(&results) {
+        Ok(json) => println!("{json}"),
+        Err(e) => {
+            eprintln!("결과를 JSON으로 바꾸지 못했습니다: {e}");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if results.iter().all(succeeded_or_skipped) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn succeeded_or_skipped(result: &StampFileResult) -> bool {
+    result.ok || result.skipped
+}
+
+/// `--help`이면 `None`을 돌려줍니다. `--paths`는 다음 옵션이 나올 때까지의 값을 모두 받습니다.
+/// 설정은 명령줄 옵션, `--config` 폴더의 설정 파일, 기본값(우하단·보통) 순으로 채웁니다.
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Option<CliArgs>, String> {
+    let mut paths = Vec::new();
+    let mut logo = None;
+    let mut output_dir = None;
+    let mut config_dir = None;
+    let mut flags = PartialSettings::new();
+
+    let mut args = args.into_iter().peekable();
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--help" | "-h" => return Ok(None),
+            "--paths" => {
+                let before = paths.len();
+                while let Some(path) = args.next_if(|next| !next.starts_with("--")) {
+                    paths.push(path);
+                }
+                if paths.len() == before {
+                    return Err("--paths 옵션에 값이 필요합니다.".to_string());
+                }
+            }
+            "--logo" => logo = Some(PathBuf::from(next_value(&mut args, &flag)?)),
+            "--position" => {
+                flags.insert("position".into(), next_value(&mut args, &flag)?.into());
+            }
+            "--size" => {
+                let size = next_value(&mut args, &flag)?;
+                match size.parse::<f32>() {
+                    Ok(percent) => flags.insert("sizePercent".into(), percent.into()),
+                    Err(_) => flags.insert("sizePreset".into(), size.into()),
+                };
+            }
+            "--margin" => {
+                let margin: f32 = next_value(&mut args, &flag)?
+                    .parse()
+                    .map_err(|_| "--margin 값은 숫자여야 합니다.".to_string())?;
+                flags.insert("marginPercent".into(), margin.into());
+            }
+            "--output-dir" => output_dir = Some(next_value(&mut args, &flag)?),
+            "--config" => config_dir = Some(PathBuf::from(next_value(&mut args, &flag)?)),
+            other => return Err(format!("알 수 없는 옵션입니다: {other}")),
+        }
+    }
+
+    if paths.is_empty() {
+        return Err("--paths로 처리할 파일을 하나 이상 지정해야 합니다.".to_string());
+    }
+    let logo = logo.ok_or_else(|| "--logo로 로고 파일을 지정해야 합니다.".to_string())?;
+    let config = cli_config(config_dir.as_deref())?;
+    let settings = app_config::merge_settings(&config, flags).map_err(|e| e.to_string())?;
+    let output_dir = app_config::output_dir_or_default(&config, output_dir).map(PathBuf::from);
+    Ok(Some(CliArgs {
+        paths,
+        logo,
+        settings,
+        output_dir,
+    }))
+}
+
+/// `--config` 폴더의 설정에 CLI 기본값(우하단·보통)을 채워 넣습니다.
+fn cli_config(config_dir: Option<&Path>) -> Result<AppConfig, String> {
+    let mut config = match config_dir {
+        Some(dir) => app_config::load_config(dir).map_err(|e| e.to_string())?,
+        None => AppConfig::default(),
+    };
+    config.position.get_or_insert_with(|| "우하단".to_string());
+    config.size_preset.get_or_insert_with(|| "보통".to_string());
+    Ok(config)
+}
+
+fn next_value(args: &mut impl Iterator<Item = String>, flag: &str) -> Result<String, String> {
+    args.next()
+        .filter(|value| !value.starts_with("--"))
+        .ok_or_else(|| format!("{flag} 옵션에 값이 필요합니다."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_args_reads_paths_until_next_flag() {
+        let parsed = parse_args(args(&[
+            "--paths", "a.png", "b.pdf", "--logo", "logo.png", "--size", "12.5", "--margin", "3",
+        ]))
+        .expect("parse")
+        .expect("not help");
+        assert_eq!(parsed.paths, ["a.png", "b.pdf"]);
+        assert_eq!(parsed.logo, PathBuf::from("logo.png"));
+        assert_eq!(parsed.settings.size_percent, Some(12.5));
+        assert_eq!(parsed.settings.margin_percent, 3.0);
+        assert_eq!(parsed.settings.position, "우하단");
+
+        assert!(parse_args(args(&["--paths", "a.png"])).is_err());
+        assert!(parse_args(args(&["--paths", "--logo", "logo.png"])).is_err());
+        assert!(parse_args(args(&["--help"])).expect("help").is_none());
+    }
+
+    #[test]
+    fn flags_override_config_file_defaults() {
+        let dir =
+            std::env::temp_dir().join(format!("cornerbrand-cli-config-{}", std::process::id()));
+        let config = AppConfig {
+            position: Some("좌상단".to_string()),
+            margin_percent: Some(4.0),
+            output_dir: Some("/tmp/from-config".to_string()),
+            ..Default::default()
+        };
+        app_config::save_config(&dir, &config).expect("save config");
+
+        let parsed = parse_args(
+            args(&[
+                "--paths", "a.png", "--logo", "logo.png", "--margin", "1", "--config",
+            ])
+            .into_iter()
+            .chain([dir.to_string_lossy().into_owned()]),
+        )
+        .expect("parse")
+        .expect("not help");
+        assert_eq!(parsed.settings.position, "좌상단");
+        assert_eq!(parsed.settings.size_preset, "보통");
+        assert_eq!(parsed.settings.margin_percent, 1.0);
+        assert_eq!(parsed.output_dir, Some(PathBuf::from("/tmp/from-config")));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
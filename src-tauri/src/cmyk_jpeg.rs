@@ -0,0 +1,285 @@
+use crate::i18n::Message;
+use image::{Rgba, RgbaImage};
+use lcms2::{ColorSpaceSignature, Intent, PixelFormat, Profile, Transform};
+use zune_jpeg::zune_core::bytestream::ZCursor;
+use zune_jpeg::zune_core::colorspace::ColorSpace;
+use zune_jpeg::zune_core::options::DecoderOptions;
+use zune_jpeg::JpegDecoder;
+
+/// Adobe APP14의 transform 값. 2는 YCCK(YCbCr로 압축한 CMYK)입니다.
+const ADOBE_TRANSFORM_YCCK: u8 = 2;
+
+/// 마커에서 읽은 JPEG 색 구성입니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct JpegLayout {
+    components: u8,
+    /// Adobe APP14 세그먼트가 있으면 그 transform 값입니다.
+    adobe_transform: Option<u8>,
+}
+
+/// 네 채널 CMYK로 저장된 JPEG인지 봅니다. YCCK도 포함합니다. `image` 크레이트는 YCCK의 색을 반전된 채로 읽습니다.
+pub(crate) fn is_cmyk(bytes: &[u8]) -> bool {
+    inspect(bytes).is_some_and(|layout| layout.components == 4)
+}
+
+/// CMYK JPEG를 원시 CMYK로 디코딩해 RGBA8로 바꿉니다.
+/// Photoshop 등이 쓰는 Adobe APP14가 있으면 값이 반전돼 저장된 것으로 보고, 없으면 그대로 잉크 양으로 봅니다.
+/// YCCK는 앞 세 채널을 YCbCr에서 (반전된) CMY로 되돌린 뒤 같은 방식으로 처리합니다.
+/// CMYK ICC 프로파일이 들어 있으면 그 프로파일로 sRGB 변환하고, 없으면 단순 공식(1-C)(1-K)를 씁니다.
+pub(crate) fn decode_rgba(bytes: &[u8]) -> Result<RgbaImage, Message> {
+    let layout = inspect(bytes).ok_or_else(|| Message::new("JPEG_HEADER_UNREADABLE"))?;
+    let inverted = layout.adobe_transform.is_some();
+    let ycck = layout.adobe_transform == Some(ADOBE_TRANSFORM_YCCK);
+
+    let options = DecoderOptions::default()
+        .set_strict_mode(false)
+        .set_max_width(usize::MAX)
+        .set_max_height(usize::MAX)
+        .jpeg_set_out_colorspace(if ycck {
+            ColorSpace::YCCK
+        } else {
+            ColorSpace::CMYK
+        });
+    let mut decoder = JpegDecoder::new_with_options(ZCursor::new(bytes), options);
+    let cmyk = decoder
+        .decode()
+        .map_err(|e| Message::new("CMYK_DECODE_FAILED").arg(format!("{e:?}")))?;
+    let (width, height) = decoder
+        .dimensions()
+        .ok_or_else(|| Message::new("CMYK_SIZE_UNREADABLE"))?;
+    let (width, height) = (width as u32, height as u32);
+    if cmyk.len() != width as usize * height as usize * 4 {
+        return Err(Message::new("CMYK_CHANNELS_UNEXPECTED"));
+    }
+
+    let pixels: Vec<[u8; 4]> = cmyk
+        .chunks_exact(4)
+        .map(|px| {
+            if ycck {
+                ycc_to_cmy([px[0], px[1], px[2], px[3]])
+            } else {
+                [px[0], px[1], px[2], px[3]]
+            }
+        })
+        .collect();
+    let rgb = decoder
+        .icc_profile()
+        .and_then(|icc| convert_with_profile(&pixels, &icc, inverted))
+        .unwrap_or_else(|| convert_naive(&pixels, inverted));
+
+    Ok(RgbaImage::from_fn(width, height, |x, y| {
+        let [r, g, b] = rgb[(y * width + x) as usize];
+        Rgba([r, g, b, 255])
+    }))
+}
+
+/// 프로파일이 CMYK가 아니거나 해석하지 못하면 `None`을 돌려 단순 변환으로 넘깁니다.
+fn convert_with_profile(pixels: &[[u8; 4]], icc: &[u8], inverted: bool) -> Option<Vec<[u8; 3]>> {
+    let profile = Profile::new_icc(icc).ok()?;
+    if profile.color_space() != ColorSpaceSignature::CmykData {
+        return None;
+    }
+    let format = if inverted {
+        PixelFormat::CMYK_8_REV
+    } else {
+        PixelFormat::CMYK_8
+    };
+    let transform = Transform::<[u8; 4], [u8; 3]>::new(
+        &profile,
+        format,
+        &Profile::new_srgb(),
+        PixelFormat::RGB_8,
+        Intent::Perceptual,
+    )
+    .ok()?;
+    let mut rgb = vec![[0u8; 3]; pixels.len()];
+    transform.transform_pixels(pixels, &mut rgb);
+    Some(rgb)
+}
+
+/// JFIF YCbCr 공식으로 앞 세 채널을 되돌립니다. Adobe YCCK에서는 결과가 반전된 CMY이고 K는 그대로입니다.
+fn ycc_to_cmy([y, cb, cr, k]: [u8; 4]) -> [u8; 4] {
+    let (y, cb, cr) = (f32::from(y), f32::from(cb) - 128.0, f32::from(cr) - 128.0);
+    let clamp = |v: f32| v.round().clamp(0.0, 255.0) as u8;
+    [
+        clamp(y + 1.402 * cr),
+        clamp(y - 0.344_136 * cb - 0.714_136 * cr),
+        clamp(y + 1.772 * cb),
+        k,
+    ]
+}
+
+fn convert_naive(pixels: &[[u8; 4]], inverted: bool) -> Vec<[u8; 3]> {
+    pixels
+        .iter()
+        .map(|px| {
+            // 잉크가 없는 정도(0~255)로 맞춘 뒤 K와 곱합니다.
+            let [c, m, y, k] = if inverted { *px } else { px.map(|v| 255 - v) };
+            let mul = |v: u8| ((u16::from(v) * u16::from(k) + 127) / 255) as u8;
+            [mul(c), mul(m), mul(y)]
+        })
+        .collect()
+}
+
+/// SOS 전까지 마커를 훑어 SOF의 채널 수와 Adobe APP14 transform 값을 찾습니다.
+fn inspect(bytes: &[u8]) -> Option<JpegLayout> {
+    if !bytes.starts_with(&[0xFF, 0xD8]) {
+        return None;
+    }
+    let mut adobe_transform = None;
+    let mut pos = 2;
+    while pos + 4 <= bytes.len() {
+        if bytes[pos] != 0xFF {
+            return None;
+        }
+        let marker = bytes[pos + 1];
+        if marker == 0xFF {
+            pos += 1;
+            continue;
+        }
+        if marker == 0x01 || (0xD0..=0xD7).contains(&marker) {
+            pos += 2;
+            continue;
+        }
+        let length = usize::from(u16::from_be_bytes([bytes[pos + 2], bytes[pos + 3]]));
+        let payload = bytes.get(pos + 4..pos + 2 + length)?;
+        match marker {
+            0xEE if payload.len() >= 12 && payload.starts_with(b"Adobe") => {
+                adobe_transform = Some(payload[11]);
+            }
+            0xC0..=0xCF if !matches!(marker, 0xC4 | 0xC8 | 0xCC) => {
+                return Some(JpegLayout {
+                    components: *payload.get(5)?,
+                    adobe_transform,
+                });
+            }
+            0xDA | 0xD9 => return None,
+            _ => {}
+        }
+        pos += 2 + length;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image_engine::{self, StampSettingsInput};
+    use std::fs;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    /// 한 가지 색으로 채운 8x8 4채널 베이스라인 JPEG를 만듭니다. 블록마다 DC 계수만 씁니다.
+    fn uniform_cmyk_jpeg(stored: [u8; 4], adobe: Option<u8>) -> Vec<u8> {
+        let mut out = vec![0xFF, 0xD8];
+        let mut segment = |marker: u8, payload: &[u8]| {
+            out.extend([0xFF, marker]);
+            out.extend(((payload.len() + 2) as u16).to_be_bytes());
+            out.extend(payload);
+        };
+        if let Some(transform) = adobe {
+            segment(
+                0xEE,
+                &[b'A', b'd', b'o', b'b', b'e', 0, 100, 0, 0, 0, 0, transform],
+            );
+        }
+        let mut dqt = vec![0u8];
+        dqt.extend([1u8; 64]);
+        segment(0xDB, &dqt);
+        let mut sof = vec![8, 0, 8, 0, 8, 4];
+        for id in 1..=4u8 {
+            sof.extend([id, 0x11, 0]);
+        }
+        segment(0xC0, &sof);
+        // DC: 0~11 범주에 4비트 코드 0~11, AC: EOB 하나에 1비트 코드 `0`.
+        let mut dht_dc = vec![0x00, 0, 0, 0, 12, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        dht_dc.extend(0..12u8);
+        segment(0xC4, &dht_dc);
+        segment(
+            0xC4,
+            &[0x10, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x00],
+        );
+        let mut sos = vec![4];
+        for id in 1..=4u8 {
+            sos.extend([id, 0x00]);
+        }
+        sos.extend([0, 63, 0]);
+        segment(0xDA, &sos);
+
+        let mut bits: Vec<bool> = Vec::new();
+        let push = |value: u32, count: u32, bits: &mut Vec<bool>| {
+            for i in (0..count).rev() {
+                bits.push(value >> i & 1 == 1);
+            }
+        };
+        for value in stored {
+            let dc = (i32::from(value) - 128) * 8;
+            let category = 32 - dc.unsigned_abs().leading_zeros();
+            push(category, 4, &mut bits);
+            let amplitude = if dc < 0 { dc - 1 } else { dc } as u32;
+            push(amplitude & ((1 << category) - 1), category, &mut bits);
+            push(0, 1, &mut bits);
+        }
+        while !bits.len().is_multiple_of(8) {
+            bits.push(true);
+        }
+        for chunk in bits.chunks(8) {
+            let byte = chunk.iter().fold(0u8, |acc, bit| acc << 1 | u8::from(*bit));
+            out.push(byte);
+            if byte == 0xFF {
+                out.push(0x00);
+            }
+        }
+        out.extend([0xFF, 0xD9]);
+        out
+    }
+
+    #[test]
+    fn cmyk_jpeg_is_stamped_with_non_inverted_colors() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-cmyk-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+        let logo = root.join("logo.png");
+        RgbaImage::from_pixel(2, 2, Rgba([0, 0, 255, 255]))
+            .save(&logo)
+            .expect("logo");
+
+        // 빨강 = C 0, M 100%, Y 100%, K 0. Adobe 파일은 반전된 값으로 저장하고,
+        // YCCK는 반전된 CMY(255, 0, 0)를 YCbCr(76, 85, 255)로 바꿔 저장합니다.
+        let fixtures = [
+            (
+                "ycck.jpg",
+                uniform_cmyk_jpeg([76, 85, 255, 255], Some(ADOBE_TRANSFORM_YCCK)),
+            ),
+            ("adobe.jpg", uniform_cmyk_jpeg([255, 0, 0, 255], Some(0))),
+            ("plain.jpg", uniform_cmyk_jpeg([0, 255, 255, 0], None)),
+        ];
+        for (name, bytes) in fixtures {
+            assert!(is_cmyk(&bytes));
+            let input = root.join(name);
+            fs::write(&input, &bytes).expect("fixture");
+
+            let settings = StampSettingsInput {
+                position: "우하단".to_string(),
+                size_preset: "보통".to_string(),
+                ..Default::default()
+            };
+            let paths = vec![input.to_string_lossy().to_string()];
+            let results = image_engine::stamp_images(&paths, settings, &logo, None);
+            assert!(results[0].ok, "{name}: {:?}", results[0].error);
+
+            let output = image::open(results[0].output_path.as_ref().expect("output"))
+                .expect("open output")
+                .to_rgb8();
+            let px = output.get_pixel(0, 0).0;
+            assert!(
+                px[0] > 200 && px[1] < 60 && px[2] < 60,
+                "{name}: expected red, got {px:?}"
+            );
+        }
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
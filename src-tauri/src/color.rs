@@ -0,0 +1,182 @@
+use crate::cmyk_jpeg;
+use crate::i18n::Message;
+use image::{ColorType, DynamicImage, ImageBuffer, ImageDecoder, ImageReader, Rgba, RgbaImage};
+use lcms2::{Flags, Intent, PixelFormat, Profile, Transform};
+use std::io::Cursor;
+
+/// 메모리의 이미지를 디코딩해 채널당 16비트 원본은 RGBA16, 그 밖에는 RGBA8로 돌려줍니다.
+/// `convert_to_srgb`이면 내장 ICC 프로파일 기준으로 sRGB로 변환하고, 프로파일이 없으면 그대로 둡니다.
+/// CMYK JPEG는 어차피 RGB로 바꿔야 하므로 설정과 관계없이 CMYK 프로파일이 있으면 그것으로 변환합니다.
+pub(crate) fn decode_image(bytes: &[u8], convert_to_srgb: bool) -> Result<DynamicImage, Message> {
+    if cmyk_jpeg::is_cmyk(bytes) {
+        return cmyk_jpeg::decode_rgba(bytes).map(DynamicImage::ImageRgba8);
+    }
+    let mut decoder = ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(|e| Message::new("IMAGE_READ_FAILED").arg(e.to_string()))?
+        .into_decoder()
+        .map_err(|e| Message::new("IMAGE_READ_FAILED").arg(e.to_string()))?;
+    let icc = if convert_to_srgb {
+        decoder.icc_profile().ok().flatten()
+    } else {
+        None
+    };
+    let image = DynamicImage::from_decoder(decoder)
+        .map_err(|e| Message::new("IMAGE_READ_FAILED").arg(e.to_string()))?;
+
+    if is_16bit(image.color()) {
+        let mut image = image.into_rgba16();
+        if let Some(icc) = icc {
+            convert_icc_to_srgb_16(&mut image, &icc)?;
+        }
+        Ok(DynamicImage::ImageRgba16(image))
+    } else {
+        let mut image = image.into_rgba8();
+        if let Some(icc) = icc {
+            convert_icc_to_srgb(&mut image, &icc)?;
+        }
+        Ok(DynamicImage::ImageRgba8(image))
+    }
+}
+
+fn is_16bit(color: ColorType) -> bool {
+    matches!(
+        color,
+        ColorType::L16 | ColorType::La16 | ColorType::Rgb16 | ColorType::Rgba16
+    )
+}
+
+fn convert_icc_to_srgb(image: &mut RgbaImage, icc: &[u8]) -> Result<(), Message> {
+    let transform = Transform::<u8, u8>::new_flags(
+        &source_profile(icc)?,
+        PixelFormat::RGBA_8,
+        &Profile::new_srgb(),
+        PixelFormat::RGBA_8,
+        Intent::Perceptual,
+        Flags::COPY_ALPHA,
+    )
+    .map_err(|e| Message::new("SRGB_TRANSFORM_FAILED").arg(e.to_string()))?;
+
+    transform.transform_in_place(image.as_mut());
+    Ok(())
+}
+
+fn convert_icc_to_srgb_16(
+    image: &mut ImageBuffer<Rgba<u16>, Vec<u16>>,
+    icc: &[u8],
+) -> Result<(), Message> {
+    let transform = Transform::<u16, u16>::new_flags(
+        &source_profile(icc)?,
+        PixelFormat::RGBA_16,
+        &Profile::new_srgb(),
+        PixelFormat::RGBA_16,
+        Intent::Perceptual,
+        Flags::COPY_ALPHA,
+    )
+    .map_err(|e| Message::new("SRGB_TRANSFORM_FAILED").arg(e.to_string()))?;
+
+    transform.transform_in_place(image.as_mut());
+    Ok(())
+}
+
+fn source_profile(icc: &[u8]) -> Result<Profile, Message> {
+    Profile::new_icc(icc).map_err(|e| Message::new("ICC_PROFILE_INVALID").arg(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::codecs::png::PngEncoder;
+    use image::{ExtendedColorType, ImageEncoder};
+    use lcms2::{CIExyY, CIExyYTRIPLE, ToneCurve};
+    use std::fs;
+    use std::path::Path;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn open_file(path: &Path, convert_to_srgb: bool) -> Result<DynamicImage, Message> {
+        decode_image(&fs::read(path).expect("read image"), convert_to_srgb)
+    }
+
+    fn adobe_rgb_icc() -> Vec<u8> {
+        let white = CIExyY {
+            x: 0.3127,
+            y: 0.3290,
+            Y: 1.0,
+        };
+        let primaries = CIExyYTRIPLE {
+            Red: CIExyY {
+                x: 0.64,
+                y: 0.33,
+                Y: 1.0,
+            },
+            Green: CIExyY {
+                x: 0.21,
+                y: 0.71,
+                Y: 1.0,
+            },
+            Blue: CIExyY {
+                x: 0.15,
+                y: 0.06,
+                Y: 1.0,
+            },
+        };
+        let curve = ToneCurve::new(2.199_218_75);
+        Profile::new_rgb(&white, &primaries, &[&curve, &curve, &curve])
+            .expect("adobe rgb profile")
+            .icc()
+            .expect("icc bytes")
+    }
+
+    #[test]
+    fn adobe_rgb_pixels_are_converted_and_untagged_pass_through() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-color-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let pixel = [40u8, 180, 90, 200];
+        let raw: Vec<u8> = pixel.repeat(4);
+
+        let tagged = root.join("adobe.png");
+        let mut encoder = PngEncoder::new(fs::File::create(&tagged).expect("create"));
+        encoder.set_icc_profile(adobe_rgb_icc()).expect("set icc");
+        encoder
+            .write_image(&raw, 2, 2, ExtendedColorType::Rgba8)
+            .expect("write tagged png");
+
+        let untagged = root.join("plain.png");
+        RgbaImage::from_raw(2, 2, raw.clone())
+            .expect("raw")
+            .save(&untagged)
+            .expect("write plain png");
+
+        let converted = open_file(&tagged, true).expect("convert").to_rgba8();
+        let [r, g, b, a] = converted.get_pixel(0, 0).0;
+        // Adobe RGB의 녹색은 sRGB보다 채도가 높아, sRGB로 옮기면 녹색은 커지고 빨강은 작아집니다.
+        assert!(g > pixel[1], "green should move outward: {g}");
+        assert!(r < pixel[0], "red should drop: {r}");
+        assert_ne!(b, 0);
+        assert_eq!(a, pixel[3], "alpha should be kept");
+
+        assert_eq!(
+            open_file(&tagged, false)
+                .expect("raw")
+                .to_rgba8()
+                .get_pixel(0, 0)
+                .0,
+            pixel
+        );
+        assert_eq!(
+            open_file(&untagged, true)
+                .expect("plain")
+                .to_rgba8()
+                .get_pixel(0, 0)
+                .0,
+            pixel
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,98 @@
+use image::ImageFormat;
+
+const INCHES_PER_METER: f32 = 39.370_08;
+const CM_PER_INCH: f32 = 2.54;
+
+/// 파일에 기록된 가로 해상도(DPI)를 읽습니다. PNG `pHYs`와 JPEG JFIF 밀도만 보며,
+/// 단위 없이 비율만 적혀 있거나 값이 없으면 `None`입니다.
+pub(crate) fn read_dpi(bytes: &[u8], format: ImageFormat) -> Option<f32> {
+    let dpi = match format {
+        ImageFormat::Png => png_dpi(bytes),
+        ImageFormat::Jpeg => jfif_dpi(bytes),
+        _ => None,
+    }?;
+    (dpi.is_finite() && dpi > 0.0).then_some(dpi)
+}
+
+/// IDAT 전까지 청크를 훑어 `pHYs`(미터당 픽셀, 단위 1)를 찾습니다.
+fn png_dpi(bytes: &[u8]) -> Option<f32> {
+    let mut pos = 8;
+    while pos + 8 <= bytes.len() {
+        let length = u32::from_be_bytes(bytes[pos..pos + 4].try_into().ok()?) as usize;
+        let kind = &bytes[pos + 4..pos + 8];
+        let data = bytes.get(pos + 8..pos + 8 + length)?;
+        match kind {
+            b"pHYs" if length == 9 => {
+                let per_meter = u32::from_be_bytes(data[0..4].try_into().ok()?);
+                return (data[8] == 1).then(|| per_meter as f32 / INCHES_PER_METER);
+            }
+            b"IDAT" | b"IEND" => return None,
+            _ => {}
+        }
+        pos += 12 + length;
+    }
+    None
+}
+
+/// 첫 APP0 JFIF 세그먼트의 단위(1: 인치, 2: 센티미터)와 가로 밀도를 읽습니다.
+fn jfif_dpi(bytes: &[u8]) -> Option<f32> {
+    if !bytes.starts_with(&[0xFF, 0xD8]) {
+        return None;
+    }
+    let mut pos = 2;
+    while pos + 4 <= bytes.len() && bytes[pos] == 0xFF {
+        let marker = bytes[pos + 1];
+        let length = usize::from(u16::from_be_bytes([bytes[pos + 2], bytes[pos + 3]]));
+        let payload = bytes.get(pos + 4..pos + 2 + length)?;
+        match marker {
+            0xE0 if payload.len() >= 12 && payload.starts_with(b"JFIF\0") => {
+                let density = f32::from(u16::from_be_bytes([payload[8], payload[9]]));
+                return match payload[7] {
+                    1 => Some(density),
+                    2 => Some(density * CM_PER_INCH),
+                    _ => None,
+                };
+            }
+            0xDA | 0xD9 => return None,
+            _ => {}
+        }
+        pos += 2 + length;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+
+    #[test]
+    fn reads_png_phys_and_jfif_density() {
+        let mut png = Vec::new();
+        {
+            let mut encoder = png::Encoder::new(&mut png, 4, 4);
+            encoder.set_color(png::ColorType::Rgba);
+            encoder.set_pixel_dims(Some(png::PixelDimensions {
+                xppu: 11_811,
+                yppu: 11_811,
+                unit: png::Unit::Meter,
+            }));
+            let mut writer = encoder.write_header().expect("png header");
+            writer
+                .write_image_data(RgbaImage::from_pixel(4, 4, Rgba([0, 0, 0, 255])).as_raw())
+                .expect("png data");
+        }
+        let dpi = read_dpi(&png, ImageFormat::Png).expect("png dpi");
+        assert!((dpi - 300.0).abs() < 0.1, "{dpi}");
+
+        let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10];
+        jpeg.extend(b"JFIF\0");
+        jpeg.extend([1, 1, 1, 0, 150, 0, 150, 0, 0]);
+        jpeg.extend([0xFF, 0xD9]);
+        assert_eq!(read_dpi(&jpeg, ImageFormat::Jpeg), Some(150.0));
+
+        // 단위 0은 가로세로 비율만 뜻합니다.
+        jpeg[13] = 0;
+        assert_eq!(read_dpi(&jpeg, ImageFormat::Jpeg), None);
+    }
+}
@@ -0,0 +1,161 @@
+use crate::i18n::Message;
+use crate::image_engine::{self, StampSettings, StampSettingsInput};
+use crate::path_policy::{self, ExpandedInput};
+use crate::svg_logo;
+use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+use serde::Serialize;
+use std::io::Cursor;
+use std::path::Path;
+use std::time::Instant;
+
+/// 보정용 표본 이미지 한 변의 픽셀 수입니다. 명령이 바로 응답하도록 작게 둡니다.
+const SAMPLE_SIDE: u32 = 512;
+const BYTES_PER_MB: f64 = 1024.0 * 1024.0;
+
+/// 실행 전에 보여 줄 배치 규모와 대략적인 소요 시간입니다.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchEstimate {
+    pub image_count: usize,
+    pub pdf_count: usize,
+    /// 확장자가 지원 형식이 아닌 파일 수입니다.
+    pub unsupported_count: usize,
+    /// 없는 파일과 일치하는 파일이 없는 패턴 수입니다.
+    pub missing_count: usize,
+    /// 처리할 이미지/PDF 입력 파일 크기의 합입니다.
+    pub total_bytes: u64,
+    /// 이 PC에서 표본 한 장을 처리해 잰 입력 1MB당 시간(ms)입니다.
+    pub ms_per_mb: f64,
+    pub estimated_ms: u64,
+}
+
+/// 패턴을 펼쳐 지원 파일을 세고, 표본 처리 속도로 전체 시간을 어림합니다. 파일은 만들지 않습니다.
+/// 압축률과 PDF 페이지 수에 따라 실제 시간은 크게 다를 수 있어 화면에는 어림값으로만 보여 줍니다.
+pub fn estimate_batch(
+    paths: &[String],
+    settings_input: StampSettingsInput,
+    logo_path: &Path,
+) -> Result<BatchEstimate, Message> {
+    let size_mode = image_engine::parse_size_mode(settings_input.size_mode.as_deref())?;
+    let settings = StampSettings::try_from(settings_input)?;
+    let logo = svg_logo::open_logo(logo_path, size_mode)
+        .map_err(|e| Message::new("LOGO_READ_FAILED").arg(e))?;
+
+    let mut estimate = BatchEstimate::default();
+    for entry in path_policy::expand_globs(paths) {
+        let ExpandedInput::Path(input) = entry else {
+            estimate.missing_count += 1;
+            continue;
+        };
+        let path = Path::new(&input);
+        let metadata = match path.metadata() {
+            Ok(metadata) if metadata.is_file() => metadata,
+            _ => {
+                estimate.missing_count += 1;
+                continue;
+            }
+        };
+        if path_policy::detect_supported_image(path).is_some() {
+            estimate.image_count += 1;
+        } else if path_policy::is_supported_pdf(path) {
+            estimate.pdf_count += 1;
+        } else {
+            estimate.unsupported_count += 1;
+            continue;
+        }
+        estimate.total_bytes += metadata.len();
+    }
+
+    if estimate.total_bytes > 0 {
+        estimate.ms_per_mb = sample_ms_per_mb(&logo, &settings)?;
+        estimate.estimated_ms =
+            (estimate.total_bytes as f64 / BYTES_PER_MB * estimate.ms_per_mb).ceil() as u64;
+    }
+    Ok(estimate)
+}
+
+/// 잡음 표본을 PNG로 만들어 두고, 디코딩·합성·인코딩에 걸린 시간을 PNG 크기(MB)로 나눕니다.
+fn sample_ms_per_mb(logo: &RgbaImage, settings: &StampSettings) -> Result<f64, Message> {
+    let mut state = 0x9e37_79b9_u32;
+    let sample = RgbaImage::from_fn(SAMPLE_SIDE, SAMPLE_SIDE, |_, _| {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        let [r, g, b, _] = state.to_le_bytes();
+        Rgba([r, g, b, 255])
+    });
+    let encoded = encode_png(&sample)?;
+
+    let started = Instant::now();
+    let decoded = image::load_from_memory_with_format(&encoded, ImageFormat::Png)
+        .map_err(|e| Message::new("SAMPLE_READ_FAILED").arg(e.to_string()))?
+        .to_rgba8();
+    let stamped = image_engine::try_stamp_rgba(&decoded, logo, settings)?;
+    encode_png(&stamped)?;
+    let elapsed_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+    Ok(elapsed_ms / (encoded.len() as f64 / BYTES_PER_MB))
+}
+
+fn encode_png(image: &RgbaImage) -> Result<Vec<u8>, Message> {
+    let mut bytes = Vec::new();
+    DynamicImage::ImageRgba8(image.clone())
+        .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+        .map_err(|e| Message::new("SAMPLE_CREATE_FAILED").arg(e.to_string()))?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn estimate_counts_only_supported_files() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-estimate-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let logo = root.join("logo.png");
+        let photo = root.join("photo.PNG");
+        let doc = root.join("doc.pdf");
+        let notes = root.join("notes.txt");
+        RgbaImage::from_pixel(8, 8, Rgba([255, 0, 0, 255]))
+            .save(&logo)
+            .expect("logo");
+        RgbaImage::from_pixel(64, 64, Rgba([200, 200, 200, 255]))
+            .save_with_format(&photo, ImageFormat::Png)
+            .expect("photo");
+        fs::write(&doc, b"%PDF-1.5 placeholder").expect("pdf");
+        fs::write(&notes, b"not an image").expect("txt");
+
+        let paths = vec![
+            photo.to_string_lossy().to_string(),
+            doc.to_string_lossy().to_string(),
+            notes.to_string_lossy().to_string(),
+            root.join("missing.png").to_string_lossy().to_string(),
+            root.join("*.webp").to_string_lossy().to_string(),
+        ];
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            ..Default::default()
+        };
+        let estimate = estimate_batch(&paths, settings, &logo).expect("estimate");
+
+        assert_eq!(estimate.image_count, 1);
+        assert_eq!(estimate.pdf_count, 1);
+        assert_eq!(estimate.unsupported_count, 1);
+        assert_eq!(estimate.missing_count, 2);
+        let expected_bytes =
+            fs::metadata(&photo).expect("meta").len() + fs::metadata(&doc).expect("meta").len();
+        assert_eq!(estimate.total_bytes, expected_bytes);
+        assert!(estimate.ms_per_mb > 0.0);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,726 @@
+use crate::i18n::Message;
+use crate::image_engine::{self, StampFileResult, StampSettings, StampSettingsInput};
+use crate::{
+    app_config, batch, builtin_logo, cancellation, estimate, i18n, job, path_policy, pdf_engine,
+    presets, preview, probe, remote_logo, self_test, watcher,
+};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tauri::{path::BaseDirectory, AppHandle, Emitter, Manager};
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WatchProcessedEvent {
+    watch_id: String,
+    result: StampFileResult,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchCompleteEvent {
+    request_id: String,
+    #[serde(flatten)]
+    complete: batch::BatchComplete,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchProgressEvent {
+    request_id: String,
+    total: usize,
+    done: usize,
+    input_path: String,
+    status: batch::ProgressStatus,
+    current_page: Option<u32>,
+    total_pages: Option<u32>,
+}
+
+// Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
+#[tauri::command]
+fn greet(name: &str) -> String {
+    format!("Hello, {}! You've been greeted from Rust!", name)
+}
+
+#[tauri::command]
+fn stamp_images(
+    app: AppHandle,
+    paths: Vec<String>,
+    settings: app_config::PartialSettings,
+    output_path: Option<String>,
+    locale: Option<String>,
+) -> Vec<StampFileResult> {
+    let locale = i18n::parse_locale(locale.as_deref());
+    let logo_path = match app.path().resolve("logo.webp", BaseDirectory::Resource) {
+        Ok(path) => path,
+        Err(e) => {
+            let message = Message::new("LOGO_RESOURCE_PATH_FAILED").arg(e.to_string());
+            return failure_results(paths, message, locale);
+        }
+    };
+
+    let settings = match resolve_settings(settings) {
+        Ok(settings) => settings,
+        Err(message) => return failure_results(paths, message, locale),
+    };
+    let mut results = match single_output_path(&paths, output_path) {
+        Ok(Some((input, output_path))) => {
+            vec![image_engine::stamp_image_to_path(
+                input,
+                settings,
+                &logo_path,
+                &output_path,
+            )]
+        }
+        Ok(None) => image_engine::stamp_images(&paths, settings, &logo_path, None),
+        Err(message) => failure_results(paths, message, locale),
+    };
+    for result in &mut results {
+        i18n::localize(result, locale);
+    }
+    results
+}
+
+#[tauri::command]
+fn stamp_pdfs(
+    app: AppHandle,
+    paths: Vec<String>,
+    settings: app_config::PartialSettings,
+    output_path: Option<String>,
+    locale: Option<String>,
+) -> Vec<StampFileResult> {
+    let locale = i18n::parse_locale(locale.as_deref());
+    let logo_path = match app.path().resolve("logo.webp", BaseDirectory::Resource) {
+        Ok(path) => path,
+        Err(e) => {
+            let message = Message::new("LOGO_RESOURCE_PATH_FAILED").arg(e.to_string());
+            return failure_results(paths, message, locale);
+        }
+    };
+
+    let settings = match resolve_settings(settings) {
+        Ok(settings) => settings,
+        Err(message) => return failure_results(paths, message, locale),
+    };
+    let mut results = match single_output_path(&paths, output_path) {
+        Ok(Some((input, output_path))) => {
+            vec![pdf_engine::stamp_pdf_to_path(
+                input,
+                settings,
+                &logo_path,
+                &output_path,
+            )]
+        }
+        Ok(None) => pdf_engine::stamp_pdfs(&paths, settings, &logo_path, None),
+        Err(message) => failure_results(paths, message, locale),
+    };
+    for result in &mut results {
+        i18n::localize(result, locale);
+    }
+    results
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+fn stamp_batch(
+    app: AppHandle,
+    paths: Vec<String>,
+    settings: app_config::PartialSettings,
+    logo_path: Option<String>,
+    builtin_logo: Option<String>,
+    logo_url: Option<String>,
+    output_dir: Option<String>,
+    options: Option<batch::BatchOptions>,
+) -> Vec<StampFileResult> {
+    let locale = batch_locale(options.as_ref());
+    let logo = match resolve_logo(&app, logo_path, builtin_logo, logo_url) {
+        Ok(logo) => logo,
+        Err(e) => {
+            let message = Message::new("LOGO_PATH_FAILED").arg(e);
+            return failure_results(paths, message, locale);
+        }
+    };
+
+    let settings = match resolve_settings(settings) {
+        Ok(settings) => settings,
+        Err(message) => return failure_results(paths, message, locale),
+    };
+    let output_dir = resolve_output_dir(output_dir);
+
+    let options = with_log_dir(&app, options);
+    let mut results = batch::stamp_batch(
+        &paths,
+        settings,
+        &logo.path,
+        output_dir.as_deref(),
+        &options,
+    );
+    add_logo_warning(&mut results, logo.warning.as_ref(), locale);
+    results
+}
+
+/// `stamp_batch`와 같고 성공/실패/건너뜀 집계와 걸린 시간을 함께 돌려줍니다.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+fn stamp_batch_summary(
+    app: AppHandle,
+    paths: Vec<String>,
+    settings: app_config::PartialSettings,
+    logo_path: Option<String>,
+    builtin_logo: Option<String>,
+    logo_url: Option<String>,
+    output_dir: Option<String>,
+    options: Option<batch::BatchOptions>,
+) -> batch::BatchOutcome {
+    let started = Instant::now();
+    let results = stamp_batch(
+        app,
+        paths,
+        settings,
+        logo_path,
+        builtin_logo,
+        logo_url,
+        output_dir,
+        options,
+    );
+    batch::BatchOutcome::new(results, started.elapsed())
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn stamp_batch_progress(
+    app: AppHandle,
+    paths: Vec<String>,
+    settings: app_config::PartialSettings,
+    logo_path: Option<String>,
+    builtin_logo: Option<String>,
+    logo_url: Option<String>,
+    output_dir: Option<String>,
+    request_id: String,
+    options: Option<batch::BatchOptions>,
+) -> Vec<StampFileResult> {
+    let started = Instant::now();
+    let locale = batch_locale(options.as_ref());
+    let logo = match resolve_logo(&app, logo_path, builtin_logo, logo_url) {
+        Ok(logo) => logo,
+        Err(e) => {
+            let message = Message::new("LOGO_PATH_FAILED").arg(e);
+            let results = failure_results(paths, message, locale);
+            emit_batch_complete(&app, &request_id, &results, started);
+            return results;
+        }
+    };
+
+    let settings = match resolve_settings(settings) {
+        Ok(settings) => settings,
+        Err(message) => {
+            let results = failure_results(paths, message, locale);
+            emit_batch_complete(&app, &request_id, &results, started);
+            return results;
+        }
+    };
+    let output_dir = resolve_output_dir(output_dir);
+
+    let mut options = with_log_dir(&app, options);
+    let app_for_emit = app.clone();
+    let paths_for_error = paths.clone();
+    let request_id_for_error = request_id.clone();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let cancellation = cancellation::begin(&request_id);
+        options.cancel_token = Some(cancellation.token());
+        let mut emit_progress = |progress: batch::ProgressUpdate| {
+            let payload = BatchProgressEvent {
+                request_id: request_id.clone(),
+                total: progress.total,
+                done: progress.done,
+                input_path: progress.input_path,
+                status: progress.status,
+                current_page: progress.current_page,
+                total_pages: progress.total_pages,
+            };
+            let _ = app_for_emit.emit("cornerbrand://progress", payload);
+        };
+        let mut emit_complete = |complete: batch::BatchComplete| {
+            let payload = BatchCompleteEvent {
+                request_id: request_id.clone(),
+                complete,
+            };
+            let _ = app_for_emit.emit("cornerbrand://complete", payload);
+        };
+
+        let mut results = batch::stamp_batch_with_events(
+            &paths,
+            settings,
+            &logo.path,
+            output_dir.as_deref(),
+            &options,
+            &mut emit_progress,
+            &mut emit_complete,
+        );
+        add_logo_warning(&mut results, logo.warning.as_ref(), locale);
+        results
+    })
+    .await
+    .unwrap_or_else(|error| {
+        let message = Message::new("BATCH_TASK_STOPPED").arg(error.to_string());
+        let results = failure_results(paths_for_error, message, locale);
+        emit_batch_complete(&app, &request_id_for_error, &results, started);
+        results
+    })
+}
+
+/// 배치를 시작하지 못했거나 작업이 중단된 경우에도 화면이 기다리지 않도록 완료 알림을 보냅니다.
+fn emit_batch_complete(
+    app: &AppHandle,
+    request_id: &str,
+    results: &[StampFileResult],
+    started: Instant,
+) {
+    let payload = BatchCompleteEvent {
+        request_id: request_id.to_string(),
+        complete: batch::BatchComplete::from_results(results, started.elapsed()),
+    };
+    let _ = app.emit("cornerbrand://complete", payload);
+}
+
+/// `stamp_batch_progress`로 시작한 배치를 취소합니다. 처리 중인 파일은 마저 끝내고 나머지는 `CANCELLED`로 남깁니다.
+#[tauri::command]
+fn cancel_batch(request_id: String) -> bool {
+    cancellation::cancel(&request_id)
+}
+
+/// 진행 중인 모든 배치를 취소하고 새로 취소된 배치 수를 돌려줍니다. 앱 종료나 전체 중지에 씁니다.
+#[tauri::command]
+fn cancel_all_batches() -> usize {
+    cancellation::cancel_all()
+}
+
+/// 취소할 수 있는 진행 중 배치의 `request_id` 목록입니다. 새로고침 뒤 화면 상태를 맞출 때 씁니다.
+#[tauri::command]
+fn list_active_requests() -> Vec<String> {
+    cancellation::active_requests()
+}
+
+#[tauri::command]
+fn stamp_from_job_file(app: AppHandle, job_path: String) -> Result<Vec<StampFileResult>, Message> {
+    let job = job::load_job(&PathBuf::from(job_path))?;
+    let logo = resolve_logo(&app, job.logo_path, job.builtin_logo, job.logo_url)
+        .map_err(|e| Message::new("LOGO_PATH_FAILED").arg(e))?;
+    let output_dir = job.output_dir.map(PathBuf::from);
+    let locale = batch_locale(job.options.as_ref());
+    let options = with_log_dir(&app, job.options);
+
+    let mut results = batch::stamp_batch(
+        &job.paths,
+        job.settings,
+        &logo.path,
+        output_dir.as_deref(),
+        &options,
+    );
+    add_logo_warning(&mut results, logo.warning.as_ref(), locale);
+    Ok(results)
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+fn watch_folder(
+    app: AppHandle,
+    dir: String,
+    settings: app_config::PartialSettings,
+    logo_path: Option<String>,
+    builtin_logo: Option<String>,
+    logo_url: Option<String>,
+    output_dir: Option<String>,
+    watch_id: String,
+    locale: Option<String>,
+) -> Result<(), Message> {
+    let locale = i18n::parse_locale(locale.as_deref());
+    let logo = resolve_logo(&app, logo_path, builtin_logo, logo_url)
+        .map_err(|e| Message::new("LOGO_PATH_FAILED").arg(e))?;
+    let settings = resolve_settings(settings)?;
+    let output_dir = resolve_output_dir(output_dir);
+
+    let app_for_emit = app.clone();
+    let id_for_emit = watch_id.clone();
+    let watch = watcher::start_watch(
+        &PathBuf::from(dir.trim()),
+        settings,
+        logo.path,
+        output_dir,
+        move |mut result| {
+            i18n::localize(&mut result, locale);
+            add_logo_warning(
+                std::slice::from_mut(&mut result),
+                logo.warning.as_ref(),
+                locale,
+            );
+            let payload = WatchProcessedEvent {
+                watch_id: id_for_emit.clone(),
+                result,
+            };
+            let _ = app_for_emit.emit("cornerbrand://watch", payload);
+        },
+    )?;
+
+    watcher::register(watch_id, watch);
+    Ok(())
+}
+
+#[tauri::command]
+fn stop_watch_folder(watch_id: String) -> bool {
+    watcher::stop(&watch_id)
+}
+
+#[tauri::command]
+fn compare_preview(
+    app: AppHandle,
+    path: String,
+    settings: app_config::PartialSettings,
+    logo_path: Option<String>,
+    builtin_logo: Option<String>,
+    logo_url: Option<String>,
+) -> Result<Vec<u8>, Message> {
+    let logo_path = resolve_logo_path(&app, logo_path, builtin_logo, logo_url)
+        .map_err(|e| Message::new("LOGO_PATH_FAILED").arg(e))?;
+    let settings = resolve_settings(settings)?;
+    preview::compare_preview(&PathBuf::from(path), settings, &logo_path)
+}
+
+/// PDF 미리보기 화면용으로, 로고를 넣은 한 페이지를 PNG 바이트로 돌려줍니다.
+#[tauri::command]
+fn preview_pdf_page(
+    app: AppHandle,
+    path: String,
+    page_number: u32,
+    settings: app_config::PartialSettings,
+    logo_path: Option<String>,
+    builtin_logo: Option<String>,
+    logo_url: Option<String>,
+) -> Result<Vec<u8>, Message> {
+    let logo_path = resolve_logo_path(&app, logo_path, builtin_logo, logo_url)
+        .map_err(|e| Message::new("LOGO_PATH_FAILED").arg(e))?;
+    let settings = resolve_settings(settings)?;
+    preview::preview_pdf_page(&PathBuf::from(path), page_number, settings, &logo_path)
+}
+
+/// 실시간 편집 화면의 오버레이용으로, 렌더링 없이 로고가 놓일 영역을 돌려줍니다.
+/// 로고 원본(파일, 내장 로고, URL)을 함께 주면 그 파일을 읽어 `trimLogo` 여백 자르기까지 반영합니다.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+fn compute_stamp_rect(
+    app: AppHandle,
+    source_width: u32,
+    source_height: u32,
+    settings: app_config::PartialSettings,
+    logo_width: u32,
+    logo_height: u32,
+    logo_path: Option<String>,
+    builtin_logo: Option<String>,
+    logo_url: Option<String>,
+) -> Result<image_engine::StampRect, Message> {
+    let settings = StampSettings::try_from(resolve_settings(settings)?)?;
+    let source_size = (source_width, source_height);
+    if [&logo_path, &builtin_logo, &logo_url]
+        .iter()
+        .all(|source| source.is_none())
+    {
+        return image_engine::compute_stamp_rect(source_size, &settings, (logo_width, logo_height));
+    }
+    let logo_path = resolve_logo_path(&app, logo_path, builtin_logo, logo_url)
+        .map_err(|e| Message::new("LOGO_PATH_FAILED").arg(e))?;
+    image_engine::compute_stamp_rect_for_logo(source_size, &settings, &logo_path)
+}
+
+/// 실행 전에 "약 1,240개, 8분"처럼 보여 줄 파일 수와 어림 시간입니다.
+#[tauri::command]
+fn estimate_batch(
+    app: AppHandle,
+    paths: Vec<String>,
+    settings: app_config::PartialSettings,
+    logo_path: Option<String>,
+    builtin_logo: Option<String>,
+    logo_url: Option<String>,
+) -> Result<estimate::BatchEstimate, Message> {
+    let logo_path = resolve_logo_path(&app, logo_path, builtin_logo, logo_url)
+        .map_err(|e| Message::new("LOGO_PATH_FAILED").arg(e))?;
+    let settings = resolve_settings(settings)?;
+    estimate::estimate_batch(&paths, settings, &logo_path)
+}
+
+/// 지원 문의용으로 이미지/PDF 엔진이 이 PC에서 동작하는지 확인합니다.
+#[tauri::command]
+fn self_test() -> self_test::SelfTestReport {
+    self_test::self_test()
+}
+
+#[tauri::command]
+fn cleanup_previews(max_age_secs: u64) -> Result<usize, Message> {
+    preview::cleanup_previews(&preview::preview_root(), Duration::from_secs(max_age_secs))
+}
+
+#[tauri::command]
+fn probe_file(path: String) -> Result<probe::FileProbe, Message> {
+    probe::probe_file(&PathBuf::from(path.trim()))
+}
+
+#[tauri::command]
+fn supported_formats() -> Vec<path_policy::SupportedExtension> {
+    path_policy::supported_formats()
+}
+
+#[tauri::command]
+fn save_preset(
+    app: AppHandle,
+    name: String,
+    settings: StampSettingsInput,
+    overwrite: Option<bool>,
+) -> Result<(), Message> {
+    let config_dir = resolve_app_config_dir(&app)?;
+    presets::save_preset(&config_dir, &name, settings, overwrite.unwrap_or(false))
+}
+
+/// 로고 선택 화면에 보여 줄 번들 로고 이름 목록입니다.
+#[tauri::command]
+fn list_builtin_logos() -> Vec<String> {
+    builtin_logo::builtin_logo_names()
+}
+
+#[tauri::command]
+fn list_presets(app: AppHandle) -> Result<Vec<String>, Message> {
+    let config_dir = resolve_app_config_dir(&app)?;
+    presets::list_presets(&config_dir)
+}
+
+#[tauri::command]
+fn load_preset(app: AppHandle, name: String) -> Result<StampSettingsInput, Message> {
+    let config_dir = resolve_app_config_dir(&app)?;
+    presets::load_preset(&config_dir, &name)
+}
+
+/// 앱 설정 파일(`cornerbrand.config.json`)의 기본값을 돌려줍니다. 파일이 없으면 모두 비어 있습니다.
+#[tauri::command]
+fn load_config(app: AppHandle) -> Result<app_config::AppConfig, Message> {
+    let config_dir = resolve_app_config_dir(&app)?;
+    let config = app_config::load_config(&config_dir)?;
+    app_config::set_current(config.clone());
+    Ok(config)
+}
+
+/// 기본값을 저장하고 곧바로 이후 명령에 적용합니다.
+#[tauri::command]
+fn save_config(app: AppHandle, config: app_config::AppConfig) -> Result<(), Message> {
+    let config_dir = resolve_app_config_dir(&app)?;
+    app_config::save_config(&config_dir, &config)?;
+    app_config::set_current(config);
+    Ok(())
+}
+
+/// 프론트엔드가 보내지 않은 설정 필드를 앱 설정 기본값으로 채웁니다.
+fn resolve_settings(settings: app_config::PartialSettings) -> Result<StampSettingsInput, Message> {
+    app_config::merge_settings(&app_config::current(), settings)
+}
+
+/// 비운 출력 폴더는 앱 설정의 `output_dir`로 채웁니다.
+fn resolve_output_dir(output_dir: Option<String>) -> Option<PathBuf> {
+    app_config::output_dir_or_default(&app_config::current(), output_dir)
+        .and_then(normalize_optional_path)
+        .map(PathBuf::from)
+}
+
+fn with_log_dir(app: &AppHandle, options: Option<batch::BatchOptions>) -> batch::BatchOptions {
+    let mut options = options.unwrap_or_default();
+    options.log_dir = app.path().app_data_dir().ok();
+    options
+}
+
+fn resolve_app_config_dir(app: &AppHandle) -> Result<PathBuf, Message> {
+    app.path()
+        .app_config_dir()
+        .map_err(|e| Message::new("APP_CONFIG_DIR_FAILED").arg(e.to_string()))
+}
+
+fn resolve_logo_path(
+    app: &AppHandle,
+    logo_path: Option<String>,
+    builtin_logo: Option<String>,
+    logo_url: Option<String>,
+) -> Result<PathBuf, Message> {
+    resolve_logo(app, logo_path, builtin_logo, logo_url).map(|logo| logo.path)
+}
+
+/// 로고 경로와 함께, URL 로고를 받지 못해 저장해 둔 사본을 썼을 때의 경고를 돌려줍니다.
+fn resolve_logo(
+    app: &AppHandle,
+    logo_path: Option<String>,
+    builtin_logo: Option<String>,
+    logo_url: Option<String>,
+) -> Result<remote_logo::FetchedLogo, Message> {
+    let logo_path = logo_path.and_then(normalize_optional_path);
+    let builtin_logo = builtin_logo.and_then(normalize_optional_path);
+    let logo_url = logo_url.and_then(normalize_optional_path);
+    let sources = [&logo_path, &builtin_logo, &logo_url];
+    if sources.iter().filter(|source| source.is_some()).count() > 1 {
+        return Err(Message::new("LOGO_SOURCE_CONFLICT"));
+    }
+
+    if let Some(url) = logo_url {
+        let cache_dir = app
+            .path()
+            .app_cache_dir()
+            .map_err(|e| Message::new("APP_CACHE_DIR_FAILED").arg(e.to_string()))?;
+        return remote_logo::fetch_logo(&url, &cache_dir);
+    }
+
+    if let Some(name) = builtin_logo {
+        return builtin_logo::resolve_builtin_logo(&name, |resource| {
+            app.path()
+                .resolve(resource, BaseDirectory::Resource)
+                .map_err(|e| Message::new("RESOURCE_PATH_FAILED").arg(e.to_string()))
+        })
+        .map(local_logo);
+    }
+
+    if let Some(user_logo_path) = logo_path {
+        let candidate = PathBuf::from(user_logo_path);
+        if candidate.is_file() {
+            return Ok(local_logo(candidate));
+        }
+        return Err(Message::new("LOGO_FILE_MISSING"));
+    }
+
+    let mut candidates = Vec::new();
+    if let Ok(path) = app.path().resolve("logo.png", BaseDirectory::Resource) {
+        candidates.push(path);
+    }
+    if let Ok(path) = app.path().resolve("logo.webp", BaseDirectory::Resource) {
+        candidates.push(path);
+    }
+
+    let cwd = std::env::current_dir().map_err(|e| Message::new("CURRENT_DIR_FAILED").arg(e))?;
+    candidates.push(cwd.join("logo.png"));
+    candidates.push(cwd.join("logo.webp"));
+
+    candidates
+        .into_iter()
+        .find(|path| path.is_file())
+        .map(local_logo)
+        .ok_or_else(|| Message::new("DEFAULT_LOGO_NOT_FOUND"))
+}
+
+fn local_logo(path: PathBuf) -> remote_logo::FetchedLogo {
+    remote_logo::FetchedLogo {
+        path,
+        warning: None,
+    }
+}
+
+/// 배치 옵션의 결과 메시지 언어입니다. 옵션이 없으면 한국어입니다.
+fn batch_locale(options: Option<&batch::BatchOptions>) -> i18n::Locale {
+    i18n::parse_locale(options.and_then(|options| options.locale.as_deref()))
+}
+
+/// 로고 경고를 배치의 모든 결과에 덧붙입니다. 배치가 결과를 바꾼 뒤라 여기서 언어를 맞춥니다.
+fn add_logo_warning(
+    results: &mut [StampFileResult],
+    warning: Option<&Message>,
+    locale: i18n::Locale,
+) {
+    if let Some(warning) = warning {
+        let warning = Message::from(warning.render(locale));
+        for result in results {
+            result.warnings.push(warning.clone());
+        }
+    }
+}
+
+/// 단일 파일 명령의 `output_path`를 확인합니다. 지정했다면 입력이 정확히 하나여야 합니다.
+fn single_output_path(
+    paths: &[String],
+    output_path: Option<String>,
+) -> Result<Option<(&str, PathBuf)>, Message> {
+    let Some(output_path) = output_path.and_then(normalize_optional_path) else {
+        return Ok(None);
+    };
+    match paths {
+        [input] => Ok(Some((input.as_str(), PathBuf::from(output_path)))),
+        _ => Err(Message::new("OUTPUT_PATH_NEEDS_SINGLE_FILE")),
+    }
+}
+
+fn failure_results(
+    paths: Vec<String>,
+    message: Message,
+    locale: i18n::Locale,
+) -> Vec<StampFileResult> {
+    let message = Message::from(message.render(locale));
+    paths
+        .into_iter()
+        .map(|input_path| StampFileResult {
+            input_path,
+            ok: false,
+            output_path: None,
+            error: Some(message.clone()),
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn normalize_optional_path(value: String) -> Option<String> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+#[cfg_attr(mobile, tauri::mobile_entry_point)]
+pub fn run() {
+    tauri::Builder::default()
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_updater::Builder::new().build())
+        .setup(|app| {
+            if let Ok(config) = resolve_app_config_dir(app.handle())
+                .and_then(|config_dir| app_config::load_config(&config_dir))
+            {
+                app_config::set_current(config);
+            }
+            std::thread::spawn(|| {
+                let _ =
+                    preview::cleanup_previews(&preview::preview_root(), preview::STALE_PREVIEW_AGE);
+            });
+            Ok(())
+        })
+        .invoke_handler(tauri::generate_handler![
+            greet,
+            stamp_images,
+            stamp_pdfs,
+            stamp_batch,
+            stamp_batch_summary,
+            stamp_batch_progress,
+            cancel_batch,
+            cancel_all_batches,
+            list_active_requests,
+            stamp_from_job_file,
+            watch_folder,
+            stop_watch_folder,
+            supported_formats,
+            probe_file,
+            compare_preview,
+            preview_pdf_page,
+            compute_stamp_rect,
+            estimate_batch,
+            self_test,
+            cleanup_previews,
+            save_preset,
+            list_presets,
+            list_builtin_logos,
+            load_preset,
+            load_config,
+            save_config
+        ])
+        .run(tauri::generate_context!())
+        .expect("error while running tauri application");
+}
@@ -0,0 +1,138 @@
+use crate::i18n::Message;
+use image::RgbaImage;
+use std::path::Path;
+
+/// HEIC/HEIF 파일의 대표 이미지를 RGBA로 디코딩합니다. 회전/자르기 등 파일에 기록된 변환도 적용됩니다.
+#[cfg(feature = "heic")]
+pub(crate) fn decode_rgba(path: &Path) -> Result<RgbaImage, Message> {
+    use libheif_rs::{ColorSpace, HeifContext, LibHeif, RgbChroma};
+
+    let path = path
+        .to_str()
+        .ok_or_else(|| Message::new("HEIC_PATH_INVALID"))?;
+    let context =
+        HeifContext::read_from_file(path).map_err(|e| Message::new("HEIC_READ_FAILED").arg(e))?;
+    let handle = context
+        .primary_image_handle()
+        .map_err(|e| Message::new("HEIC_PRIMARY_NOT_FOUND").arg(e))?;
+    let image = LibHeif::new()
+        .decode(&handle, ColorSpace::Rgb(RgbChroma::Rgba), None)
+        .map_err(|e| Message::new("HEIC_DECODE_FAILED").arg(e))?;
+
+    let planes = image.planes();
+    let plane = planes
+        .interleaved
+        .ok_or_else(|| Message::new("HEIC_RGBA_NOT_FOUND"))?;
+
+    let row_bytes = plane.width as usize * 4;
+    let mut pixels = Vec::with_capacity(row_bytes * plane.height as usize);
+    for row in plane.data.chunks(plane.stride).take(plane.height as usize) {
+        pixels.extend_from_slice(&row[..row_bytes]);
+    }
+
+    RgbaImage::from_raw(plane.width, plane.height, pixels)
+        .ok_or_else(|| Message::new("HEIC_SIZE_INVALID"))
+}
+
+#[cfg(not(feature = "heic"))]
+pub(crate) fn decode_rgba(_path: &Path) -> Result<RgbaImage, Message> {
+    Err(Message::new("HEIC_NOT_BUILT"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_root(label: &str) -> std::path::PathBuf {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-{label}-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+        root
+    }
+
+    #[cfg(feature = "heic")]
+    #[test]
+    fn heic_round_trip_decodes_to_rgba() {
+        use libheif_rs::{
+            Channel, ColorSpace, CompressionFormat, EncoderQuality, HeifContext, Image, LibHeif,
+            RgbChroma,
+        };
+
+        let root = temp_root("heic");
+        let path = root.join("photo.heic");
+
+        let mut image = Image::new(16, 8, ColorSpace::Rgb(RgbChroma::Rgba)).expect("image");
+        image
+            .create_plane(Channel::Interleaved, 16, 8, 8)
+            .expect("plane");
+        {
+            let planes = image.planes_mut();
+            let plane = planes.interleaved.expect("interleaved");
+            for row in plane.data.chunks_mut(plane.stride).take(8) {
+                for px in row[..16 * 4].chunks_mut(4) {
+                    px.copy_from_slice(&[200, 120, 40, 255]);
+                }
+            }
+        }
+
+        let lib_heif = LibHeif::new();
+        let mut encoder = lib_heif
+            .encoder_for_format(CompressionFormat::Hevc)
+            .expect("hevc encoder");
+        encoder
+            .set_quality(EncoderQuality::LossLess)
+            .expect("quality");
+        let mut context = HeifContext::new().expect("context");
+        context
+            .encode_image(&image, &mut encoder, None)
+            .expect("encode");
+        context
+            .write_to_file(path.to_str().expect("utf-8 path"))
+            .expect("write heic");
+
+        let decoded = decode_rgba(&path).expect("decode");
+        assert_eq!(decoded.dimensions(), (16, 8));
+        let [r, g, b, a] = decoded.get_pixel(8, 4).0;
+        assert!(r.abs_diff(200) <= 8 && g.abs_diff(120) <= 8 && b.abs_diff(40) <= 8);
+        assert_eq!(a, 255);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[cfg(not(feature = "heic"))]
+    #[test]
+    fn heic_input_reports_missing_feature() {
+        use crate::image_engine::{stamp_images, StampSettingsInput};
+
+        let root = temp_root("heic-missing");
+        let path = root.join("photo.HEIC");
+        fs::write(&path, b"not decoded").expect("write input");
+        let logo = root.join("logo.png");
+        RgbaImage::from_pixel(4, 4, image::Rgba([255, 0, 0, 255]))
+            .save(&logo)
+            .expect("write logo");
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            ..Default::default()
+        };
+        let results = stamp_images(&[path.to_string_lossy().to_string()], settings, &logo, None);
+        assert!(!results[0].ok);
+        assert!(
+            results[0]
+                .error
+                .as_ref()
+                .is_some_and(|e| e.to_string().contains("HEIC")),
+            "unexpected error: {:?}",
+            results[0].error
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,502 @@
+use crate::batch::{
+    CODE_CANCELLED, CODE_DUPLICATE, CODE_NO_MATCH, CODE_OUTPUT_NOT_WRITABLE, CODE_RESUME_FAILED,
+    CODE_TIMEOUT,
+};
+use crate::image_engine::{
+    StampFileResult, CODE_ALREADY_STAMPED, CODE_LIKELY_WATERMARKED, CODE_LOGO_IS_INPUT,
+    CODE_LOGO_NOT_VISIBLE, CODE_UNSUPPORTED_FORMAT, CODE_UNSUPPORTED_OFFICE,
+    CODE_UNSUPPORTED_VIDEO,
+};
+use crate::verify::CODE_VERIFY_FAILED;
+use serde::{Serialize, Serializer};
+use std::{fmt, io};
+
+/// 결과 메시지 언어입니다. 비었거나 모르는 값이면 한국어입니다.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum Locale {
+    #[default]
+    Ko,
+    En,
+    Ja,
+}
+
+/// "en", "en-US", "ja_JP"처럼 언어 부분만 봅니다. 비었거나 모르는 값이면 한국어입니다.
+pub(crate) fn parse_locale(value: Option<&str>) -> Locale {
+    let language = value
+        .unwrap_or_default()
+        .trim()
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    match language.as_str() {
+        "en" => Locale::En,
+        "ja" => Locale::Ja,
+        _ => Locale::Ko,
+    }
+}
+
+/// 엔진이 돌려주는 오류·경고 한 줄입니다. 키와 값만 담아 두고, 명령이나 보고서처럼
+/// 밖으로 내보내는 곳에서 `localize`로 한 번 해당 언어 글자로 바꿉니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    /// `CATALOG`의 키와 `{}` 자리에 차례로 들어갈 값입니다. 결과 코드가 있는 메시지는 코드가 곧 키입니다.
+    Keyed(&'static str, Vec<Message>),
+    /// 바꾸지 않고 그대로 보여 줄 글자(경로, 숫자, 외부 라이브러리 오류 등)입니다.
+    Text(String),
+    /// 조각마다 바꾼 뒤 " / "로 이어 붙이는 목록입니다. PDF 페이지별 오류에 씁니다.
+    List(Vec<Message>),
+}
+
+impl Message {
+    pub(crate) fn new(key: &'static str) -> Self {
+        Self::Keyed(key, Vec::new())
+    }
+
+    /// 다음 `{}` 자리에 들어갈 값을 더합니다. 다른 메시지를 넘기면 그 메시지도 같은 언어로 바꿉니다.
+    pub(crate) fn arg(mut self, value: impl Into<Message>) -> Self {
+        if let Self::Keyed(_, args) = &mut self {
+            args.push(value.into());
+        }
+        self
+    }
+
+    /// 카탈로그에 없는 키는 키 이름을 그대로 씁니다.
+    pub(crate) fn render(&self, locale: Locale) -> String {
+        match self {
+            Self::Text(text) => text.clone(),
+            Self::List(items) => items
+                .iter()
+                .map(|item| item.render(locale))
+                .collect::<Vec<_>>()
+                .join(" / "),
+            Self::Keyed(key, args) => {
+                let Some(template) = template(key, locale) else {
+                    return key.to_string();
+                };
+                let mut pieces = template.split("{}");
+                let mut rendered = pieces.next().unwrap_or_default().to_string();
+                let mut args = args.iter();
+                for piece in pieces {
+                    if let Some(arg) = args.next() {
+                        rendered.push_str(&arg.render(locale));
+                    }
+                    rendered.push_str(piece);
+                }
+                rendered
+            }
+        }
+    }
+
+    fn localize(&mut self, locale: Locale) {
+        if !matches!(self, Self::Text(_)) {
+            *self = Self::Text(self.render(locale));
+        }
+    }
+}
+
+impl From<String> for Message {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<&str> for Message {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+
+/// `io::Error::other`로 감싼 메시지(취소된 쓰기 등)는 그대로 꺼내고, 나머지 입출력 오류는 글자로 둡니다.
+impl From<io::Error> for Message {
+    fn from(error: io::Error) -> Self {
+        match error
+            .get_ref()
+            .and_then(|inner| inner.downcast_ref::<Message>())
+        {
+            Some(message) => message.clone(),
+            None => Self::Text(error.to_string()),
+        }
+    }
+}
+
+impl std::error::Error for Message {}
+
+/// 언어를 정하지 않고 글자로 쓸 때(로그, CLI 오류 등)는 한국어입니다.
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(Locale::Ko))
+    }
+}
+
+/// 프론트엔드와 보고서에는 글자로 나갑니다. `localize`를 거치지 않았으면 한국어입니다.
+impl Serialize for Message {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// 메시지 키별 (한국어, 영어, 일본어) 틀입니다. `{}` 자리에는 `Message::arg`로 더한 값이 같은 순서로 들어갑니다.
+const CATALOG: &[(&str, &str, &str, &str)] = &[
+    ("CONFIG_READ_FAILED", "앱 설정 파일을 읽지 못했습니다: {}", "Could not read the app settings file: {}", "アプリ設定ファイルを読み込めませんでした: {}"),
+    ("CONFIG_MALFORMED", "앱 설정 파일 형식이 올바르지 않습니다: {}", "The app settings file is malformed: {}", "アプリ設定ファイルの形式が正しくありません: {}"),
+    ("SETTINGS_DIR_CREATE_FAILED", "설정 폴더를 만들지 못했습니다: {}", "Could not create the settings folder: {}", "設定フォルダーを作成できませんでした: {}"),
+    ("CONFIG_SERIALIZE_FAILED", "앱 설정을 직렬화하지 못했습니다: {}", "Could not serialize the app settings: {}", "アプリ設定をシリアライズできませんでした: {}"),
+    ("CONFIG_SAVE_FAILED", "앱 설정 파일을 저장하지 못했습니다: {}", "Could not save the app settings file: {}", "アプリ設定ファイルを保存できませんでした: {}"),
+    ("SETTINGS_INVALID", "설정이 올바르지 않습니다: {}", "Invalid settings: {}", "設定が正しくありません: {}"),
+    ("GLOBAL_SETTINGS_NOT_OBJECT", "전역 설정을 합칠 수 없습니다.", "Could not merge the global settings.", "全体設定を結合できません。"),
+    ("GLOBAL_SETTINGS_INVALID", "전역 설정을 읽지 못했습니다: {}", "Could not read the global settings: {}", "全体設定を読み込めませんでした: {}"),
+    ("FILE_SETTINGS_INVALID", "파일별 설정이 올바르지 않습니다: {}", "Invalid per-file settings: {}", "ファイル別設定が正しくありません: {}"),
+    (CODE_CANCELLED, "사용자가 취소해 처리하지 않았습니다.", "Not processed because the batch was cancelled.", "キャンセルされたため処理しませんでした。"),
+    (CODE_DUPLICATE, "앞에서 처리한 파일과 같은 파일이라 건너뛰었습니다: {}", "Skipped because the same file was already processed: {}", "同じファイルを既に処理したためスキップしました: {}"),
+    ("CONTACT_SHEET_FAILED", "확인용 시트를 쓰지 못했습니다: {}", "Could not write the contact sheet: {}", "確認用シートを書き込めませんでした: {}"),
+    (CODE_RESUME_FAILED, "이어서 처리할 보고서를 읽지 못했습니다: {}", "Could not read the report to resume from: {}", "再開用のレポートを読み込めませんでした: {}"),
+    ("RESUME_REPORT_MALFORMED", "이어서 처리할 보고서 형식이 올바르지 않습니다: {}", "The report to resume from is malformed: {}", "再開用のレポートの形式が正しくありません: {}"),
+    (CODE_TIMEOUT, "처리 시간이 제한({}ms)을 넘어 건너뛰었습니다.", "Skipped because processing exceeded the time limit ({} ms).", "処理時間が制限({}ms)を超えたためスキップしました。"),
+    ("TASK_PANICKED", "파일 처리 작업이 비정상 종료되었습니다.", "The file processing task ended unexpectedly.", "ファイル処理が異常終了しました。"),
+    (CODE_UNSUPPORTED_FORMAT, "지원하지 않는 파일 형식입니다. (jpg/jpeg/png/webp/tiff/pdf)", "Unsupported file type. (jpg/jpeg/png/webp/tiff/pdf)", "対応していないファイル形式です。(jpg/jpeg/png/webp/tiff/pdf)"),
+    ("INVALID_BLEND_MODE", "유효하지 않은 합성 방식입니다.", "Invalid blend mode.", "無効な合成モードです。"),
+    ("UNKNOWN_BUILTIN_LOGO", "알 수 없는 내장 로고입니다: {}", "Unknown built-in logo: {}", "不明な内蔵ロゴです: {}"),
+    ("BUILTIN_LOGO_NOT_FOUND", "내장 로고 파일을 찾지 못했습니다: {}", "Could not find the built-in logo file: {}", "内蔵ロゴファイルが見つかりませんでした: {}"),
+    ("JPEG_HEADER_UNREADABLE", "JPEG 헤더를 읽지 못했습니다.", "Could not read the JPEG header.", "JPEGヘッダーを読み込めませんでした。"),
+    ("CMYK_DECODE_FAILED", "CMYK JPEG를 디코딩하지 못했습니다: {}", "Could not decode the CMYK JPEG: {}", "CMYK JPEGをデコードできませんでした: {}"),
+    ("CMYK_SIZE_UNREADABLE", "CMYK JPEG 크기를 읽지 못했습니다.", "Could not read the CMYK JPEG size.", "CMYK JPEGのサイズを読み込めませんでした。"),
+    ("CMYK_CHANNELS_UNEXPECTED", "CMYK JPEG 채널 수가 예상과 다릅니다.", "The CMYK JPEG has an unexpected number of channels.", "CMYK JPEGのチャンネル数が想定と異なります。"),
+    ("IMAGE_READ_FAILED", "이미지 파일을 읽지 못했습니다: {}", "Could not read the image file: {}", "画像ファイルを読み込めませんでした: {}"),
+    ("SRGB_TRANSFORM_FAILED", "sRGB 색 변환을 준비하지 못했습니다: {}", "Could not prepare the sRGB color conversion: {}", "sRGBへの色変換を準備できませんでした: {}"),
+    ("ICC_PROFILE_INVALID", "ICC 프로파일을 해석하지 못했습니다: {}", "Could not parse the ICC profile: {}", "ICCプロファイルを解析できませんでした: {}"),
+    ("LOGO_READ_FAILED", "로고 리소스를 읽지 못했습니다: {}", "Could not read the logo: {}", "ロゴを読み込めませんでした: {}"),
+    ("SAMPLE_READ_FAILED", "표본 이미지를 읽지 못했습니다: {}", "Could not read the sample image: {}", "サンプル画像を読み込めませんでした: {}"),
+    ("SAMPLE_CREATE_FAILED", "표본 이미지를 만들지 못했습니다: {}", "Could not create the sample image: {}", "サンプル画像を作成できませんでした: {}"),
+    ("HEIC_PATH_INVALID", "HEIC 파일 경로를 해석하지 못했습니다.", "Could not resolve the HEIC file path.", "HEICファイルのパスを解釈できませんでした。"),
+    ("HEIC_READ_FAILED", "HEIC 파일을 읽지 못했습니다: {}", "Could not read the HEIC file: {}", "HEICファイルを読み込めませんでした: {}"),
+    ("HEIC_PRIMARY_NOT_FOUND", "HEIC 대표 이미지를 찾지 못했습니다: {}", "Could not find the primary HEIC image: {}", "HEICのメイン画像が見つかりませんでした: {}"),
+    ("HEIC_DECODE_FAILED", "HEIC 이미지를 디코딩하지 못했습니다: {}", "Could not decode the HEIC image: {}", "HEIC画像をデコードできませんでした: {}"),
+    ("HEIC_RGBA_NOT_FOUND", "HEIC 이미지의 RGBA 데이터를 찾지 못했습니다.", "Could not find RGBA data in the HEIC image.", "HEIC画像のRGBAデータが見つかりませんでした。"),
+    ("HEIC_SIZE_INVALID", "HEIC 이미지 크기가 유효하지 않습니다.", "The HEIC image size is invalid.", "HEIC画像のサイズが無効です。"),
+    ("HEIC_NOT_BUILT", "이 빌드에는 HEIC/HEIF 지원이 포함되어 있지 않습니다. JPEG/PNG로 변환한 뒤 다시 시도해 주세요.", "This build does not include HEIC/HEIF support. Convert to JPEG/PNG and try again.", "このビルドにはHEIC/HEIFのサポートが含まれていません。JPEG/PNGに変換してから再度お試しください。"),
+    ("PHYSICAL_SIZE_INVALID", "인쇄 크기의 인치와 DPI는 0보다 커야 합니다.", "Print size inches and DPI must be greater than 0.", "印刷サイズのインチとDPIは0より大きくする必要があります。"),
+    ("FIT_BOX_INVALID", "로고 상자의 가로/세로 비율은 0보다 크고 100 이하여야 합니다.", "Logo box width/height percentages must be greater than 0 and at most 100.", "ロゴボックスの幅/高さの割合は0より大きく100以下である必要があります。"),
+    ("INVALID_SIZE_MODE", "유효하지 않은 크기 기준입니다.", "Invalid size mode.", "無効なサイズ基準です。"),
+    ("INVALID_SIZE_PRESET", "유효하지 않은 크기 프리셋입니다.", "Invalid size preset.", "無効なサイズプリセットです。"),
+    ("LABEL_LOGO_SIZE", "로고 크기", "Logo size", "ロゴサイズ"),
+    ("LABEL_MARGIN", "여백", "Margin", "余白"),
+    ("LABEL_MARGIN_TOP", "위쪽 여백", "Top margin", "上余白"),
+    ("LABEL_MARGIN_RIGHT", "오른쪽 여백", "Right margin", "右余白"),
+    ("LABEL_MARGIN_BOTTOM", "아래쪽 여백", "Bottom margin", "下余白"),
+    ("LABEL_MARGIN_LEFT", "왼쪽 여백", "Left margin", "左余白"),
+    ("OUT_OF_BOUNDS", "{} {}%가 허용 범위({}~{}%)를 벗어났습니다.", "{} {}% is outside the allowed range ({}–{}%).", "{} {}%が許容範囲({}〜{}%)を超えています。"),
+    ("CLAMPED", "{} {}%가 허용 범위({}~{}%)를 벗어나 {}%로 조정했습니다.", "{} {}% was outside the allowed range ({}–{}%) and was adjusted to {}%.", "{} {}%が許容範囲({}〜{}%)を超えていたため{}%に調整しました。"),
+    ("POSITION_TOP_LEFT", "좌상단", "top-left", "左上"),
+    ("POSITION_TOP_RIGHT", "우상단", "top-right", "右上"),
+    ("POSITION_BOTTOM_LEFT", "좌하단", "bottom-left", "左下"),
+    ("POSITION_BOTTOM_RIGHT", "우하단", "bottom-right", "右下"),
+    ("POSITION_CENTER", "가운데", "center", "中央"),
+    ("POSITION_THIRD_TOP_LEFT", "삼분할 좌상단", "thirds top-left", "三分割 左上"),
+    ("POSITION_THIRD_TOP_RIGHT", "삼분할 우상단", "thirds top-right", "三分割 右上"),
+    ("POSITION_THIRD_BOTTOM_LEFT", "삼분할 좌하단", "thirds bottom-left", "三分割 左下"),
+    ("POSITION_THIRD_BOTTOM_RIGHT", "삼분할 우하단", "thirds bottom-right", "三分割 右下"),
+    ("INVALID_POSITION", "유효하지 않은 위치 값입니다.", "Invalid position.", "無効な位置です。"),
+    ("INVALID_MARGIN_MODE", "유효하지 않은 여백 기준입니다.", "Invalid margin mode.", "無効な余白基準です。"),
+    ("INVALID_RESIZE_FILTER", "유효하지 않은 크기 조절 방식입니다.", "Invalid resize filter.", "無効なリサイズ方式です。"),
+    ("INVALID_LOGO_ANCHOR", "유효하지 않은 로고 기준점입니다.", "Invalid logo anchor.", "無効なロゴ基準点です。"),
+    ("LOGO_FULLY_TRANSPARENT", "로고가 완전히 투명해 여백을 잘라낼 수 없습니다.", "The logo is fully transparent, so its padding cannot be trimmed.", "ロゴが完全に透明なため余白を切り取れません。"),
+    ("INVALID_OUTPUT_ASPECT", "유효하지 않은 출력 비율입니다.", "Invalid output aspect ratio.", "無効な出力比率です。"),
+    ("MIN_CONTRAST_INVALID", "최소 대비는 1 이상이어야 합니다.", "Minimum contrast must be at least 1.", "最小コントラストは1以上である必要があります。"),
+    ("BANNER_WITH_TILE", "배너 모드와 반복 로고는 함께 쓸 수 없습니다.", "Banner mode and tiled logos cannot be used together.", "バナーモードとロゴの繰り返しは同時に使えません。"),
+    ("UNSUPPORTED_IMAGE_FILE", "지원하지 않는 파일 형식입니다. (jpg/png/webp/tiff)", "Unsupported file type. (jpg/png/webp/tiff)", "対応していないファイル形式です。(jpg/png/webp/tiff)"),
+    ("TIFF_FIRST_PAGE_ONLY", "{}쪽 TIFF이지만 색 형식({})을 쪽별로 다루지 못해 첫 쪽에만 로고를 넣었고, 나머지 쪽은 결과에 포함되지 않습니다.", "This {}-page TIFF uses a color type ({}) that cannot be handled per page, so only the first page was stamped and the other pages are not in the output.", "{}ページのTIFFですが、色形式({})をページごとに扱えないため最初のページにのみロゴを入れました。残りのページは出力に含まれません。"),
+    ("IMAGE_SIZE_INVALID", "이미지 크기가 유효하지 않습니다.", "The image size is invalid.", "画像サイズが無効です。"),
+    (CODE_LIKELY_WATERMARKED, "모서리에 기존 워터마크로 보이는 영역이 있어 건너뛰었습니다.", "Skipped because a corner already looks watermarked.", "角に既存の透かしがあるようなのでスキップしました。"),
+    (CODE_LOGO_NOT_VISIBLE, "로고가 보이는 영역이 없어 결과 파일을 만들지 않았습니다.", "No output was written because the logo would not be visible.", "ロゴが見える部分がないため出力しませんでした。"),
+    ("LOGO_NOT_UPSCALED", "로고 원본({}px)이 요청한 크기({}px)보다 작아 키우지 않고 원본 크기로 넣었습니다.", "The logo ({} px) is smaller than the requested size ({} px), so it was placed at its original size instead of being enlarged.", "ロゴの元サイズ({}px)が要求サイズ({}px)より小さいため、拡大せず元のサイズで入れました。"),
+    ("LOW_CONTRAST", "{} 위치의 로고 대비가 낮아({}:1) 잘 보이지 않을 수 있습니다. 배경과 대비되는 로고 색을 고려하세요.", "The logo at {} has low contrast ({}:1) and may be hard to see. Consider a logo color that stands out from the background.", "{}のロゴはコントラストが低く({}:1)見えにくい可能性があります。背景と対比するロゴの色をご検討ください。"),
+    ("JPEG_RECOMPRESSED_AGAIN", "이미 로고를 넣은 JPEG를 다시 압축했습니다. 반복할수록 화질이 떨어지니 원본에서 다시 만드는 것을 권장합니다.", "Re-compressed a JPEG that already had a logo. Quality drops each time, so re-create it from the original instead.", "既にロゴを入れたJPEGを再圧縮しました。繰り返すほど画質が落ちるため、元の画像から作り直すことをお勧めします。"),
+    ("JPEG_RECOMPRESSED", "JPEG를 다시 압축해 저장했습니다. 원본보다 화질이 조금 떨어질 수 있습니다.", "The JPEG was re-compressed. Quality may be slightly lower than the original.", "JPEGを再圧縮して保存しました。元の画像より画質が少し落ちる場合があります。"),
+    ("METADATA_SKIPPED", "이 형식은 메타데이터 삽입을 지원하지 않아 로고만 삽입했습니다.", "This format does not support embedding metadata, so only the logo was added.", "この形式はメタデータの埋め込みに対応していないため、ロゴのみ入れました。"),
+    ("IMAGE_SAVE_FAILED", "결과 이미지를 저장하지 못했습니다: {}", "Could not save the output image: {}", "出力画像を保存できませんでした: {}"),
+    ("WEB_COPY_FAILED", "웹용 사본을 만들지 못했습니다: {}", "Could not create the web copy: {}", "Web用コピーを作成できませんでした: {}"),
+    ("WEB_COPY_ANIMATED_WEBP", "애니메이션 WebP는 웹용 사본을 만들지 않습니다.", "Web copies are not created for animated WebP.", "アニメーションWebPのWeb用コピーは作成しません。"),
+    ("TIFF_PAGE_SIZE_INVALID", "TIFF {}쪽의 크기가 유효하지 않습니다.", "TIFF page {} has an invalid size.", "TIFFの{}ページ目のサイズが無効です。"),
+    ("WEB_COPY_MULTI_PAGE_TIFF", "여러 쪽 TIFF는 웹용 사본을 만들지 않습니다.", "Web copies are not created for multi-page TIFFs.", "複数ページのTIFFのWeb用コピーは作成しません。"),
+    ("POSITION_NUDGED", "로고와 여백이 이미지 안에 모두 들어가지 않아 위치를 가장자리 쪽으로 조정했습니다.", "The logo and margin did not fit inside the image, so the position was moved toward the edge.", "ロゴと余白が画像内に収まらないため、位置を端に寄せて調整しました。"),
+    ("LOGO_SIZE_INVALID", "로고 크기가 유효하지 않습니다.", "The logo size is invalid.", "ロゴサイズが無効です。"),
+    ("TILE_HAS_NO_SINGLE_RECT", "타일 모드는 로고 영역이 하나가 아니라 계산할 수 없습니다.", "Tile mode places more than one logo, so a single logo area cannot be computed.", "タイルモードではロゴ領域が1つではないため計算できません。"),
+    (CODE_LOGO_IS_INPUT, "로고로 고른 파일과 같은 파일이라 처리하지 않았습니다. 로고나 입력 파일을 다시 골라 주세요.", "Not processed because this file is also selected as the logo. Choose a different logo or input file.", "ロゴとして選んだファイルと同じファイルのため処理しませんでした。ロゴまたは入力ファイルを選び直してください。"),
+    ("JITTER_CONFLICT", "흔들림 비율과 흔들림 px은 함께 쓸 수 없습니다.", "Jitter percent and jitter px cannot be used together.", "ずらし割合とずらしpxは同時に使えません。"),
+    ("JITTER_PERCENT_INVALID", "흔들림 비율은 0보다 크고 20 이하여야 합니다.", "Jitter percent must be greater than 0 and at most 20.", "ずらし割合は0より大きく20以下である必要があります。"),
+    ("JOB_READ_FAILED", "작업 파일을 읽지 못했습니다: {}", "Could not read the job file: {}", "ジョブファイルを読み込めませんでした: {}"),
+    ("JOB_MALFORMED", "작업 파일 형식이 올바르지 않습니다: {}", "The job file is malformed: {}", "ジョブファイルの形式が正しくありません: {}"),
+    ("JOB_NO_PATHS", "작업 파일에 처리할 경로(paths)가 없습니다.", "The job file has no paths to process.", "ジョブファイルに処理するパス(paths)がありません。"),
+    ("LOGO_RESOURCE_PATH_FAILED", "로고 리소스 경로를 찾지 못했습니다: {}", "Could not find the logo resource path: {}", "ロゴリソースのパスが見つかりませんでした: {}"),
+    ("LOGO_PATH_FAILED", "로고 파일 경로를 찾지 못했습니다: {}", "Could not find the logo file: {}", "ロゴファイルが見つかりませんでした: {}"),
+    ("BATCH_TASK_STOPPED", "배치 처리 작업이 중단되었습니다: {}", "The batch task stopped unexpectedly: {}", "一括処理が中断されました: {}"),
+    ("APP_CONFIG_DIR_FAILED", "앱 설정 폴더를 찾지 못했습니다: {}", "Could not find the app settings folder: {}", "アプリ設定フォルダーが見つかりませんでした: {}"),
+    ("LOGO_SOURCE_CONFLICT", "로고 파일, 내장 로고, 로고 URL 중 하나만 지정할 수 있습니다.", "Specify only one of a logo file, a built-in logo, or a logo URL.", "ロゴファイル、内蔵ロゴ、ロゴURLのいずれか1つだけを指定できます。"),
+    ("APP_CACHE_DIR_FAILED", "앱 캐시 폴더를 찾지 못했습니다: {}", "Could not find the app cache folder: {}", "アプリのキャッシュフォルダーが見つかりませんでした: {}"),
+    ("RESOURCE_PATH_FAILED", "리소스 경로를 확인하지 못했습니다: {}", "Could not resolve the resource path: {}", "リソースのパスを確認できませんでした: {}"),
+    ("LOGO_FILE_MISSING", "선택한 로고 파일이 존재하지 않거나 파일이 아닙니다.", "The selected logo file does not exist or is not a file.", "選択したロゴファイルが存在しないか、ファイルではありません。"),
+    ("CURRENT_DIR_FAILED", "현재 경로를 확인하지 못했습니다: {}", "Could not determine the current directory: {}", "現在のパスを確認できませんでした: {}"),
+    ("DEFAULT_LOGO_NOT_FOUND", "기본 로고를 찾지 못했습니다. (resource logo.png/logo.webp, cwd logo.png/logo.webp)", "Could not find the default logo. (resource logo.png/logo.webp, cwd logo.png/logo.webp)", "既定のロゴが見つかりませんでした。(resource logo.png/logo.webp, cwd logo.png/logo.webp)"),
+    ("OUTPUT_PATH_NEEDS_SINGLE_FILE", "출력 파일 경로는 파일 하나를 처리할 때만 지정할 수 있습니다.", "An output file path can only be given when processing a single file.", "出力ファイルのパスは1つのファイルを処理するときだけ指定できます。"),
+    ("IMAGE_ENCODE_FAILED", "결과 이미지를 인코딩하지 못했습니다: {}", "Could not encode the output image: {}", "出力画像をエンコードできませんでした: {}"),
+    ("METADATA_UNSUPPORTED", "이 형식은 메타데이터 삽입을 지원하지 않습니다.", "This format does not support embedding metadata.", "この形式はメタデータの埋め込みに対応していません。"),
+    ("PNG_METADATA_FAILED", "PNG 메타데이터를 기록하지 못했습니다: {}", "Could not write the PNG metadata: {}", "PNGメタデータを書き込めませんでした: {}"),
+    (CODE_UNSUPPORTED_VIDEO, "동영상은 지원하지 않습니다. 이미지(jpg/jpeg/png/webp/tiff) 또는 PDF만 넣을 수 있습니다.", "Videos are not supported. Only images (jpg/jpeg/png/webp/tiff) and PDFs can be stamped.", "動画には対応していません。画像(jpg/jpeg/png/webp/tiff)またはPDFのみ処理できます。"),
+    (CODE_UNSUPPORTED_OFFICE, "오피스 문서는 지원하지 않습니다. PDF로 내보낸 뒤 넣어 주세요.", "Office documents are not supported. Export to PDF first.", "Officeドキュメントには対応していません。PDFに書き出してから追加してください。"),
+    ("INVALID_CONFLICT_POLICY", "유효하지 않은 이름 충돌 처리 방식입니다.", "Invalid name conflict policy.", "無効な名前衝突の処理方式です。"),
+    ("INVALID_PATTERN", "잘못된 경로 패턴입니다: {}", "Invalid path pattern: {}", "無効なパスパターンです: {}"),
+    (CODE_NO_MATCH, "패턴과 일치하는 지원 파일이 없습니다: {}", "No supported files matched the pattern: {}", "パターンに一致する対応ファイルがありません: {}"),
+    ("UNSUPPORTED_IMAGE_TYPE", "지원하지 않는 이미지 형식입니다. (jpg/png/webp/tiff)", "Unsupported image type. (jpg/png/webp/tiff)", "対応していない画像形式です。(jpg/png/webp/tiff)"),
+    ("OUTPUT_EXTENSION_MISMATCH", "출력 파일 확장자는 입력과 같은 형식(.{})이어야 합니다.", "The output file extension must match the input format (.{}).", "出力ファイルの拡張子は入力と同じ形式(.{})である必要があります。"),
+    ("OUTPUT_EXTENSION_NOT_PDF", "출력 파일 확장자는 .pdf여야 합니다.", "The output file extension must be .pdf.", "出力ファイルの拡張子は.pdfである必要があります。"),
+    ("OVERWRITE_NEEDS_BACKUP", "원본 파일을 덮어쓰려면 원본 백업(backup_originals)을 켜야 합니다.", "Turn on backup_originals to overwrite the original file.", "元のファイルを上書きするには元ファイルのバックアップ(backup_originals)をオンにする必要があります。"),
+    ("INPUT_PARENT_NOT_FOUND", "입력 파일의 상위 경로를 찾을 수 없습니다.", "Could not find the parent folder of the input file.", "入力ファイルの親フォルダーが見つかりません。"),
+    ("BACKUP_FAILED", "원본을 백업하지 못했습니다: {}", "Could not back up the original: {}", "元のファイルをバックアップできませんでした: {}"),
+    ("OUTPUT_EXISTS", "출력 파일이 이미 있습니다: {}", "The output file already exists: {}", "出力ファイルが既に存在します: {}"),
+    ("OUTPUT_CREATE_FAILED", "출력 파일을 만들지 못했습니다: {}", "Could not create the output file: {}", "出力ファイルを作成できませんでした: {}"),
+    ("OUTPUT_PARENT_NOT_FOUND", "결과 파일의 상위 경로를 찾을 수 없습니다.", "Could not find the parent folder of the output file.", "出力ファイルの親フォルダーが見つかりません。"),
+    ("WEB_COPY_DIR_FAILED", "웹용 사본 폴더를 만들지 못했습니다: {}", "Could not create the web copy folder: {}", "Web用コピーのフォルダーを作成できませんでした: {}"),
+    ("UNSUPPORTED_PDF_TYPE", "지원하지 않는 PDF 형식입니다. (.pdf)", "Unsupported PDF type. (.pdf)", "対応していないPDF形式です。(.pdf)"),
+    ("OUTPUT_NAME_RESERVE_FAILED", "출력 파일 이름을 확보하지 못했습니다: {}", "Could not reserve an output file name: {}", "出力ファイル名を確保できませんでした: {}"),
+    ("OUTPUT_NAME_EXHAUSTED", "사용 가능한 출력 파일 이름이 없습니다.", "No output file name is available.", "使用できる出力ファイル名がありません。"),
+    (CODE_OUTPUT_NOT_WRITABLE, "출력 폴더에 쓸 수 없습니다: {}", "The output folder is not writable: {}", "出力フォルダーに書き込めません: {}"),
+    ("OUTPUT_DIR_CREATE_FAILED", "출력 폴더를 만들지 못했습니다: {}", "Could not create the output folder: {}", "出力フォルダーを作成できませんでした: {}"),
+    ("OUTPUT_PATH_NOT_DIR", "출력 경로가 디렉터리가 아닙니다.", "The output path is not a folder.", "出力パスがフォルダーではありません。"),
+    ("OUTPUT_BASE_RESOLVE_FAILED", "출력 기준 폴더 경로를 확인하지 못했습니다: {}", "Could not resolve the output base folder: {}", "出力の基準フォルダーのパスを確認できませんでした: {}"),
+    ("OUTPUT_DIR_RESOLVE_FAILED", "출력 폴더 경로를 확인하지 못했습니다: {}", "Could not resolve the output folder: {}", "出力フォルダーのパスを確認できませんでした: {}"),
+    ("OUTPUT_DIR_OUTSIDE_BASE", "출력 폴더가 기준 폴더 밖을 가리킵니다: {}", "The output folder points outside the base folder: {}", "出力フォルダーが基準フォルダーの外を指しています: {}"),
+    ("OUTPUT_DIR_NOT_DIR", "출력 폴더 경로가 디렉터리가 아닙니다.", "The output folder path is not a folder.", "出力フォルダーのパスがフォルダーではありません。"),
+    ("PDF_A_ENCRYPTED", "암호화된 PDF는 PDF/A로 저장할 수 없습니다.", "Encrypted PDFs cannot be saved as PDF/A.", "暗号化されたPDFはPDF/Aとして保存できません。"),
+    ("PDF_CATALOG_NOT_FOUND", "PDF 카탈로그를 찾지 못했습니다: {}", "Could not find the PDF catalog: {}", "PDFカタログが見つかりませんでした: {}"),
+    ("SRGB_PROFILE_FAILED", "sRGB 프로파일을 만들지 못했습니다: {}", "Could not create the sRGB profile: {}", "sRGBプロファイルを作成できませんでした: {}"),
+    ("PDF_CATALOG_READ_FAILED", "PDF 카탈로그를 읽지 못했습니다: {}", "Could not read the PDF catalog: {}", "PDFカタログを読み込めませんでした: {}"),
+    ("PDF_A_FONTS_NOT_EMBEDDED", "원본에 포함되지 않은 글꼴이 있어 PDF/A 검사를 통과하지 못할 수 있습니다.", "The original uses fonts that are not embedded, so the file may fail PDF/A validation.", "元のPDFに埋め込まれていないフォントがあるため、PDF/A検証に合格しない場合があります。"),
+    ("INVALID_PAGE_SELECTION", "유효하지 않은 PDF 페이지 선택입니다.", "Invalid PDF page selection.", "無効なPDFページ指定です。"),
+    ("PAGE_SKIPPED", "페이지 {}를 건너뛰었습니다: {}", "Skipped page {}: {}", "{}ページをスキップしました: {}"),
+    ("ALL_PAGES_FAILED", "모든 페이지에 로고를 넣지 못했습니다. {}", "Could not stamp any page. {}", "すべてのページにロゴを入れられませんでした。{}"),
+    ("PAGE_NUMBER_ZERO", "페이지 번호는 1부터 시작해야 합니다.", "Page numbers start at 1.", "ページ番号は1から始まる必要があります。"),
+    ("PDF_READ_FAILED", "PDF를 읽지 못했습니다: {}", "Could not read the PDF: {}", "PDFを読み込めませんでした: {}"),
+    (CODE_ALREADY_STAMPED, "이미 CornerBrand 로고가 들어간 PDF라 건너뛰었습니다.", "Skipped because this PDF already has a CornerBrand logo.", "このPDFには既にCornerBrandのロゴがあるためスキップしました。"),
+    ("PDF_LOGO_NOT_UPSCALED", "로고 원본이 요청한 크기보다 작아 키우지 않고 원본 크기(1px = 1pt)로 넣었습니다.", "The logo is smaller than the requested size, so it was placed at its original size (1px = 1pt) instead of being enlarged.", "ロゴの元サイズが要求サイズより小さいため、拡大せず元のサイズ(1px = 1pt)で入れました。"),
+    ("FLATTENED_NO_LAYER", "평면화한 PDF는 로고가 페이지 이미지에 합쳐져 별도 레이어로 만들 수 없습니다.", "In a flattened PDF the logo is merged into the page image, so it cannot be a separate layer.", "平面化したPDFではロゴがページ画像に統合されるため、別レイヤーにできません。"),
+    ("PDF_SAVE_FAILED", "결과 PDF를 저장하지 못했습니다: {}", "Could not save the output PDF: {}", "出力PDFを保存できませんでした: {}"),
+    ("PDF_NO_PAGES", "페이지가 없는 PDF 파일입니다.", "The PDF has no pages.", "ページのないPDFファイルです。"),
+    ("LOGO_XOBJECT_FAILED", "로고 XObject 생성에 실패했습니다: {}", "Could not create the logo XObject: {}", "ロゴXObjectを作成できませんでした: {}"),
+    ("PAGE_STAMP_FAILED", "페이지 {}에 로고 삽입 실패: {}", "Could not add the logo to page {}: {}", "{}ページへのロゴの挿入に失敗しました: {}"),
+    ("LOGO_SIZE_FAILED", "로고 크기 계산에 실패했습니다: {}", "Could not compute the logo size: {}", "ロゴサイズを計算できませんでした: {}"),
+    ("LOGO_DECODE_FAILED", "로고 이미지 디코딩에 실패했습니다: {}", "Could not decode the logo image: {}", "ロゴ画像をデコードできませんでした: {}"),
+    ("LOGO_CONVERT_FAILED", "로고 이미지 변환에 실패했습니다: {}", "Could not convert the logo image: {}", "ロゴ画像を変換できませんでした: {}"),
+    ("PAGE_SIZE_INVALID", "페이지 크기가 유효하지 않습니다.", "The page size is invalid.", "ページサイズが無効です。"),
+    ("PAGE_OBJECT_READ_FAILED", "페이지 {} 객체를 읽지 못했습니다: {}", "Could not read the object for page {}: {}", "{}ページのオブジェクトを読み込めませんでした: {}"),
+    ("MEDIABOX_PARSE_FAILED", "페이지 {} MediaBox를 해석하지 못했습니다: {}", "Could not parse the MediaBox of page {}: {}", "{}ページのMediaBoxを解析できませんでした: {}"),
+    ("MEDIABOX_NOT_FOUND", "페이지 {}의 MediaBox를 찾지 못했습니다.", "Could not find the MediaBox of page {}.", "{}ページのMediaBoxが見つかりませんでした。"),
+    ("OBJECT_LOOKUP_FAILED", "객체 조회 실패({}): {}", "Could not look up object ({}): {}", "オブジェクトの取得に失敗しました({}): {}"),
+    ("PAGE_OBJECT_NOT_DICTIONARY", "페이지 객체가 Dictionary/Stream이 아닙니다.", "The page object is not a Dictionary/Stream.", "ページオブジェクトがDictionary/Streamではありません。"),
+    ("MEDIABOX_REF_LOOKUP_FAILED", "MediaBox 참조 객체 조회 실패({}): {}", "Could not look up the MediaBox reference ({}): {}", "MediaBox参照オブジェクトの取得に失敗しました({}): {}"),
+    ("MEDIABOX_REF_NOT_ARRAY", "MediaBox 참조 객체가 배열이 아닙니다.", "The MediaBox reference is not an array.", "MediaBox参照オブジェクトが配列ではありません。"),
+    ("MEDIABOX_NOT_ARRAY", "MediaBox는 배열이어야 합니다.", "MediaBox must be an array.", "MediaBoxは配列である必要があります。"),
+    ("MEDIABOX_WRONG_LENGTH", "MediaBox 길이가 4가 아닙니다.", "MediaBox does not have 4 entries.", "MediaBoxの長さが4ではありません。"),
+    ("MEDIABOX_EMPTY", "MediaBox 너비/높이가 0 이하입니다.", "MediaBox width/height is 0 or less.", "MediaBoxの幅/高さが0以下です。"),
+    ("MEDIABOX_NOT_NUMBERS", "MediaBox 좌표가 숫자가 아닙니다.", "MediaBox coordinates are not numbers.", "MediaBoxの座標が数値ではありません。"),
+    ("PDFIUM_LOAD_FAILED", "PDF 렌더러(pdfium)를 불러오지 못했습니다: {}", "Could not load the PDF renderer (pdfium): {}", "PDFレンダラー(pdfium)を読み込めませんでした: {}"),
+    ("PDF_RENDER_OPEN_FAILED", "PDF를 렌더링용으로 열지 못했습니다: {}", "Could not open the PDF for rendering: {}", "PDFをレンダリング用に開けませんでした: {}"),
+    ("PAGE_NOT_FOUND", "페이지 {}이(가) 없습니다.", "Page {} does not exist.", "{}ページがありません。"),
+    ("PAGE_RENDER_FAILED", "페이지 {}를 렌더링하지 못했습니다: {}", "Could not render page {}: {}", "{}ページをレンダリングできませんでした: {}"),
+    ("PAGE_IMAGE_COMPRESS_FAILED", "페이지 이미지를 압축하지 못했습니다: {}", "Could not compress the page image: {}", "ページ画像を圧縮できませんでした: {}"),
+    ("PAGE_CONTENT_FAILED", "페이지 내용을 만들지 못했습니다: {}", "Could not build the page content: {}", "ページの内容を作成できませんでした: {}"),
+    ("PRESET_EXISTS", "같은 이름의 프리셋이 이미 있습니다: {}", "A preset with the same name already exists: {}", "同じ名前のプリセットが既に存在します: {}"),
+    ("PRESET_NOT_FOUND", "프리셋을 찾지 못했습니다: {}", "Could not find the preset: {}", "プリセットが見つかりませんでした: {}"),
+    ("PRESET_NAME_EMPTY", "프리셋 이름이 비어 있습니다.", "The preset name is empty.", "プリセット名が空です。"),
+    ("PRESET_NAME_TOO_LONG", "프리셋 이름은 {}자 이하여야 합니다.", "Preset names must be at most {} characters.", "プリセット名は{}文字以下である必要があります。"),
+    ("PRESET_NAME_CONTROL_CHARS", "프리셋 이름에 제어 문자를 사용할 수 없습니다.", "Preset names cannot contain control characters.", "プリセット名に制御文字は使えません。"),
+    ("PRESET_READ_FAILED", "프리셋 파일을 읽지 못했습니다: {}", "Could not read the preset file: {}", "プリセットファイルを読み込めませんでした: {}"),
+    ("PRESET_SAVE_FAILED", "프리셋 파일을 저장하지 못했습니다: {}", "Could not save the preset file: {}", "プリセットファイルを保存できませんでした: {}"),
+    ("PRESET_MALFORMED", "프리셋 파일 형식이 올바르지 않습니다: {}", "The preset file is malformed: {}", "プリセットファイルの形式が正しくありません: {}"),
+    ("PRESET_SERIALIZE_FAILED", "프리셋을 직렬화하지 못했습니다: {}", "Could not serialize the preset: {}", "プリセットをシリアライズできませんでした: {}"),
+    ("PREVIEW_DIR_READ_FAILED", "미리보기 폴더를 읽지 못했습니다: {}", "Could not read the preview folder: {}", "プレビューフォルダーを読み込めませんでした: {}"),
+    ("PREVIEW_IMAGES_ONLY", "비교 미리보기는 이미지 파일만 지원합니다.", "Comparison previews only support image files.", "比較プレビューは画像ファイルのみ対応しています。"),
+    ("PREVIEW_CREATE_FAILED", "미리보기 이미지를 만들지 못했습니다: {}", "Could not create the preview image: {}", "プレビュー画像を作成できませんでした: {}"),
+    ("PREVIEW_PDF_ONLY", "PDF 페이지 미리보기는 PDF 파일만 지원합니다.", "PDF page previews only support PDF files.", "PDFページのプレビューはPDFファイルのみ対応しています。"),
+    ("PREVIEW_PAGE_OUT_OF_RANGE", "페이지 번호는 1~{} 사이여야 합니다.", "The page number must be between 1 and {}.", "ページ番号は1〜{}の範囲である必要があります。"),
+    ("PREVIEW_DIR_CREATE_FAILED", "미리보기 폴더를 만들지 못했습니다: {}", "Could not create the preview folder: {}", "プレビューフォルダーを作成できませんでした: {}"),
+    ("PDF_STAMP_FAILED", "PDF에 로고를 넣지 못했습니다.", "Could not add the logo to the PDF.", "PDFにロゴを入れられませんでした。"),
+    ("FILE_INFO_FAILED", "파일 정보를 읽지 못했습니다: {}", "Could not read the file information: {}", "ファイル情報を読み込めませんでした: {}"),
+    ("UNSUPPORTED_PROBE_TYPE", "지원하지 않는 파일 형식입니다. (jpg/png/webp/tiff, pdf)", "Unsupported file type. (jpg/png/webp/tiff, pdf)", "対応していないファイル形式です。(jpg/png/webp/tiff, pdf)"),
+    ("IMAGE_SIZE_READ_FAILED", "이미지 크기를 읽지 못했습니다: {}", "Could not read the image size: {}", "画像サイズを読み込めませんでした: {}"),
+    ("LOGO_URL_SCHEME", "로고 URL은 http:// 또는 https://로 시작해야 합니다.", "Logo URLs must start with http:// or https://.", "ロゴURLはhttp://またはhttps://で始まる必要があります。"),
+    ("DOWNLOAD_NOT_IMAGE", "내려받은 파일이 이미지가 아닙니다.", "The downloaded file is not an image.", "ダウンロードしたファイルは画像ではありません。"),
+    ("DOWNLOAD_DECODE_FAILED", "내려받은 로고 이미지를 읽지 못했습니다: {}", "Could not read the downloaded logo image: {}", "ダウンロードしたロゴ画像を読み込めませんでした: {}"),
+    ("LOGO_CACHE_USED", "로고를 내려받지 못해 전에 저장해 둔 사본을 썼습니다: {}", "Could not download the logo, so a previously saved copy was used: {}", "ロゴをダウンロードできなかったため、以前保存したコピーを使いました: {}"),
+    ("LOGO_DOWNLOAD_NO_CACHE", "로고를 내려받지 못했고 저장해 둔 사본도 없습니다: {}", "Could not download the logo and there is no saved copy: {}", "ロゴをダウンロードできず、保存済みのコピーもありません: {}"),
+    ("LOGO_HTTP_STATUS", "서버가 로고를 돌려주지 않았습니다. (HTTP {})", "The server did not return the logo. (HTTP {})", "サーバーがロゴを返しませんでした。(HTTP {})"),
+    ("LOGO_CONNECT_FAILED", "로고 서버에 연결하지 못했습니다: {}", "Could not connect to the logo server: {}", "ロゴのサーバーに接続できませんでした: {}"),
+    ("LOGO_DOWNLOAD_FAILED", "로고를 내려받는 중 실패했습니다: {}", "Downloading the logo failed: {}", "ロゴのダウンロード中に失敗しました: {}"),
+    ("LOGO_CACHE_DIR_FAILED", "로고 캐시 폴더를 만들지 못했습니다: {}", "Could not create the logo cache folder: {}", "ロゴのキャッシュフォルダーを作成できませんでした: {}"),
+    ("LOGO_CACHE_SAVE_FAILED", "내려받은 로고를 저장하지 못했습니다: {}", "Could not save the downloaded logo: {}", "ダウンロードしたロゴを保存できませんでした: {}"),
+    ("WRITE_CANCELLED", "작업이 취소되어 결과 파일을 쓰지 않았습니다.", "The output file was not written because the task was cancelled.", "処理がキャンセルされたため出力ファイルを書き込みませんでした。"),
+    ("SHEET_FONT_READ_FAILED", "시트 글꼴을 읽지 못했습니다: {}", "Could not read the sheet font: {}", "シートのフォントを読み込めませんでした: {}"),
+    ("SHEET_FONT_INVALID", "시트 글꼴 형식을 읽지 못했습니다. (TTF/OTF)", "Could not read the sheet font format. (TTF/OTF)", "シートのフォント形式を読み込めませんでした。(TTF/OTF)"),
+    ("SHEET_SAVE_FAILED", "시트 이미지를 저장하지 못했습니다: {}", "Could not save the sheet image: {}", "シート画像を保存できませんでした: {}"),
+    ("SIDECAR_WRITE_FAILED", "사이드카 JSON을 쓰지 못했습니다: {}", "Could not write the sidecar JSON: {}", "サイドカーJSONを書き込めませんでした: {}"),
+    ("SVG_PARSE_FAILED", "SVG 로고를 해석하지 못했습니다: {}", "Could not parse the SVG logo: {}", "SVGロゴを解析できませんでした: {}"),
+    ("SVG_SIZE_INVALID", "SVG 로고 크기가 유효하지 않습니다.", "The SVG logo size is invalid.", "SVGロゴのサイズが無効です。"),
+    ("SVG_RENDER_FAILED", "SVG 로고를 이미지로 바꾸지 못했습니다.", "Could not convert the SVG logo to an image.", "SVGロゴを画像に変換できませんでした。"),
+    ("TIFF_PAGE_READ_FAILED", "TIFF {}쪽을 읽지 못했습니다: {}", "Could not read TIFF page {}: {}", "TIFFの{}ページ目を読み込めませんでした: {}"),
+    ("TIFF_PAGE_NOT_FOUND", "TIFF {}쪽을 찾지 못했습니다: {}", "Could not find TIFF page {}: {}", "TIFFの{}ページ目が見つかりませんでした: {}"),
+    ("TIFF_ENCODE_FAILED", "TIFF 인코딩에 실패했습니다: {}", "Could not encode the TIFF: {}", "TIFFをエンコードできませんでした: {}"),
+    ("TIFF_COLOR_UNSUPPORTED", "지원하지 않는 TIFF 색 형식입니다: {}", "Unsupported TIFF color type: {}", "対応していないTIFFの色形式です: {}"),
+    ("TIFF_PAGE_DATA_SHORT", "TIFF 페이지 데이터가 크기보다 짧습니다.", "The TIFF page data is shorter than its size.", "TIFFページのデータがサイズより短いです。"),
+    (CODE_VERIFY_FAILED, "저장한 결과 파일을 다시 읽지 못했습니다: {}", "The saved output could not be read back and may be corrupt: {}", "保存した出力ファイルを読み直せませんでした: {}"),
+    ("VERIFY_PDF_NO_PAGES", "PDF에 페이지가 없습니다.", "The PDF has no pages.", "PDFにページがありません。"),
+    ("FILE_READ_FAILED", "파일을 읽지 못했습니다: {}", "Could not read the file: {}", "ファイルを読み込めませんでした: {}"),
+    ("WATCH_DIR_MISSING", "감시할 폴더가 존재하지 않거나 폴더가 아닙니다.", "The folder to watch does not exist or is not a folder.", "監視するフォルダーが存在しないか、フォルダーではありません。"),
+    ("WATCH_START_FAILED", "폴더 감시를 시작하지 못했습니다: {}", "Could not start watching the folder: {}", "フォルダーの監視を開始できませんでした: {}"),
+    ("WEB_COPY_MAX_INVALID", "웹용 사본의 최대 크기는 1 이상이어야 합니다.", "The web copy maximum size must be at least 1.", "Web用コピーの最大サイズは1以上である必要があります。"),
+    ("INVALID_WEB_COPY_FORMAT", "유효하지 않은 웹용 사본 형식입니다.", "Invalid web copy format.", "無効なWeb用コピーの形式です。"),
+    ("WEB_COPY_QUALITY_INVALID", "웹용 사본 품질은 1~100 사이여야 합니다.", "Web copy quality must be between 1 and 100.", "Web用コピーの品質は1〜100の範囲である必要があります。"),
+    ("WEB_COPY_SAVE_FAILED", "웹용 사본을 저장하지 못했습니다: {}", "Could not save the web copy: {}", "Web用コピーを保存できませんでした: {}"),
+    ("WEB_COPY_ENCODE_FAILED", "웹용 사본 인코딩에 실패했습니다: {}", "Could not encode the web copy: {}", "Web用コピーをエンコードできませんでした: {}"),
+    ("WEBP_QUALITY_INVALID", "WebP 품질은 0~100 사이여야 합니다.", "WebP quality must be between 0 and 100.", "WebPの品質は0〜100の範囲である必要があります。"),
+    ("WEBP_CONFIG_FAILED", "WebP 인코더 설정을 만들지 못했습니다.", "Could not configure the WebP encoder.", "WebPエンコーダーの設定を作成できませんでした。"),
+    ("WEBP_ENCODE_FAILED", "WebP 인코딩에 실패했습니다: {}", "Could not encode the WebP: {}", "WebPをエンコードできませんでした: {}"),
+    ("ANIMATED_WEBP_READ_FAILED", "애니메이션 WebP를 읽지 못했습니다: {}", "Could not read the animated WebP: {}", "アニメーションWebPを読み込めませんでした: {}"),
+    ("ANIMATED_WEBP_NO_FRAMES", "애니메이션 WebP에 프레임이 없습니다.", "The animated WebP has no frames.", "アニメーションWebPにフレームがありません。"),
+    ("ANIMATED_WEBP_ENCODE_FAILED", "애니메이션 WebP 인코딩에 실패했습니다: {}", "Could not encode the animated WebP: {}", "アニメーションWebPをエンコードできませんでした: {}"),
+    ("ZIP_DIR_CREATE_FAILED", "ZIP 폴더를 만들지 못했습니다: {}", "Could not create the ZIP folder: {}", "ZIPのフォルダーを作成できませんでした: {}"),
+    ("ZIP_EXISTS", "ZIP 파일이 이미 있어 덮어쓰지 않았습니다: {}", "The ZIP file already exists and was not overwritten: {}", "ZIPファイルが既に存在するため上書きしませんでした: {}"),
+    ("ZIP_CREATE_FAILED", "ZIP 파일을 만들지 못했습니다: {}", "Could not create the ZIP file: {}", "ZIPファイルを作成できませんでした: {}"),
+    ("ZIP_READ_OUTPUT_FAILED", "압축할 결과 파일을 읽지 못했습니다: {}", "Could not read an output file to compress: {}", "圧縮する出力ファイルを読み込めませんでした: {}"),
+    ("ZIP_FINISH_FAILED", "ZIP 파일을 마무리하지 못했습니다: {}", "Could not finish the ZIP file: {}", "ZIPファイルを完成できませんでした: {}"),
+    ("ZIP_ENTRY_FAILED", "ZIP 항목을 쓰지 못했습니다({}): {}", "Could not write the ZIP entry ({}): {}", "ZIPの項目を書き込めませんでした({}): {}"),
+    ("RESUME_IN_ZIP", "ZIP으로 묶을 때는 이전 보고서에서 이어서 처리할 수 없습니다.", "Cannot resume from a previous report when bundling into a ZIP.", "ZIPにまとめる場合は以前のレポートから再開できません。"),];
+
+pub(crate) fn template(key: &str, locale: Locale) -> Option<&'static str> {
+    let (_, ko, en, ja) = CATALOG.iter().find(|(entry, ..)| *entry == key)?;
+    Some(match locale {
+        Locale::Ko => ko,
+        Locale::En => en,
+        Locale::Ja => ja,
+    })
+}
+
+/// 결과의 오류와 경고를 해당 언어 글자로 바꿉니다. 사이드카·보고서를 쓰거나 명령이 결과를 돌려주기 전에 부릅니다.
+pub(crate) fn localize(result: &mut StampFileResult, locale: Locale) {
+    if let Some(error) = &mut result.error {
+        error.localize(locale);
+    }
+    for warning in &mut result.warnings {
+        warning.localize(locale);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn english_message_for_unsupported_format() {
+        assert_eq!(parse_locale(Some("en-US")), Locale::En);
+        assert_eq!(parse_locale(Some("fr")), Locale::Ko);
+        assert_eq!(
+            template(CODE_UNSUPPORTED_FORMAT, Locale::En),
+            Some("Unsupported file type. (jpg/jpeg/png/webp/tiff/pdf)")
+        );
+
+        let result = StampFileResult {
+            input_path: "notes.txt".to_string(),
+            error: Some(Message::new(CODE_UNSUPPORTED_FORMAT)),
+            code: Some(CODE_UNSUPPORTED_FORMAT.to_string()),
+            ..Default::default()
+        };
+        let mut korean = result.clone();
+        localize(&mut korean, Locale::Ko);
+        assert_eq!(
+            korean.error,
+            Some(Message::from(
+                "지원하지 않는 파일 형식입니다. (jpg/jpeg/png/webp/tiff/pdf)"
+            ))
+        );
+        let mut english = result;
+        localize(&mut english, Locale::En);
+        assert_eq!(
+            english.error.map(|error| error.to_string()).as_deref(),
+            Some("Unsupported file type. (jpg/jpeg/png/webp/tiff/pdf)")
+        );
+    }
+
+    #[test]
+    fn nested_messages_keep_their_detail() {
+        let error = Message::new("LOGO_PATH_FAILED")
+            .arg(Message::new("LOGO_CONNECT_FAILED").arg("connection refused"));
+        assert_eq!(
+            error.render(Locale::En),
+            "Could not find the logo file: Could not connect to the logo server: connection refused"
+        );
+
+        let pages = Message::List(vec![
+            Message::new("PAGE_SKIPPED")
+                .arg("1")
+                .arg(Message::new("PAGE_SIZE_INVALID")),
+            Message::new("PAGE_SKIPPED").arg("2").arg("bad stream"),
+        ]);
+        assert_eq!(
+            Message::new("ALL_PAGES_FAILED").arg(pages).render(Locale::Ja),
+            "すべてのページにロゴを入れられませんでした。1ページをスキップしました: ページサイズが無効です。 / 2ページをスキップしました: bad stream"
+        );
+
+        // 바꾸지 않는 글자는 그대로 둡니다.
+        assert_eq!(Message::from("unexpected").render(Locale::En), "unexpected");
+    }
+
+    #[test]
+    fn warnings_are_translated_with_their_values() {
+        let clamped = Message::new("CLAMPED")
+            .arg(Message::new("LABEL_LOGO_SIZE"))
+            .arg("30")
+            .arg("1")
+            .arg("25")
+            .arg("25");
+        let mut result = StampFileResult {
+            input_path: "photo.jpg".to_string(),
+            ok: true,
+            warnings: vec![
+                clamped.clone(),
+                Message::new("LOW_CONTRAST")
+                    .arg(Message::new("POSITION_BOTTOM_LEFT"))
+                    .arg("1.2"),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            clamped.to_string(),
+            "로고 크기 30%가 허용 범위(1~25%)를 벗어나 25%로 조정했습니다."
+        );
+
+        localize(&mut result, Locale::En);
+        assert_eq!(
+            result.warnings,
+            vec![
+                Message::from("Logo size 30% was outside the allowed range (1–25%) and was adjusted to 25%."),
+                Message::from("The logo at bottom-left has low contrast (1.2:1) and may be hard to see. Consider a logo color that stands out from the background."),
+            ]
+        );
+        assert_eq!(
+            clamped.render(Locale::Ja),
+            "ロゴサイズ 30%が許容範囲(1〜25%)を超えていたため25%に調整しました。"
+        );
+    }
+
+    #[test]
+    fn every_translation_has_the_same_placeholders() {
+        for (index, (key, ko, en, ja)) in CATALOG.iter().enumerate() {
+            assert!(
+                CATALOG[..index].iter().all(|(other, ..)| other != key),
+                "{key}"
+            );
+            let count = ko.matches("{}").count();
+            assert_eq!(en.matches("{}").count(), count, "{key}");
+            assert_eq!(ja.matches("{}").count(), count, "{key}");
+        }
+    }
+}
@@ -1,98 +1,912 @@
-use crate::path_policy;
-use image::imageops::{overlay, resize, FilterType};
-use image::RgbaImage;
+use crate::banner;
+use crate::blend::{self, BlendMode, UnitChannel};
+use crate::cancellation::CancellationToken;
+use crate::cmyk_jpeg;
+use crate::color;
+use crate::dpi;
+use crate::heif;
+use crate::i18n::Message;
+use crate::jitter::{Jitter, JitterRecord, JitterSettings};
+use crate::metadata;
+use crate::path_policy::{self, OutputNaming, OutputTarget};
+use crate::retry::RetryPolicy;
+use crate::svg_logo;
+use crate::tiff_pages::{self, TiffLayout, TiffPages};
+use crate::web_copy::{WebCopy, WebCopySettings};
+use crate::webp_codec::{self, WebpOptions};
+use image::imageops::{replace, resize, FilterType};
+use image::{DynamicImage, GenericImageView, ImageBuffer, ImageFormat, Pixel, Rgba, RgbaImage};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::io::Cursor;
 use std::path::{Path, PathBuf};
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StampSettingsInput {
+    /// "좌상단", "우상단", "좌하단", "우하단", "가운데", 또는 로고 가운데를 삼분할선 교차점에 맞추는
+    /// "삼분할 좌상단", "삼분할 우상단", "삼분할 좌하단", "삼분할 우하단".
     pub position: String,
     #[serde(default = "default_size_preset")]
     pub size_preset: String,
     #[serde(default)]
     pub size_percent: Option<f32>,
+    /// `size_percent`의 기준: "relative_to_page"(기본, 짧은 변 대비) 또는 "relative_to_logo"(로고 원본 크기 대비,
+    /// 150이면 1.5배). 로고 기준이면 프리셋은 쓰지 않고, `size_percent`가 없으면 원본 크기로 넣습니다.
+    #[serde(default)]
+    pub size_mode: Option<String>,
     pub margin_percent: f32,
+    /// 변별 여백(%) 덮어쓰기. 비우면 `margin_percent`를 쓰며, 선택한 모서리에 닿는 두 변만 적용됩니다.
+    #[serde(default)]
+    pub margin_top: Option<f32>,
+    #[serde(default)]
+    pub margin_right: Option<f32>,
+    #[serde(default)]
+    pub margin_bottom: Option<f32>,
+    #[serde(default)]
+    pub margin_left: Option<f32>,
+    /// 출력 이미지를 맞출 가로:세로 비율. 지정하면 로고 삽입 후 여백을 채워 비율을 맞춥니다.
+    #[serde(default)]
+    pub output_aspect: Option<(u32, u32)>,
+    /// 비율 맞춤 여백 색상(RGBA). 기본값은 불투명 흰색입니다.
+    #[serde(default)]
+    pub pad_color: Option<[u8; 4]>,
+    /// JPEG는 투명도를 저장하지 못하므로, 결과에 남은 투명 영역을 이 색(RGB) 위에 합성해 저장합니다. 기본값은 흰색입니다.
+    #[serde(default)]
+    pub jpeg_background: Option<[u8; 3]>,
+    /// 지정하면 로고 평균색과 그 아래 배경의 대비(WCAG 대비비, 1~21)가 이 값보다 낮을 때 경고를 남깁니다. 반복 로고에는 쓰지 않습니다.
+    #[serde(default)]
+    pub min_contrast: Option<f32>,
+    /// 로고 긴 변을 실제 인쇄 크기로 맞춥니다. 지정하면 `size_percent`/`size_preset` 대신 씁니다.
+    /// 이미지는 파일에 기록된 DPI(없으면 `dpi`)로 픽셀을 정하고, PDF는 `inches * 72`pt로 그립니다.
+    #[serde(default)]
+    pub physical_size: Option<PhysicalSize>,
+    /// 로고가 캔버스(페이지) 가로·세로의 이 비율(%) 상자 안에 모두 들어가도록 줄입니다.
+    /// 크기 설정으로 정한 크기보다 키우지는 않으며, 아주 넓거나 긴 로고가 여백 영역을 넘지 않게 합니다.
+    #[serde(default)]
+    pub fit_box: Option<FitBox>,
+    /// 로고 원본이 요청한 크기보다 작으면 키우지 않고 원본 크기(PDF는 1px = 1pt)로 넣고 경고합니다.
+    /// 작은 래스터 로고가 흐릿하게 늘어나는 것을 막습니다.
+    #[serde(default)]
+    pub no_upscale: bool,
+    /// 모서리에 이미 다른 워터마크가 있어 보이면 삽입을 건너뜁니다. (휴리스틱이므로 오탐이 있을 수 있습니다.)
+    #[serde(default)]
+    pub skip_if_watermarked: Option<bool>,
+    /// 여백 기준: "short_side"(기본, 짧은 변 기준 동일 여백) 또는 "per_axis"(가로/세로 각각의 길이 기준).
+    #[serde(default)]
+    pub margin_mode: Option<String>,
+    /// 로고를 흑백(휘도)으로 바꿔 삽입합니다.
+    #[serde(default)]
+    pub logo_desaturate: bool,
+    /// 로고를 한 가지 색(RGB)으로 물들입니다. 밝기는 유지되고 색상만 바뀝니다.
+    #[serde(default)]
+    pub logo_tint: Option<[u8; 3]>,
+    /// 어두운 문서용으로 로고 RGB를 반전합니다(알파 유지). 흑백/색 입히기보다 먼저 적용됩니다.
+    #[serde(default)]
+    pub logo_invert: bool,
+    /// 로고 둘레의 투명한 여백을 잘라내, 보이는 부분이 요청한 크기에 맞도록 합니다.
+    #[serde(default)]
+    pub trim_logo: bool,
+    /// 위조 방지를 위해 계산된 위치를 ±N만큼(이미지는 px, PDF는 pt) 흔듭니다.
+    #[serde(default)]
+    pub jitter_px: Option<u32>,
+    /// 흔들림 시드. 비우면 실행 시각으로 정하며, 실제 사용한 시드는 결과에 기록됩니다.
+    #[serde(default)]
+    pub jitter_seed: Option<u64>,
+    /// 위치를 캔버스(페이지) 짧은 변 대비 비율로 흔듭니다. 같은 시드면 파일 경로마다 항상 같은 오프셋이라,
+    /// 묶음 안의 로고가 픽셀 단위로 겹치지 않으면서도 재현할 수 있습니다. `jitter_px`와 함께 쓸 수 없습니다.
+    #[serde(default)]
+    pub jitter: Option<JitterSettings>,
+    /// PDF 페이지 번호(1부터)별 위치 덮어쓰기. 지정하지 않은 페이지는 `position`을 따릅니다.
+    #[serde(default)]
+    pub pdf_page_positions: Option<BTreeMap<u32, String>>,
+    /// PDF 각 페이지를 이미지로 렌더링한 뒤 로고를 합성해, 페이지마다 이미지 한 장뿐인 PDF로 다시 만듭니다.
+    #[serde(default)]
+    pub pdf_flatten: bool,
+    /// 로고를 넣을 PDF 페이지: "all"(기본), "first", "last". 나머지 페이지는 그대로 둡니다.
+    #[serde(default)]
+    pub pdf_pages: Option<String>,
+    /// 책자처럼 짝수 페이지에서 좌우 모서리를 바꿔 삽입합니다. `pdf_page_positions` 지정 페이지는 그 값을 따릅니다.
+    #[serde(default)]
+    pub mirror_even_pages: bool,
+    /// 보이는 로고와 별개로 출력 파일 메타데이터(PNG iTXt/JPEG 주석/PDF /Info)에 남길 출처 문자열입니다.
+    #[serde(default)]
+    pub embed_metadata: Option<String>,
+    /// 원본에 ICC 프로파일이 있으면 삽입 전에 sRGB로 변환합니다. 프로파일이 없으면 그대로 둡니다.
+    #[serde(default)]
+    pub convert_to_srgb: bool,
+    /// PDF에서 일부 페이지에 로고를 넣지 못해도 나머지 페이지를 처리해 저장하고, 실패한 페이지는 경고로 남깁니다.
+    #[serde(default)]
+    pub continue_on_page_error: bool,
+    /// 로고를 이미지 전체에 격자로 반복해 넣습니다. 위치/여백/흔들림은 쓰지 않습니다. (이미지 전용)
+    #[serde(default)]
+    pub tile: Option<bool>,
+    /// 로고 불투명도(0.0~1.0). 비우면 1.0(배너 모드는 0.3)입니다. PDF에는 배너 모드에서만 적용됩니다.
+    #[serde(default)]
+    pub opacity: Option<f32>,
+    /// "DRAFT"/"SAMPLE"처럼 로고를 45° 기울여 가운데에 크게, 반투명하게 넣습니다.
+    /// 위치/여백/기준점/흔들림은 쓰지 않으며 `tile`과 함께 켤 수 없습니다.
+    #[serde(default)]
+    pub banner_mode: bool,
+    /// 결과 이미지의 긴 변 최대 길이(px). 더 크면 비율을 유지해 줄인 뒤 로고를 넣습니다. (이미지 전용)
+    #[serde(default)]
+    pub max_dimension: Option<u32>,
+    /// 시안(교정본) 기본값을 씁니다: 반복 로고, 불투명도 40%, 긴 변 1600px. 직접 지정한 값이 우선합니다.
+    #[serde(default)]
+    pub proof_mode: bool,
+    /// 결과가 원본 파일을 덮어쓰게 될 때 먼저 `CornerBrand_Backup` 폴더에 원본을 복사합니다.
+    /// 끄면 원본 위치로는 저장하지 않습니다.
+    #[serde(default)]
+    pub backup_originals: bool,
+    /// JPEG를 다시 압축할 때(특히 이미 로고를 넣은 결과 파일일 때) 화질 저하를 경고로 남깁니다.
+    #[serde(default)]
+    pub warn_on_recompress: bool,
+    /// WebP 결과를 무손실로 저장할지 여부. 비우면 `webp_quality`가 없을 때만 무손실입니다.
+    #[serde(default)]
+    pub webp_lossless: Option<bool>,
+    /// WebP 손실 압축 품질(0~100, 기본 90).
+    #[serde(default)]
+    pub webp_quality: Option<f32>,
+    /// 로고 크기 조절 방식: "nearest"(픽셀 아트), "triangle", "catmullrom", "lanczos3"(기본, 가장 선명).
+    #[serde(default)]
+    pub resize_filter: Option<String>,
+    /// 계산한 위치에 로고의 어느 점을 맞출지: "top-left"(기본), "top", "top-right", "left", "center",
+    /// "right", "bottom-left", "bottom", "bottom-right". 캔버스를 벗어나면 가장자리로 당깁니다.
+    #[serde(default)]
+    pub anchor: Option<String>,
+    /// 로고 합성 방식: "over"(기본, 일반 합성), "multiply"(어둡게 눌러 찍기), "screen"(밝게).
+    /// 로고 알파만큼만 섞으며 이미지에만 적용됩니다. PDF는 일반 합성이고, PDF 배너만 곱하기로 그립니다.
+    #[serde(default)]
+    pub blend_mode: Option<String>,
+    /// 지정하면 결과 파일과 함께 결과 폴더의 `web` 하위 폴더에 줄인 사본(`<이름>_web.<확장자>`)도 만듭니다. 이미지에만 적용됩니다.
+    #[serde(default)]
+    pub web_copy: Option<WebCopySettings>,
+    /// 자동 결과 이름에 입력 확장자의 대소문자를 그대로 씁니다(`PHOTO.JPG` → `PHOTO_cornerbrand.JPG`). 기본은 소문자입니다.
+    #[serde(default)]
+    pub preserve_extension_case: bool,
+    /// 자동 결과 이름이 겹칠 때: "increment"(기본, `(1)` 등) 또는 "timestamp"(항상 `_YYYYMMDD_HHMMSS` UTC를 붙임).
+    #[serde(default)]
+    pub collision_strategy: Option<String>,
+    /// PDF 로고를 뷰어에서 켜고 끌 수 있는 "CornerBrand Watermark" 레이어(OCG)에 넣습니다. 평면화 모드에는 적용되지 않습니다.
+    #[serde(default)]
+    pub pdf_as_layer: bool,
+    /// PDF 로고의 투명 영역을 합성할 바탕색(RGB). 기본값은 흰색이며, 어두운 문서는 배경색에 맞추면
+    /// 로고 둘레의 밝은 테두리가 줄어듭니다. 평면화 모드에서도 로고를 이 색 위에 합성해 그립니다.
+    #[serde(default)]
+    pub pdf_matte_color: Option<[u8; 3]>,
+    /// PDF 결과에 sRGB 출력 의도, XMP 메타데이터, 문서 ID를 넣어 PDF/A-2b 기본 검사를 통과하도록 저장합니다.
+    /// 암호화된 PDF는 실패하고, 원본에 포함되지 않은 글꼴이 있으면 경고합니다.
+    #[serde(default)]
+    pub pdf_a: bool,
+    /// 이미 CornerBrand로 로고를 넣은 PDF(/Info의 `CornerBrand` 항목, 로고 레이어, 로고 XObject 표시)면
+    /// 다시 넣지 않고 `ALREADY_STAMPED`로 건너뜁니다. 평면화 모드 결과에는 표시가 남지 않습니다.
+    #[serde(default)]
+    pub skip_already_stamped: bool,
+    /// 크기/여백이 허용 범위를 벗어나면 범위 안으로 맞추고 경고하는 대신 그 값을 담은 오류로 실패합니다.
+    #[serde(default)]
+    pub strict_bounds: bool,
+    /// (실험적) PDF 로고 위치를 MediaBox가 아니라 텍스트/이미지/도형이 그려진 영역 기준으로 잡습니다.
+    /// 영역을 어림하지 못하면 MediaBox를 씁니다. 평면화 모드에는 적용되지 않습니다.
+    #[serde(default)]
+    pub anchor_to_content: bool,
+    /// 읽기/쓰기가 일시적인 I/O 오류로 실패했을 때 첫 시도를 포함해 몇 번까지 시도할지(기본 3).
+    #[serde(default)]
+    pub io_retry_attempts: Option<u32>,
+    /// 다시 시도하기 전 첫 대기 시간(ms, 기본 100). 시도마다 두 배로 늘어납니다.
+    #[serde(default)]
+    pub io_retry_backoff_ms: Option<u64>,
+}
+
+/// 인쇄 기준 로고 크기입니다.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhysicalSize {
+    /// 로고 긴 변의 길이(인치)입니다.
+    pub inches: f32,
+    /// 이미지에 DPI 정보가 없을 때 쓰는 해상도입니다.
+    pub dpi: f32,
+}
+
+impl PhysicalSize {
+    pub(crate) fn validated(self) -> Result<Self, Message> {
+        let positive = |v: f32| v.is_finite() && v > 0.0;
+        if positive(self.inches) && positive(self.dpi) {
+            Ok(self)
+        } else {
+            Err(Message::new("PHYSICAL_SIZE_INVALID"))
+        }
+    }
+
+    /// 이미지에서 로고 긴 변의 픽셀 수입니다.
+    fn pixels(self) -> u32 {
+        ((self.inches * self.dpi).round() as u32).max(1)
+    }
+}
+
+/// 로고가 들어가야 하는 상자로, 캔버스(페이지) 가로·세로 대비 비율(%)입니다.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FitBox {
+    pub max_width_percent: f32,
+    pub max_height_percent: f32,
+}
+
+impl FitBox {
+    pub(crate) fn validated(self) -> Result<Self, Message> {
+        let percent = |v: f32| v.is_finite() && v > 0.0 && v <= 100.0;
+        if percent(self.max_width_percent) && percent(self.max_height_percent) {
+            Ok(self)
+        } else {
+            Err(Message::new("FIT_BOX_INVALID"))
+        }
+    }
+
+    /// 로고를 상자 안에 모두 넣을 수 있는 가장 큰 배율입니다. 두 엔진이 함께 사용합니다.
+    pub(crate) fn max_scale(
+        self,
+        (width, height): (f64, f64),
+        (logo_width, logo_height): (f64, f64),
+    ) -> f64 {
+        let box_width = width * f64::from(self.max_width_percent) / 100.0;
+        let box_height = height * f64::from(self.max_height_percent) / 100.0;
+        (box_width / logo_width.max(1.0)).min(box_height / logo_height.max(1.0))
+    }
 }
 
 fn default_size_preset() -> String {
     "보통".to_string()
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StampFileResult {
     pub input_path: String,
     pub ok: bool,
     pub output_path: Option<String>,
-    pub error: Option<String>,
+    /// `web_copy`로 함께 만든 웹용 사본 경로입니다.
+    pub web_output_path: Option<String>,
+    pub error: Option<Message>,
+    pub code: Option<String>,
+    pub skipped: bool,
+    pub warnings: Vec<Message>,
+    pub jitter: Option<JitterRecord>,
 }
 
-#[derive(Debug, Clone, Copy)]
-enum CornerPosition {
+pub const CODE_LIKELY_WATERMARKED: &str = "LIKELY_WATERMARKED";
+pub const CODE_LOGO_NOT_VISIBLE: &str = "LOGO_NOT_VISIBLE";
+pub const CODE_UNSUPPORTED_FORMAT: &str = "UNSUPPORTED_FORMAT";
+pub const CODE_UNSUPPORTED_VIDEO: &str = "UNSUPPORTED_VIDEO";
+pub const CODE_UNSUPPORTED_OFFICE: &str = "UNSUPPORTED_OFFICE";
+pub const CODE_CMYK_UNSUPPORTED: &str = "CMYK_UNSUPPORTED";
+pub const CODE_ALREADY_STAMPED: &str = "ALREADY_STAMPED";
+pub const CODE_LOGO_IS_INPUT: &str = "LOGO_IS_INPUT";
+
+/// 이미지/PDF 엔진이 함께 쓰는 로고 크기(짧은 변 대비 %) 허용 범위입니다.
+/// 혼합 배치에서 같은 값이 두 엔진에서 같은 비율로 적용되도록 한곳에서 관리합니다.
+pub const SIZE_PERCENT_MIN: f32 = 1.0;
+pub const SIZE_PERCENT_MAX: f32 = 50.0;
+/// `size_mode`가 "relative_to_logo"일 때의 `size_percent` 범위입니다.
+pub const LOGO_SIZE_PERCENT_MIN: f32 = 1.0;
+pub const LOGO_SIZE_PERCENT_MAX: f32 = 1000.0;
+pub const MARGIN_PERCENT_MIN: f32 = 0.0;
+pub const MARGIN_PERCENT_MAX: f32 = 20.0;
+
+/// `proof_mode`에서 따로 지정하지 않았을 때 쓰는 값입니다.
+pub const PROOF_OPACITY: f32 = 0.4;
+pub const PROOF_MAX_DIMENSION: u32 = 1600;
+
+/// `size_percent`가 무엇에 대한 비율인지입니다. 두 엔진이 함께 사용합니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SizeMode {
+    /// 캔버스(페이지) 짧은 변 대비 비율입니다.
+    RelativeToPage,
+    /// 로고 원본 크기 대비 배율입니다. PDF는 로고 1px을 1pt로 봅니다.
+    RelativeToLogo,
+}
+
+impl SizeMode {
+    /// 이 기준에서 `size_percent`의 허용 범위입니다.
+    fn percent_bounds(self) -> (f32, f32) {
+        match self {
+            Self::RelativeToPage => (SIZE_PERCENT_MIN, SIZE_PERCENT_MAX),
+            Self::RelativeToLogo => (LOGO_SIZE_PERCENT_MIN, LOGO_SIZE_PERCENT_MAX),
+        }
+    }
+}
+
+pub(crate) fn parse_size_mode(value: Option<&str>) -> Result<SizeMode, Message> {
+    match value.map(str::trim) {
+        None | Some("") | Some("relative_to_page") => Ok(SizeMode::RelativeToPage),
+        Some("relative_to_logo") => Ok(SizeMode::RelativeToLogo),
+        Some(_) => Err(Message::new("INVALID_SIZE_MODE")),
+    }
+}
+
+/// `size_percent`가 유효하면 범위 안으로 맞추고, 아니면 크기 프리셋으로 짧은 변 대비 비율을 정합니다.
+/// 로고 기준이면 로고 원본 대비 배율이며, `size_percent`가 없으면 1(원본 크기)입니다.
+pub(crate) fn resolve_size_ratio(
+    size_percent: Option<f32>,
+    size_preset: &str,
+    size_mode: SizeMode,
+) -> Result<f32, Message> {
+    if let Some(size_percent) = size_percent.filter(|v| v.is_finite()) {
+        let (min, max) = size_mode.percent_bounds();
+        return Ok(size_percent.clamp(min, max) / 100.0);
+    }
+    if size_mode == SizeMode::RelativeToLogo {
+        return Ok(1.0);
+    }
+
+    match size_preset {
+        "작음" => Ok(0.08),
+        "보통" => Ok(0.12),
+        "큼" => Ok(0.16),
+        _ => Err(Message::new("INVALID_SIZE_PRESET")),
+    }
+}
+
+pub(crate) fn clamp_margin_percent(margin_percent: f32) -> f32 {
+    margin_percent.clamp(MARGIN_PERCENT_MIN, MARGIN_PERCENT_MAX)
+}
+
+/// 허용 범위를 벗어나 조정된 크기/여백 값을 경고 문장으로 돌려줍니다. 두 엔진이 파일마다 결과에 붙입니다.
+/// `strict_bounds`이면 조정하지 않고 처음 벗어난 값을 담은 오류를 돌려줍니다.
+pub(crate) fn clamp_warnings(value: &StampSettingsInput) -> Result<Vec<Message>, Message> {
+    let (size_min, size_max) = parse_size_mode(value.size_mode.as_deref())?.percent_bounds();
+    let bounded = [
+        ("LABEL_LOGO_SIZE", value.size_percent, size_min, size_max),
+        (
+            "LABEL_MARGIN",
+            Some(value.margin_percent),
+            MARGIN_PERCENT_MIN,
+            MARGIN_PERCENT_MAX,
+        ),
+        (
+            "LABEL_MARGIN_TOP",
+            value.margin_top,
+            MARGIN_PERCENT_MIN,
+            MARGIN_PERCENT_MAX,
+        ),
+        (
+            "LABEL_MARGIN_RIGHT",
+            value.margin_right,
+            MARGIN_PERCENT_MIN,
+            MARGIN_PERCENT_MAX,
+        ),
+        (
+            "LABEL_MARGIN_BOTTOM",
+            value.margin_bottom,
+            MARGIN_PERCENT_MIN,
+            MARGIN_PERCENT_MAX,
+        ),
+        (
+            "LABEL_MARGIN_LEFT",
+            value.margin_left,
+            MARGIN_PERCENT_MIN,
+            MARGIN_PERCENT_MAX,
+        ),
+    ];
+
+    let mut warnings = Vec::new();
+    for (label, input, min, max) in bounded {
+        let Some(input) = input.filter(|v| v.is_finite()) else {
+            continue;
+        };
+        let clamped = input.clamp(min, max);
+        if clamped == input {
+            continue;
+        }
+        if value.strict_bounds {
+            return Err(Message::new("OUT_OF_BOUNDS")
+                .arg(Message::new(label))
+                .arg(input.to_string())
+                .arg(min.to_string())
+                .arg(max.to_string()));
+        }
+        warnings.push(
+            Message::new("CLAMPED")
+                .arg(Message::new(label))
+                .arg(input.to_string())
+                .arg(min.to_string())
+                .arg(max.to_string())
+                .arg(clamped.to_string()),
+        );
+    }
+
+    Ok(warnings)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CornerPosition {
     TopLeft,
     TopRight,
     BottomLeft,
     BottomRight,
+    Center,
+    /// 삼분할선 교차점. 로고 가운데를 가로·세로 1/3 또는 2/3 지점에 맞추며 여백은 쓰지 않습니다.
+    ThirdTopLeft,
+    ThirdTopRight,
+    ThirdBottomLeft,
+    ThirdBottomRight,
+}
+
+impl CornerPosition {
+    /// 좌우를 뒤집은 위치입니다. 가운데는 그대로입니다.
+    pub(crate) fn mirrored(self) -> Self {
+        match self {
+            Self::TopLeft => Self::TopRight,
+            Self::TopRight => Self::TopLeft,
+            Self::BottomLeft => Self::BottomRight,
+            Self::BottomRight => Self::BottomLeft,
+            Self::Center => Self::Center,
+            Self::ThirdTopLeft => Self::ThirdTopRight,
+            Self::ThirdTopRight => Self::ThirdTopLeft,
+            Self::ThirdBottomLeft => Self::ThirdBottomRight,
+            Self::ThirdBottomRight => Self::ThirdBottomLeft,
+        }
+    }
+
+    /// 삼분할 위치면 로고 가운데를 맞출 교차점을 왼쪽 위에서 잰 가로/세로 비율로 돌려줍니다.
+    pub(crate) fn thirds_point(self) -> Option<(f64, f64)> {
+        const NEAR: f64 = 1.0 / 3.0;
+        const FAR: f64 = 2.0 / 3.0;
+        match self {
+            Self::ThirdTopLeft => Some((NEAR, NEAR)),
+            Self::ThirdTopRight => Some((FAR, NEAR)),
+            Self::ThirdBottomLeft => Some((NEAR, FAR)),
+            Self::ThirdBottomRight => Some((FAR, FAR)),
+            _ => None,
+        }
+    }
+
+    /// 경고 문구에 쓰는 화면 표기입니다. 한국어 표기는 `parse_position`이 받는 값과 같습니다.
+    pub(crate) fn label(self) -> Message {
+        Message::new(match self {
+            Self::TopLeft => "POSITION_TOP_LEFT",
+            Self::TopRight => "POSITION_TOP_RIGHT",
+            Self::BottomLeft => "POSITION_BOTTOM_LEFT",
+            Self::BottomRight => "POSITION_BOTTOM_RIGHT",
+            Self::Center => "POSITION_CENTER",
+            Self::ThirdTopLeft => "POSITION_THIRD_TOP_LEFT",
+            Self::ThirdTopRight => "POSITION_THIRD_TOP_RIGHT",
+            Self::ThirdBottomLeft => "POSITION_THIRD_BOTTOM_LEFT",
+            Self::ThirdBottomRight => "POSITION_THIRD_BOTTOM_RIGHT",
+        })
+    }
+}
+
+pub(crate) fn parse_position(value: &str) -> Result<CornerPosition, Message> {
+    match value {
+        "좌상단" => Ok(CornerPosition::TopLeft),
+        "우상단" => Ok(CornerPosition::TopRight),
+        "좌하단" => Ok(CornerPosition::BottomLeft),
+        "우하단" => Ok(CornerPosition::BottomRight),
+        "가운데" => Ok(CornerPosition::Center),
+        "삼분할 좌상단" => Ok(CornerPosition::ThirdTopLeft),
+        "삼분할 우상단" => Ok(CornerPosition::ThirdTopRight),
+        "삼분할 좌하단" => Ok(CornerPosition::ThirdBottomLeft),
+        "삼분할 우하단" => Ok(CornerPosition::ThirdBottomRight),
+        _ => Err(Message::new("INVALID_POSITION")),
+    }
+}
+
+/// 네 변의 여백(%)입니다. 두 엔진이 함께 사용합니다.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Margins {
+    top: f32,
+    right: f32,
+    bottom: f32,
+    left: f32,
+}
+
+impl Margins {
+    pub(crate) fn from_input(value: &StampSettingsInput) -> Self {
+        let side = |over: Option<f32>| {
+            clamp_margin_percent(
+                over.filter(|v| v.is_finite())
+                    .unwrap_or(value.margin_percent),
+            )
+        };
+        Self {
+            top: side(value.margin_top),
+            right: side(value.margin_right),
+            bottom: side(value.margin_bottom),
+            left: side(value.margin_left),
+        }
+    }
+
+    /// 위치에 닿는 (가로, 세로) 여백(%)입니다. 가운데와 삼분할 위치는 여백을 쓰지 않습니다.
+    pub(crate) fn for_position(&self, position: CornerPosition) -> (f32, f32) {
+        match position {
+            CornerPosition::TopLeft => (self.left, self.top),
+            CornerPosition::TopRight => (self.right, self.top),
+            CornerPosition::BottomLeft => (self.left, self.bottom),
+            CornerPosition::BottomRight => (self.right, self.bottom),
+            CornerPosition::Center
+            | CornerPosition::ThirdTopLeft
+            | CornerPosition::ThirdTopRight
+            | CornerPosition::ThirdBottomLeft
+            | CornerPosition::ThirdBottomRight => (0.0, 0.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MarginMode {
+    ShortSide,
+    PerAxis,
+}
+
+pub(crate) fn parse_margin_mode(value: Option<&str>) -> Result<MarginMode, Message> {
+    match value.map(str::trim) {
+        None | Some("") | Some("short_side") => Ok(MarginMode::ShortSide),
+        Some("per_axis") => Ok(MarginMode::PerAxis),
+        Some(_) => Err(Message::new("INVALID_MARGIN_MODE")),
+    }
+}
+
+pub(crate) fn parse_resize_filter(value: Option<&str>) -> Result<FilterType, Message> {
+    match value.map(str::trim) {
+        None | Some("") | Some("lanczos3") => Ok(FilterType::Lanczos3),
+        Some("nearest") => Ok(FilterType::Nearest),
+        Some("triangle") => Ok(FilterType::Triangle),
+        Some("catmullrom") => Ok(FilterType::CatmullRom),
+        Some(_) => Err(Message::new("INVALID_RESIZE_FILTER")),
+    }
+}
+
+/// 계산한 위치에 맞출 로고 안의 점으로, 왼쪽 위에서 잰 가로/세로 비율(0~1)입니다. 두 엔진이 함께 사용합니다.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct LogoAnchor {
+    pub fx: f64,
+    pub fy: f64,
+}
+
+pub(crate) fn parse_anchor(value: Option<&str>) -> Result<LogoAnchor, Message> {
+    let (fx, fy) = match value.map(str::trim) {
+        None | Some("") | Some("top-left") => (0.0, 0.0),
+        Some("top") => (0.5, 0.0),
+        Some("top-right") => (1.0, 0.0),
+        Some("left") => (0.0, 0.5),
+        Some("center") => (0.5, 0.5),
+        Some("right") => (1.0, 0.5),
+        Some("bottom-left") => (0.0, 1.0),
+        Some("bottom") => (0.5, 1.0),
+        Some("bottom-right") => (1.0, 1.0),
+        Some(_) => return Err(Message::new("INVALID_LOGO_ANCHOR")),
+    };
+    Ok(LogoAnchor { fx, fy })
+}
+
+/// 크기 조절 전에 로고 픽셀에 적용하는 색 변환입니다. 두 엔진이 함께 사용합니다.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LogoStyle {
+    invert: bool,
+    desaturate: bool,
+    tint: Option<[u8; 3]>,
+    trim: bool,
+}
+
+/// 이 값 이하의 알파는 로고 여백을 자를 때 투명한 것으로 봅니다.
+const TRIM_ALPHA_THRESHOLD: u8 = 8;
+
+impl LogoStyle {
+    pub(crate) fn from_input(value: &StampSettingsInput) -> Self {
+        Self {
+            invert: value.logo_invert,
+            desaturate: value.logo_desaturate,
+            tint: value.logo_tint,
+            trim: value.trim_logo,
+        }
+    }
+
+    pub(crate) fn without_trim(&self) -> Self {
+        Self {
+            trim: false,
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn apply(&self, logo: &mut RgbaImage) -> Result<(), Message> {
+        if self.trim {
+            *logo = trim_transparent(logo)?;
+        }
+
+        if self.invert {
+            for px in logo.pixels_mut() {
+                px[0] = 255 - px[0];
+                px[1] = 255 - px[1];
+                px[2] = 255 - px[2];
+            }
+        }
+
+        if !self.desaturate && self.tint.is_none() {
+            return Ok(());
+        }
+
+        for px in logo.pixels_mut() {
+            let luma =
+                0.299 * f32::from(px[0]) + 0.587 * f32::from(px[1]) + 0.114 * f32::from(px[2]);
+            let rgb = match self.tint {
+                Some(tint) => tint.map(|c| (f32::from(c) * luma / 255.0).round() as u8),
+                None => [luma.round() as u8; 3],
+            };
+            px[0] = rgb[0];
+            px[1] = rgb[1];
+            px[2] = rgb[2];
+        }
+        Ok(())
+    }
+}
+
+/// 알파가 기준값보다 큰 픽셀을 모두 감싸는 영역만 남깁니다.
+fn trim_transparent(logo: &RgbaImage) -> Result<RgbaImage, Message> {
+    let mut bounds: Option<(u32, u32, u32, u32)> = None;
+    for (x, y, px) in logo.enumerate_pixels() {
+        if px[3] <= TRIM_ALPHA_THRESHOLD {
+            continue;
+        }
+        bounds = Some(match bounds {
+            Some((x0, y0, x1, y1)) => (x0.min(x), y0.min(y), x1.max(x), y1.max(y)),
+            None => (x, y, x, y),
+        });
+    }
+
+    let (x0, y0, x1, y1) = bounds.ok_or_else(|| Message::new("LOGO_FULLY_TRANSPARENT"))?;
+    Ok(image::imageops::crop_imm(logo, x0, y0, x1 - x0 + 1, y1 - y0 + 1).to_image())
+}
+
+/// 준비를 마친 로고입니다. SVG 로고는 파일마다 그릴 크기에 맞춰 다시 그리도록 원본을 함께 둡니다.
+struct LogoImage {
+    image: RgbaImage,
+    svg: Option<Vec<u8>>,
+}
+
+impl LogoImage {
+    /// `canvas`에 넣을 로고입니다. SVG면 `SVG_RENDER_LONG_SIDE`로 그려 준비한 로고가 목표 크기가 되는
+    /// 비율로 다시 그려, 래스터를 늘리거나 줄이지 않고 선명하게 넣습니다. 로고 원본 크기 기준이면
+    /// SVG에 적힌 크기로 그려 둔 로고를 그대로 씁니다.
+    fn for_canvas(
+        &self,
+        canvas: (u32, u32),
+        settings: &StampSettings,
+    ) -> Result<Cow<'_, RgbaImage>, Message> {
+        let Some(svg) = self
+            .svg
+            .as_deref()
+            .filter(|_| settings.size_mode == SizeMode::RelativeToPage)
+        else {
+            return Ok(Cow::Borrowed(&self.image));
+        };
+        let (width, height) = self.image.dimensions();
+        let (target_width, target_height) =
+            logo_target_size(working_size(canvas, settings), (width, height), settings);
+        let scale =
+            f64::from(target_width.max(target_height)) / f64::from(width.max(height).max(1));
+        let long_side = (f64::from(svg_logo::SVG_RENDER_LONG_SIDE) * scale).ceil() as u32;
+        let mut logo = svg_logo::render(svg, long_side)?;
+        settings.prepare_logo(&mut logo)?;
+        Ok(Cow::Owned(logo))
+    }
 }
 
+/// 검증을 마친 이미지 삽입 설정입니다. `StampSettingsInput`에서 `TryFrom`으로 만듭니다.
 #[derive(Debug, Clone)]
-struct StampSettings {
+pub struct StampSettings {
     position: CornerPosition,
     size_ratio: f32,
-    margin_percent: f32,
+    size_mode: SizeMode,
+    margins: Margins,
+    margin_mode: MarginMode,
+    output_aspect: Option<(u32, u32)>,
+    pad_color: [u8; 4],
+    jpeg_background: [u8; 3],
+    min_contrast: Option<f32>,
+    physical_size: Option<PhysicalSize>,
+    fit_box: Option<FitBox>,
+    no_upscale: bool,
+    skip_if_watermarked: bool,
+    logo_style: LogoStyle,
+    jitter: Option<Jitter>,
+    embed_metadata: Option<String>,
+    convert_to_srgb: bool,
+    clamp_warnings: Vec<Message>,
+    tile: bool,
+    banner: bool,
+    opacity: f32,
+    max_dimension: Option<u32>,
+    backup_originals: bool,
+    warn_on_recompress: bool,
+    webp: WebpOptions,
+    resize_filter: FilterType,
+    anchor: LogoAnchor,
+    blend_mode: BlendMode,
+    web_copy: Option<WebCopy>,
+    naming: OutputNaming,
+    retry: RetryPolicy,
+}
+
+impl StampSettings {
+    /// 여백 자르기, 색 변환과 불투명도를 로고에 적용하고, 배너 모드면 로고를 돌려 둡니다.
+    fn prepare_logo(&self, logo: &mut RgbaImage) -> Result<(), Message> {
+        self.logo_style.apply(logo)?;
+        if self.opacity < 1.0 {
+            for px in logo.pixels_mut() {
+                px[3] = (f32::from(px[3]) * self.opacity).round() as u8;
+            }
+        }
+        if self.banner {
+            *logo = banner::rotate_logo(logo);
+        }
+        Ok(())
+    }
+}
+
+enum ImageStampOutcome {
+    Stamped {
+        output_path: PathBuf,
+        web_output_path: Option<PathBuf>,
+        warnings: Vec<Message>,
+        jitter: Option<JitterRecord>,
+    },
+    Skipped {
+        code: &'static str,
+        message: Message,
+    },
+    /// 건너뛴 것이 아니라 실패했지만, 화면에서 원인별로 안내할 수 있게 코드를 붙입니다.
+    Failed {
+        code: &'static str,
+        message: Message,
+    },
 }
 
 impl TryFrom<StampSettingsInput> for StampSettings {
-    type Error = String;
+    type Error = Message;
 
     fn try_from(value: StampSettingsInput) -> Result<Self, Self::Error> {
-        let position = match value.position.as_str() {
-            "좌상단" => CornerPosition::TopLeft,
-            "우상단" => CornerPosition::TopRight,
-            "좌하단" => CornerPosition::BottomLeft,
-            "우하단" => CornerPosition::BottomRight,
-            _ => return Err("유효하지 않은 위치 값입니다.".to_string()),
-        };
+        let position = parse_position(&value.position)?;
 
-        let size_ratio = if let Some(size_percent) = value.size_percent {
-            if size_percent.is_finite() {
-                size_percent.clamp(1.0, 50.0) / 100.0
-            } else {
-                match value.size_preset.as_str() {
-                    "작음" => 0.08,
-                    "보통" => 0.12,
-                    "큼" => 0.16,
-                    _ => return Err("유효하지 않은 크기 프리셋입니다.".to_string()),
-                }
-            }
-        } else {
-            match value.size_preset.as_str() {
-                "작음" => 0.08,
-                "보통" => 0.12,
-                "큼" => 0.16,
-                _ => return Err("유효하지 않은 크기 프리셋입니다.".to_string()),
+        let logo_style = LogoStyle::from_input(&value);
+        let size_mode = parse_size_mode(value.size_mode.as_deref())?;
+        let size_ratio = resolve_size_ratio(value.size_percent, &value.size_preset, size_mode)?;
+        let margins = Margins::from_input(&value);
+        let clamp_warnings = clamp_warnings(&value)?;
+        let jitter = Jitter::from_input(&value)?;
+
+        let output_aspect = match value.output_aspect {
+            Some((0, _)) | Some((_, 0)) => {
+                return Err(Message::new("INVALID_OUTPUT_ASPECT"));
             }
+            other => other,
         };
 
-        let margin_percent = value.margin_percent.clamp(0.0, 20.0);
+        if value
+            .min_contrast
+            .is_some_and(|ratio| !ratio.is_finite() || ratio < 1.0)
+        {
+            return Err(Message::new("MIN_CONTRAST_INVALID"));
+        }
+
+        if value.banner_mode && value.tile == Some(true) {
+            return Err(Message::new("BANNER_WITH_TILE"));
+        }
 
         Ok(Self {
             position,
             size_ratio,
-            margin_percent,
+            size_mode,
+            margins,
+            margin_mode: parse_margin_mode(value.margin_mode.as_deref())?,
+            output_aspect,
+            pad_color: value.pad_color.unwrap_or([255, 255, 255, 255]),
+            jpeg_background: value.jpeg_background.unwrap_or([255, 255, 255]),
+            min_contrast: value.min_contrast,
+            physical_size: value
+                .physical_size
+                .map(PhysicalSize::validated)
+                .transpose()?,
+            fit_box: value.fit_box.map(FitBox::validated).transpose()?,
+            no_upscale: value.no_upscale,
+            skip_if_watermarked: value.skip_if_watermarked.unwrap_or(false),
+            logo_style,
+            jitter,
+            embed_metadata: normalize_metadata(value.embed_metadata),
+            convert_to_srgb: value.convert_to_srgb,
+            clamp_warnings,
+            tile: !value.banner_mode && value.tile.unwrap_or(value.proof_mode),
+            banner: value.banner_mode,
+            opacity: value
+                .opacity
+                .filter(|v| v.is_finite())
+                .or(value.banner_mode.then_some(banner::BANNER_OPACITY))
+                .or(value.proof_mode.then_some(PROOF_OPACITY))
+                .unwrap_or(1.0)
+                .clamp(0.0, 1.0),
+            max_dimension: value
+                .max_dimension
+                .or(value.proof_mode.then_some(PROOF_MAX_DIMENSION))
+                .filter(|max| *max > 0),
+            backup_originals: value.backup_originals,
+            warn_on_recompress: value.warn_on_recompress,
+            webp: WebpOptions::from_input(value.webp_lossless, value.webp_quality)?,
+            resize_filter: parse_resize_filter(value.resize_filter.as_deref())?,
+            anchor: parse_anchor(value.anchor.as_deref())?,
+            blend_mode: blend::parse_blend_mode(value.blend_mode.as_deref())?,
+            web_copy: value.web_copy.as_ref().map(WebCopy::try_from).transpose()?,
+            naming: OutputNaming::from_input(
+                value.preserve_extension_case,
+                value.collision_strategy.as_deref(),
+            )?,
+            retry: RetryPolicy::from_input(value.io_retry_attempts, value.io_retry_backoff_ms),
         })
     }
 }
 
+/// 빈 출처 문자열은 메타데이터를 쓰지 않은 것으로 봅니다. 두 엔진이 함께 사용합니다.
+pub(crate) fn normalize_metadata(value: Option<String>) -> Option<String> {
+    value
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty())
+}
+
 pub fn stamp_images(
     paths: &[String],
     settings_input: StampSettingsInput,
     logo_path: &Path,
     output_base_dir: Option<&Path>,
+) -> Vec<StampFileResult> {
+    stamp_images_with_cancel(paths, settings_input, logo_path, output_base_dir, None)
+}
+
+/// `stamp_images`와 같고, `cancel`이 취소되면 결과 파일을 쓰지 않고 실패합니다.
+/// 배치가 파일별 제한 시간을 걸 때 써서, 시간을 넘겨 버려진 작업이 `TIMEOUT`으로 기록된 뒤에 결과를 남기지 않게 합니다.
+pub fn stamp_images_with_cancel(
+    paths: &[String],
+    settings_input: StampSettingsInput,
+    logo_path: &Path,
+    output_base_dir: Option<&Path>,
+    cancel: Option<&CancellationToken>,
+) -> Vec<StampFileResult> {
+    stamp_images_to(
+        paths,
+        settings_input,
+        logo_path,
+        OutputTarget::Auto(output_base_dir),
+        cancel,
+    )
+}
+
+/// 이미지 하나를 자동 이름 대신 `output_path`에 그대로 저장합니다. 파일이 이미 있으면 실패합니다.
+pub fn stamp_image_to_path(
+    input: &str,
+    settings_input: StampSettingsInput,
+    logo_path: &Path,
+    output_path: &Path,
+) -> StampFileResult {
+    let paths = [input.to_string()];
+    stamp_images_to(
+        &paths,
+        settings_input,
+        logo_path,
+        OutputTarget::Exact(output_path),
+        None,
+    )
+    .remove(0)
+}
+
+fn stamp_images_to(
+    paths: &[String],
+    settings_input: StampSettingsInput,
+    logo_path: &Path,
+    target: OutputTarget,
+    cancel: Option<&CancellationToken>,
 ) -> Vec<StampFileResult> {
     let settings = match StampSettings::try_from(settings_input) {
-        Ok(s) => s,
+        Ok(s) => StampSettings {
+            retry: s.retry.with_cancel(cancel.cloned()),
+            ..s
+        },
         Err(err) => {
             return paths
                 .iter()
@@ -101,13 +915,32 @@ pub fn stamp_images(
         }
     };
 
-    let logo = match image::open(logo_path) {
-        Ok(img) => img.to_rgba8(),
-        Err(e) => {
-            let message = format!("로고 리소스를 읽지 못했습니다: {e}");
+    let guard = LogoInputGuard::new(logo_path);
+    let logo = match settings
+        .retry
+        .read(logo_path)
+        .map_err(Message::from)
+        .and_then(|bytes| {
+            let decoded = svg_logo::decode_logo(logo_path, &bytes, settings.size_mode)?;
+            Ok((
+                decoded,
+                svg_logo::is_svg(logo_path, &bytes).then_some(bytes),
+            ))
+        })
+        .map_err(|e| Message::new("LOGO_READ_FAILED").arg(e))
+        .and_then(|(mut image, svg)| {
+            settings.prepare_logo(&mut image)?;
+            Ok(LogoImage { image, svg })
+        }) {
+        Ok(logo) => logo,
+        Err(message) => {
             return paths
                 .iter()
-                .map(|path| failure_result(path.clone(), message.clone()))
+                .map(|path| {
+                    guard
+                        .check(path)
+                        .unwrap_or_else(|| failure_result(path.clone(), message.clone()))
+                })
                 .collect();
         }
     };
@@ -115,13 +948,38 @@ pub fn stamp_images(
     paths
         .iter()
         .map(|input| {
+            if let Some(result) = guard.check(input) {
+                return result;
+            }
             let input_path = Path::new(input);
-            match stamp_single_image(input_path, &logo, &settings, output_base_dir) {
-                Ok(output_path) => StampFileResult {
+            match stamp_single_image(input_path, &logo, &settings, target) {
+                Ok(ImageStampOutcome::Stamped {
+                    output_path,
+                    web_output_path,
+                    warnings,
+                    jitter,
+                }) => StampFileResult {
                     input_path: input.clone(),
                     ok: true,
                     output_path: Some(output_path.to_string_lossy().to_string()),
+                    web_output_path: web_output_path.map(|path| path.to_string_lossy().to_string()),
                     error: None,
+                    warnings,
+                    jitter,
+                    ..Default::default()
+                },
+                Ok(ImageStampOutcome::Skipped { code, message }) => StampFileResult {
+                    input_path: input.clone(),
+                    ok: false,
+                    output_path: None,
+                    error: Some(message),
+                    code: Some(code.to_string()),
+                    skipped: true,
+                    ..Default::default()
+                },
+                Ok(ImageStampOutcome::Failed { code, message }) => StampFileResult {
+                    code: Some(code.to_string()),
+                    ..failure_result(input.clone(), message)
                 },
                 Err(error) => failure_result(input.clone(), error),
             }
@@ -131,68 +989,945 @@ pub fn stamp_images(
 
 fn stamp_single_image(
     input_path: &Path,
-    logo_image: &RgbaImage,
+    logo: &LogoImage,
     settings: &StampSettings,
-    output_base_dir: Option<&Path>,
-) -> Result<PathBuf, String> {
+    target: OutputTarget,
+) -> Result<ImageStampOutcome, Message> {
     let format_info = path_policy::detect_supported_image(input_path)
-        .ok_or_else(|| "지원하지 않는 파일 형식입니다. (jpg/png/webp)".to_string())?;
+        .ok_or_else(|| Message::new("UNSUPPORTED_IMAGE_FILE"))?;
+
+    let (source, source_dpi, tiff_warning) = if format_info.heif {
+        (
+            DynamicImage::ImageRgba8(heif::decode_rgba(input_path)?),
+            None,
+            None,
+        )
+    } else {
+        let bytes = settings
+            .retry
+            .read(input_path)
+            .map_err(|e| Message::new("IMAGE_READ_FAILED").arg(e.to_string()))?;
+        if format_info.format == ImageFormat::WebP && webp_codec::is_animated(&bytes) {
+            return stamp_animated_webp(input_path, &bytes, &logo.image, settings, target);
+        }
+        let mut tiff_warning = None;
+        if format_info.format == ImageFormat::Tiff {
+            match tiff_pages::decode_pages(&bytes)? {
+                TiffLayout::Pages(document) => {
+                    return stamp_tiff_pages(input_path, document, logo, settings, target);
+                }
+                TiffLayout::Unsupported { pages, color } => {
+                    tiff_warning = Some(
+                        Message::new("TIFF_FIRST_PAGE_ONLY")
+                            .arg(pages.to_string())
+                            .arg(color),
+                    );
+                }
+                TiffLayout::Single => {}
+            }
+        }
+        let source = match color::decode_image(&bytes, settings.convert_to_srgb) {
+            Ok(image) => image,
+            Err(message) if cmyk_jpeg::is_cmyk(&bytes) => {
+                return Ok(ImageStampOutcome::Failed {
+                    code: CODE_CMYK_UNSUPPORTED,
+                    message,
+                });
+            }
+            Err(message) => return Err(message),
+        };
+        (
+            source,
+            dpi::read_dpi(&bytes, format_info.format),
+            tiff_warning,
+        )
+    };
 
-    let source = image::open(input_path)
-        .map_err(|e| format!("이미지 파일을 읽지 못했습니다: {e}"))?
-        .to_rgba8();
+    // 인쇄 크기는 파일에 기록된 DPI가 있으면 그 값으로 픽셀 수를 정합니다.
+    let file_settings;
+    let settings = match (settings.physical_size, source_dpi) {
+        (Some(size), Some(dpi)) => {
+            file_settings = StampSettings {
+                physical_size: Some(PhysicalSize { dpi, ..size }),
+                ..settings.clone()
+            };
+            &file_settings
+        }
+        _ => settings,
+    };
 
     let (width, height) = source.dimensions();
     if width == 0 || height == 0 {
-        return Err("이미지 크기가 유효하지 않습니다.".to_string());
+        return Err(Message::new("IMAGE_SIZE_INVALID"));
     }
+    let logo_image = logo.for_canvas((width, height), settings)?;
+    let logo_image: &RgbaImage = &logo_image;
 
-    let short_side = width.min(height) as f32;
-    let margin_px = ((short_side * settings.margin_percent / 100.0).round() as u32).max(0);
-    let logo_max = logo_image.width().max(logo_image.height()).max(1);
-    let target_max = ((short_side * settings.size_ratio).round() as u32).max(1);
-    let scale = target_max as f32 / logo_max as f32;
-
-    let target_width = ((logo_image.width() as f32 * scale).round() as u32).max(1);
-    let target_height = ((logo_image.height() as f32 * scale).round() as u32).max(1);
-
-    let resized_logo = resize(
-        logo_image,
-        target_width,
-        target_height,
-        FilterType::Lanczos3,
-    );
+    if settings.skip_if_watermarked && looks_watermarked(&source.to_rgba8()) {
+        return Ok(ImageStampOutcome::Skipped {
+            code: CODE_LIKELY_WATERMARKED,
+            message: Message::new("LIKELY_WATERMARKED"),
+        });
+    }
 
-    let max_x = width.saturating_sub(target_width);
-    let max_y = height.saturating_sub(target_height);
+    let offset = image_jitter(input_path, (width, height), settings);
+    let (merged, clamped, changed, low_contrast, jitter_offset) = match source {
+        // 16비트를 담을 수 있는 형식이면 로고를 16비트로 올려 원본 정밀도를 지킵니다.
+        DynamicImage::ImageRgba16(source) if holds_16bit(format_info.format) => {
+            let logo = DynamicImage::ImageRgba8(logo_image.clone()).into_rgba16();
+            let pad = Rgba(settings.pad_color.map(|c| u16::from(c) * 257));
+            let composed = compose_stamp(&source, &logo, settings, offset, pad);
+            (
+                DynamicImage::ImageRgba16(composed.image),
+                composed.clamped,
+                composed.changed,
+                composed.low_contrast,
+                composed.jitter_offset,
+            )
+        }
+        source => {
+            let pad = Rgba(settings.pad_color);
+            let composed = compose_stamp(&source.into_rgba8(), logo_image, settings, offset, pad);
+            (
+                DynamicImage::ImageRgba8(composed.image),
+                composed.clamped,
+                composed.changed,
+                composed.low_contrast,
+                composed.jitter_offset,
+            )
+        }
+    };
+    if !changed {
+        // 다시 인코딩하면 손실 형식은 화질만 떨어지므로 결과 파일을 만들지 않습니다.
+        return Ok(ImageStampOutcome::Skipped {
+            code: CODE_LOGO_NOT_VISIBLE,
+            message: Message::new("LOGO_NOT_VISIBLE"),
+        });
+    }
 
-    let (x, y) = match settings.position {
-        CornerPosition::TopLeft => (margin_px.min(max_x), margin_px.min(max_y)),
-        CornerPosition::TopRight => (max_x.saturating_sub(margin_px), margin_px.min(max_y)),
-        CornerPosition::BottomLeft => (margin_px.min(max_x), max_y.saturating_sub(margin_px)),
-        CornerPosition::BottomRight => (
-            max_x.saturating_sub(margin_px),
-            max_y.saturating_sub(margin_px),
-        ),
+    let mut warnings = placement_warnings(settings, clamped);
+    warnings.extend(tiff_warning);
+    if let Some((requested, native)) = capped_upscale(
+        working_size((width, height), settings),
+        logo.image.dimensions(),
+        settings,
+    ) {
+        warnings.push(
+            Message::new("LOGO_NOT_UPSCALED")
+                .arg(native.to_string())
+                .arg(requested.to_string()),
+        );
+    }
+    if let Some(ratio) = low_contrast {
+        let position = if settings.banner {
+            CornerPosition::Center
+        } else {
+            settings.position
+        };
+        warnings.push(
+            Message::new("LOW_CONTRAST")
+                .arg(position.label())
+                .arg(format!("{ratio:.2}")),
+        );
+    }
+    if settings.warn_on_recompress && format_info.format == ImageFormat::Jpeg {
+        warnings.push(if path_policy::looks_like_stamped_output(input_path) {
+            Message::new("JPEG_RECOMPRESSED_AGAIN")
+        } else {
+            Message::new("JPEG_RECOMPRESSED")
+        });
+    }
+
+    let merged = if format_info.format == ImageFormat::Jpeg {
+        matte_alpha(merged, settings.jpeg_background)
+    } else {
+        merged
     };
 
-    let mut merged = source;
-    overlay(&mut merged, &resized_logo, i64::from(x), i64::from(y));
+    let output = target.image_path(input_path, settings.backup_originals, settings.naming)?;
+    let output_path = output.path.clone();
+    let encoded = match settings.embed_metadata.as_deref() {
+        Some(text) if metadata::supports_image_metadata(format_info.format) => {
+            metadata::encode_image_with_metadata(&merged, format_info.format, text)
+        }
+        embed => {
+            if embed.is_some() {
+                warnings.push(Message::new("METADATA_SKIPPED"));
+            }
+            if format_info.format == ImageFormat::WebP {
+                webp_codec::encode(&merged.to_rgba8(), settings.webp)
+            } else {
+                let mut bytes = Vec::new();
+                merged
+                    .write_to(&mut Cursor::new(&mut bytes), format_info.format)
+                    .map(|()| bytes)
+                    .map_err(|e| Message::from(e.to_string()))
+            }
+        }
+    };
+    let saved = encoded.and_then(|bytes| {
+        settings
+            .retry
+            .write(&output_path, &bytes)
+            .map_err(Message::from)
+    });
+    if let Err(e) = saved {
+        output.discard();
+        return Err(Message::new("IMAGE_SAVE_FAILED").arg(e));
+    }
 
-    let output_path = path_policy::build_output_path(input_path, output_base_dir)?;
-    image::DynamicImage::ImageRgba8(merged)
-        .save_with_format(&output_path, format_info.format)
-        .map_err(|e| format!("결과 이미지를 저장하지 못했습니다: {e}"))?;
+    // 보관용 결과는 이미 저장됐으므로, 웹용 사본이 실패해도 경고만 남깁니다.
+    let web_output_path = settings.web_copy.and_then(|web_copy| {
+        web_copy
+            .write(
+                &merged,
+                input_path,
+                &output_path,
+                format_info.format,
+                &settings.retry,
+            )
+            .map_err(|e| warnings.push(Message::new("WEB_COPY_FAILED").arg(e)))
+            .ok()
+    });
 
-    Ok(output_path)
+    Ok(ImageStampOutcome::Stamped {
+        output_path,
+        web_output_path,
+        warnings,
+        jitter: jitter_record(settings, vec![jitter_offset]),
+    })
 }
 
-fn failure_result(input_path: String, error: String) -> StampFileResult {
-    StampFileResult {
-        input_path,
-        ok: false,
-        output_path: None,
+/// 애니메이션 WebP의 모든 프레임에 같은 위치로 로고를 넣고 프레임 시각을 유지해 다시 저장합니다.
+fn stamp_animated_webp(
+    input_path: &Path,
+    bytes: &[u8],
+    logo_image: &RgbaImage,
+    settings: &StampSettings,
+    target: OutputTarget,
+) -> Result<ImageStampOutcome, Message> {
+    let mut animation = webp_codec::decode_animation(bytes)?;
+    if settings.skip_if_watermarked && looks_watermarked(&animation.frames[0].0) {
+        return Ok(ImageStampOutcome::Skipped {
+            code: CODE_LIKELY_WATERMARKED,
+            message: Message::new("LIKELY_WATERMARKED"),
+        });
+    }
+
+    let offset = image_jitter(input_path, animation.frames[0].0.dimensions(), settings);
+    let pad = Rgba(settings.pad_color);
+    let (mut clamped, mut changed) = (false, false);
+    // 프레임 크기가 모두 같으므로 실제로 옮긴 거리도 프레임마다 같습니다.
+    let mut jitter_offset = [0, 0];
+    for (frame, _) in &mut animation.frames {
+        let composed = compose_stamp(frame, logo_image, settings, offset, pad);
+        clamped |= composed.clamped;
+        changed |= composed.changed;
+        jitter_offset = composed.jitter_offset;
+        *frame = composed.image;
+    }
+    if !changed {
+        return Ok(ImageStampOutcome::Skipped {
+            code: CODE_LOGO_NOT_VISIBLE,
+            message: Message::new("LOGO_NOT_VISIBLE"),
+        });
+    }
+
+    let mut warnings = placement_warnings(settings, clamped);
+    if settings.embed_metadata.is_some() {
+        warnings.push(Message::new("METADATA_SKIPPED"));
+    }
+    if settings.web_copy.is_some() {
+        warnings.push(Message::new("WEB_COPY_ANIMATED_WEBP"));
+    }
+
+    let output = target.image_path(input_path, settings.backup_originals, settings.naming)?;
+    let saved = webp_codec::encode_animation(&animation, settings.webp).and_then(|bytes| {
+        settings
+            .retry
+            .write(&output.path, &bytes)
+            .map_err(Message::from)
+    });
+    if let Err(e) = saved {
+        output.discard();
+        return Err(Message::new("IMAGE_SAVE_FAILED").arg(e));
+    }
+
+    Ok(ImageStampOutcome::Stamped {
+        output_path: output.path,
+        web_output_path: None,
+        warnings,
+        jitter: jitter_record(settings, vec![jitter_offset]),
+    })
+}
+
+/// 여러 쪽 TIFF(팩스·스캔 묶음)는 쪽마다 크기에 맞춰 로고를 넣고 같은 쪽 수의 TIFF로 다시 씁니다.
+/// 흔들림은 PDF처럼 쪽 번호마다 따로 정합니다. 한 쪽짜리 TIFF는 일반 이미지 경로로 처리합니다.
+fn stamp_tiff_pages(
+    input_path: &Path,
+    mut document: TiffPages,
+    logo: &LogoImage,
+    settings: &StampSettings,
+    target: OutputTarget,
+) -> Result<ImageStampOutcome, Message> {
+    if settings.skip_if_watermarked && looks_watermarked(&document.pages[0].image.to_rgba8()) {
+        return Ok(ImageStampOutcome::Skipped {
+            code: CODE_LIKELY_WATERMARKED,
+            message: Message::new("LIKELY_WATERMARKED"),
+        });
+    }
+
+    let key = input_path.to_string_lossy();
+    let (mut clamped, mut changed) = (false, false);
+    let mut offsets = Vec::with_capacity(document.pages.len());
+    for (page, index) in document.pages.iter_mut().zip(1..) {
+        let canvas = (page.image.width(), page.image.height());
+        if canvas.0 == 0 || canvas.1 == 0 {
+            return Err(Message::new("TIFF_PAGE_SIZE_INVALID").arg(index.to_string()));
+        }
+        let (width, height) = working_size(canvas, settings);
+        let offset = settings.jitter.map_or((0, 0), |jitter| {
+            jitter.offset(&key, index, f64::from(width.min(height)))
+        });
+        let logo_image = logo.for_canvas(canvas, settings)?;
+        let (image, page_clamped, page_changed, jitter_offset) = match &page.image {
+            DynamicImage::ImageRgba16(source) => {
+                let logo = DynamicImage::ImageRgba8(logo_image.clone().into_owned()).into_rgba16();
+                let pad = Rgba(settings.pad_color.map(|c| u16::from(c) * 257));
+                let composed = compose_stamp(source, &logo, settings, offset, pad);
+                (
+                    DynamicImage::ImageRgba16(composed.image),
+                    composed.clamped,
+                    composed.changed,
+                    composed.jitter_offset,
+                )
+            }
+            other => {
+                let source = other.to_rgba8();
+                let pad = Rgba(settings.pad_color);
+                let composed = compose_stamp(&source, &*logo_image, settings, offset, pad);
+                (
+                    DynamicImage::ImageRgba8(composed.image),
+                    composed.clamped,
+                    composed.changed,
+                    composed.jitter_offset,
+                )
+            }
+        };
+        clamped |= page_clamped;
+        changed |= page_changed;
+        page.image = image;
+        offsets.push(jitter_offset);
+    }
+    if !changed {
+        return Ok(ImageStampOutcome::Skipped {
+            code: CODE_LOGO_NOT_VISIBLE,
+            message: Message::new("LOGO_NOT_VISIBLE"),
+        });
+    }
+
+    let mut warnings = placement_warnings(settings, clamped);
+    if settings.embed_metadata.is_some() {
+        warnings.push(Message::new("METADATA_SKIPPED"));
+    }
+    if settings.web_copy.is_some() {
+        warnings.push(Message::new("WEB_COPY_MULTI_PAGE_TIFF"));
+    }
+
+    let output = target.image_path(input_path, settings.backup_originals, settings.naming)?;
+    let saved = tiff_pages::encode_pages(&document).and_then(|bytes| {
+        settings
+            .retry
+            .write(&output.path, &bytes)
+            .map_err(Message::from)
+    });
+    if let Err(e) = saved {
+        output.discard();
+        return Err(Message::new("IMAGE_SAVE_FAILED").arg(e));
+    }
+
+    Ok(ImageStampOutcome::Stamped {
+        output_path: output.path,
+        web_output_path: None,
+        warnings,
+        jitter: jitter_record(settings, offsets),
+    })
+}
+
+/// 이미지 한 장에 쓸 흔들림 오프셋입니다. 흔들지 않으면 `(0, 0)`입니다.
+fn image_jitter(input_path: &Path, canvas: (u32, u32), settings: &StampSettings) -> (i32, i32) {
+    let (width, height) = working_size(canvas, settings);
+    let short_side = f64::from(width.min(height));
+    settings.jitter.map_or((0, 0), |jitter| {
+        jitter.offset(&input_path.to_string_lossy(), 1, short_side)
+    })
+}
+
+/// 결과에 남길 흔들림 기록입니다. `offsets`는 가장자리에 맞춰 자른 뒤 실제로 옮긴 거리입니다.
+fn jitter_record(settings: &StampSettings, offsets: Vec<[i32; 2]>) -> Option<JitterRecord> {
+    settings.jitter.map(|jitter| JitterRecord {
+        seed: jitter.seed,
+        offsets,
+    })
+}
+
+fn placement_warnings(settings: &StampSettings, clamped: bool) -> Vec<Message> {
+    let mut warnings = settings.clamp_warnings.clone();
+    if clamped {
+        warnings.push(Message::new("POSITION_NUDGED"));
+    }
+    warnings
+}
+
+/// 파일 입출력 없이 메모리의 원본에 로고를 삽입한 새 이미지를 돌려줍니다. 같은 입력이면 항상 같은 결과입니다.
+/// 흔들림은 입력 경로를 키로 쓰므로 여기서는 적용하지 않습니다. `trim_logo`인데 로고가 완전히 투명하면
+/// 자르지 않고 넣습니다. 이 경우를 오류로 받으려면 `try_stamp_rgba`를 씁니다.
+pub fn stamp_rgba(source: &RgbaImage, logo: &RgbaImage, settings: &StampSettings) -> RgbaImage {
+    try_stamp_rgba(source, logo, settings).unwrap_or_else(|_| {
+        let untrimmed = StampSettings {
+            logo_style: settings.logo_style.without_trim(),
+            ..settings.clone()
+        };
+        let mut logo = logo.clone();
+        untrimmed
+            .prepare_logo(&mut logo)
+            .expect("logo styling without trim cannot fail");
+        compose_stamp(source, &logo, &untrimmed, (0, 0), Rgba(untrimmed.pad_color)).image
+    })
+}
+
+/// `stamp_rgba`와 같지만 로고를 준비하지 못하면(완전히 투명한 로고의 여백 자르기 등) 오류를 돌려줍니다.
+pub fn try_stamp_rgba(
+    source: &RgbaImage,
+    logo: &RgbaImage,
+    settings: &StampSettings,
+) -> Result<RgbaImage, Message> {
+    let mut logo = logo.clone();
+    settings.prepare_logo(&mut logo)?;
+    Ok(compose_stamp(source, &logo, settings, (0, 0), Rgba(settings.pad_color)).image)
+}
+
+/// 채널당 16비트로 저장할 수 있는 출력 형식입니다.
+fn holds_16bit(format: ImageFormat) -> bool {
+    matches!(format, ImageFormat::Png | ImageFormat::Tiff)
+}
+
+/// 남은 알파를 단색 배경 위에 합성해 불투명 RGB로 만듭니다. 인코더가 알파를 그냥 버리면
+/// 투명 픽셀 아래 숨은 색이 그대로 드러나므로 JPEG로 저장하기 전에 씁니다.
+fn matte_alpha(image: DynamicImage, background: [u8; 3]) -> DynamicImage {
+    if !image.color().has_alpha() {
+        return image;
+    }
+    let rgba = image.into_rgba8();
+    if rgba.pixels().all(|px| px[3] == u8::MAX) {
+        return DynamicImage::ImageRgba8(rgba);
+    }
+    let matted = image::RgbImage::from_fn(rgba.width(), rgba.height(), |x, y| {
+        let [r, g, b, a] = rgba.get_pixel(x, y).0;
+        let blend = |fg: u8, bg: u8| {
+            ((u16::from(fg) * u16::from(a) + u16::from(bg) * u16::from(255 - a) + 127) / 255) as u8
+        };
+        image::Rgb([
+            blend(r, background[0]),
+            blend(g, background[1]),
+            blend(b, background[2]),
+        ])
+    });
+    DynamicImage::ImageRgb8(matted)
+}
+
+type Canvas<P> = ImageBuffer<P, Vec<<P as Pixel>::Subpixel>>;
+
+struct ComposedStamp<P: Pixel> {
+    image: Canvas<P>,
+    clamped: bool,
+    /// 결과가 원본 픽셀과 하나라도 다른지 여부입니다. `max_dimension` 크기 줄임과 비율 맞춤 여백도 바뀐 것으로 셉니다.
+    changed: bool,
+    /// `min_contrast`를 밑돈 로고의 대비비입니다.
+    low_contrast: Option<f32>,
+    /// 캔버스 가장자리에 맞춰 자른 뒤 실제로 옮겨진 흔들림 [dx, dy]입니다.
+    jitter_offset: [i32; 2],
+}
+
+/// 색 변환이 끝난 로고를 크기 조절·배치해 합성하고, 출력 비율이 있으면 `pad`로 여백을 채웁니다.
+/// 원본과 로고는 같은 픽셀 형식(8비트 또는 16비트 RGBA)이어야 합니다.
+fn compose_stamp<P>(
+    source: &Canvas<P>,
+    logo: &Canvas<P>,
+    settings: &StampSettings,
+    (dx, dy): (i32, i32),
+    pad: P,
+) -> ComposedStamp<P>
+where
+    P: Pixel + 'static,
+    P::Subpixel: Into<f32> + UnitChannel,
+{
+    let original = source;
+    let downscaled;
+    let working = working_size(source.dimensions(), settings);
+    let source = if working != source.dimensions() {
+        downscaled = resize(source, working.0, working.1, FilterType::Lanczos3);
+        &downscaled
+    } else {
+        source
+    };
+
+    let (width, height) = source.dimensions();
+    let (target_width, target_height) =
+        logo_target_size((width, height), logo.dimensions(), settings);
+
+    let resized_logo = resize(logo, target_width, target_height, settings.resize_filter);
+    let mut merged = source.clone();
+
+    let (clamped, low_contrast, jitter_offset) = if settings.tile {
+        for (x, y) in tile_origins((width, height), (target_width, target_height)) {
+            blend::blend_overlay(&mut merged, &resized_logo, x, y, settings.blend_mode);
+        }
+        (false, None, [0, 0])
+    } else {
+        let (x, y, clamped, jitter_offset) = logo_origin(
+            (width, height),
+            (target_width, target_height),
+            settings,
+            (dx, dy),
+        );
+        blend::blend_overlay(&mut merged, &resized_logo, x, y, settings.blend_mode);
+        let low_contrast = settings.min_contrast.and_then(|min| {
+            logo_contrast(source, &resized_logo, (x, y)).filter(|ratio| *ratio < min)
+        });
+        (clamped, low_contrast, jitter_offset)
+    };
+
+    if let Some(aspect) = settings.output_aspect {
+        merged = pad_to_aspect(merged, aspect, pad);
+    }
+    // 줄인 작업 이미지가 아니라 원본과 비교해야 크기만 줄인 결과도 쓸모 있는 결과로 남습니다.
+    let changed =
+        merged.dimensions() != original.dimensions() || merged.as_raw() != original.as_raw();
+
+    ComposedStamp {
+        image: merged,
+        clamped,
+        changed,
+        low_contrast,
+        jitter_offset,
+    }
+}
+
+/// 로고의 알파 가중 평균색과 로고가 덮는 배경의 평균색 사이 WCAG 대비비입니다. 보이는 로고 픽셀이 없으면 `None`입니다.
+fn logo_contrast<P>(background: &Canvas<P>, logo: &Canvas<P>, (x, y): (i64, i64)) -> Option<f32>
+where
+    P: Pixel + 'static,
+    P::Subpixel: Into<f32>,
+{
+    let max: f32 = <P::Subpixel as image::Primitive>::DEFAULT_MAX_VALUE.into();
+    let (mut logo_sum, mut background_sum, mut weight) = ([0.0f32; 3], [0.0f32; 3], 0.0f32);
+    for (lx, ly, pixel) in logo.enumerate_pixels() {
+        let (bx, by) = (x + i64::from(lx), y + i64::from(ly));
+        if bx < 0
+            || by < 0
+            || bx >= i64::from(background.width())
+            || by >= i64::from(background.height())
+        {
+            continue;
+        }
+        let logo_px = pixel.to_rgba();
+        let alpha = logo_px[3].into() / max;
+        if alpha <= 0.0 {
+            continue;
+        }
+        let background_px = background.get_pixel(bx as u32, by as u32).to_rgba();
+        for channel in 0..3 {
+            logo_sum[channel] += logo_px[channel].into() / max * alpha;
+            background_sum[channel] += background_px[channel].into() / max * alpha;
+        }
+        weight += alpha;
+    }
+    if weight <= 0.0 {
+        return None;
+    }
+
+    let luminance = |sum: [f32; 3]| {
+        let linear = sum.map(|c| {
+            let c = c / weight;
+            if c <= 0.040_45 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        });
+        0.2126 * linear[0] + 0.7152 * linear[1] + 0.0722 * linear[2]
+    };
+    let (a, b) = (luminance(logo_sum), luminance(background_sum));
+    Some((a.max(b) + 0.05) / (a.min(b) + 0.05))
+}
+
+/// `max_dimension`을 적용한 뒤의 캔버스 크기입니다.
+fn working_size((width, height): (u32, u32), settings: &StampSettings) -> (u32, u32) {
+    match settings.max_dimension {
+        Some(max) if width.max(height) > max => {
+            let scale = max as f32 / width.max(height) as f32;
+            (
+                ((width as f32 * scale).round() as u32).max(1),
+                ((height as f32 * scale).round() as u32).max(1),
+            )
+        }
+        _ => (width, height),
+    }
+}
+
+/// 짧은 변 대비 크기 비율(또는 인쇄 크기)로 정한 로고의 그릴 크기입니다. 배너 모드는 돌려 둔 로고를 캔버스에 맞춥니다.
+fn logo_target_size(
+    (width, height): (u32, u32),
+    (logo_width, logo_height): (u32, u32),
+    settings: &StampSettings,
+) -> (u32, u32) {
+    if settings.banner {
+        let scale = banner::fit_scale(
+            (f64::from(width), f64::from(height)),
+            (f64::from(logo_width), f64::from(logo_height)),
+        );
+        return (
+            ((f64::from(logo_width) * scale).round() as u32).max(1),
+            ((f64::from(logo_height) * scale).round() as u32).max(1),
+        );
+    }
+    let logo_max = logo_width.max(logo_height).max(1);
+    let target_max = requested_logo_max((width, height), logo_max, settings);
+    let mut scale = target_max as f32 / logo_max as f32;
+    if settings.no_upscale {
+        scale = scale.min(1.0);
+    }
+    if let Some(fit_box) = settings.fit_box {
+        let limit = fit_box.max_scale(
+            (f64::from(width), f64::from(height)),
+            (f64::from(logo_width), f64::from(logo_height)),
+        );
+        scale = scale.min(limit as f32);
+    }
+    (
+        ((logo_width as f32 * scale).round() as u32).max(1),
+        ((logo_height as f32 * scale).round() as u32).max(1),
+    )
+}
+
+/// 설정이 요청한 로고 긴 변의 길이(px)입니다. `logo_max`는 로고 원본의 긴 변입니다.
+fn requested_logo_max((width, height): (u32, u32), logo_max: u32, settings: &StampSettings) -> u32 {
+    let basis = match settings.size_mode {
+        SizeMode::RelativeToPage => width.min(height),
+        SizeMode::RelativeToLogo => logo_max,
+    };
+    match settings.physical_size {
+        Some(size) => size.pixels(),
+        None => ((basis as f32 * settings.size_ratio).round() as u32).max(1),
+    }
+}
+
+/// `no_upscale` 때문에 로고를 요청한 크기보다 작게 넣었다면 `(요청, 원본)` 긴 변 길이입니다.
+fn capped_upscale(
+    canvas: (u32, u32),
+    (logo_width, logo_height): (u32, u32),
+    settings: &StampSettings,
+) -> Option<(u32, u32)> {
+    let logo_max = logo_width.max(logo_height);
+    let requested = requested_logo_max(canvas, logo_max, settings);
+    (settings.no_upscale && !settings.banner && requested > logo_max)
+        .then_some((requested, logo_max))
+}
+
+/// 위치·여백·기준점·흔들림을 적용하고 캔버스 안으로 당긴 로고의 왼쪽 위 좌표와, 여백이 잘렸는지 여부입니다.
+fn logo_origin(
+    (width, height): (u32, u32),
+    (logo_width, logo_height): (u32, u32),
+    settings: &StampSettings,
+    (dx, dy): (i32, i32),
+) -> (i64, i64, bool, [i32; 2]) {
+    if settings.banner {
+        return (
+            (i64::from(width) - i64::from(logo_width)) / 2,
+            (i64::from(height) - i64::from(logo_height)) / 2,
+            false,
+            [0, 0],
+        );
+    }
+    let placement = compute_placement((width, height), (logo_width, logo_height), settings);
+    let max_x = i64::from(width.saturating_sub(logo_width));
+    let max_y = i64::from(height.saturating_sub(logo_height));
+    let base_x =
+        i64::from(placement.x) - (settings.anchor.fx * f64::from(logo_width)).round() as i64;
+    let base_y =
+        i64::from(placement.y) - (settings.anchor.fy * f64::from(logo_height)).round() as i64;
+    let x = (base_x + i64::from(dx)).clamp(0, max_x);
+    let y = (base_y + i64::from(dy)).clamp(0, max_y);
+    // 가장자리에서 잘린 만큼을 뺀, 실제로 옮겨진 거리를 기록합니다.
+    let applied = [
+        (x - base_x.clamp(0, max_x)) as i32,
+        (y - base_y.clamp(0, max_y)) as i32,
+    ];
+    (x, y, placement.clamped, applied)
+}
+
+/// 로고가 놓일 영역(왼쪽 위 원점, px)입니다. `max_dimension`으로 줄였다면 줄인 캔버스 기준입니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StampRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// `compute_stamp_rect`와 같지만 로고 파일을 읽어 크기를 정합니다. `trim_logo`면 투명한 여백을 잘라낸 크기를 씁니다.
+pub fn compute_stamp_rect_for_logo(
+    source_size: (u32, u32),
+    settings: &StampSettings,
+    logo_path: &Path,
+) -> Result<StampRect, Message> {
+    let logo = svg_logo::open_logo(logo_path, settings.size_mode)
+        .map_err(|e| Message::new("LOGO_READ_FAILED").arg(e))?;
+    let logo_size = if settings.logo_style.trim {
+        trim_transparent(&logo)?.dimensions()
+    } else {
+        logo.dimensions()
+    };
+    compute_stamp_rect(source_size, settings, logo_size)
+}
+
+/// 렌더링 없이 실제 합성과 같은 계산으로 로고 영역을 돌려줍니다. 로고 크기는 여백을 자른 뒤의 크기여야 하며,
+/// `trim_logo`를 쓸 때는 `compute_stamp_rect_for_logo`를 씁니다.
+/// 흔들림은 입력 경로를 키로 쓰므로 적용하지 않으며, 타일 모드는 영역이 하나가 아니라 지원하지 않습니다.
+pub fn compute_stamp_rect(
+    (source_width, source_height): (u32, u32),
+    settings: &StampSettings,
+    (logo_width, logo_height): (u32, u32),
+) -> Result<StampRect, Message> {
+    if source_width == 0 || source_height == 0 {
+        return Err(Message::new("IMAGE_SIZE_INVALID"));
+    }
+    if logo_width == 0 || logo_height == 0 {
+        return Err(Message::new("LOGO_SIZE_INVALID"));
+    }
+    if settings.tile {
+        return Err(Message::new("TILE_HAS_NO_SINGLE_RECT"));
+    }
+
+    let canvas = working_size((source_width, source_height), settings);
+    let logo_size = if settings.banner {
+        banner::rotated_size((logo_width, logo_height))
+    } else {
+        (logo_width, logo_height)
+    };
+    let (width, height) = logo_target_size(canvas, logo_size, settings);
+    let (x, y, _, _) = logo_origin(canvas, (width, height), settings, (0, 0));
+    Ok(StampRect {
+        x: x as u32,
+        y: y as u32,
+        width,
+        height,
+    })
+}
+
+/// 로고 크기의 절반만큼 간격을 두고 캔버스를 채우는 격자 위치들입니다. 가장자리에서 잘리는 칸도 포함합니다.
+fn tile_origins(
+    (width, height): (u32, u32),
+    (logo_width, logo_height): (u32, u32),
+) -> Vec<(i64, i64)> {
+    let (gap_x, gap_y) = (logo_width / 2, logo_height / 2);
+    let step_x = (logo_width + gap_x).max(1) as usize;
+    let step_y = (logo_height + gap_y).max(1) as usize;
+
+    (i64::from(gap_y / 2)..i64::from(height))
+        .step_by(step_y)
+        .flat_map(|y| {
+            (i64::from(gap_x / 2)..i64::from(width))
+                .step_by(step_x)
+                .map(move |x| (x, y))
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Placement {
+    x: u32,
+    y: u32,
+    /// 로고+여백이 캔버스를 넘어 위치가 잘리거나 여백이 줄어든 경우입니다.
+    clamped: bool,
+}
+
+fn compute_placement(
+    (width, height): (u32, u32),
+    (logo_width, logo_height): (u32, u32),
+    settings: &StampSettings,
+) -> Placement {
+    let short_side = width.min(height) as f32;
+    let (basis_x, basis_y) = match settings.margin_mode {
+        MarginMode::ShortSide => (short_side, short_side),
+        MarginMode::PerAxis => (width as f32, height as f32),
+    };
+    let (margin_h, margin_v) = settings.margins.for_position(settings.position);
+    let margin_x = (basis_x * margin_h / 100.0).round() as u32;
+    let margin_y = (basis_y * margin_v / 100.0).round() as u32;
+
+    let max_x = width.saturating_sub(logo_width);
+    let max_y = height.saturating_sub(logo_height);
+
+    let (x, y) = match settings.position {
+        CornerPosition::TopLeft => (margin_x.min(max_x), margin_y.min(max_y)),
+        CornerPosition::TopRight => (max_x.saturating_sub(margin_x), margin_y.min(max_y)),
+        CornerPosition::BottomLeft => (margin_x.min(max_x), max_y.saturating_sub(margin_y)),
+        CornerPosition::BottomRight => (
+            max_x.saturating_sub(margin_x),
+            max_y.saturating_sub(margin_y),
+        ),
+        CornerPosition::Center => (max_x / 2, max_y / 2),
+        position => {
+            let (fx, fy) = position.thirds_point().unwrap_or((0.5, 0.5));
+            let centered = |size: u32, logo: u32, max: u32, ratio: f64| {
+                ((f64::from(size) * ratio - f64::from(logo) / 2.0)
+                    .round()
+                    .max(0.0) as u32)
+                    .min(max)
+            };
+            (
+                centered(width, logo_width, max_x, fx),
+                centered(height, logo_height, max_y, fy),
+            )
+        }
+    };
+
+    let clamped =
+        logo_width > width || logo_height > height || margin_x > max_x || margin_y > max_y;
+
+    Placement { x, y, clamped }
+}
+
+/// 네 모서리 영역을 안쪽 이웃 영역과 비교해 워터마크처럼 튀는 부분이 있는지 추정합니다.
+/// 모서리에 피사체가 걸친 사진 등에서는 오탐이 생길 수 있습니다.
+fn looks_watermarked(image: &RgbaImage) -> bool {
+    let (width, height) = image.dimensions();
+    let patch = ((width.min(height) as f32 * 0.2).round() as u32).max(4);
+    if patch * 2 > width || patch * 2 > height {
+        return false;
+    }
+
+    let right = width - patch;
+    let bottom = height - patch;
+    let corners = [
+        ((0, 0), (patch, 0), (0, patch)),
+        ((right, 0), (right - patch, 0), (right, patch)),
+        ((0, bottom), (patch, bottom), (0, bottom - patch)),
+        (
+            (right, bottom),
+            (right - patch, bottom),
+            (right, bottom - patch),
+        ),
+    ];
+
+    corners.iter().any(|&(corner, beside, above_or_below)| {
+        let corner = region_stats(image, corner, patch);
+        [beside, above_or_below].iter().all(|&origin| {
+            let neighbour = region_stats(image, origin, patch);
+            let mean_gap = (corner.mean_luma - neighbour.mean_luma).abs();
+            let edge_gap = corner.edge_ratio - neighbour.edge_ratio;
+            mean_gap > 24.0 || edge_gap > 0.05
+        })
+    })
+}
+
+struct RegionStats {
+    mean_luma: f32,
+    edge_ratio: f32,
+}
+
+fn region_stats(image: &RgbaImage, origin: (u32, u32), size: u32) -> RegionStats {
+    let luma = |x: u32, y: u32| {
+        let px = image.get_pixel(x, y);
+        0.299 * f32::from(px[0]) + 0.587 * f32::from(px[1]) + 0.114 * f32::from(px[2])
+    };
+
+    let mut sum = 0.0;
+    let mut edges = 0u32;
+    let mut pairs = 0u32;
+    for y in origin.1..origin.1 + size {
+        for x in origin.0..origin.0 + size {
+            let value = luma(x, y);
+            sum += value;
+            if x + 1 < origin.0 + size {
+                pairs += 1;
+                if (value - luma(x + 1, y)).abs() > 32.0 {
+                    edges += 1;
+                }
+            }
+            if y + 1 < origin.1 + size {
+                pairs += 1;
+                if (value - luma(x, y + 1)).abs() > 32.0 {
+                    edges += 1;
+                }
+            }
+        }
+    }
+
+    RegionStats {
+        mean_luma: sum / (size * size) as f32,
+        edge_ratio: edges as f32 / pairs.max(1) as f32,
+    }
+}
+
+fn pad_to_aspect<P: Pixel>(image: Canvas<P>, aspect: (u32, u32), pad: P) -> Canvas<P> {
+    let (width, height) = image.dimensions();
+    let (aspect_w, aspect_h) = (u64::from(aspect.0), u64::from(aspect.1));
+
+    // 원본을 자르지 않도록 부족한 축만 늘립니다.
+    let (canvas_w, canvas_h) = if u64::from(width) * aspect_h >= u64::from(height) * aspect_w {
+        let needed_h = (u64::from(width) * aspect_h).div_ceil(aspect_w);
+        (width, u32::try_from(needed_h).unwrap_or(u32::MAX))
+    } else {
+        let needed_w = (u64::from(height) * aspect_w).div_ceil(aspect_h);
+        (u32::try_from(needed_w).unwrap_or(u32::MAX), height)
+    };
+
+    if canvas_w == width && canvas_h == height {
+        return image;
+    }
+
+    let mut canvas = ImageBuffer::from_pixel(canvas_w, canvas_h, pad);
+    let offset_x = (canvas_w - width) / 2;
+    let offset_y = (canvas_h - height) / 2;
+    replace(
+        &mut canvas,
+        &image,
+        i64::from(offset_x),
+        i64::from(offset_y),
+    );
+    canvas
+}
+
+/// 입력이 로고와 같은 파일인지 가립니다. 경로 표기가 달라도 잡도록 정규화한 경로로 비교하며, 두 엔진이 함께 사용합니다.
+pub(crate) struct LogoInputGuard(Option<PathBuf>);
+
+impl LogoInputGuard {
+    pub(crate) fn new(logo_path: &Path) -> Self {
+        Self(std::fs::canonicalize(logo_path).ok())
+    }
+
+    /// 같은 파일이면 처리하지 않고 돌려줄 `LOGO_IS_INPUT` 실패 결과입니다.
+    pub(crate) fn check(&self, input: &str) -> Option<StampFileResult> {
+        let logo = self.0.as_deref()?;
+        let input_path = std::fs::canonicalize(input).ok()?;
+        (input_path == logo).then(|| StampFileResult {
+            code: Some(CODE_LOGO_IS_INPUT.to_string()),
+            ..failure_result(input.to_string(), Message::new(CODE_LOGO_IS_INPUT))
+        })
+    }
+}
+
+fn failure_result(input_path: String, error: Message) -> StampFileResult {
+    StampFileResult {
+        input_path,
+        ok: false,
+        output_path: None,
         error: Some(error),
+        ..Default::default()
     }
 }
 
@@ -233,6 +1968,7 @@ mod tests {
             size_preset: "보통".to_string(),
             size_percent: None,
             margin_percent: 2.0,
+            ..Default::default()
         };
         let paths = vec![input_path.to_string_lossy().to_string()];
 
@@ -247,28 +1983,1656 @@ mod tests {
     }
 
     #[test]
-    fn stamp_images_accepts_size_percent_without_size_preset() {
+    fn stamp_images_renders_svg_logo_at_target_size() {
         let nonce = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .expect("time")
             .as_nanos();
-        let root = std::env::temp_dir().join(format!("cornerbrand-image-size-percent-{nonce}"));
+        let root = std::env::temp_dir().join(format!("cornerbrand-image-svg-{nonce}"));
         fs::create_dir_all(&root).expect("temp dir");
 
         let input_path = root.join("input.png");
+        let logo_path = root.join("logo.svg");
+        write_test_png(&input_path, 200, 100, [240, 240, 240, 255]);
+        fs::write(
+            &logo_path,
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10"><rect width="10" height="10" fill="#ff0000"/></svg>"##,
+        )
+        .expect("write svg logo");
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            size_percent: Some(20.0),
+            margin_percent: 0.0,
+            ..Default::default()
+        };
+        let paths = vec![input_path.to_string_lossy().to_string()];
+
+        let results = stamp_images(&paths, settings, &logo_path, None);
+        assert!(results[0].ok, "expected success: {:?}", results[0].error);
+
+        let output = image::open(results[0].output_path.as_ref().expect("output path"))
+            .expect("open output")
+            .to_rgba8();
+        // 짧은 변 100px의 20%인 20px 정사각형이 오른쪽 아래에 꽉 차게 들어갑니다.
+        assert_eq!(output.get_pixel(180, 80).0, [255, 0, 0, 255]);
+        assert_eq!(output.get_pixel(199, 99).0, [255, 0, 0, 255]);
+        assert_eq!(output.get_pixel(179, 79).0, [240, 240, 240, 255]);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn multi_page_tiff_stamps_every_page() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-image-tiff-pages-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_path = root.join("scan.tiff");
         let logo_path = root.join("logo.png");
-        write_test_png(&input_path, 48, 48, [240, 240, 240, 255]);
+        let gray = Rgba([240, 240, 240, 255]);
+        let pages = [
+            RgbaImage::from_pixel(200, 100, gray),
+            RgbaImage::from_pixel(100, 160, gray),
+        ];
+        fs::write(
+            &input_path,
+            crate::tiff_pages::encode_pages(&TiffPages::from_rgba(pages.to_vec()))
+                .expect("encode tiff"),
+        )
+        .expect("write tiff");
         write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
 
-        let settings: StampSettingsInput =
-            serde_json::from_str(r#"{"position":"우하단","sizePercent":50,"marginPercent":0}"#)
-                .expect("deserialize settings with sizePercent");
-
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            size_percent: Some(20.0),
+            margin_percent: 0.0,
+            ..Default::default()
+        };
         let paths = vec![input_path.to_string_lossy().to_string()];
+
         let results = stamp_images(&paths, settings, &logo_path, None);
+        assert!(results[0].ok, "expected success: {:?}", results[0].error);
 
-        assert_eq!(results.len(), 1);
+        let output =
+            fs::read(results[0].output_path.as_ref().expect("output path")).expect("read output");
+        let TiffLayout::Pages(stamped) =
+            crate::tiff_pages::decode_pages(&output).expect("decode output")
+        else {
+            panic!("expected a multi-page tiff");
+        };
+        assert_eq!(stamped.pages.len(), 2);
+        for (page, source) in stamped.pages.iter().zip(&pages) {
+            let page = page.image.to_rgba8();
+            assert_eq!(page.dimensions(), source.dimensions());
+            let (width, height) = page.dimensions();
+            assert_eq!(page.get_pixel(width - 1, height - 1).0, [255, 0, 0, 255]);
+            assert_eq!(page.get_pixel(0, 0), &gray);
+        }
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn relative_to_logo_size_mode_scales_native_logo_pixels() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-image-logo-size-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_path = root.join("input.png");
+        let logo_path = root.join("logo.png");
+        write_test_png(&input_path, 100, 60, [240, 240, 240, 255]);
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "좌상단".to_string(),
+            size_preset: "보통".to_string(),
+            size_percent: Some(200.0),
+            size_mode: Some("relative_to_logo".to_string()),
+            margin_percent: 0.0,
+            ..Default::default()
+        };
+        let paths = vec![input_path.to_string_lossy().to_string()];
+
+        let results = stamp_images(&paths, settings, &logo_path, None);
         assert!(results[0].ok, "expected success: {:?}", results[0].error);
+        assert!(results[0].warnings.is_empty(), "200% is within range");
+
+        let output = image::open(results[0].output_path.as_ref().expect("output path"))
+            .expect("open output")
+            .to_rgba8();
+        // 캔버스 크기와 상관없이 8px 로고가 16px이 됩니다.
+        assert_eq!(output.get_pixel(15, 15).0, [255, 0, 0, 255]);
+        assert_eq!(output.get_pixel(16, 0).0, [240, 240, 240, 255]);
+        assert_eq!(output.get_pixel(0, 16).0, [240, 240, 240, 255]);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn stamp_images_keeps_16bit_png_depth() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-image-16bit-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_path = root.join("scan.png");
+        let logo_path = root.join("logo.png");
+        // 8비트로 줄이면 사라지는 값(1000 = 3.89 × 257)을 씁니다.
+        ImageBuffer::<Rgba<u16>, _>::from_pixel(48, 48, Rgba([1000, 40_000, 65_535, 65_535]))
+            .save(&input_path)
+            .expect("write 16-bit png");
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            ..Default::default()
+        };
+        for embed_metadata in [None, Some("scan-archive".to_string())] {
+            let paths = vec![input_path.to_string_lossy().to_string()];
+            let results = stamp_images(
+                &paths,
+                StampSettingsInput {
+                    embed_metadata,
+                    ..settings.clone()
+                },
+                &logo_path,
+                None,
+            );
+            assert!(results[0].ok, "expected success: {:?}", results[0].error);
+
+            let output = image::open(results[0].output_path.as_ref().expect("output path"))
+                .expect("open output");
+            assert_eq!(output.color(), image::ColorType::Rgba16);
+            let output = output.into_rgba16();
+            assert_eq!(output.get_pixel(0, 0).0, [1000, 40_000, 65_535, 65_535]);
+            assert_eq!(output.get_pixel(44, 44).0, [65_535, 0, 0, 65_535]);
+        }
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn stamp_image_to_path_writes_exact_file_without_overwriting() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-image-exact-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_path = root.join("input.png");
+        let logo_path = root.join("logo.png");
+        write_test_png(&input_path, 48, 48, [240, 240, 240, 255]);
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            ..Default::default()
+        };
+        let input = input_path.to_string_lossy().to_string();
+        let output_path = root.join("exports").join("final.png");
+
+        let result = stamp_image_to_path(&input, settings.clone(), &logo_path, &output_path);
+        assert!(result.ok, "expected success: {:?}", result.error);
+        assert_eq!(
+            result.output_path.as_deref(),
+            Some(output_path.to_string_lossy().as_ref())
+        );
+        assert_eq!(
+            image::open(&output_path).expect("open output").dimensions(),
+            (48, 48)
+        );
+        assert!(!root.join(path_policy::OUTPUT_DIR_NAME).exists());
+
+        let again = stamp_image_to_path(&input, settings.clone(), &logo_path, &output_path);
+        assert!(!again.ok, "existing file must not be overwritten");
+
+        let wrong_ext = stamp_image_to_path(&input, settings, &logo_path, &root.join("final.jpg"));
+        assert!(!wrong_ext.ok);
+        assert!(!root.join("final.jpg").exists());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn warn_on_recompress_flags_jpeg_inputs() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-image-recompress-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let jpeg_path = root.join("photo_cornerbrand.jpg");
+        let png_path = root.join("photo.png");
+        let logo_path = root.join("logo.png");
+        image::RgbImage::from_pixel(48, 48, image::Rgb([240, 240, 240]))
+            .save(&jpeg_path)
+            .expect("write jpeg");
+        write_test_png(&png_path, 48, 48, [240, 240, 240, 255]);
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            warn_on_recompress: true,
+            ..Default::default()
+        };
+        let paths = vec![
+            jpeg_path.to_string_lossy().to_string(),
+            png_path.to_string_lossy().to_string(),
+        ];
+        let results = stamp_images(&paths, settings, &logo_path, None);
+
+        assert!(results[0].ok, "expected success: {:?}", results[0].error);
+        assert!(
+            results[0]
+                .warnings
+                .iter()
+                .any(|w| w.to_string().contains("이미 로고를 넣은 JPEG")),
+            "unexpected warnings: {:?}",
+            results[0].warnings
+        );
+        assert!(results[1].ok);
+        assert!(!results[1]
+            .warnings
+            .iter()
+            .any(|w| w.to_string().contains("JPEG")));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn webp_output_honours_lossless_and_quality() {
+        use webp::{BitstreamFeatures, BitstreamFormat};
+
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-image-webp-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_path = root.join("photo.webp");
+        let logo_path = root.join("logo.png");
+        write_test_png(&input_path, 48, 48, [240, 240, 240, 255]);
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+        let paths = vec![input_path.to_string_lossy().to_string()];
+
+        let lossless = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            webp_lossless: Some(true),
+            ..Default::default()
+        };
+        let results = stamp_images(&paths, lossless.clone(), &logo_path, None);
+        assert!(results[0].ok, "expected success: {:?}", results[0].error);
+        let bytes = fs::read(results[0].output_path.as_ref().expect("output")).expect("read");
+        let features = BitstreamFeatures::new(&bytes).expect("webp features");
+        assert!(matches!(features.format(), Some(BitstreamFormat::Lossless)));
+        let output = image::load_from_memory(&bytes).expect("decode").to_rgba8();
+        assert_eq!(output.get_pixel(2, 2).0, [240, 240, 240, 255]);
+
+        let lossy = StampSettingsInput {
+            webp_lossless: None,
+            webp_quality: Some(60.0),
+            ..lossless.clone()
+        };
+        let results = stamp_images(&paths, lossy, &logo_path, None);
+        let bytes = fs::read(results[0].output_path.as_ref().expect("output")).expect("read");
+        let features = BitstreamFeatures::new(&bytes).expect("webp features");
+        assert!(matches!(features.format(), Some(BitstreamFormat::Lossy)));
+
+        let invalid = StampSettingsInput {
+            webp_quality: Some(120.0),
+            ..lossless
+        };
+        assert!(!stamp_images(&paths, invalid, &logo_path, None)[0].ok);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn animated_webp_keeps_every_frame() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-image-webp-anim-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_path = root.join("anim.webp");
+        let logo_path = root.join("logo.png");
+        let animation = webp_codec::AnimatedWebp {
+            frames: [
+                [240, 240, 240, 255],
+                [20, 120, 240, 255],
+                [40, 200, 40, 255],
+            ]
+            .into_iter()
+            .enumerate()
+            .map(|(i, rgba)| (RgbaImage::from_pixel(48, 48, Rgba(rgba)), i as i32 * 100))
+            .collect(),
+            loop_count: 0,
+        };
+        let bytes =
+            webp_codec::encode_animation(&animation, WebpOptions::default()).expect("encode");
+        fs::write(&input_path, bytes).expect("write animation");
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            ..Default::default()
+        };
+        let results = stamp_images(
+            &[input_path.to_string_lossy().to_string()],
+            settings,
+            &logo_path,
+            None,
+        );
+        assert!(results[0].ok, "expected success: {:?}", results[0].error);
+
+        let output = fs::read(results[0].output_path.as_ref().expect("output")).expect("read");
+        assert!(webp_codec::is_animated(&output));
+        let stamped = webp_codec::decode_animation(&output).expect("decode");
+        assert_eq!(stamped.frames.len(), 3);
+        for (frame, _) in &stamped.frames {
+            assert_eq!(frame.get_pixel(44, 44).0, [255, 0, 0, 255]);
+        }
+        assert_eq!(stamped.frames[1].0.get_pixel(2, 2).0, [20, 120, 240, 255]);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn web_copy_writes_downscaled_copy_next_to_full_output() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-image-web-copy-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_path = root.join("photo.png");
+        let logo_path = root.join("logo.png");
+        write_test_png(&input_path, 400, 300, [240, 240, 240, 255]);
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            web_copy: Some(WebCopySettings {
+                max_dimension: 100,
+                format: Some("jpeg".to_string()),
+                quality: Some(70),
+            }),
+            ..Default::default()
+        };
+        let paths = vec![input_path.to_string_lossy().to_string()];
+        let results = stamp_images(&paths, settings, &logo_path, None);
+        assert!(results[0].ok, "expected success: {:?}", results[0].error);
+
+        let full = PathBuf::from(results[0].output_path.as_ref().expect("output"));
+        let web = PathBuf::from(results[0].web_output_path.as_ref().expect("web copy"));
+        assert_eq!(
+            image::image_dimensions(&full).expect("full dims"),
+            (400, 300)
+        );
+        assert_eq!(image::image_dimensions(&web).expect("web dims"), (100, 75));
+        assert_eq!(
+            web,
+            root.join(path_policy::OUTPUT_DIR_NAME)
+                .join(path_policy::WEB_COPY_DIR_NAME)
+                .join("photo_web.jpg")
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn backup_originals_copies_source_before_overwriting_in_place() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-image-backup-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_path = root.join("photo.png");
+        let logo_path = root.join("logo.png");
+        write_test_png(&input_path, 48, 48, [240, 240, 240, 255]);
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+        let original = fs::read(&input_path).expect("read original");
+
+        let mut settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            ..Default::default()
+        };
+        let input = input_path.to_string_lossy().to_string();
+
+        let refused = stamp_image_to_path(&input, settings.clone(), &logo_path, &input_path);
+        assert!(!refused.ok, "in-place write needs backup_originals");
+        assert_eq!(fs::read(&input_path).expect("read input"), original);
+        assert!(!root.join(path_policy::BACKUP_DIR_NAME).exists());
+
+        settings.backup_originals = true;
+        let result = stamp_image_to_path(&input, settings, &logo_path, &input_path);
+        assert!(result.ok, "expected success: {:?}", result.error);
+
+        let backup_path = root.join(path_policy::BACKUP_DIR_NAME).join("photo.png");
+        assert_eq!(fs::read(&backup_path).expect("read backup"), original);
+        assert_ne!(fs::read(&input_path).expect("read stamped"), original);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn stamp_images_accepts_size_percent_without_size_preset() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-image-size-percent-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_path = root.join("input.png");
+        let logo_path = root.join("logo.png");
+        write_test_png(&input_path, 48, 48, [240, 240, 240, 255]);
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+
+        let settings: StampSettingsInput =
+            serde_json::from_str(r#"{"position":"우하단","sizePercent":50,"marginPercent":0}"#)
+                .expect("deserialize settings with sizePercent");
+
+        let paths = vec![input_path.to_string_lossy().to_string()];
+        let results = stamp_images(&paths, settings, &logo_path, None);
+
+        assert_eq!(results.len(), 1);
+        assert!(results[0].ok, "expected success: {:?}", results[0].error);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn clamped_size_percent_is_reported_as_warning() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-image-clamp-warn-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_path = root.join("input.png");
+        let logo_path = root.join("logo.png");
+        write_test_png(&input_path, 48, 48, [240, 240, 240, 255]);
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_percent: Some(1000.0),
+            margin_percent: 2.0,
+            ..Default::default()
+        };
+        let paths = vec![input_path.to_string_lossy().to_string()];
+        let results = stamp_images(&paths, settings, &logo_path, None);
+
+        assert!(results[0].ok, "expected success: {:?}", results[0].error);
+        assert!(
+            results[0]
+                .warnings
+                .iter()
+                .any(|w| w.to_string().contains("1000%") && w.to_string().contains("50%")),
+            "unexpected warnings: {:?}",
+            results[0].warnings
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn strict_bounds_rejects_out_of_range_values_instead_of_clamping() {
+        let lenient = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_percent: Some(2000.0),
+            margin_percent: 2.0,
+            ..Default::default()
+        };
+        let clamped = StampSettings::try_from(lenient.clone()).expect("clamped settings");
+        assert_eq!(clamped.size_ratio, SIZE_PERCENT_MAX / 100.0);
+
+        let strict = StampSettingsInput {
+            strict_bounds: true,
+            ..lenient
+        };
+        let error = StampSettings::try_from(strict.clone()).expect_err("size out of range");
+        assert!(error.to_string().contains("2000%"), "{error}");
+
+        let margin_error = StampSettings::try_from(StampSettingsInput {
+            size_percent: Some(10.0),
+            margin_left: Some(-3.0),
+            ..strict.clone()
+        })
+        .expect_err("margin out of range");
+        assert!(
+            margin_error.to_string().contains("왼쪽 여백 -3%"),
+            "{margin_error}"
+        );
+
+        assert!(StampSettings::try_from(StampSettingsInput {
+            size_percent: Some(10.0),
+            ..strict
+        })
+        .is_ok());
+    }
+
+    #[test]
+    fn proof_mode_downscales_and_repeats_faded_logo() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-image-proof-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_path = root.join("photo.png");
+        let logo_path = root.join("logo.png");
+        write_test_png(&input_path, 2000, 1000, [200, 200, 200, 255]);
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            proof_mode: true,
+            ..Default::default()
+        };
+        let paths = vec![input_path.to_string_lossy().to_string()];
+        let results = stamp_images(&paths, settings, &logo_path, None);
+        assert!(results[0].ok, "expected success: {:?}", results[0].error);
+
+        let output = image::open(results[0].output_path.as_ref().expect("output path"))
+            .expect("open output")
+            .to_rgba8();
+        assert_eq!(output.dimensions(), (PROOF_MAX_DIMENSION, 800));
+
+        // 로고는 96px, 간격 48px: (24, 24)에서 시작해 144px마다 반복됩니다.
+        let stamped_cells = tile_origins((1600, 800), (96, 96))
+            .into_iter()
+            .filter(|(x, y)| {
+                let [r, g, _, _] = output.get_pixel(*x as u32 + 48, *y as u32 + 48).0;
+                // 40% 불투명도: 빨강은 200보다 밝아지지만 255까지는 가지 않습니다.
+                r > 200 && r < 255 && g < 200
+            })
+            .count();
+        assert!(stamped_cells >= 50, "only {stamped_cells} tiles found");
+        assert_eq!(output.get_pixel(10, 10).0, [200, 200, 200, 255]);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn invisible_logo_on_tiny_image_writes_nothing() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-image-invisible-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_path = root.join("tiny.png");
+        let logo_path = root.join("logo.png");
+        write_test_png(&input_path, 10, 10, [240, 240, 240, 255]);
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_percent: Some(1.0),
+            margin_percent: 20.0,
+            opacity: Some(0.0),
+            ..Default::default()
+        };
+        let paths = vec![input_path.to_string_lossy().to_string()];
+        let results = stamp_images(&paths, settings, &logo_path, None);
+
+        assert!(!results[0].ok);
+        assert!(results[0].skipped);
+        assert_eq!(results[0].code.as_deref(), Some(CODE_LOGO_NOT_VISIBLE));
+        assert!(results[0].output_path.is_none());
+        assert!(!root.join(path_policy::OUTPUT_DIR_NAME).exists());
+
+        // 로고가 보이지 않아도 `max_dimension`으로 줄였다면 원본과 달라졌으므로 결과를 씁니다.
+        let downscale = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_percent: Some(1.0),
+            margin_percent: 20.0,
+            opacity: Some(0.0),
+            max_dimension: Some(5),
+            ..Default::default()
+        };
+        let results = stamp_images(&paths, downscale, &logo_path, None);
+        assert!(results[0].ok, "{:?}", results[0]);
+        let output = image::open(results[0].output_path.as_deref().expect("output"))
+            .expect("downscaled output");
+        assert_eq!(output.dimensions(), (5, 5));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn stamp_images_pads_output_to_target_aspect_ratio() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-image-aspect-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_path = root.join("input.png");
+        let logo_path = root.join("logo.png");
+        write_test_png(&input_path, 40, 20, [240, 240, 240, 255]);
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            size_percent: Some(20.0),
+            margin_percent: 0.0,
+            output_aspect: Some((1, 1)),
+            pad_color: Some([0, 0, 255, 255]),
+            ..Default::default()
+        };
+        let paths = vec![input_path.to_string_lossy().to_string()];
+
+        let results = stamp_images(&paths, settings, &logo_path, None);
+        assert!(results[0].ok, "expected success: {:?}", results[0].error);
+
+        let output_path = PathBuf::from(results[0].output_path.as_ref().expect("output path"));
+        let output = image::open(&output_path).expect("open output").to_rgba8();
+        assert_eq!(output.dimensions(), (40, 40), "output should be square");
+
+        // 원본 20px 높이가 가운데(y 10..30)에 놓이고 위아래는 여백 색으로 채워집니다.
+        assert_eq!(output.get_pixel(0, 0).0, [0, 0, 255, 255]);
+        assert_eq!(output.get_pixel(39, 39).0, [0, 0, 255, 255]);
+        assert_eq!(output.get_pixel(0, 10).0, [240, 240, 240, 255]);
+        assert_eq!(output.get_pixel(0, 29).0, [240, 240, 240, 255]);
+
+        // 로고는 원본 콘텐츠의 우하단 모서리에 그대로 남아 있어야 합니다.
+        assert_eq!(output.get_pixel(39, 29).0, [255, 0, 0, 255]);
+        assert_eq!(output.get_pixel(36, 26).0, [255, 0, 0, 255]);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn min_contrast_warns_when_logo_matches_background() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-image-contrast-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_path = root.join("white.png");
+        let logo_path = root.join("logo.png");
+        write_test_png(&input_path, 64, 64, [255, 255, 255, 255]);
+        write_test_png(&logo_path, 8, 8, [250, 250, 250, 255]);
+
+        let settings = |min_contrast| StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            min_contrast,
+            ..Default::default()
+        };
+        let paths = vec![input_path.to_string_lossy().to_string()];
+
+        let results = stamp_images(&paths, settings(Some(1.5)), &logo_path, None);
+        assert!(results[0].ok, "expected success: {:?}", results[0].error);
+        assert!(
+            results[0]
+                .warnings
+                .iter()
+                .any(|w| w.to_string().contains("우하단") && w.to_string().contains("대비")),
+            "{:?}",
+            results[0].warnings
+        );
+
+        // 확인은 켰을 때만 합니다.
+        let results = stamp_images(&paths, settings(None), &logo_path, None);
+        assert!(!results[0]
+            .warnings
+            .iter()
+            .any(|w| w.to_string().contains("대비")));
+        assert!(
+            stamp_images(&paths, settings(Some(0.5)), &logo_path, None)[0]
+                .error
+                .is_some()
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn jpeg_output_mattes_transparent_padding_onto_background() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-image-jpeg-matte-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_path = root.join("input.jpg");
+        let logo_path = root.join("logo.png");
+        image::RgbImage::from_pixel(40, 20, image::Rgb([240, 240, 240]))
+            .save(&input_path)
+            .expect("write jpeg fixture");
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+
+        // 투명한 여백 색으로 RGBA 결과를 만든 뒤 JPEG로 저장합니다.
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            output_aspect: Some((1, 1)),
+            pad_color: Some([0, 0, 0, 0]),
+            jpeg_background: Some([0, 0, 255]),
+            ..Default::default()
+        };
+        let paths = vec![input_path.to_string_lossy().to_string()];
+
+        let results = stamp_images(&paths, settings, &logo_path, None);
+        assert!(results[0].ok, "expected success: {:?}", results[0].error);
+
+        let output_path = PathBuf::from(results[0].output_path.as_ref().expect("output path"));
+        let output = image::open(&output_path).expect("open output").to_rgb8();
+        assert_eq!(output.dimensions(), (40, 40));
+        for (x, y) in [(0, 0), (20, 2), (39, 39)] {
+            let [r, g, b] = output.get_pixel(x, y).0;
+            assert!(
+                r < 30 && g < 30 && b > 220,
+                "padding at ({x}, {y}) should be blue, got {:?}",
+                [r, g, b]
+            );
+        }
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn stamp_images_skips_image_with_existing_corner_mark() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-image-watermarked-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let marked_path = root.join("marked.png");
+        let clean_path = root.join("clean.png");
+        let logo_path = root.join("logo.png");
+
+        let mut marked = RgbaImage::from_pixel(64, 64, image::Rgba([200, 200, 200, 255]));
+        for x in 52..62 {
+            for y in 52..62 {
+                marked.put_pixel(x, y, image::Rgba([20, 20, 160, 255]));
+            }
+        }
+        marked.save(&marked_path).expect("write marked fixture");
+        write_test_png(&clean_path, 64, 64, [200, 200, 200, 255]);
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            margin_percent: 2.0,
+            skip_if_watermarked: Some(true),
+            ..Default::default()
+        };
+        let paths = vec![
+            marked_path.to_string_lossy().to_string(),
+            clean_path.to_string_lossy().to_string(),
+        ];
+
+        let results = stamp_images(&paths, settings, &logo_path, None);
+        assert!(results[0].skipped, "marked image should be skipped");
+        assert!(!results[0].ok);
+        assert_eq!(results[0].code.as_deref(), Some(CODE_LIKELY_WATERMARKED));
+        assert!(results[0].output_path.is_none());
+
+        assert!(
+            results[1].ok,
+            "clean image should be stamped: {:?}",
+            results[1].error
+        );
+        assert!(!results[1].skipped);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    fn placement_settings(margin_percent: f32, margin_mode: &str) -> StampSettings {
+        StampSettings::try_from(StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            margin_percent,
+            margin_mode: Some(margin_mode.to_string()),
+            ..Default::default()
+        })
+        .expect("settings")
+    }
+
+    #[test]
+    fn stamp_rgba_places_logo_in_requested_corner_without_io() {
+        let source = RgbaImage::from_pixel(40, 40, image::Rgba([255, 255, 255, 255]));
+        let logo = RgbaImage::from_pixel(4, 4, image::Rgba([255, 0, 0, 255]));
+        let settings = |position: &str| {
+            StampSettings::try_from(StampSettingsInput {
+                position: position.to_string(),
+                size_percent: Some(10.0),
+                margin_percent: 0.0,
+                ..Default::default()
+            })
+            .expect("settings")
+        };
+
+        let bottom_right = stamp_rgba(&source, &logo, &settings("우하단"));
+        assert_eq!(bottom_right.dimensions(), (40, 40));
+        assert_eq!(bottom_right.get_pixel(39, 39).0, [255, 0, 0, 255]);
+        assert_eq!(bottom_right.get_pixel(36, 36).0, [255, 0, 0, 255]);
+        assert_eq!(bottom_right.get_pixel(35, 35).0, [255, 255, 255, 255]);
+        assert_eq!(bottom_right.get_pixel(0, 0).0, [255, 255, 255, 255]);
+
+        let top_left = stamp_rgba(&source, &logo, &settings("좌상단"));
+        assert_eq!(top_left.get_pixel(0, 0).0, [255, 0, 0, 255]);
+        assert_eq!(top_left.get_pixel(39, 39).0, [255, 255, 255, 255]);
+
+        assert_eq!(
+            stamp_rgba(&source, &logo, &settings("좌상단")).as_raw(),
+            top_left.as_raw(),
+            "same inputs should give the same pixels"
+        );
+        assert_eq!(source.get_pixel(0, 0).0, [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn margin_mode_controls_insets_on_wide_image() {
+        // 200x50 이미지, 여백 10%: 짧은 변 기준이면 양쪽 5px, 축별 기준이면 가로 20px/세로 5px.
+        let short_side =
+            compute_placement((200, 50), (10, 10), &placement_settings(10.0, "short_side"));
+        assert_eq!((short_side.x, short_side.y), (185, 35));
+        assert!(!short_side.clamped);
+
+        let per_axis =
+            compute_placement((200, 50), (10, 10), &placement_settings(10.0, "per_axis"));
+        assert_eq!((per_axis.x, per_axis.y), (170, 35));
+        assert!(!per_axis.clamped);
+    }
+
+    #[test]
+    fn per_side_margins_override_only_the_corner_edges() {
+        let settings = |position: &str| {
+            StampSettings::try_from(StampSettingsInput {
+                position: position.to_string(),
+                size_preset: "보통".to_string(),
+                margin_percent: 10.0,
+                margin_right: Some(0.0),
+                margin_bottom: Some(20.0),
+                margin_top: Some(f32::NAN),
+                ..Default::default()
+            })
+            .expect("settings")
+        };
+
+        // 100x100, 로고 10x10: 오른쪽은 0%, 아래는 20%.
+        let bottom_right = compute_placement((100, 100), (10, 10), &settings("우하단"));
+        assert_eq!((bottom_right.x, bottom_right.y), (90, 70));
+
+        // 좌상단에는 기본 10%가 적용되고(NaN 덮어쓰기는 무시), 오른쪽/아래 덮어쓰기는 영향이 없습니다.
+        let top_left = compute_placement((100, 100), (10, 10), &settings("좌상단"));
+        assert_eq!((top_left.x, top_left.y), (10, 10));
+    }
+
+    #[test]
+    fn placement_reports_clamping_when_logo_and_margin_do_not_fit() {
+        let placement =
+            compute_placement((10, 10), (9, 9), &placement_settings(20.0, "short_side"));
+        assert_eq!((placement.x, placement.y), (0, 0));
+        assert!(placement.clamped);
+    }
+
+    #[test]
+    fn stamp_images_uses_per_axis_margin_on_wide_image() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-image-margin-mode-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_path = root.join("input.png");
+        let logo_path = root.join("logo.png");
+        write_test_png(&input_path, 200, 50, [240, 240, 240, 255]);
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            size_percent: Some(20.0),
+            margin_percent: 10.0,
+            margin_mode: Some("per_axis".to_string()),
+            ..Default::default()
+        };
+        let paths = vec![input_path.to_string_lossy().to_string()];
+        let results = stamp_images(&paths, settings, &logo_path, None);
+        assert!(results[0].ok, "expected success: {:?}", results[0].error);
+        assert!(results[0].warnings.is_empty());
+
+        let output = image::open(results[0].output_path.as_ref().expect("output"))
+            .expect("open output")
+            .to_rgba8();
+        assert_eq!(output.get_pixel(170, 35).0, [255, 0, 0, 255]);
+        assert_eq!(output.get_pixel(179, 44).0, [255, 0, 0, 255]);
+        assert_eq!(output.get_pixel(185, 44).0, [240, 240, 240, 255]);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn stamp_images_desaturates_logo_when_enabled() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-image-desaturate-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_path = root.join("input.png");
+        let logo_path = root.join("logo.png");
+        write_test_png(&input_path, 100, 100, [240, 240, 240, 255]);
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "좌상단".to_string(),
+            size_preset: "보통".to_string(),
+            size_percent: Some(20.0),
+            margin_percent: 0.0,
+            logo_desaturate: true,
+            ..Default::default()
+        };
+        let paths = vec![input_path.to_string_lossy().to_string()];
+        let results = stamp_images(&paths, settings, &logo_path, None);
+        assert!(results[0].ok, "expected success: {:?}", results[0].error);
+
+        let output = image::open(results[0].output_path.as_ref().expect("output"))
+            .expect("open output")
+            .to_rgba8();
+        for (x, y) in [(0, 0), (10, 10), (19, 19)] {
+            let px = output.get_pixel(x, y);
+            assert_eq!(px[0], px[1], "stamped pixel should be gray at ({x},{y})");
+            assert_eq!(px[1], px[2], "stamped pixel should be gray at ({x},{y})");
+            assert_eq!(px[0], 76, "red should map to its luminance");
+        }
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn logo_tint_keeps_alpha_and_scales_by_luminance() {
+        let mut logo = RgbaImage::from_pixel(2, 1, image::Rgba([255, 255, 255, 128]));
+        logo.put_pixel(1, 0, image::Rgba([0, 0, 0, 255]));
+        let style = LogoStyle {
+            tint: Some([0, 128, 255]),
+            ..Default::default()
+        };
+        style.apply(&mut logo).expect("apply");
+
+        assert_eq!(logo.get_pixel(0, 0).0, [0, 128, 255, 128]);
+        assert_eq!(logo.get_pixel(1, 0).0, [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn center_anchor_centers_logo_on_computed_point() {
+        let source = RgbaImage::from_pixel(200, 200, Rgba([255, 255, 255, 255]));
+        let logo = RgbaImage::from_pixel(4, 4, Rgba([255, 0, 0, 255]));
+        let settings = |anchor: &str| {
+            StampSettings::try_from(StampSettingsInput {
+                position: "좌상단".to_string(),
+                size_preset: "보통".to_string(),
+                size_percent: Some(10.0),
+                margin_percent: 20.0,
+                anchor: Some(anchor.to_string()),
+                ..Default::default()
+            })
+            .expect("settings")
+        };
+
+        // 좌상단 20% 여백이면 기준 위치는 (40, 40)이고 로고는 20px입니다.
+        let top_left = stamp_rgba(&source, &logo, &settings("top-left"));
+        assert_eq!(top_left.get_pixel(40, 40).0, [255, 0, 0, 255]);
+        assert_eq!(top_left.get_pixel(39, 39).0, [255, 255, 255, 255]);
+
+        let centered = stamp_rgba(&source, &logo, &settings("center"));
+        assert_eq!(centered.get_pixel(30, 30).0, [255, 0, 0, 255]);
+        assert_eq!(centered.get_pixel(49, 49).0, [255, 0, 0, 255]);
+        assert_eq!(centered.get_pixel(29, 29).0, [255, 255, 255, 255]);
+        assert_eq!(centered.get_pixel(50, 50).0, [255, 255, 255, 255]);
+
+        assert!(StampSettings::try_from(StampSettingsInput {
+            position: "좌상단".to_string(),
+            size_preset: "보통".to_string(),
+            anchor: Some("middle".to_string()),
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn thirds_positions_center_logo_on_intersections() {
+        let cases = [
+            ("삼분할 좌상단", (100.0, 60.0)),
+            ("삼분할 우상단", (200.0, 60.0)),
+            ("삼분할 좌하단", (100.0, 120.0)),
+            ("삼분할 우하단", (200.0, 120.0)),
+        ];
+        for (position, (expected_x, expected_y)) in cases {
+            let settings = StampSettings::try_from(StampSettingsInput {
+                position: position.to_string(),
+                size_preset: "보통".to_string(),
+                size_percent: Some(20.0),
+                margin_percent: 10.0,
+                ..Default::default()
+            })
+            .expect("settings");
+
+            // 300x180 캔버스, 로고 36px: 여백과 관계없이 교차점에 로고 가운데가 옵니다.
+            let rect = compute_stamp_rect((300, 180), &settings, (10, 10)).expect("rect");
+            let center_x = f64::from(rect.x) + f64::from(rect.width) / 2.0;
+            let center_y = f64::from(rect.y) + f64::from(rect.height) / 2.0;
+            assert!(
+                (center_x - expected_x).abs() <= 1.0 && (center_y - expected_y).abs() <= 1.0,
+                "{position}: center = ({center_x}, {center_y})"
+            );
+        }
+
+        assert_eq!(
+            CornerPosition::ThirdTopLeft.mirrored(),
+            CornerPosition::ThirdTopRight
+        );
+    }
+
+    #[test]
+    fn compute_stamp_rect_for_logo_uses_trimmed_logo_size() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-image-rect-trim-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        // 40x10 로고 둘레에 투명한 여백을 둬 60x40으로 저장합니다.
+        let logo_path = root.join("padded.png");
+        let mut padded = RgbaImage::from_pixel(60, 40, Rgba([0, 0, 0, 0]));
+        for y in 15..25 {
+            for x in 10..50 {
+                padded.put_pixel(x, y, Rgba([255, 0, 0, 255]));
+            }
+        }
+        padded.save(&logo_path).expect("write logo");
+
+        let settings = StampSettings::try_from(StampSettingsInput {
+            position: "우하단".to_string(),
+            size_percent: Some(20.0),
+            margin_percent: 0.0,
+            trim_logo: true,
+            resize_filter: Some("nearest".to_string()),
+            ..Default::default()
+        })
+        .expect("settings");
+
+        let rect = compute_stamp_rect_for_logo((400, 200), &settings, &logo_path).expect("rect");
+        assert_eq!(
+            rect,
+            compute_stamp_rect((400, 200), &settings, (40, 10)).expect("trimmed rect")
+        );
+        assert_ne!(
+            rect,
+            compute_stamp_rect((400, 200), &settings, (60, 40)).expect("padded rect")
+        );
+
+        let source = RgbaImage::from_pixel(400, 200, Rgba([255, 255, 255, 255]));
+        let stamped = stamp_rgba(&source, &padded, &settings);
+        assert_eq!(stamped.get_pixel(rect.x, rect.y).0, [255, 0, 0, 255]);
+        assert_eq!(
+            stamped
+                .get_pixel(rect.x + rect.width - 1, rect.y + rect.height - 1)
+                .0,
+            [255, 0, 0, 255]
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn compute_stamp_rect_matches_stamped_logo_position() {
+        let source = RgbaImage::from_pixel(640, 360, Rgba([255, 255, 255, 255]));
+        let logo = RgbaImage::from_pixel(30, 10, Rgba([255, 0, 0, 255]));
+        let settings = StampSettings::try_from(StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            size_percent: Some(25.0),
+            margin_percent: 5.0,
+            margin_mode: Some("per_axis".to_string()),
+            anchor: Some("top".to_string()),
+            max_dimension: Some(320),
+            resize_filter: Some("nearest".to_string()),
+            ..Default::default()
+        })
+        .expect("settings");
+
+        let stamped = stamp_rgba(&source, &logo, &settings);
+        let red: Vec<(u32, u32)> = stamped
+            .enumerate_pixels()
+            .filter(|(_, _, px)| px.0 == [255, 0, 0, 255])
+            .map(|(x, y, _)| (x, y))
+            .collect();
+        let min_x = red.iter().map(|p| p.0).min().expect("logo pixels");
+        let min_y = red.iter().map(|p| p.1).min().expect("logo pixels");
+        let max_x = red.iter().map(|p| p.0).max().expect("logo pixels");
+        let max_y = red.iter().map(|p| p.1).max().expect("logo pixels");
+
+        let rect = compute_stamp_rect((640, 360), &settings, (30, 10)).expect("rect");
+        assert_eq!(
+            rect,
+            StampRect {
+                x: min_x,
+                y: min_y,
+                width: max_x - min_x + 1,
+                height: max_y - min_y + 1,
+            }
+        );
+    }
+
+    #[test]
+    fn no_upscale_keeps_tiny_logo_at_native_size_and_warns() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-image-no-upscale-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+        let input_path = root.join("input.png");
+        let logo_path = root.join("logo.png");
+        write_test_png(&input_path, 100, 100, [255, 255, 255, 255]);
+        write_test_png(&logo_path, 4, 4, [255, 0, 0, 255]);
+
+        let input = StampSettingsInput {
+            position: "좌상단".to_string(),
+            size_percent: Some(20.0),
+            margin_percent: 0.0,
+            no_upscale: true,
+            ..Default::default()
+        };
+        let rect = compute_stamp_rect(
+            (100, 100),
+            &StampSettings::try_from(input.clone()).expect("settings"),
+            (4, 4),
+        )
+        .expect("rect");
+        assert_eq!((rect.width, rect.height), (4, 4));
+
+        let paths = vec![input_path.to_string_lossy().to_string()];
+        let results = stamp_images(&paths, input, &logo_path, None);
+        assert!(results[0].ok, "expected success: {:?}", results[0].error);
+        assert!(
+            results[0]
+                .warnings
+                .iter()
+                .any(|w| w.to_string().contains("(4px)") && w.to_string().contains("(20px)")),
+            "{:?}",
+            results[0].warnings
+        );
+        let output = image::open(results[0].output_path.as_ref().expect("output"))
+            .expect("open output")
+            .to_rgba8();
+        let red = output.pixels().filter(|p| p.0 == [255, 0, 0, 255]).count();
+        assert_eq!(red, 16);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn fit_box_scales_wide_logo_down_to_box_height() {
+        let input = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_percent: Some(50.0),
+            ..Default::default()
+        };
+        let unboxed = StampSettings::try_from(input.clone()).expect("settings");
+        let rect = compute_stamp_rect((200, 200), &unboxed, (100, 40)).expect("rect");
+        assert_eq!((rect.width, rect.height), (100, 40));
+
+        // 200px 캔버스의 상자는 160x20px이므로 높이에 맞춰 절반으로 줄어듭니다.
+        let boxed = StampSettings::try_from(StampSettingsInput {
+            fit_box: Some(FitBox {
+                max_width_percent: 80.0,
+                max_height_percent: 10.0,
+            }),
+            ..input.clone()
+        })
+        .expect("settings");
+        let rect = compute_stamp_rect((200, 200), &boxed, (100, 40)).expect("rect");
+        assert_eq!((rect.width, rect.height), (50, 20));
+
+        assert!(StampSettings::try_from(StampSettingsInput {
+            fit_box: Some(FitBox {
+                max_width_percent: 0.0,
+                max_height_percent: 10.0,
+            }),
+            ..input
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn physical_size_sets_logo_pixels_from_inches_and_dpi() {
+        let settings = StampSettings::try_from(StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            physical_size: Some(PhysicalSize {
+                inches: 1.0,
+                dpi: 300.0,
+            }),
+            ..Default::default()
+        })
+        .expect("settings");
+        let rect = compute_stamp_rect((4000, 3000), &settings, (100, 50)).expect("rect");
+        assert_eq!((rect.width, rect.height), (300, 150));
+
+        // 파일에 150 DPI가 기록돼 있으면 그 값을 따릅니다: 1인치 = 150px.
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-image-physical-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+        let input_path = root.join("print.png");
+        let logo_path = root.join("logo.png");
+        write_test_png(&logo_path, 10, 10, [255, 0, 0, 255]);
+        let file = fs::File::create(&input_path).expect("create input");
+        let mut encoder = png::Encoder::new(std::io::BufWriter::new(file), 400, 400);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_pixel_dims(Some(png::PixelDimensions {
+            xppu: 5_906,
+            yppu: 5_906,
+            unit: png::Unit::Meter,
+        }));
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&[255; 400 * 400 * 4]))
+            .expect("write input");
+
+        let input = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            margin_percent: 0.0,
+            physical_size: Some(PhysicalSize {
+                inches: 1.0,
+                dpi: 300.0,
+            }),
+            ..Default::default()
+        };
+        let paths = vec![input_path.to_string_lossy().to_string()];
+        let results = stamp_images(&paths, input, &logo_path, None);
+        assert!(results[0].ok, "expected success: {:?}", results[0].error);
+        let output = image::open(results[0].output_path.as_ref().expect("output"))
+            .expect("open output")
+            .to_rgba8();
+        let red = (0..400)
+            .filter(|x| output.get_pixel(*x, 399).0 == [255, 0, 0, 255])
+            .count();
+        assert_eq!(red, 150);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn banner_mode_covers_center_along_rising_diagonal() {
+        let source = RgbaImage::from_pixel(200, 100, Rgba([255, 255, 255, 255]));
+        let logo = RgbaImage::from_pixel(40, 8, Rgba([255, 0, 0, 255]));
+        let settings = StampSettings::try_from(StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            banner_mode: true,
+            ..Default::default()
+        })
+        .expect("settings");
+
+        let stamped = stamp_rgba(&source, &logo, &settings);
+        let tinted = |x: u32, y: u32| {
+            let px = stamped.get_pixel(x, y);
+            px[0] == 255 && px[1] < 200 && px[1] > 100
+        };
+        // 가운데와 오른쪽 위 대각선 위는 반투명 빨강, 반대 대각선과 모서리는 그대로입니다.
+        assert!(tinted(100, 50));
+        assert!(tinted(120, 30));
+        assert!(tinted(80, 70));
+        assert_eq!(stamped.get_pixel(120, 70).0, [255, 255, 255, 255]);
+        assert_eq!(stamped.get_pixel(0, 0).0, [255, 255, 255, 255]);
+        assert_eq!(stamped.get_pixel(199, 99).0, [255, 255, 255, 255]);
+
+        let rect = compute_stamp_rect((200, 100), &settings, (40, 8)).expect("rect");
+        assert!(rect.x <= 100 && rect.x + rect.width >= 100);
+        assert!(rect.height <= 90);
+        assert!(StampSettings::try_from(StampSettingsInput {
+            position: "우하단".to_string(),
+            banner_mode: true,
+            tile: Some(true),
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn nearest_resize_filter_keeps_hard_logo_edges() {
+        let source = RgbaImage::from_pixel(100, 100, Rgba([255, 255, 255, 255]));
+        let mut logo = RgbaImage::from_pixel(2, 2, Rgba([0, 0, 0, 255]));
+        logo.put_pixel(0, 0, Rgba([255, 255, 255, 255]));
+        logo.put_pixel(1, 1, Rgba([255, 255, 255, 255]));
+
+        let distinct_colors = |filter: &str| {
+            let settings = StampSettings::try_from(StampSettingsInput {
+                position: "좌상단".to_string(),
+                size_preset: "보통".to_string(),
+                size_percent: Some(40.0),
+                resize_filter: Some(filter.to_string()),
+                ..Default::default()
+            })
+            .expect("settings");
+            let stamped = stamp_rgba(&source, &logo, &settings);
+            stamped
+                .pixels()
+                .map(|px| px.0)
+                .collect::<std::collections::BTreeSet<_>>()
+                .len()
+        };
+
+        assert_eq!(distinct_colors("nearest"), 2);
+        assert!(distinct_colors("lanczos3") > 2);
+        assert!(StampSettings::try_from(StampSettingsInput {
+            position: "좌상단".to_string(),
+            size_preset: "보통".to_string(),
+            resize_filter: Some("bicubic".to_string()),
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn trim_logo_crops_transparent_padding() {
+        let mut logo = RgbaImage::from_pixel(40, 30, image::Rgba([0, 0, 0, 0]));
+        for y in 10..20 {
+            for x in 12..28 {
+                logo.put_pixel(x, y, image::Rgba([255, 0, 0, 255]));
+            }
+        }
+        let style = LogoStyle {
+            trim: true,
+            ..Default::default()
+        };
+        style.apply(&mut logo).expect("trim");
+        assert_eq!(logo.dimensions(), (16, 10));
+        assert!(logo.pixels().all(|px| px.0 == [255, 0, 0, 255]));
+
+        let mut blank = RgbaImage::from_pixel(8, 8, image::Rgba([255, 255, 255, 0]));
+        let err = style.apply(&mut blank).expect_err("fully transparent logo");
+        assert!(err.to_string().contains("투명"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn stamp_rgba_stays_infallible_for_a_fully_transparent_trimmed_logo() {
+        let source = RgbaImage::from_pixel(40, 40, image::Rgba([255, 255, 255, 255]));
+        let blank = RgbaImage::from_pixel(8, 8, image::Rgba([0, 0, 0, 0]));
+        let settings = StampSettings::try_from(StampSettingsInput {
+            position: "우하단".to_string(),
+            size_percent: Some(10.0),
+            trim_logo: true,
+            ..Default::default()
+        })
+        .expect("settings");
+
+        assert!(try_stamp_rgba(&source, &blank, &settings).is_err());
+        assert_eq!(stamp_rgba(&source, &blank, &settings), source);
+    }
+
+    #[test]
+    fn stamp_images_jitter_is_recorded_and_reproducible() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-image-jitter-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_path = root.join("input.png");
+        let logo_path = root.join("logo.png");
+        write_test_png(&input_path, 100, 100, [240, 240, 240, 255]);
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "좌상단".to_string(),
+            size_preset: "보통".to_string(),
+            size_percent: Some(10.0),
+            margin_percent: 10.0,
+            jitter_px: Some(6),
+            jitter_seed: Some(7),
+            ..Default::default()
+        };
+        let paths = vec![input_path.to_string_lossy().to_string()];
+
+        let first = stamp_images(&paths, settings.clone(), &logo_path, None);
+        let second = stamp_images(&paths, settings, &logo_path, None);
+        let first_jitter = first[0].jitter.clone().expect("jitter record");
+        let second_jitter = second[0].jitter.clone().expect("jitter record");
+        assert_eq!(first_jitter.seed, 7);
+        assert_eq!(first_jitter.offsets, second_jitter.offsets);
+
+        let [dx, dy] = first_jitter.offsets[0];
+        assert!((-6..=6).contains(&dx) && (-6..=6).contains(&dy));
+
+        // 기본 위치(10,10)에서 기록된 오프셋만큼 이동한 곳에 로고가 있어야 합니다.
+        let output = image::open(first[0].output_path.as_ref().expect("output"))
+            .expect("open output")
+            .to_rgba8();
+        let (x, y) = ((10 + dx) as u32, (10 + dy) as u32);
+        assert_eq!(output.get_pixel(x, y).0, [255, 0, 0, 255]);
+        assert_eq!(output.get_pixel(x + 9, y + 9).0, [255, 0, 0, 255]);
+
+        // 여백 없이 모서리에 붙이면 바깥쪽 흔들림은 잘리고, 기록도 실제로 옮긴 거리만 남습니다.
+        for seed in 1..=8 {
+            let settings = StampSettingsInput {
+                position: "좌상단".to_string(),
+                size_preset: "보통".to_string(),
+                size_percent: Some(10.0),
+                margin_percent: 0.0,
+                jitter_px: Some(6),
+                jitter_seed: Some(seed),
+                ..Default::default()
+            };
+            let result = stamp_images(&paths, settings, &logo_path, None).remove(0);
+            let [dx, dy] = result.jitter.expect("jitter record").offsets[0];
+            assert!((0..=6).contains(&dx) && (0..=6).contains(&dy), "{dx}, {dy}");
+            let output = image::open(result.output_path.as_ref().expect("output"))
+                .expect("open output")
+                .to_rgba8();
+            assert_eq!(output.get_pixel(dx as u32, dy as u32).0, [255, 0, 0, 255]);
+            if dx > 0 {
+                assert_eq!(
+                    output.get_pixel(dx as u32 - 1, dy as u32).0,
+                    [240, 240, 240, 255]
+                );
+            }
+        }
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn stamp_images_inverts_logo_colors_when_enabled() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-image-invert-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_path = root.join("input.png");
+        let logo_path = root.join("logo.png");
+        write_test_png(&input_path, 100, 100, [20, 20, 20, 255]);
+        write_test_png(&logo_path, 8, 8, [255, 200, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "좌상단".to_string(),
+            size_preset: "보통".to_string(),
+            size_percent: Some(20.0),
+            margin_percent: 0.0,
+            logo_invert: true,
+            ..Default::default()
+        };
+        let paths = vec![input_path.to_string_lossy().to_string()];
+        let results = stamp_images(&paths, settings, &logo_path, None);
+        assert!(results[0].ok, "expected success: {:?}", results[0].error);
+
+        let output = image::open(results[0].output_path.as_ref().expect("output"))
+            .expect("open output")
+            .to_rgba8();
+        assert_eq!(output.get_pixel(5, 5).0, [0, 55, 255, 255]);
+        assert_eq!(output.get_pixel(50, 50).0, [20, 20, 20, 255]);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn stamp_images_embeds_metadata_text_in_png() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-image-metadata-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_path = root.join("input.png");
+        let logo_path = root.join("logo.png");
+        write_test_png(&input_path, 32, 32, [240, 240, 240, 255]);
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            embed_metadata: Some("run-42 / 홍보팀".to_string()),
+            ..Default::default()
+        };
+        let paths = vec![input_path.to_string_lossy().to_string()];
+        let results = stamp_images(&paths, settings, &logo_path, None);
+        assert!(results[0].ok, "expected success: {:?}", results[0].error);
+
+        let bytes = fs::read(results[0].output_path.as_ref().expect("output")).expect("read");
+        let decoder = png::Decoder::new(std::io::Cursor::new(bytes));
+        let reader = decoder.read_info().expect("png info");
+        let texts: Vec<(String, String)> = reader
+            .info()
+            .utf8_text
+            .iter()
+            .map(|chunk| (chunk.keyword.clone(), chunk.get_text().expect("text")))
+            .collect();
+        assert_eq!(
+            texts,
+            vec![(
+                metadata::METADATA_KEY.to_string(),
+                "run-42 / 홍보팀".to_string()
+            )]
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn multiply_blend_darkens_pixels_under_opaque_logo() {
+        let source = RgbaImage::from_pixel(40, 40, Rgba([200, 160, 120, 255]));
+        let logo = RgbaImage::from_pixel(8, 8, Rgba([128, 128, 128, 255]));
+        let settings = |blend_mode: &str| {
+            StampSettings::try_from(StampSettingsInput {
+                position: "우하단".to_string(),
+                size_percent: Some(25.0),
+                margin_percent: 0.0,
+                blend_mode: Some(blend_mode.to_string()),
+                ..Default::default()
+            })
+            .expect("settings")
+        };
+
+        let multiplied = stamp_rgba(&source, &logo, &settings("multiply"));
+        let under_logo = multiplied.get_pixel(35, 35).0;
+        assert_eq!(under_logo, [100, 80, 60, 255]);
+        assert_eq!(multiplied.get_pixel(5, 5).0, [200, 160, 120, 255]);
+
+        let screened = stamp_rgba(&source, &logo, &settings("screen"));
+        let brightened = screened.get_pixel(35, 35).0;
+        assert!(brightened[..3]
+            .iter()
+            .zip([200, 160, 120])
+            .all(|(c, s)| *c > s));
+
+        let over = stamp_rgba(&source, &logo, &settings("over"));
+        assert_eq!(over.get_pixel(35, 35).0, [128, 128, 128, 255]);
+        assert!(StampSettings::try_from(StampSettingsInput {
+            position: "우하단".to_string(),
+            blend_mode: Some("overlay".to_string()),
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn input_that_is_also_the_logo_fails_with_logo_is_input() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-logo-is-input-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let photo = root.join("photo.png");
+        let other = root.join("other.png");
+        write_test_png(&photo, 48, 48, [255, 0, 0, 255]);
+        write_test_png(&other, 48, 48, [240, 240, 240, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            ..Default::default()
+        };
+        let paths = vec![
+            photo.to_string_lossy().to_string(),
+            other.to_string_lossy().to_string(),
+        ];
+        // 표기가 다른 같은 경로도 같은 파일로 봅니다.
+        let logo = root.join(".").join("photo.png");
+        let results = stamp_images(&paths, settings, &logo, None);
+
+        assert!(!results[0].ok);
+        assert_eq!(results[0].code.as_deref(), Some(CODE_LOGO_IS_INPUT));
+        assert!(results[1].ok, "{:?}", results[1].error);
 
         let _ = fs::remove_dir_all(&root);
     }
@@ -0,0 +1,176 @@
+use crate::i18n::Message;
+use crate::image_engine::StampSettingsInput;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 파일/페이지마다 로고 위치를 조금씩 흔드는 설정입니다.
+/// 같은 시드와 같은 입력 경로/페이지면 항상 같은 오프셋이 나오므로 결과 보고서의 시드로 재현·검증할 수 있습니다.
+#[derive(Debug, Clone, Copy)]
+pub struct Jitter {
+    pub range: JitterRange,
+    pub seed: u64,
+}
+
+/// 흔들림의 최대 이동 거리입니다.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JitterRange {
+    /// 고정 거리입니다. 이미지는 px, PDF는 pt입니다.
+    Fixed(u32),
+    /// 캔버스(페이지) 짧은 변 대비 비율(%)입니다. 크기가 제각각인 파일에도 비슷한 정도로 흔듭니다.
+    Percent(f32),
+}
+
+impl JitterRange {
+    fn max_offset(self, short_side: f64) -> u32 {
+        match self {
+            Self::Fixed(max_offset) => max_offset,
+            Self::Percent(percent) => (short_side * f64::from(percent) / 100.0).round() as u32,
+        }
+    }
+}
+
+/// `jitter` 설정 입력입니다.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JitterSettings {
+    /// 캔버스(페이지) 짧은 변 대비 최대 이동 거리(%, 0 초과 20 이하)입니다.
+    pub max_offset_percent: f32,
+    /// 비우면 실행 시각으로 정하며, 실제 사용한 시드는 결과에 기록됩니다.
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+/// 보고서에 남기는 흔들림 기록입니다. `offsets`는 페이지(이미지는 1개) 순서의 [dx, dy]이며, 가장자리에 맞춰 자른 뒤 실제로 옮긴 거리입니다.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JitterRecord {
+    pub seed: u64,
+    pub offsets: Vec<[i32; 2]>,
+}
+
+impl Jitter {
+    /// `jitter_px`가 0이거나 없으면 흔들지 않습니다. 시드가 없으면 현재 시각으로 정합니다.
+    pub fn from_settings(max_offset: Option<u32>, seed: Option<u64>) -> Option<Self> {
+        let max_offset = max_offset.filter(|v| *v > 0)?;
+        Some(Self {
+            range: JitterRange::Fixed(max_offset),
+            seed: seed_or_now(seed),
+        })
+    }
+
+    /// 비율로 정한 `jitter`가 있으면 그 값을, 없으면 `jitter_px`/`jitter_seed`를 씁니다. 둘을 함께 쓰면 오류입니다.
+    pub fn from_input(value: &StampSettingsInput) -> Result<Option<Self>, Message> {
+        let Some(settings) = value.jitter else {
+            return Ok(Self::from_settings(value.jitter_px, value.jitter_seed));
+        };
+        if value.jitter_px.is_some_and(|px| px > 0) {
+            return Err(Message::new("JITTER_CONFLICT"));
+        }
+        let percent = settings.max_offset_percent;
+        if !percent.is_finite() || percent <= 0.0 || percent > 20.0 {
+            return Err(Message::new("JITTER_PERCENT_INVALID"));
+        }
+        Ok(Some(Self {
+            range: JitterRange::Percent(percent),
+            seed: seed_or_now(settings.seed),
+        }))
+    }
+
+    /// 짧은 변이 `short_side`인 캔버스에서 `[-max_offset, max_offset]` 범위의 (dx, dy)를 돌려줍니다.
+    pub fn offset(&self, key: &str, page: u32, short_side: f64) -> (i32, i32) {
+        let max_offset = self.range.max_offset(short_side);
+        let mut state = self.seed ^ fnv1a(key.as_bytes()) ^ u64::from(page).rotate_left(32);
+        let span = u64::from(max_offset) * 2 + 1;
+        let max = i64::from(max_offset);
+        let dx = (splitmix64(&mut state) % span) as i64 - max;
+        let dy = (splitmix64(&mut state) % span) as i64 - max;
+        (dx as i32, dy as i32)
+    }
+}
+
+fn seed_or_now(seed: Option<u64>) -> u64 {
+    seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0)
+    })
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offsets_stay_within_bounds_and_repeat_for_fixed_seed() {
+        let jitter = Jitter::from_settings(Some(5), Some(42)).expect("jitter");
+
+        for page in 1..=200 {
+            let (dx, dy) = jitter.offset("/photos/a.png", page, 100.0);
+            assert!((-5..=5).contains(&dx), "dx {dx} out of range");
+            assert!((-5..=5).contains(&dy), "dy {dy} out of range");
+            assert_eq!(jitter.offset("/photos/a.png", page, 100.0), (dx, dy));
+        }
+
+        let other_seed = Jitter::from_settings(Some(5), Some(43)).expect("jitter");
+        let differs = (1..=20).any(|page| {
+            jitter.offset("/photos/a.png", page, 100.0)
+                != other_seed.offset("/photos/a.png", page, 100.0)
+        });
+        assert!(differs, "a different seed should move the stamp");
+    }
+
+    #[test]
+    fn zero_or_missing_range_disables_jitter() {
+        assert!(Jitter::from_settings(None, Some(1)).is_none());
+        assert!(Jitter::from_settings(Some(0), Some(1)).is_none());
+    }
+
+    #[test]
+    fn percent_jitter_gives_files_different_but_reproducible_offsets() {
+        let input = StampSettingsInput {
+            jitter: Some(JitterSettings {
+                max_offset_percent: 5.0,
+                seed: Some(7),
+            }),
+            ..Default::default()
+        };
+        let jitter = Jitter::from_input(&input).expect("valid").expect("jitter");
+        assert_eq!(jitter.range, JitterRange::Percent(5.0));
+
+        // 짧은 변 400px의 5%이므로 ±20px 안에서 움직입니다.
+        let a = jitter.offset("/proofs/a.png", 1, 400.0);
+        let b = jitter.offset("/proofs/b.png", 1, 400.0);
+        assert_ne!(a, b, "different files should land on different offsets");
+        for (dx, dy) in [a, b] {
+            assert!((-20..=20).contains(&dx) && (-20..=20).contains(&dy));
+        }
+
+        let again = Jitter::from_input(&input).expect("valid").expect("jitter");
+        assert_eq!(again.offset("/proofs/a.png", 1, 400.0), a);
+        assert_eq!(again.offset("/proofs/b.png", 1, 400.0), b);
+
+        let both = StampSettingsInput {
+            jitter_px: Some(3),
+            ..input.clone()
+        };
+        assert!(Jitter::from_input(&both).is_err());
+    }
+}
@@ -0,0 +1,130 @@
+use crate::batch::BatchOptions;
+use crate::i18n::Message;
+use crate::image_engine::StampSettingsInput;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// `stamp_from_job_file`이 읽는 작업 파일입니다. 상대 경로는 작업 파일이 있는 폴더 기준입니다.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct JobFile {
+    pub paths: Vec<String>,
+    pub settings: StampSettingsInput,
+    #[serde(default)]
+    pub output_dir: Option<String>,
+    #[serde(default)]
+    pub logo_path: Option<String>,
+    /// `logo_path` 대신 쓸 번들 로고 이름입니다. (`default`, `dark`, `mono`)
+    #[serde(default)]
+    pub builtin_logo: Option<String>,
+    /// 회사 서버 등에서 내려받아 쓸 로고 주소입니다. 받지 못하면 전에 받아 둔 사본을 씁니다.
+    #[serde(default)]
+    pub logo_url: Option<String>,
+    #[serde(default)]
+    pub options: Option<BatchOptions>,
+}
+
+pub fn load_job(job_path: &Path) -> Result<JobFile, Message> {
+    let bytes =
+        fs::read(job_path).map_err(|e| Message::new("JOB_READ_FAILED").arg(e.to_string()))?;
+    let mut job: JobFile = serde_json::from_slice(&bytes)
+        .map_err(|e| Message::new("JOB_MALFORMED").arg(e.to_string()))?;
+
+    job.paths.retain(|path| !path.trim().is_empty());
+    if job.paths.is_empty() {
+        return Err(Message::new("JOB_NO_PATHS"));
+    }
+
+    let base_dir = job_path.parent().unwrap_or_else(|| Path::new(""));
+    let resolve = |value: &str| -> String {
+        let path = PathBuf::from(value.trim());
+        if path.is_absolute() {
+            path.to_string_lossy().to_string()
+        } else {
+            base_dir.join(path).to_string_lossy().to_string()
+        }
+    };
+
+    job.paths = job.paths.iter().map(|path| resolve(path)).collect();
+    job.output_dir = job
+        .output_dir
+        .as_deref()
+        .filter(|dir| !dir.trim().is_empty())
+        .map(resolve);
+    job.logo_path = job
+        .logo_path
+        .as_deref()
+        .filter(|logo| !logo.trim().is_empty())
+        .map(resolve);
+
+    Ok(job)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn load_job_resolves_relative_paths_and_rejects_malformed_files() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-job-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let valid = root.join("valid.json");
+        fs::write(
+            &valid,
+            r#"{
+                "paths": ["photos/a.png", "/abs/b.pdf"],
+                "settings": { "position": "우하단", "marginPercent": 2.0 },
+                "outputDir": "exports"
+            }"#,
+        )
+        .expect("write valid job");
+
+        let job = load_job(&valid).expect("valid job");
+        assert_eq!(
+            job.paths,
+            vec![
+                root.join("photos/a.png").to_string_lossy().to_string(),
+                "/abs/b.pdf".to_string()
+            ]
+        );
+        assert_eq!(job.settings.size_preset, "보통");
+        assert_eq!(
+            job.output_dir,
+            Some(root.join("exports").to_string_lossy().to_string())
+        );
+        assert!(job.logo_path.is_none());
+
+        let missing_settings = root.join("missing.json");
+        fs::write(&missing_settings, r#"{ "paths": ["a.png"] }"#).expect("write");
+        let error = load_job(&missing_settings).expect_err("settings are required");
+        assert!(
+            error.to_string().contains("settings"),
+            "unexpected error: {error}"
+        );
+
+        let typo = root.join("typo.json");
+        fs::write(
+            &typo,
+            r#"{ "path": ["a.png"], "settings": { "position": "우하단", "marginPercent": 2.0 } }"#,
+        )
+        .expect("write");
+        assert!(load_job(&typo).is_err());
+
+        let empty = root.join("empty.json");
+        fs::write(
+            &empty,
+            r#"{ "paths": [], "settings": { "position": "우하단", "marginPercent": 2.0 } }"#,
+        )
+        .expect("write");
+        assert!(load_job(&empty).is_err());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
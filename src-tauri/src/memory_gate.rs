@@ -0,0 +1,122 @@
+use std::path::Path;
+use std::sync::{Arc, Condvar, Mutex};
+
+/// 원본 디코딩본과 합성 결과가 함께 메모리에 있으므로 RGBA 한 장 크기의 두 배로 어림합니다.
+const COPIES_PER_FILE: u64 = 2;
+
+/// 헤더의 가로·세로로 파일 하나를 처리하는 동안 필요한 메모리를 어림합니다.
+/// PDF나 헤더를 읽지 못한 파일은 0으로 보며, 이런 파일은 예산과 상관없이 바로 시작합니다.
+pub(crate) fn estimate_file_bytes(path: &Path) -> u64 {
+    image::image_dimensions(path)
+        .map(|(width, height)| u64::from(width) * u64::from(height) * 4 * COPIES_PER_FILE)
+        .unwrap_or(0)
+}
+
+/// 동시에 처리 중인 파일의 어림 메모리 합이 예산을 넘지 않도록 시작을 미룹니다.
+/// 혼자서도 예산을 넘는 파일은 처리 중인 파일이 없을 때만 시작하므로 결국 하나씩 처리됩니다.
+pub(crate) struct MemoryGate {
+    inner: Arc<GateInner>,
+}
+
+struct GateInner {
+    budget: u64,
+    state: Mutex<GateState>,
+    released: Condvar,
+}
+
+#[derive(Default)]
+struct GateState {
+    in_flight_bytes: u64,
+    in_flight_files: usize,
+}
+
+/// 예산에서 잡아 둔 몫입니다. 게이트를 빌리지 않으므로, 제한 시간을 넘겨 버려진 작업 스레드로 옮겨
+/// 그 작업이 실제로 끝날 때 돌려줄 수 있습니다.
+pub(crate) struct MemoryPermit {
+    gate: Arc<GateInner>,
+    bytes: u64,
+}
+
+impl MemoryGate {
+    pub(crate) fn new(budget: u64) -> Self {
+        Self {
+            inner: Arc::new(GateInner {
+                budget,
+                state: Mutex::new(GateState::default()),
+                released: Condvar::new(),
+            }),
+        }
+    }
+
+    pub(crate) fn acquire(&self, bytes: u64) -> MemoryPermit {
+        let gate = &self.inner;
+        let mut state = gate.state.lock().unwrap_or_else(|e| e.into_inner());
+        while state.in_flight_files > 0 && state.in_flight_bytes.saturating_add(bytes) > gate.budget
+        {
+            state = gate.released.wait(state).unwrap_or_else(|e| e.into_inner());
+        }
+        state.in_flight_bytes += bytes;
+        state.in_flight_files += 1;
+        MemoryPermit {
+            gate: Arc::clone(gate),
+            bytes,
+        }
+    }
+}
+
+impl Drop for MemoryPermit {
+    fn drop(&mut self) {
+        let mut state = self.gate.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.in_flight_bytes -= self.bytes;
+        state.in_flight_files -= 1;
+        self.gate.released.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    fn peak_concurrency(budget: u64, cost: u64) -> usize {
+        let gate = MemoryGate::new(budget);
+        let running = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    let _permit = gate.acquire(cost);
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    std::thread::sleep(Duration::from_millis(50));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                });
+            }
+        });
+        peak.load(Ordering::SeqCst)
+    }
+
+    #[test]
+    fn low_budget_serializes_files_and_still_admits_oversized_ones() {
+        // 예산보다 큰 파일도 막히지 않고 하나씩 처리됩니다.
+        assert_eq!(peak_concurrency(10, 100), 1);
+        assert_eq!(peak_concurrency(150, 100), 1);
+        assert!(peak_concurrency(1_000, 100) > 1);
+    }
+
+    #[test]
+    fn permit_moved_to_detached_thread_is_held_until_it_finishes() {
+        let gate = MemoryGate::new(100);
+        let permit = gate.acquire(80);
+        let started = std::time::Instant::now();
+        std::thread::spawn(move || {
+            let _permit = permit;
+            std::thread::sleep(Duration::from_millis(100));
+        });
+
+        // 버려진 작업이 몫을 쥐고 있는 동안 예산을 넘는 다음 파일은 기다립니다.
+        let _next = gate.acquire(80);
+        assert!(started.elapsed() >= Duration::from_millis(100));
+    }
+}
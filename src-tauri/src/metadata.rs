@@ -0,0 +1,116 @@
+use crate::i18n::Message;
+use image::{DynamicImage, ImageFormat};
+use lopdf::{Dictionary, Document, Object, StringFormat};
+use std::io::Cursor;
+
+/// 출력 파일 메타데이터에 남기는 키입니다. (PNG iTXt 키워드, PDF /Info 항목)
+pub const METADATA_KEY: &str = "CornerBrand";
+
+pub(crate) fn supports_image_metadata(format: ImageFormat) -> bool {
+    matches!(format, ImageFormat::Png | ImageFormat::Jpeg)
+}
+
+/// 보이지 않는 출처 표시를 넣어 이미지를 인코딩합니다.
+/// PNG는 iTXt 청크, JPEG는 COM 세그먼트에 `CornerBrand: <text>`로 기록합니다. 16비트 PNG는 비트 깊이를 유지합니다.
+pub(crate) fn encode_image_with_metadata(
+    image: &DynamicImage,
+    format: ImageFormat,
+    text: &str,
+) -> Result<Vec<u8>, Message> {
+    match format {
+        ImageFormat::Png => encode_png_with_text(image, text),
+        ImageFormat::Jpeg => {
+            let mut bytes = Vec::new();
+            image
+                .write_to(&mut Cursor::new(&mut bytes), format)
+                .map_err(|e| Message::new("IMAGE_ENCODE_FAILED").arg(e.to_string()))?;
+            Ok(insert_jpeg_comment(
+                bytes,
+                &format!("{METADATA_KEY}: {text}"),
+            ))
+        }
+        _ => Err(Message::new("METADATA_UNSUPPORTED")),
+    }
+}
+
+fn encode_png_with_text(image: &DynamicImage, text: &str) -> Result<Vec<u8>, Message> {
+    // PNG의 16비트 샘플은 빅엔디언입니다.
+    let (depth, data) = match image {
+        DynamicImage::ImageRgba16(image) => (
+            png::BitDepth::Sixteen,
+            image
+                .as_raw()
+                .iter()
+                .flat_map(|v| v.to_be_bytes())
+                .collect(),
+        ),
+        image => (png::BitDepth::Eight, image.to_rgba8().into_raw()),
+    };
+
+    let mut bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut bytes, image.width(), image.height());
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(depth);
+    encoder
+        .add_itxt_chunk(METADATA_KEY.to_string(), text.to_string())
+        .map_err(|e| Message::new("PNG_METADATA_FAILED").arg(e.to_string()))?;
+
+    let mut writer = encoder
+        .write_header()
+        .map_err(|e| Message::new("IMAGE_ENCODE_FAILED").arg(e.to_string()))?;
+    writer
+        .write_image_data(&data)
+        .map_err(|e| Message::new("IMAGE_ENCODE_FAILED").arg(e.to_string()))?;
+    writer
+        .finish()
+        .map_err(|e| Message::new("IMAGE_ENCODE_FAILED").arg(e.to_string()))?;
+    Ok(bytes)
+}
+
+/// SOI 바로 뒤에 COM 세그먼트를 끼워 넣습니다. 세그먼트 길이 한도(64KB)를 넘는 부분은 자릅니다.
+fn insert_jpeg_comment(jpeg: Vec<u8>, comment: &str) -> Vec<u8> {
+    if jpeg.len() < 2 || jpeg[..2] != [0xFF, 0xD8] {
+        return jpeg;
+    }
+
+    let payload = &comment.as_bytes()[..comment.len().min(u16::MAX as usize - 2)];
+    let length = (payload.len() + 2) as u16;
+
+    let mut out = Vec::with_capacity(jpeg.len() + payload.len() + 4);
+    out.extend_from_slice(&jpeg[..2]);
+    out.extend_from_slice(&[0xFF, 0xFE]);
+    out.extend_from_slice(&length.to_be_bytes());
+    out.extend_from_slice(payload);
+    out.extend_from_slice(&jpeg[2..]);
+    out
+}
+
+/// 문서 정보 사전(/Info)에 `/CornerBrand` 항목을 씁니다. 없으면 새로 만듭니다.
+pub(crate) fn set_pdf_info(doc: &mut Document, text: &str) {
+    let value = pdf_text_string(text);
+    let info_id = match doc.trailer.get(b"Info").and_then(Object::as_reference) {
+        Ok(id) if doc.get_dictionary(id).is_ok() => id,
+        _ => {
+            let id = doc.add_object(Dictionary::new());
+            doc.trailer.set("Info", id);
+            id
+        }
+    };
+
+    if let Ok(info) = doc.get_dictionary_mut(info_id) {
+        info.set(METADATA_KEY, value);
+    }
+}
+
+/// ASCII는 그대로, 그 밖의 문자는 BOM을 붙인 UTF-16BE로 인코딩합니다.
+fn pdf_text_string(text: &str) -> Object {
+    if text.is_ascii() {
+        return Object::String(text.as_bytes().to_vec(), StringFormat::Literal);
+    }
+
+    let mut bytes = vec![0xFE, 0xFF];
+    for unit in text.encode_utf16() {
+        bytes.extend_from_slice(&unit.to_be_bytes());
+    }
+    Object::String(bytes, StringFormat::Hexadecimal)
+}
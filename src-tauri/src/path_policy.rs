@@ -1,48 +1,451 @@
+use crate::i18n::Message;
 use image::ImageFormat;
-use std::fs;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fs::{self, OpenOptions};
+use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 pub const OUTPUT_DIR_NAME: &str = "CornerBrand_Output";
+/// 원본을 덮어쓰기 전에 사본을 두는 폴더 이름입니다. 입력 파일과 같은 폴더 아래에 만듭니다.
+pub const BACKUP_DIR_NAME: &str = "CornerBrand_Backup";
+/// 웹용 축소본을 모으는 결과 폴더 안의 하위 폴더 이름입니다.
+pub const WEB_COPY_DIR_NAME: &str = "web";
+/// 자동으로 만드는 결과 파일 이름에 붙는 꼬리입니다. 겹치면 뒤에 `(1)` 등이 더 붙습니다.
+const OUTPUT_NAME_SUFFIX: &str = "_cornerbrand";
+
+/// 이미지 엔진이 처리하는 확장자 목록입니다. 새 형식은 여기에만 추가하면 됩니다.
+const IMAGE_EXTENSIONS: &[(&str, ImageFormat)] = &[
+    ("jpg", ImageFormat::Jpeg),
+    ("jpeg", ImageFormat::Jpeg),
+    ("png", ImageFormat::Png),
+    ("webp", ImageFormat::WebP),
+    ("tif", ImageFormat::Tiff),
+    ("tiff", ImageFormat::Tiff),
+];
+
+/// HEIC/HEIF는 `heic` 기능으로 디코딩하고, 인코더가 드물어 JPEG로 저장합니다.
+const HEIF_EXTENSIONS: &[&str] = &["heic", "heif"];
+
+const PDF_EXTENSION: &str = "pdf";
+
+/// 지원하지 않지만 자주 들어오는 확장자입니다. 일반 안내 대신 종류에 맞는 안내를 보여 줍니다.
+const VIDEO_EXTENSIONS: &[&str] = &["mp4", "mov", "m4v", "avi", "mkv", "webm", "wmv"];
+const OFFICE_EXTENSIONS: &[&str] = &["doc", "docx", "xls", "xlsx", "ppt", "pptx", "hwp", "hwpx"];
+
+/// 처리하지 않는 입력 중 종류를 알 수 있는 것입니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnsupportedKind {
+    Video,
+    Office,
+}
+
+pub fn classify_unsupported(path: &Path) -> Option<UnsupportedKind> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    if VIDEO_EXTENSIONS.contains(&extension.as_str()) {
+        Some(UnsupportedKind::Video)
+    } else if OFFICE_EXTENSIONS.contains(&extension.as_str()) {
+        Some(UnsupportedKind::Office)
+    } else {
+        None
+    }
+}
+
+/// 자동 결과 이름이 이미 있을 때 새 이름을 정하는 방식입니다.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CollisionStrategy {
+    /// `이름_cornerbrand(1)`, `(2)`, ... 순서로 붙입니다.
+    #[default]
+    Increment,
+    /// 항상 `이름_cornerbrand_YYYYMMDD_HHMMSS`(UTC)로 저장해 실행마다 구분되게 합니다.
+    /// 같은 초에 겹치면 그 뒤에 `(1)` 등이 붙습니다.
+    Timestamp,
+}
+
+/// 자동 결과 이름을 만드는 규칙입니다.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OutputNaming {
+    pub preserve_extension_case: bool,
+    pub collision: CollisionStrategy,
+}
+
+impl OutputNaming {
+    pub fn from_input(
+        preserve_extension_case: bool,
+        collision_strategy: Option<&str>,
+    ) -> Result<Self, Message> {
+        let collision = match collision_strategy.map(str::trim) {
+            None | Some("") | Some("increment") => CollisionStrategy::Increment,
+            Some("timestamp") => CollisionStrategy::Timestamp,
+            Some(_) => return Err(Message::new("INVALID_CONFLICT_POLICY")),
+        };
+        Ok(Self {
+            preserve_extension_case,
+            collision,
+        })
+    }
+
+    /// 확장자 앞에 올 자동 결과 이름입니다.
+    fn base_name(self, stem: &str) -> String {
+        match self.collision {
+            CollisionStrategy::Increment => format!("{stem}{OUTPUT_NAME_SUFFIX}"),
+            CollisionStrategy::Timestamp => format!(
+                "{stem}{OUTPUT_NAME_SUFFIX}_{}",
+                utc_timestamp(SystemTime::now())
+            ),
+        }
+    }
+}
+
+/// `YYYYMMDD_HHMMSS` 형식의 UTC 시각입니다.
+pub(crate) fn utc_timestamp(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let (days, rem) = ((secs / 86_400) as i64, secs % 86_400);
+    // 1970-01-01부터 지난 날 수를 그레고리력 날짜로 바꿉니다. (H. Hinnant의 civil_from_days)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}{month:02}{day:02}_{:02}{:02}{:02}",
+        rem / 3_600,
+        rem % 3_600 / 60,
+        rem % 60
+    )
+}
 
 #[derive(Debug, Clone)]
 pub struct SupportedFormat {
     pub format: ImageFormat,
     pub output_extension: String,
+    /// `image` 크레이트 대신 libheif로 읽어야 하는 입력입니다.
+    pub heif: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FormatKind {
+    Raster,
+    Pdf,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportedExtension {
+    pub extension: String,
+    pub kind: FormatKind,
+}
+
+/// 프론트엔드 파일 선택 필터 등에 쓰는 지원 확장자 전체 목록입니다.
+pub fn supported_formats() -> Vec<SupportedExtension> {
+    IMAGE_EXTENSIONS
+        .iter()
+        .map(|(extension, _)| *extension)
+        .chain(
+            HEIF_EXTENSIONS
+                .iter()
+                .copied()
+                .filter(|_| cfg!(feature = "heic")),
+        )
+        .map(|extension| SupportedExtension {
+            extension: extension.to_string(),
+            kind: FormatKind::Raster,
+        })
+        .chain(std::iter::once(SupportedExtension {
+            extension: PDF_EXTENSION.to_string(),
+            kind: FormatKind::Pdf,
+        }))
+        .collect()
 }
 
 pub fn detect_supported_image(path: &Path) -> Option<SupportedFormat> {
     let extension = path.extension()?.to_str()?.to_ascii_lowercase();
 
-    let format = match extension.as_str() {
-        "jpg" | "jpeg" => ImageFormat::Jpeg,
-        "png" => ImageFormat::Png,
-        "webp" => ImageFormat::WebP,
-        _ => return None,
-    };
+    if HEIF_EXTENSIONS.contains(&extension.as_str()) {
+        return Some(SupportedFormat {
+            format: ImageFormat::Jpeg,
+            output_extension: "jpg".to_string(),
+            heif: true,
+        });
+    }
+
+    let format = IMAGE_EXTENSIONS
+        .iter()
+        .find(|(candidate, _)| *candidate == extension)
+        .map(|(_, format)| *format)?;
 
     Some(SupportedFormat {
         format,
         output_extension: extension,
+        heif: false,
     })
 }
 
 pub fn is_supported_pdf(path: &Path) -> bool {
     path.extension()
         .and_then(|e| e.to_str())
-        .map(|e| e.eq_ignore_ascii_case("pdf"))
+        .map(|e| e.eq_ignore_ascii_case(PDF_EXTENSION))
         .unwrap_or(false)
 }
 
+/// 입력 목록을 펼친 한 항목입니다. 일치하는 파일이 없는 패턴은 사유와 함께 그대로 남깁니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExpandedInput {
+    Path(String),
+    Unmatched { pattern: String, reason: Message },
+}
+
+impl ExpandedInput {
+    /// 파일 경로 또는 일치하는 파일이 없던 패턴 문자열입니다.
+    pub fn input(&self) -> &String {
+        match self {
+            Self::Path(path) => path,
+            Self::Unmatched { pattern, .. } => pattern,
+        }
+    }
+}
+
+/// `*`, `?`, `[`가 들어간 입력을 지원 형식 파일 목록으로 펼칩니다. 그 밖의 경로와 실제로 있는 파일 경로는 그대로 둡니다.
+/// 결과는 입력 순서(패턴 안에서는 이름순)를 지킵니다. 패턴이 찾은 경로는 이미 나온 경로와 겹치면 빼지만,
+/// 직접 적은 경로는 빼지 않으므로 패턴이 없는 목록은 항목 수와 순서가 그대로입니다.
+pub fn expand_globs(inputs: &[String]) -> Vec<ExpandedInput> {
+    let mut seen = BTreeSet::new();
+    let mut expanded = Vec::with_capacity(inputs.len());
+
+    for input in inputs {
+        if !input.contains(['*', '?', '[']) || Path::new(input).is_file() {
+            seen.insert(input.clone());
+            expanded.push(ExpandedInput::Path(input.clone()));
+            continue;
+        }
+
+        let entries = match glob::glob(input) {
+            Ok(entries) => entries,
+            Err(e) => {
+                expanded.push(ExpandedInput::Unmatched {
+                    pattern: input.clone(),
+                    reason: Message::new("INVALID_PATTERN").arg(e.to_string()),
+                });
+                continue;
+            }
+        };
+
+        let matches: Vec<String> = entries
+            .filter_map(Result::ok)
+            .filter(|path| path.is_file())
+            .filter(|path| detect_supported_image(path).is_some() || is_supported_pdf(path))
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+
+        if matches.is_empty() {
+            expanded.push(ExpandedInput::Unmatched {
+                pattern: input.clone(),
+                reason: Message::new("NO_MATCH").arg(input.to_string()),
+            });
+            continue;
+        }
+
+        for path in matches {
+            if seen.insert(path.clone()) {
+                expanded.push(ExpandedInput::Path(path));
+            }
+        }
+    }
+
+    expanded
+}
+
+/// 결과 파일을 어디에 만들지 정합니다. 보통은 자동 이름(`_cornerbrand`)이고,
+/// 단일 파일 명령에서는 사용자가 지정한 경로를 그대로 쓸 수 있습니다.
+#[derive(Debug, Clone, Copy)]
+pub enum OutputTarget<'a> {
+    /// 출력 기준 폴더(없으면 입력 파일 폴더) 아래 `CornerBrand_Output`에 자동 이름으로 저장합니다.
+    Auto(Option<&'a Path>),
+    /// 이 경로에 정확히 저장합니다. 이미 파일이 있으면 덮어쓰지 않고 실패합니다.
+    /// 단, 입력 파일 자신이고 원본 백업을 켰다면 백업한 뒤 덮어씁니다.
+    Exact(&'a Path),
+}
+
+/// 저장하기 위해 확보한 출력 경로입니다. 원본을 덮어쓰는 경우 백업 위치도 함께 가집니다.
+#[derive(Debug)]
+pub struct ReservedOutput {
+    pub path: PathBuf,
+    pub backup: Option<PathBuf>,
+}
+
+impl ReservedOutput {
+    fn new(path: PathBuf) -> Self {
+        Self { path, backup: None }
+    }
+
+    /// 저장에 실패했을 때 부릅니다. 선점한 파일은 지우고, 원본을 덮어쓰던 중이었다면 백업으로 되돌립니다.
+    pub fn discard(&self) {
+        match &self.backup {
+            Some(backup) => {
+                let _ = fs::copy(backup, &self.path);
+            }
+            None => {
+                let _ = fs::remove_file(&self.path);
+            }
+        }
+    }
+}
+
+impl OutputTarget<'_> {
+    pub fn image_path(
+        self,
+        input_path: &Path,
+        backup_originals: bool,
+        naming: OutputNaming,
+    ) -> Result<ReservedOutput, Message> {
+        match self {
+            Self::Auto(output_base_dir) => {
+                build_output_path(input_path, output_base_dir, naming).map(ReservedOutput::new)
+            }
+            Self::Exact(output_path) => {
+                let input_format = detect_supported_image(input_path)
+                    .ok_or_else(|| Message::new("UNSUPPORTED_IMAGE_TYPE"))?;
+                let output_format = detect_supported_image(output_path).filter(|f| !f.heif);
+                if output_format.map(|f| f.format) != Some(input_format.format) {
+                    return Err(Message::new("OUTPUT_EXTENSION_MISMATCH")
+                        .arg(input_format.output_extension));
+                }
+                reserve_exact_or_in_place(input_path, output_path, backup_originals)
+            }
+        }
+    }
+
+    pub fn pdf_path(
+        self,
+        input_path: &Path,
+        backup_originals: bool,
+        naming: OutputNaming,
+    ) -> Result<ReservedOutput, Message> {
+        match self {
+            Self::Auto(output_base_dir) => {
+                build_output_pdf_path(input_path, output_base_dir, naming).map(ReservedOutput::new)
+            }
+            Self::Exact(output_path) => {
+                if !is_supported_pdf(output_path) {
+                    return Err(Message::new("OUTPUT_EXTENSION_NOT_PDF"));
+                }
+                reserve_exact_or_in_place(input_path, output_path, backup_originals)
+            }
+        }
+    }
+}
+
+/// 출력 경로가 입력 파일 자신이면 백업 후 그 자리를 쓰고, 아니면 새 파일로 선점합니다.
+fn reserve_exact_or_in_place(
+    input_path: &Path,
+    output_path: &Path,
+    backup_originals: bool,
+) -> Result<ReservedOutput, Message> {
+    if !is_same_file(input_path, output_path) {
+        return reserve_exact_path(output_path).map(ReservedOutput::new);
+    }
+    if !backup_originals {
+        return Err(Message::new("OVERWRITE_NEEDS_BACKUP"));
+    }
+
+    let backup = backup_original(input_path)?;
+    Ok(ReservedOutput {
+        path: output_path.to_path_buf(),
+        backup: Some(backup),
+    })
+}
+
+fn is_same_file(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// 원본을 입력 폴더의 `CornerBrand_Backup`에 같은 이름(겹치면 `(1)` 등)으로 복사하고 그 경로를 돌려줍니다.
+pub fn backup_original(input_path: &Path) -> Result<PathBuf, Message> {
+    let parent = input_path
+        .parent()
+        .ok_or_else(|| Message::new("INPUT_PARENT_NOT_FOUND"))?;
+    let backup_dir = parent.join(BACKUP_DIR_NAME);
+    ensure_directory(&backup_dir)?;
+
+    let stem = input_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .filter(|s| !s.is_empty())
+        .unwrap_or("file");
+    let extension = input_path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default();
+    let backup_path = reserve_unique_path(&backup_dir, stem, extension)?;
+
+    if let Err(e) = fs::copy(input_path, &backup_path) {
+        let _ = fs::remove_file(&backup_path);
+        return Err(Message::new("BACKUP_FAILED").arg(e.to_string()));
+    }
+    Ok(backup_path)
+}
+
+/// 상위 폴더를 만들고 `create_new`로 정확히 그 이름을 선점합니다. 기존 파일은 건드리지 않습니다.
+fn reserve_exact_path(output_path: &Path) -> Result<PathBuf, Message> {
+    if let Some(parent) = output_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        ensure_directory(parent)?;
+    }
+
+    match OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(output_path)
+    {
+        Ok(_) => Ok(output_path.to_path_buf()),
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+            Err(Message::new("OUTPUT_EXISTS").arg(output_path.display().to_string()))
+        }
+        Err(e) => Err(Message::new("OUTPUT_CREATE_FAILED").arg(e.to_string())),
+    }
+}
+
+/// 자동 이름의 확장자입니다. 기본은 소문자이고, `preserve_case`면 입력에 적힌 대소문자를 따릅니다.
+/// HEIC처럼 다른 형식으로 저장하는 입력은 입력 확장자가 모두 대문자일 때만 대문자로 씁니다.
+fn output_extension(input_path: &Path, lowercase: &str, preserve_case: bool) -> String {
+    let input_extension = input_path.extension().and_then(|e| e.to_str());
+    match input_extension {
+        Some(extension) if preserve_case && extension.eq_ignore_ascii_case(lowercase) => {
+            extension.to_string()
+        }
+        Some(extension)
+            if preserve_case
+                && extension.bytes().any(|b| b.is_ascii_alphabetic())
+                && !extension.bytes().any(|b| b.is_ascii_lowercase()) =>
+        {
+            lowercase.to_ascii_uppercase()
+        }
+        _ => lowercase.to_string(),
+    }
+}
+
 pub fn build_output_path(
     input_path: &Path,
     output_base_dir: Option<&Path>,
-) -> Result<PathBuf, String> {
-    let format = detect_supported_image(input_path)
-        .ok_or_else(|| "지원하지 않는 이미지 형식입니다. (jpg/png/webp)".to_string())?;
+    naming: OutputNaming,
+) -> Result<PathBuf, Message> {
+    let format =
+        detect_supported_image(input_path).ok_or_else(|| Message::new("UNSUPPORTED_IMAGE_TYPE"))?;
 
     let parent = input_path
         .parent()
-        .ok_or_else(|| "입력 파일의 상위 경로를 찾을 수 없습니다.".to_string())?;
+        .ok_or_else(|| Message::new("INPUT_PARENT_NOT_FOUND"))?;
 
     let output_dir = resolve_output_dir(parent, output_base_dir)?;
 
@@ -52,37 +455,72 @@ pub fn build_output_path(
         .filter(|s| !s.is_empty())
         .unwrap_or("image");
 
-    let base_name = format!("{stem}_cornerbrand");
+    let base_name = naming.base_name(stem);
+    let extension = output_extension(
+        input_path,
+        &format.output_extension,
+        naming.preserve_extension_case,
+    );
 
-    let first = output_dir.join(format!("{}.{}", base_name, format.output_extension));
-    if !first.exists() {
-        return Ok(first);
-    }
+    reserve_unique_path(&output_dir, &base_name, &extension)
+}
 
-    let mut index = 1u32;
-    loop {
-        let candidate = output_dir.join(format!(
-            "{}({}).{}",
-            base_name, index, format.output_extension
-        ));
-        if !candidate.exists() {
-            return Ok(candidate);
-        }
-        index = index.saturating_add(1);
-    }
+/// 웹용 사본을 결과 파일 폴더의 `web` 하위 폴더에 `<이름>_web.<확장자>`로 확보합니다.
+pub fn reserve_web_copy_path(
+    input_path: &Path,
+    main_output: &Path,
+    format: ImageFormat,
+) -> Result<PathBuf, Message> {
+    let output_dir = main_output
+        .parent()
+        .ok_or_else(|| Message::new("OUTPUT_PARENT_NOT_FOUND"))?
+        .join(WEB_COPY_DIR_NAME);
+    fs::create_dir_all(&output_dir)
+        .map_err(|e| Message::new("WEB_COPY_DIR_FAILED").arg(e.to_string()))?;
+
+    let stem = input_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .filter(|s| !s.is_empty())
+        .unwrap_or("image");
+    let extension = format.extensions_str().first().copied().unwrap_or("png");
+    reserve_unique_path(&output_dir, &format!("{stem}_web"), extension)
+}
+
+/// 파일 이름이 자동 결과 이름(`이름_cornerbrand`, `이름_cornerbrand(1)`,
+/// `이름_cornerbrand_20240101_120000` 등)이면 `true`입니다.
+pub fn looks_like_stamped_output(path: &Path) -> bool {
+    let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+        return false;
+    };
+    let stem = match stem.strip_suffix(')').and_then(|s| s.rsplit_once('(')) {
+        Some((base, n)) if !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()) => base,
+        _ => stem,
+    };
+    let is_timestamp = |date: &str, time: &str| {
+        date.len() == 8
+            && time.len() == 6
+            && date.bytes().chain(time.bytes()).all(|b| b.is_ascii_digit())
+    };
+    let stem = match stem.rsplitn(3, '_').collect::<Vec<_>>()[..] {
+        [time, date, base] if is_timestamp(date, time) => base,
+        _ => stem,
+    };
+    stem.ends_with(OUTPUT_NAME_SUFFIX)
 }
 
 pub fn build_output_pdf_path(
     input_path: &Path,
     output_base_dir: Option<&Path>,
-) -> Result<PathBuf, String> {
+    naming: OutputNaming,
+) -> Result<PathBuf, Message> {
     if !is_supported_pdf(input_path) {
-        return Err("지원하지 않는 PDF 형식입니다. (.pdf)".to_string());
+        return Err(Message::new("UNSUPPORTED_PDF_TYPE"));
     }
 
     let parent = input_path
         .parent()
-        .ok_or_else(|| "입력 파일의 상위 경로를 찾을 수 없습니다.".to_string())?;
+        .ok_or_else(|| Message::new("INPUT_PARENT_NOT_FOUND"))?;
 
     let output_dir = resolve_output_dir(parent, output_base_dir)?;
 
@@ -92,75 +530,144 @@ pub fn build_output_pdf_path(
         .filter(|s| !s.is_empty())
         .unwrap_or("file");
 
-    let base_name = format!("{stem}_cornerbrand");
+    let base_name = naming.base_name(stem);
 
-    let first = output_dir.join(format!("{base_name}.pdf"));
-    if !first.exists() {
-        return Ok(first);
-    }
+    let extension = output_extension(input_path, PDF_EXTENSION, naming.preserve_extension_case);
 
-    let mut index = 1u32;
-    loop {
-        let candidate = output_dir.join(format!("{}({}).pdf", base_name, index));
-        if !candidate.exists() {
-            return Ok(candidate);
-        }
-        index = index.saturating_add(1);
-    }
+    reserve_unique_path(&output_dir, &base_name, &extension)
 }
 
 pub fn build_report_path(
     input_dir: &Path,
     output_base_dir: Option<&Path>,
-) -> Result<PathBuf, String> {
+) -> Result<PathBuf, Message> {
     let output_dir = resolve_output_dir(input_dir, output_base_dir)?;
 
-    let base_name = "cornerbrand_report";
-    let first = output_dir.join(format!("{base_name}.json"));
-    if !first.exists() {
-        return Ok(first);
-    }
+    reserve_unique_path(&output_dir, "cornerbrand_report", "json")
+}
 
-    let mut index = 1u32;
+pub fn build_manifest_path(
+    input_dir: &Path,
+    output_base_dir: Option<&Path>,
+) -> Result<PathBuf, Message> {
+    let output_dir = resolve_output_dir(input_dir, output_base_dir)?;
+
+    reserve_unique_path(&output_dir, "cornerbrand_manifest", "json")
+}
+
+/// 확인용 시트 `sheet_{number}.png`의 이름을 선점합니다. 이미 있으면 `sheet_1(1).png`처럼 번호를 붙입니다.
+pub fn reserve_sheet_path(output_dir: &Path, number: usize) -> Result<PathBuf, Message> {
+    reserve_unique_path(output_dir, &format!("sheet_{number}"), "png")
+}
+
+/// `base.ext`, `base(1).ext`, ... 순서로 빈 파일을 `create_new`로 만들어 이름을 선점합니다.
+/// 존재 확인과 생성이 한 번의 시스템 호출이라 동시에 실행되는 배치끼리 같은 이름을 받지 않습니다.
+fn reserve_unique_path(
+    output_dir: &Path,
+    base_name: &str,
+    extension: &str,
+) -> Result<PathBuf, Message> {
+    let (base_name, extension) = (sanitize_file_name(base_name), sanitize_file_name(extension));
+    let mut index = 0u32;
     loop {
-        let candidate = output_dir.join(format!("{}({}).json", base_name, index));
-        if !candidate.exists() {
-            return Ok(candidate);
+        let file_name = if index == 0 {
+            format!("{base_name}.{extension}")
+        } else {
+            format!("{base_name}({index}).{extension}")
+        };
+        let candidate = output_dir.join(file_name);
+
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&candidate)
+        {
+            Ok(_) => return Ok(candidate),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(Message::new("OUTPUT_NAME_RESERVE_FAILED").arg(e.to_string())),
         }
-        index = index.saturating_add(1);
+
+        index = index
+            .checked_add(1)
+            .ok_or_else(|| Message::new("OUTPUT_NAME_EXHAUSTED"))?;
     }
 }
 
-fn resolve_output_dir(input_dir: &Path, output_base_dir: Option<&Path>) -> Result<PathBuf, String> {
+/// 출력 폴더를 만들고 표식 파일을 만들었다 지워 실제로 쓸 수 있는지 미리 확인합니다.
+pub fn probe_output_dir(
+    input_dir: &Path,
+    output_base_dir: Option<&Path>,
+) -> Result<PathBuf, Message> {
+    let output_dir = resolve_output_dir(input_dir, output_base_dir)?;
+    let marker = output_dir.join(format!(".cornerbrand_write_probe_{}", std::process::id()));
+
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&marker)
+        .and_then(|mut file| std::io::Write::write_all(&mut file, b"probe"))
+        .map_err(|e| Message::new("OUTPUT_NOT_WRITABLE").arg(e.to_string()))?;
+    let _ = fs::remove_file(&marker);
+
+    Ok(output_dir)
+}
+
+/// 입력이 심볼릭 링크여도 따라가지 않습니다. `input_dir`는 링크가 놓인 폴더(`Path::parent`)이므로 결과는
+/// 링크 옆에 생깁니다. 돌려주는 경로는 화면에 보이는 그대로 두고, 정규화한 경로로는 출력 폴더가
+/// 기준 폴더 바로 아래에 있는지만 확인합니다. (`CornerBrand_Output`이 다른 곳을 가리키는 링크면 거부합니다.)
+fn resolve_output_dir(
+    input_dir: &Path,
+    output_base_dir: Option<&Path>,
+) -> Result<PathBuf, Message> {
     let base_dir = match output_base_dir {
         Some(path) => {
             ensure_directory(path)?;
             path
         }
+        None if input_dir.as_os_str().is_empty() => Path::new("."),
         None => input_dir,
     };
 
     let output_dir = base_dir.join(OUTPUT_DIR_NAME);
-    fs::create_dir_all(&output_dir).map_err(|e| format!("출력 폴더를 만들지 못했습니다: {e}"))?;
+    fs::create_dir_all(&output_dir)
+        .map_err(|e| Message::new("OUTPUT_DIR_CREATE_FAILED").arg(e.to_string()))?;
 
     if !output_dir.is_dir() {
-        return Err("출력 경로가 디렉터리가 아닙니다.".to_string());
+        return Err(Message::new("OUTPUT_PATH_NOT_DIR"));
+    }
+
+    let canonical_base = fs::canonicalize(base_dir)
+        .map_err(|e| Message::new("OUTPUT_BASE_RESOLVE_FAILED").arg(e.to_string()))?;
+    let canonical_output = fs::canonicalize(&output_dir)
+        .map_err(|e| Message::new("OUTPUT_DIR_RESOLVE_FAILED").arg(e.to_string()))?;
+    if canonical_output.parent() != Some(canonical_base.as_path()) {
+        return Err(
+            Message::new("OUTPUT_DIR_OUTSIDE_BASE").arg(canonical_output.display().to_string())
+        );
     }
 
     Ok(output_dir)
 }
 
-fn ensure_directory(path: &Path) -> Result<(), String> {
+/// 경로 구분자(`/`, `\`)를 `_`로 바꿔, 파일 이름이 출력 폴더 밖이나 하위 폴더를 가리키지 못하게 합니다.
+/// `\`는 유닉스에서 평범한 글자지만 같은 결과를 Windows로 옮기면 구분자가 되므로 함께 막습니다.
+fn sanitize_file_name(name: &str) -> String {
+    name.replace(['/', '\\'], "_")
+}
+
+fn ensure_directory(path: &Path) -> Result<(), Message> {
     if path.exists() {
         if path.is_dir() {
             return Ok(());
         }
-        return Err("출력 폴더 경로가 디렉터리가 아닙니다.".to_string());
+        return Err(Message::new("OUTPUT_DIR_NOT_DIR"));
     }
 
-    fs::create_dir_all(path).map_err(|e| format!("출력 폴더를 만들지 못했습니다: {e}"))?;
+    fs::create_dir_all(path)
+        .map_err(|e| Message::new("OUTPUT_DIR_CREATE_FAILED").arg(e.to_string()))?;
     if !path.is_dir() {
-        return Err("출력 폴더 경로가 디렉터리가 아닙니다.".to_string());
+        return Err(Message::new("OUTPUT_DIR_NOT_DIR"));
     }
 
     Ok(())
@@ -181,6 +688,22 @@ mod tests {
         assert!(detect_supported_image(Path::new("a.pdf")).is_none());
     }
 
+    #[test]
+    fn supported_formats_lists_raster_and_pdf_extensions() {
+        let formats = supported_formats();
+        let kind_of = |ext: &str| formats.iter().find(|f| f.extension == ext).map(|f| f.kind);
+
+        assert_eq!(kind_of("png"), Some(FormatKind::Raster));
+        assert_eq!(kind_of("webp"), Some(FormatKind::Raster));
+        assert_eq!(kind_of("pdf"), Some(FormatKind::Pdf));
+        assert_eq!(kind_of("gif"), None);
+
+        for format in formats.iter().filter(|f| f.kind == FormatKind::Raster) {
+            let sample = format!("a.{}", format.extension);
+            assert!(detect_supported_image(Path::new(&sample)).is_some());
+        }
+    }
+
     #[test]
     fn pdf_extension_is_detected() {
         assert!(is_supported_pdf(Path::new("a.pdf")));
@@ -188,6 +711,110 @@ mod tests {
         assert!(!is_supported_pdf(Path::new("a.png")));
     }
 
+    #[test]
+    fn globs_expand_to_supported_files_and_keep_literals() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-glob-{nonce}"));
+        fs::create_dir_all(root.join("nested.jpg")).expect("temp dir");
+        for name in ["b.jpg", "a.JPG", "c.png", "notes.txt", "scan.pdf"] {
+            fs::write(root.join(name), b"x").expect("write file");
+        }
+
+        let dir = root.to_string_lossy().to_string();
+        let literal = format!("{dir}/missing.png");
+        let inputs = vec![
+            format!("{dir}/*"),
+            format!("{dir}/*.jpg"),
+            literal.clone(),
+            format!("{dir}/*.gif"),
+        ];
+        let expanded = expand_globs(&inputs);
+
+        let path = |name: &str| ExpandedInput::Path(root.join(name).to_string_lossy().to_string());
+        assert_eq!(
+            expanded[..5],
+            [
+                path("a.JPG"),
+                path("b.jpg"),
+                path("c.png"),
+                path("scan.pdf"),
+                ExpandedInput::Path(literal),
+            ]
+        );
+        assert_eq!(expanded.len(), 6);
+        assert!(matches!(
+            &expanded[5],
+            ExpandedInput::Unmatched { pattern, .. } if pattern.ends_with("*.gif")
+        ));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn output_extension_case_follows_policy_for_uppercase_inputs() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-ext-case-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let file_name = |path: PathBuf| {
+            path.file_name()
+                .expect("file name")
+                .to_string_lossy()
+                .to_string()
+        };
+        let naming = |preserve_extension_case| OutputNaming {
+            preserve_extension_case,
+            ..Default::default()
+        };
+        let mut extensions: Vec<&str> = IMAGE_EXTENSIONS.iter().map(|(ext, _)| *ext).collect();
+        extensions.push(PDF_EXTENSION);
+        for extension in extensions {
+            let upper = extension.to_ascii_uppercase();
+            let input = root.join(format!("{upper}-photo.{upper}"));
+            fs::write(&input, b"x").expect("input");
+            let build = |preserve| {
+                if extension == PDF_EXTENSION {
+                    build_output_pdf_path(&input, None, naming(preserve))
+                } else {
+                    build_output_path(&input, None, naming(preserve))
+                }
+                .expect("output path")
+            };
+            // 대소문자를 구분하지 않는 파일 시스템에서도 이름이 겹치지 않도록 확인한 결과는 지웁니다.
+            let file_name = |path: PathBuf| {
+                let name = file_name(path.clone());
+                fs::remove_file(path).expect("remove reserved output");
+                name
+            };
+
+            assert_eq!(
+                file_name(build(false)),
+                format!("{upper}-photo_cornerbrand.{extension}")
+            );
+            assert_eq!(
+                file_name(build(true)),
+                format!("{upper}-photo_cornerbrand.{upper}")
+            );
+        }
+
+        let mixed = root.join("Mixed.Png");
+        fs::write(&mixed, b"x").expect("input");
+        let output = build_output_path(&mixed, None, naming(true)).expect("output path");
+        assert_eq!(file_name(output), "Mixed_cornerbrand.Png");
+
+        let heic = root.join("IMG_0001.HEIC");
+        assert_eq!(output_extension(&heic, "jpg", true), "JPG");
+        assert_eq!(output_extension(&heic, "jpg", false), "jpg");
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
     #[test]
     fn output_name_is_incremented_on_collision() {
         let nonce = SystemTime::now()
@@ -201,11 +828,11 @@ mod tests {
         let input = root.join("sample.png");
         fs::write(&input, b"x").expect("input");
 
-        let first = build_output_path(&input, None).expect("first path");
+        let first = build_output_path(&input, None, OutputNaming::default()).expect("first path");
         fs::create_dir_all(first.parent().expect("parent")).expect("output dir");
         fs::write(&first, b"x").expect("first file");
 
-        let second = build_output_path(&input, None).expect("second path");
+        let second = build_output_path(&input, None, OutputNaming::default()).expect("second path");
         let name = second
             .file_name()
             .and_then(|n| n.to_str())
@@ -216,6 +843,111 @@ mod tests {
         let _ = fs::remove_dir_all(&root);
     }
 
+    #[test]
+    fn looks_like_stamped_output_matches_generated_names() {
+        assert!(looks_like_stamped_output(Path::new(
+            "a/sample_cornerbrand.jpg"
+        )));
+        assert!(looks_like_stamped_output(Path::new(
+            "sample_cornerbrand(12).jpg"
+        )));
+        assert!(!looks_like_stamped_output(Path::new("sample.jpg")));
+        assert!(!looks_like_stamped_output(Path::new(
+            "sample_cornerbrand(x).jpg"
+        )));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_input_writes_beside_the_link_and_output_dir_must_stay_inside_base() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-symlink-{nonce}"));
+        let real_dir = root.join("real");
+        let link_dir = root.join("links");
+        fs::create_dir_all(&real_dir).expect("real dir");
+        fs::create_dir_all(&link_dir).expect("link dir");
+        let target = real_dir.join("photo.png");
+        fs::write(&target, b"x").expect("target");
+        let link = link_dir.join("photo.png");
+        std::os::unix::fs::symlink(&target, &link).expect("symlink");
+
+        let output = build_output_path(&link, None, OutputNaming::default()).expect("output");
+        assert_eq!(
+            output.parent(),
+            Some(link_dir.join(OUTPUT_DIR_NAME).as_path())
+        );
+        assert!(!real_dir.join(OUTPUT_DIR_NAME).exists());
+
+        // 기준 폴더의 `CornerBrand_Output`이 밖을 가리키는 링크면 쓰지 않습니다.
+        let base = root.join("base");
+        let outside = root.join("outside");
+        fs::create_dir_all(&base).expect("base");
+        fs::create_dir_all(&outside).expect("outside");
+        std::os::unix::fs::symlink(&outside, base.join(OUTPUT_DIR_NAME)).expect("planted link");
+        let error = build_output_path(&target, Some(&base), OutputNaming::default())
+            .expect_err("escaping output dir");
+        assert!(error.to_string().contains("밖을"), "{error}");
+        assert_eq!(fs::read_dir(&outside).expect("outside").count(), 0);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    /// Windows에서는 `\`가 구분자라 애초에 파일 이름에 들어가지 않습니다.
+    #[cfg(unix)]
+    #[test]
+    fn separator_in_file_stem_cannot_escape_output_dir() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-traversal-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input = root.join("..\\..\\evil.png");
+        let output =
+            build_output_path(&input, Some(&root), OutputNaming::default()).expect("output");
+        assert_eq!(output.parent(), Some(root.join(OUTPUT_DIR_NAME).as_path()));
+        assert_eq!(
+            output.file_name().and_then(|n| n.to_str()),
+            Some(".._.._evil_cornerbrand.png")
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn timestamp_strategy_keeps_runs_distinguishable() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-timestamp-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+        let input = root.join("sample.png");
+        fs::write(&input, b"x").expect("input");
+
+        let naming = OutputNaming::from_input(false, Some("timestamp")).expect("naming");
+        let first = build_output_path(&input, None, naming).expect("first path");
+        let second = build_output_path(&input, None, naming).expect("second path");
+        assert_ne!(first, second);
+        for path in [&first, &second] {
+            let name = path.file_name().and_then(|n| n.to_str()).expect("name");
+            assert!(name.starts_with("sample_cornerbrand_"), "{name}");
+            assert!(looks_like_stamped_output(path), "{name}");
+        }
+
+        assert_eq!(
+            utc_timestamp(UNIX_EPOCH + std::time::Duration::from_secs(1_709_210_096)),
+            "20240229_123456"
+        );
+        assert!(OutputNaming::from_input(false, Some("random")).is_err());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
     #[test]
     fn pdf_output_name_is_incremented_on_collision() {
         let nonce = SystemTime::now()
@@ -229,11 +961,13 @@ mod tests {
         let input = root.join("sample.pdf");
         fs::write(&input, b"x").expect("input");
 
-        let first = build_output_pdf_path(&input, None).expect("first path");
+        let first =
+            build_output_pdf_path(&input, None, OutputNaming::default()).expect("first path");
         fs::create_dir_all(first.parent().expect("parent")).expect("output dir");
         fs::write(&first, b"x").expect("first file");
 
-        let second = build_output_pdf_path(&input, None).expect("second path");
+        let second =
+            build_output_pdf_path(&input, None, OutputNaming::default()).expect("second path");
         let name = second
             .file_name()
             .and_then(|n| n.to_str())
@@ -268,4 +1002,37 @@ mod tests {
 
         let _ = fs::remove_dir_all(&root);
     }
+
+    #[test]
+    fn concurrent_callers_never_receive_the_same_output_name() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+
+        let root = std::env::temp_dir().join(format!("cornerbrand-race-test-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+        let input = root.join("sample.png");
+        fs::write(&input, b"x").expect("input");
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let input = input.clone();
+                std::thread::spawn(move || {
+                    build_output_path(&input, None, OutputNaming::default()).expect("output path")
+                })
+            })
+            .collect();
+        let mut names: Vec<PathBuf> = handles
+            .into_iter()
+            .map(|handle| handle.join().expect("thread"))
+            .collect();
+
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), 8, "every caller should get its own name");
+        assert!(names.iter().all(|path| path.exists()));
+
+        let _ = fs::remove_dir_all(&root);
+    }
 }
@@ -0,0 +1,276 @@
+use crate::i18n::Message;
+use crate::path_policy;
+use lopdf::{decode_text_string, dictionary, Dictionary, Document, Object, Stream, StringFormat};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::time::SystemTime;
+
+/// XMP `pdfaid`에 적는 PDF/A 판과 적합성 수준입니다. (PDF/A-2b)
+const PDFA_PART: u32 = 2;
+const PDFA_CONFORMANCE: &str = "B";
+/// 출력 의도(OutputIntent)에 적는 색 조건입니다. 프로파일은 lcms2의 내장 sRGB를 씁니다.
+const OUTPUT_CONDITION: &str = "sRGB IEC61966-2.1";
+const PRODUCER: &str = "CornerBrand";
+
+/// /Info와 XMP가 서로 맞아야 하는 항목입니다. 날짜 항목은 XMP 날짜와 맞추기 어려워 /Info에서 지웁니다.
+const INFO_DATE_KEYS: [&[u8]; 2] = [b"CreationDate", b"ModDate"];
+
+/// 로고를 넣은 문서에 PDF/A-2b 기본 검사에 필요한 출력 의도(sRGB), XMP 메타데이터, 문서 ID를 넣습니다.
+/// 로고는 글꼴을 쓰지 않으므로 글꼴은 건드리지 않고, 원본에 포함되지 않은 글꼴이 있으면 경고로 알립니다.
+pub(crate) fn apply_pdf_a(doc: &mut Document) -> Result<Vec<Message>, Message> {
+    if doc.trailer.get(b"Encrypt").is_ok() {
+        return Err(Message::new("PDF_A_ENCRYPTED"));
+    }
+    let catalog_id = doc
+        .trailer
+        .get(b"Root")
+        .and_then(Object::as_reference)
+        .map_err(|e| Message::new("PDF_CATALOG_NOT_FOUND").arg(e.to_string()))?;
+
+    // PDF/A-2는 PDF 1.7까지를 바탕으로 합니다.
+    if !doc.version.starts_with("1.") {
+        doc.version = "1.7".to_string();
+    }
+
+    let profile = lcms2::Profile::new_srgb()
+        .icc()
+        .map_err(|e| Message::new("SRGB_PROFILE_FAILED").arg(e.to_string()))?;
+    let profile_id = doc.add_object(Stream::new(dictionary! { "N" => 3 }, profile));
+    let intent_id = doc.add_object(dictionary! {
+        "Type" => "OutputIntent",
+        "S" => "GTS_PDFA1",
+        "OutputConditionIdentifier" => Object::string_literal(OUTPUT_CONDITION),
+        "Info" => Object::string_literal(OUTPUT_CONDITION),
+        "DestOutputProfile" => profile_id,
+    });
+
+    let info = sync_info(doc);
+    let xmp = xmp_packet(&info, &iso8601_now());
+    // PDF/A는 메타데이터 스트림을 압축하지 않아야 합니다.
+    let metadata_id = doc.add_object(
+        Stream::new(
+            dictionary! { "Type" => "Metadata", "Subtype" => "XML" },
+            xmp.into_bytes(),
+        )
+        .with_compression(false),
+    );
+
+    let catalog = doc
+        .get_dictionary_mut(catalog_id)
+        .map_err(|e| Message::new("PDF_CATALOG_READ_FAILED").arg(e.to_string()))?;
+    catalog.set("OutputIntents", vec![Object::Reference(intent_id)]);
+    catalog.set("Metadata", metadata_id);
+    name_layer_config(doc, catalog_id);
+
+    if doc.trailer.get(b"ID").is_err() {
+        let id = Object::String(document_id(doc).to_vec(), StringFormat::Hexadecimal);
+        doc.trailer.set("ID", vec![id.clone(), id]);
+    }
+
+    let mut warnings = Vec::new();
+    if has_unembedded_font(doc) {
+        warnings.push(Message::new("PDF_A_FONTS_NOT_EMBEDDED"));
+    }
+    Ok(warnings)
+}
+
+/// XMP에 옮겨 적을 /Info 값입니다.
+#[derive(Default)]
+struct InfoFields {
+    title: Option<String>,
+    author: Option<String>,
+    subject: Option<String>,
+    keywords: Option<String>,
+    creator: Option<String>,
+}
+
+/// /Info의 생산자를 CornerBrand로 바꾸고 날짜 항목을 지운 뒤, XMP에 옮길 값을 읽어 옵니다.
+fn sync_info(doc: &mut Document) -> InfoFields {
+    let info_id = match doc.trailer.get(b"Info").and_then(Object::as_reference) {
+        Ok(id) if doc.get_dictionary(id).is_ok() => id,
+        _ => {
+            let id = doc.add_object(Dictionary::new());
+            doc.trailer.set("Info", id);
+            id
+        }
+    };
+    let Ok(info) = doc.get_dictionary_mut(info_id) else {
+        return InfoFields::default();
+    };
+    for key in INFO_DATE_KEYS {
+        info.remove(key);
+    }
+    info.set("Producer", Object::string_literal(PRODUCER));
+
+    let text = |key: &[u8]| {
+        info.get(key)
+            .ok()
+            .and_then(|value| decode_text_string(value).ok())
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+    InfoFields {
+        title: text(b"Title"),
+        author: text(b"Author"),
+        subject: text(b"Subject"),
+        keywords: text(b"Keywords"),
+        creator: text(b"Creator"),
+    }
+}
+
+fn xmp_packet(info: &InfoFields, now: &str) -> String {
+    let mut properties = String::new();
+    let mut push = |line: String| {
+        properties.push_str(&line);
+        properties.push('\n');
+    };
+    push(format!("<pdfaid:part>{PDFA_PART}</pdfaid:part>"));
+    push(format!(
+        "<pdfaid:conformance>{PDFA_CONFORMANCE}</pdfaid:conformance>"
+    ));
+    push("<dc:format>application/pdf</dc:format>".to_string());
+    push(format!("<pdf:Producer>{PRODUCER}</pdf:Producer>"));
+    push(format!("<xmp:ModifyDate>{now}</xmp:ModifyDate>"));
+    push(format!("<xmp:MetadataDate>{now}</xmp:MetadataDate>"));
+    if let Some(title) = &info.title {
+        push(format!(
+            "<dc:title><rdf:Alt><rdf:li xml:lang=\"x-default\">{}</rdf:li></rdf:Alt></dc:title>",
+            xml_escape(title)
+        ));
+    }
+    if let Some(author) = &info.author {
+        push(format!(
+            "<dc:creator><rdf:Seq><rdf:li>{}</rdf:li></rdf:Seq></dc:creator>",
+            xml_escape(author)
+        ));
+    }
+    if let Some(subject) = &info.subject {
+        push(format!(
+            "<dc:description><rdf:Alt><rdf:li xml:lang=\"x-default\">{}</rdf:li></rdf:Alt></dc:description>",
+            xml_escape(subject)
+        ));
+    }
+    if let Some(keywords) = &info.keywords {
+        push(format!(
+            "<pdf:Keywords>{}</pdf:Keywords>",
+            xml_escape(keywords)
+        ));
+    }
+    if let Some(creator) = &info.creator {
+        push(format!(
+            "<xmp:CreatorTool>{}</xmp:CreatorTool>",
+            xml_escape(creator)
+        ));
+    }
+
+    format!(
+        "<?xpacket begin=\"\u{feff}\" id=\"W5M0MpCehiHzreSzNTczkc9d\"?>\n\
+<x:xmpmeta xmlns:x=\"adobe:ns:meta/\">\n\
+  <rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\n\
+    <rdf:Description rdf:about=\"\"\n\
+        xmlns:pdfaid=\"http://www.aiim.org/pdfa/ns/id/\"\n\
+        xmlns:dc=\"http://purl.org/dc/elements/1.1/\"\n\
+        xmlns:pdf=\"http://ns.adobe.com/pdf/1.3/\"\n\
+        xmlns:xmp=\"http://ns.adobe.com/xap/1.0/\">\n\
+{properties}</rdf:Description>\n\
+  </rdf:RDF>\n\
+</x:xmpmeta>\n\
+<?xpacket end=\"w\"?>"
+    )
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// XMP 날짜 형식(`YYYY-MM-DDTHH:MM:SSZ`)의 현재 UTC 시각입니다.
+fn iso8601_now() -> String {
+    let stamp = path_policy::utc_timestamp(SystemTime::now());
+    // `YYYYMMDD_HHMMSS`를 나눠 다시 씁니다.
+    format!(
+        "{}-{}-{}T{}:{}:{}Z",
+        &stamp[0..4],
+        &stamp[4..6],
+        &stamp[6..8],
+        &stamp[9..11],
+        &stamp[11..13],
+        &stamp[13..15]
+    )
+}
+
+/// PDF/A 선택적 콘텐츠 설정 사전에는 `/Name`이 있어야 합니다. 로고 레이어 등으로 설정이 있으면 채웁니다.
+fn name_layer_config(doc: &mut Document, catalog_id: lopdf::ObjectId) {
+    let properties = match doc
+        .get_dictionary(catalog_id)
+        .and_then(|catalog| catalog.get(b"OCProperties"))
+    {
+        Ok(Object::Reference(id)) => *id,
+        Ok(Object::Dictionary(_)) => catalog_id,
+        _ => return,
+    };
+    let config_owner = if properties == catalog_id {
+        doc.get_dictionary_mut(catalog_id)
+            .and_then(|catalog| catalog.get_mut(b"OCProperties"))
+            .and_then(Object::as_dict_mut)
+    } else {
+        doc.get_dictionary_mut(properties)
+    };
+    let config_ref = match config_owner.and_then(|owner| owner.get_mut(b"D")) {
+        Ok(Object::Dictionary(config)) => {
+            set_default_name(config);
+            return;
+        }
+        Ok(Object::Reference(id)) => *id,
+        _ => return,
+    };
+    if let Ok(config) = doc.get_dictionary_mut(config_ref) {
+        set_default_name(config);
+    }
+}
+
+fn set_default_name(config: &mut Dictionary) {
+    if !config.has(b"Name") {
+        config.set("Name", Object::string_literal("Default"));
+    }
+}
+
+/// 트레일러 `/ID`로 쓰는 16바이트 값입니다. 시각과 문서 구조로 만들어 파일마다 다릅니다.
+fn document_id(doc: &Document) -> [u8; 16] {
+    let mut id = [0_u8; 16];
+    for (half, salt) in id.chunks_mut(8).zip([0_u8, 1]) {
+        let mut hasher = DefaultHasher::new();
+        salt.hash(&mut hasher);
+        SystemTime::now().hash(&mut hasher);
+        doc.max_id.hash(&mut hasher);
+        doc.objects.len().hash(&mut hasher);
+        half.copy_from_slice(&hasher.finish().to_be_bytes());
+    }
+    id
+}
+
+/// 글꼴 설명 사전에 `FontFile`/`FontFile2`/`FontFile3`이 없는 글꼴이 있는지 봅니다. Type3 글꼴은 제외합니다.
+fn has_unembedded_font(doc: &Document) -> bool {
+    doc.objects.values().any(|object| {
+        let Ok(font) = object.as_dict() else {
+            return false;
+        };
+        let is_font = font.get(b"Type").and_then(Object::as_name).ok() == Some(b"Font".as_slice());
+        let subtype = font.get(b"Subtype").and_then(Object::as_name).ok();
+        if !is_font || matches!(subtype, Some(b"Type3") | Some(b"Type0")) {
+            return false;
+        }
+        let descriptor = match font.get(b"FontDescriptor") {
+            Ok(Object::Reference(id)) => doc.get_dictionary(*id).ok(),
+            Ok(Object::Dictionary(descriptor)) => Some(descriptor),
+            _ => None,
+        };
+        descriptor.is_none_or(|descriptor| {
+            !(descriptor.has(b"FontFile")
+                || descriptor.has(b"FontFile2")
+                || descriptor.has(b"FontFile3"))
+        })
+    })
+}
@@ -0,0 +1,275 @@
+use lopdf::content::Operation;
+use lopdf::{Dictionary, Document, Object, ObjectId};
+
+/// 6개 값으로 된 PDF 변환 행렬 `[a b c d e f]`입니다.
+type Matrix = [f64; 6];
+
+const IDENTITY: Matrix = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+
+/// 글꼴 정보를 읽지 않으므로 글자 폭을 글꼴 크기의 절반으로 어림합니다.
+const APPROX_GLYPH_WIDTH: f64 = 0.5;
+
+/// 실제로 칠해진 경로, 이미지/폼 XObject, 텍스트가 차지하는 영역을 어림한 페이지 좌표 상자입니다.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ContentBounds {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// 페이지 콘텐츠 스트림을 훑어 내용이 그려진 영역을 어림합니다.
+/// 스트림을 해석하지 못했거나 그려진 것이 없으면 `None`을 돌려주며, 호출자는 MediaBox를 씁니다.
+pub(crate) fn estimate(doc: &Document, page_id: ObjectId) -> Option<ContentBounds> {
+    let content = doc.get_and_decode_page_content(page_id).ok()?;
+    let xobjects = page_xobjects(doc, page_id);
+
+    let mut state = ScanState::default();
+    for operation in &content.operations {
+        state.apply(doc, xobjects.as_ref(), operation);
+    }
+    state.bounds()
+}
+
+fn page_xobjects(doc: &Document, page_id: ObjectId) -> Option<Dictionary> {
+    let (direct, inherited) = doc.get_page_resources(page_id).ok()?;
+    let resources = direct.into_iter().chain(
+        inherited
+            .iter()
+            .filter_map(|id| doc.get_dictionary(*id).ok()),
+    );
+    for dict in resources {
+        if let Ok(xobjects) = doc.get_dict_in_dict(dict, b"XObject") {
+            return Some(xobjects.clone());
+        }
+    }
+    None
+}
+
+struct ScanState {
+    ctm: Matrix,
+    stack: Vec<Matrix>,
+    path: Vec<(f64, f64)>,
+    text_matrix: Matrix,
+    line_matrix: Matrix,
+    font_size: f64,
+    leading: f64,
+    min: (f64, f64),
+    max: (f64, f64),
+}
+
+impl Default for ScanState {
+    fn default() -> Self {
+        Self {
+            ctm: IDENTITY,
+            stack: Vec::new(),
+            path: Vec::new(),
+            text_matrix: IDENTITY,
+            line_matrix: IDENTITY,
+            font_size: 0.0,
+            leading: 0.0,
+            min: (f64::INFINITY, f64::INFINITY),
+            max: (f64::NEG_INFINITY, f64::NEG_INFINITY),
+        }
+    }
+}
+
+impl ScanState {
+    fn apply(&mut self, doc: &Document, xobjects: Option<&Dictionary>, operation: &Operation) {
+        let numbers: Vec<f64> = operation.operands.iter().filter_map(number).collect();
+        match operation.operator.as_str() {
+            "q" => self.stack.push(self.ctm),
+            "Q" => self.ctm = self.stack.pop().unwrap_or(IDENTITY),
+            "cm" => {
+                if let Some(matrix) = to_matrix(&numbers) {
+                    self.ctm = multiply(&matrix, &self.ctm);
+                }
+            }
+            "m" | "l" | "c" | "v" | "y" => self.push_points(&numbers),
+            "re" => {
+                if let [x, y, w, h] = numbers[..] {
+                    self.push_points(&[x, y, x + w, y + h, x + w, y, x, y + h]);
+                }
+            }
+            "S" | "s" | "f" | "F" | "f*" | "B" | "B*" | "b" | "b*" => {
+                let ctm = self.ctm;
+                for (x, y) in std::mem::take(&mut self.path) {
+                    self.include(&ctm, x, y);
+                }
+            }
+            // 클리핑 전용 경로(`W n`)나 칠하지 않은 경로는 내용으로 보지 않습니다.
+            "n" => self.path.clear(),
+            "Do" => self.include_xobject(doc, xobjects, operation),
+            "BT" => {
+                self.text_matrix = IDENTITY;
+                self.line_matrix = IDENTITY;
+            }
+            "Tf" => {
+                if let Some(size) = numbers.last() {
+                    self.font_size = size.abs();
+                }
+            }
+            "TL" => {
+                if let Some(leading) = numbers.first() {
+                    self.leading = *leading;
+                }
+            }
+            "Tm" => {
+                if let Some(matrix) = to_matrix(&numbers) {
+                    self.text_matrix = matrix;
+                    self.line_matrix = matrix;
+                }
+            }
+            "Td" | "TD" => {
+                if let [tx, ty] = numbers[..] {
+                    if operation.operator == "TD" {
+                        self.leading = -ty;
+                    }
+                    self.next_line(tx, ty);
+                }
+            }
+            "T*" => self.next_line(0.0, -self.leading),
+            "Tj" | "TJ" => self.show_text(&operation.operands),
+            "'" | "\"" => {
+                self.next_line(0.0, -self.leading);
+                self.show_text(&operation.operands);
+            }
+            _ => {}
+        }
+    }
+
+    fn push_points(&mut self, numbers: &[f64]) {
+        self.path
+            .extend(numbers.chunks_exact(2).map(|pair| (pair[0], pair[1])));
+    }
+
+    fn include(&mut self, matrix: &Matrix, x: f64, y: f64) {
+        let (px, py) = transform(matrix, x, y);
+        self.min = (self.min.0.min(px), self.min.1.min(py));
+        self.max = (self.max.0.max(px), self.max.1.max(py));
+    }
+
+    fn include_rect(&mut self, matrix: &Matrix, x0: f64, y0: f64, x1: f64, y1: f64) {
+        for (x, y) in [(x0, y0), (x1, y0), (x0, y1), (x1, y1)] {
+            self.include(matrix, x, y);
+        }
+    }
+
+    /// 이미지는 단위 정사각형, 폼은 `/BBox`에 `/Matrix`를 적용한 영역으로 봅니다.
+    fn include_xobject(
+        &mut self,
+        doc: &Document,
+        xobjects: Option<&Dictionary>,
+        operation: &Operation,
+    ) {
+        let ctm = self.ctm;
+        let form = operation
+            .operands
+            .first()
+            .and_then(|name| name.as_name().ok())
+            .and_then(|name| xobjects?.get(name).ok())
+            .and_then(|object| match object {
+                Object::Reference(id) => doc.get_object(*id).ok(),
+                other => Some(other),
+            })
+            .and_then(|object| object.as_stream().ok())
+            .filter(|stream| {
+                stream
+                    .dict
+                    .get(b"Subtype")
+                    .and_then(Object::as_name)
+                    .is_ok_and(|subtype| subtype == b"Form")
+            });
+
+        let Some(form) = form else {
+            self.include_rect(&ctm, 0.0, 0.0, 1.0, 1.0);
+            return;
+        };
+        let bbox: Vec<f64> = form
+            .dict
+            .get(b"BBox")
+            .and_then(Object::as_array)
+            .map(|items| items.iter().filter_map(number).collect())
+            .unwrap_or_default();
+        let form_matrix: Vec<f64> = form
+            .dict
+            .get(b"Matrix")
+            .and_then(Object::as_array)
+            .map(|items| items.iter().filter_map(number).collect())
+            .unwrap_or_default();
+        let matrix = multiply(&to_matrix(&form_matrix).unwrap_or(IDENTITY), &ctm);
+        if let [x0, y0, x1, y1] = bbox[..] {
+            self.include_rect(&matrix, x0, y0, x1, y1);
+        }
+    }
+
+    fn next_line(&mut self, tx: f64, ty: f64) {
+        self.line_matrix = multiply(&[1.0, 0.0, 0.0, 1.0, tx, ty], &self.line_matrix);
+        self.text_matrix = self.line_matrix;
+    }
+
+    /// 글자 수로 폭을 어림해 (0, 0)~(폭, 글꼴 크기) 상자를 넣고 텍스트 위치를 옮깁니다.
+    fn show_text(&mut self, operands: &[Object]) {
+        let glyphs: usize = operands.iter().map(glyph_count).sum();
+        if glyphs == 0 || self.font_size == 0.0 {
+            return;
+        }
+        let width = glyphs as f64 * self.font_size * APPROX_GLYPH_WIDTH;
+        let matrix = multiply(&self.text_matrix, &self.ctm);
+        self.include_rect(&matrix, 0.0, 0.0, width, self.font_size);
+        self.text_matrix = multiply(&[1.0, 0.0, 0.0, 1.0, width, 0.0], &self.text_matrix);
+    }
+
+    fn bounds(&self) -> Option<ContentBounds> {
+        let width = self.max.0 - self.min.0;
+        let height = self.max.1 - self.min.1;
+        if !(width.is_finite() && height.is_finite()) || width <= 0.0 || height <= 0.0 {
+            return None;
+        }
+        Some(ContentBounds {
+            x: self.min.0,
+            y: self.min.1,
+            width,
+            height,
+        })
+    }
+}
+
+fn glyph_count(object: &Object) -> usize {
+    match object {
+        Object::String(bytes, _) => bytes.len(),
+        Object::Array(items) => items.iter().map(glyph_count).sum(),
+        _ => 0,
+    }
+}
+
+fn number(object: &Object) -> Option<f64> {
+    match object {
+        Object::Integer(v) => Some(*v as f64),
+        Object::Real(v) => Some(f64::from(*v)),
+        _ => None,
+    }
+}
+
+fn to_matrix(numbers: &[f64]) -> Option<Matrix> {
+    numbers.try_into().ok()
+}
+
+/// `left`를 먼저 적용한 뒤 `right`를 적용하는 행렬(`left × right`)입니다.
+fn multiply(left: &Matrix, right: &Matrix) -> Matrix {
+    let [a1, b1, c1, d1, e1, f1] = *left;
+    let [a2, b2, c2, d2, e2, f2] = *right;
+    [
+        a1 * a2 + b1 * c2,
+        a1 * b2 + b1 * d2,
+        c1 * a2 + d1 * c2,
+        c1 * b2 + d1 * d2,
+        e1 * a2 + f1 * c2 + e2,
+        e1 * b2 + f1 * d2 + f2,
+    ]
+}
+
+fn transform(matrix: &Matrix, x: f64, y: f64) -> (f64, f64) {
+    let [a, b, c, d, e, f] = *matrix;
+    (a * x + c * y + e, b * x + d * y + f)
+}
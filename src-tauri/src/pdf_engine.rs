@@ -1,61 +1,265 @@
-use crate::image_engine::{StampFileResult, StampSettingsInput};
-use crate::path_policy;
+use crate::banner;
+use crate::cancellation::CancellationToken;
+use crate::i18n::Message;
+use crate::image_engine::{self, CornerPosition, StampFileResult, StampSettingsInput};
+use crate::jitter::{Jitter, JitterRecord};
+use crate::metadata;
+use crate::path_policy::{self, OutputNaming, OutputTarget};
+use crate::pdf_a;
+use crate::pdf_content_bounds;
+use crate::pdf_flatten;
+use crate::pdf_layer;
+use crate::retry::RetryPolicy;
+use crate::svg_logo;
+use image::imageops::FilterType;
 use image::{DynamicImage, GenericImageView, ImageBuffer, ImageFormat, Rgb};
-use lopdf::{Dictionary, Document, Object, ObjectId};
+use lopdf::content::{Content, Operation};
+use lopdf::{dictionary, Dictionary, Document, Object, ObjectId};
+use std::collections::BTreeMap;
 use std::io::Cursor;
 use std::path::{Path, PathBuf};
 
-#[derive(Debug, Clone, Copy)]
-enum CornerPosition {
-    TopLeft,
-    TopRight,
-    BottomLeft,
-    BottomRight,
+/// 로고를 넣을 페이지 범위입니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PageSelection {
+    All,
+    First,
+    Last,
+}
+
+impl PageSelection {
+    fn parse(value: Option<&str>) -> Result<Self, Message> {
+        match value.map(str::trim) {
+            None | Some("") | Some("all") => Ok(Self::All),
+            Some("first") => Ok(Self::First),
+            Some("last") => Ok(Self::Last),
+            Some(_) => Err(Message::new("INVALID_PAGE_SELECTION")),
+        }
+    }
+
+    fn count(self, total_pages: u32) -> u32 {
+        match self {
+            Self::All => total_pages,
+            Self::First | Self::Last => total_pages.min(1),
+        }
+    }
+
+    fn includes(self, page_number: u32, total_pages: u32) -> bool {
+        match self {
+            Self::All => true,
+            Self::First => page_number == 1,
+            Self::Last => page_number == total_pages,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 struct StampSettings {
     position: CornerPosition,
+    pages: PageSelection,
+    /// 미리보기처럼 한 페이지만 볼 때 그 페이지(1부터)에만 로고를 넣습니다. `pages` 선택과 함께 적용됩니다.
+    only_page: Option<u32>,
+    page_positions: BTreeMap<u32, CornerPosition>,
+    mirror_even_pages: bool,
     size_ratio: f64,
-    margin_percent: f64,
+    size_mode: image_engine::SizeMode,
+    /// 지정하면 `size_ratio` 대신 로고 긴 변을 이 길이(pt)로 그립니다.
+    physical_points: Option<f64>,
+    fit_box: Option<image_engine::FitBox>,
+    margins: image_engine::Margins,
+    margin_mode: image_engine::MarginMode,
+    logo_style: image_engine::LogoStyle,
+    /// 로고 알파를 합성할 바탕색입니다. PDF 로고 XObject는 투명도 없이 넣기 때문입니다.
+    matte_color: [u8; 3],
+    jitter: Option<Jitter>,
+    flatten: bool,
+    embed_metadata: Option<String>,
+    continue_on_page_error: bool,
+    clamp_warnings: Vec<Message>,
+    backup_originals: bool,
+    resize_filter: FilterType,
+    as_layer: bool,
+    pdf_a: bool,
+    skip_already_stamped: bool,
+    no_upscale: bool,
+    anchor_to_content: bool,
+    anchor: image_engine::LogoAnchor,
+    naming: OutputNaming,
+    /// 배너 모드일 때의 로고 불투명도입니다. 비어 있으면 모서리에 넣습니다.
+    banner_opacity: Option<f64>,
+    retry: RetryPolicy,
+}
+
+impl StampSettings {
+    /// 페이지별 지정 위치가 있으면 그 값을, 없으면 기본 위치(짝수 페이지 좌우 반전 적용)를 씁니다.
+    fn position_for_page(&self, page_number: u32) -> CornerPosition {
+        if let Some(position) = self.page_positions.get(&page_number) {
+            return *position;
+        }
+        if self.mirror_even_pages && page_number.is_multiple_of(2) {
+            return self.position.mirrored();
+        }
+        self.position
+    }
+
+    fn stamps_page(&self, page_number: u32, total_pages: u32) -> bool {
+        self.pages.includes(page_number, total_pages)
+            && self.only_page.is_none_or(|only| only == page_number)
+    }
+
+    /// 로고를 넣으려는 페이지 수입니다.
+    fn target_page_count(&self, total_pages: u32) -> u32 {
+        match self.only_page {
+            Some(page) => u32::from(page <= total_pages && self.stamps_page(page, total_pages)),
+            None => self.pages.count(total_pages),
+        }
+    }
+}
+
+enum PdfStampResult {
+    Stamped(PdfStampOutcome),
+    Skipped {
+        code: &'static str,
+        message: Message,
+    },
+}
+
+struct PdfStampOutcome {
+    output_path: PathBuf,
+    jitter: Option<JitterRecord>,
+    warnings: Vec<Message>,
+}
+
+/// 결과로 옮길 페이지별 기록(흔들림 오프셋, 건너뛴 페이지)입니다.
+#[derive(Default)]
+struct PageLog {
+    jitter_offsets: Vec<[i32; 2]>,
+    warnings: Vec<Message>,
+    /// `no_upscale` 때문에 한 페이지라도 요청보다 작게 넣었는지 여부입니다.
+    upscale_capped: bool,
+}
+
+/// 페이지 하나에 로고를 넣은 결과입니다.
+#[derive(Default)]
+struct PageStamp {
+    offset: Option<[i32; 2]>,
+    upscale_capped: bool,
+}
+
+/// 페이지(또는 내용 영역) 좌표계에서 로고를 그릴 사각형(pt)입니다.
+#[derive(Debug, Clone, Copy)]
+struct LogoRect {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    /// `no_upscale`로 요청한 크기까지 키우지 않았으면 `true`입니다.
+    upscale_capped: bool,
+}
+
+impl PageLog {
+    /// 한 페이지의 처리 결과를 반영합니다. `continue_on_page_error`가 아니면 첫 오류에서 멈춥니다.
+    fn record_page(
+        &mut self,
+        page_number: u32,
+        outcome: Result<PageStamp, Message>,
+        settings: &StampSettings,
+    ) -> Result<(), Message> {
+        match outcome {
+            Ok(stamp) => {
+                self.jitter_offsets.extend(stamp.offset);
+                self.upscale_capped |= stamp.upscale_capped;
+            }
+            Err(error) if settings.continue_on_page_error => self.warnings.push(
+                Message::new("PAGE_SKIPPED")
+                    .arg(page_number.to_string())
+                    .arg(error),
+            ),
+            Err(error) => return Err(error),
+        }
+        Ok(())
+    }
+
+    /// `target_pages`는 로고를 넣으려 한 페이지 수입니다. 그 페이지가 모두 실패했으면 오류입니다.
+    fn finish(self, target_pages: u32) -> Result<Self, Message> {
+        if target_pages > 0 && self.warnings.len() == target_pages as usize {
+            return Err(Message::new("ALL_PAGES_FAILED").arg(Message::List(self.warnings.clone())));
+        }
+        Ok(self)
+    }
 }
 
 impl TryFrom<StampSettingsInput> for StampSettings {
-    type Error = String;
+    type Error = Message;
 
     fn try_from(value: StampSettingsInput) -> Result<Self, Self::Error> {
-        let position = match value.position.as_str() {
-            "좌상단" => CornerPosition::TopLeft,
-            "우상단" => CornerPosition::TopRight,
-            "좌하단" => CornerPosition::BottomLeft,
-            "우하단" => CornerPosition::BottomRight,
-            _ => return Err("유효하지 않은 위치 값입니다.".to_string()),
-        };
+        let position = image_engine::parse_position(&value.position)?;
 
-        let size_ratio = if let Some(size_percent) = value.size_percent {
-            if size_percent.is_finite() {
-                f64::from(size_percent.clamp(1.0, 50.0) / 100.0)
-            } else {
-                match value.size_preset.as_str() {
-                    "작음" => 0.08,
-                    "보통" => 0.12,
-                    "큼" => 0.16,
-                    _ => return Err("유효하지 않은 크기 프리셋입니다.".to_string()),
-                }
-            }
-        } else {
-            match value.size_preset.as_str() {
-                "작음" => 0.08,
-                "보통" => 0.12,
-                "큼" => 0.16,
-                _ => return Err("유효하지 않은 크기 프리셋입니다.".to_string()),
+        let mut page_positions = BTreeMap::new();
+        for (page_number, page_position) in value.pdf_page_positions.iter().flatten() {
+            if *page_number == 0 {
+                return Err(Message::new("PAGE_NUMBER_ZERO"));
             }
-        };
+            page_positions.insert(*page_number, image_engine::parse_position(page_position)?);
+        }
+
+        let logo_style = image_engine::LogoStyle::from_input(&value);
+        let clamp_warnings = image_engine::clamp_warnings(&value)?;
+        let jitter = Jitter::from_input(&value)?;
+        let size_mode = image_engine::parse_size_mode(value.size_mode.as_deref())?;
+        let size_ratio = f64::from(image_engine::resolve_size_ratio(
+            value.size_percent,
+            &value.size_preset,
+            size_mode,
+        )?);
 
         Ok(Self {
             position,
+            pages: PageSelection::parse(value.pdf_pages.as_deref())?,
+            only_page: None,
+            page_positions,
+            mirror_even_pages: value.mirror_even_pages,
             size_ratio,
-            margin_percent: f64::from(value.margin_percent.clamp(0.0, 20.0)),
+            size_mode,
+            physical_points: value
+                .physical_size
+                .map(|size| size.validated().map(|size| f64::from(size.inches) * 72.0))
+                .transpose()?,
+            fit_box: value
+                .fit_box
+                .map(image_engine::FitBox::validated)
+                .transpose()?,
+            margins: image_engine::Margins::from_input(&value),
+            margin_mode: image_engine::parse_margin_mode(value.margin_mode.as_deref())?,
+            logo_style,
+            matte_color: value.pdf_matte_color.unwrap_or([255, 255, 255]),
+            jitter,
+            flatten: value.pdf_flatten,
+            embed_metadata: image_engine::normalize_metadata(value.embed_metadata),
+            continue_on_page_error: value.continue_on_page_error,
+            clamp_warnings,
+            backup_originals: value.backup_originals,
+            resize_filter: image_engine::parse_resize_filter(value.resize_filter.as_deref())?,
+            as_layer: value.pdf_as_layer,
+            pdf_a: value.pdf_a,
+            skip_already_stamped: value.skip_already_stamped,
+            no_upscale: value.no_upscale,
+            anchor_to_content: value.anchor_to_content,
+            anchor: image_engine::parse_anchor(value.anchor.as_deref())?,
+            naming: OutputNaming::from_input(
+                value.preserve_extension_case,
+                value.collision_strategy.as_deref(),
+            )?,
+            banner_opacity: value.banner_mode.then(|| {
+                f64::from(
+                    value
+                        .opacity
+                        .filter(|v| v.is_finite())
+                        .unwrap_or(banner::BANNER_OPACITY)
+                        .clamp(0.0, 1.0),
+                )
+            }),
+            retry: RetryPolicy::from_input(value.io_retry_attempts, value.io_retry_backoff_ms),
         })
     }
 }
@@ -66,22 +270,130 @@ pub fn stamp_pdfs(
     logo_path: &Path,
     output_base_dir: Option<&Path>,
 ) -> Vec<StampFileResult> {
+    stamp_pdfs_with_page_progress(
+        paths,
+        settings_input,
+        logo_path,
+        output_base_dir,
+        None,
+        &mut |_, _, _| {},
+    )
+}
+
+/// `stamp_pdfs`와 같지만 페이지 하나를 끝낼 때마다 `(입력 경로, 현재 페이지, 전체 페이지)`를 알립니다.
+/// `cancel`이 취소되면 결과 파일을 쓰지 않고 실패합니다(`image_engine::stamp_images_with_cancel` 참고).
+pub fn stamp_pdfs_with_page_progress(
+    paths: &[String],
+    settings_input: StampSettingsInput,
+    logo_path: &Path,
+    output_base_dir: Option<&Path>,
+    cancel: Option<&CancellationToken>,
+    on_page: &mut dyn FnMut(&str, u32, u32),
+) -> Vec<StampFileResult> {
+    stamp_pdfs_to(
+        paths,
+        settings_input,
+        logo_path,
+        OutputTarget::Auto(output_base_dir),
+        cancel,
+        on_page,
+    )
+}
+
+/// PDF 하나를 자동 이름 대신 `output_path`에 그대로 저장합니다. 파일이 이미 있으면 실패합니다.
+pub fn stamp_pdf_to_path(
+    input: &str,
+    settings_input: StampSettingsInput,
+    logo_path: &Path,
+    output_path: &Path,
+) -> StampFileResult {
+    let paths = [input.to_string()];
+    stamp_pdfs_to(
+        &paths,
+        settings_input,
+        logo_path,
+        OutputTarget::Exact(output_path),
+        None,
+        &mut |_, _, _| {},
+    )
+    .remove(0)
+}
+
+/// 미리보기용으로 `page_number`(1부터) 페이지에만 로고를 넣어 `output_path`에 저장합니다. 다른 페이지는 그대로 둡니다.
+/// 평면화 설정이어도 페이지를 이미지로 바꾸지 않고 로고를 벡터로 넣습니다. 로고 위치와 크기는 같습니다.
+pub fn stamp_pdf_page_to_path(
+    input: &str,
+    page_number: u32,
+    settings_input: StampSettingsInput,
+    logo_path: &Path,
+    output_path: &Path,
+) -> StampFileResult {
     let settings = match StampSettings::try_from(settings_input) {
-        Ok(settings) => settings,
-        Err(err) => {
-            return paths
-                .iter()
-                .map(|path| failure_result(path.clone(), err.clone()))
-                .collect();
-        }
+        Ok(settings) => StampSettings {
+            only_page: Some(page_number),
+            flatten: false,
+            ..settings
+        },
+        Err(err) => return failure_result(input.to_string(), err),
     };
+    let paths = [input.to_string()];
+    stamp_pdfs_with_settings(
+        &paths,
+        settings,
+        logo_path,
+        OutputTarget::Exact(output_path),
+        &mut |_, _, _| {},
+    )
+    .remove(0)
+}
+
+fn stamp_pdfs_to(
+    paths: &[String],
+    settings_input: StampSettingsInput,
+    logo_path: &Path,
+    target: OutputTarget,
+    cancel: Option<&CancellationToken>,
+    on_page: &mut dyn FnMut(&str, u32, u32),
+) -> Vec<StampFileResult> {
+    match StampSettings::try_from(settings_input) {
+        Ok(settings) => {
+            let settings = StampSettings {
+                retry: settings.retry.with_cancel(cancel.cloned()),
+                ..settings
+            };
+            stamp_pdfs_with_settings(paths, settings, logo_path, target, on_page)
+        }
+        Err(err) => paths
+            .iter()
+            .map(|path| failure_result(path.clone(), err.clone()))
+            .collect(),
+    }
+}
 
-    let logo_stream = match build_logo_stream(logo_path) {
+fn stamp_pdfs_with_settings(
+    paths: &[String],
+    settings: StampSettings,
+    logo_path: &Path,
+    target: OutputTarget,
+    on_page: &mut dyn FnMut(&str, u32, u32),
+) -> Vec<StampFileResult> {
+    let guard = image_engine::LogoInputGuard::new(logo_path);
+    let logo_stream = match build_logo_stream(
+        logo_path,
+        &settings.logo_style,
+        settings.size_mode,
+        settings.matte_color,
+        &settings.retry,
+    ) {
         Ok(stream) => stream,
         Err(err) => {
             return paths
                 .iter()
-                .map(|path| failure_result(path.clone(), err.clone()))
+                .map(|path| {
+                    guard
+                        .check(path)
+                        .unwrap_or_else(|| failure_result(path.clone(), err.clone()))
+                })
                 .collect();
         }
     };
@@ -89,13 +401,35 @@ pub fn stamp_pdfs(
     paths
         .iter()
         .map(|input| {
+            if let Some(result) = guard.check(input) {
+                return result;
+            }
             let input_path = Path::new(input);
-            match stamp_single_pdf(input_path, &settings, &logo_stream, output_base_dir) {
-                Ok(output_path) => StampFileResult {
+            let mut on_input_page = |current, total| on_page(input, current, total);
+            match stamp_single_pdf(
+                input_path,
+                &settings,
+                &logo_stream,
+                target,
+                &mut on_input_page,
+            ) {
+                Ok(PdfStampResult::Stamped(PdfStampOutcome {
+                    output_path,
+                    jitter,
+                    warnings,
+                })) => StampFileResult {
                     input_path: input.clone(),
                     ok: true,
                     output_path: Some(output_path.to_string_lossy().to_string()),
                     error: None,
+                    warnings,
+                    jitter,
+                    ..Default::default()
+                },
+                Ok(PdfStampResult::Skipped { code, message }) => StampFileResult {
+                    code: Some(code.to_string()),
+                    skipped: true,
+                    ..failure_result(input.clone(), message)
                 },
                 Err(error) => failure_result(input.clone(), error),
             }
@@ -107,68 +441,535 @@ fn stamp_single_pdf(
     input_path: &Path,
     settings: &StampSettings,
     logo_stream: &[u8],
-    output_base_dir: Option<&Path>,
-) -> Result<PathBuf, String> {
+    target: OutputTarget,
+    on_page: &mut dyn FnMut(u32, u32),
+) -> Result<PdfStampResult, Message> {
     if !path_policy::is_supported_pdf(input_path) {
-        return Err("지원하지 않는 PDF 형식입니다. (.pdf)".to_string());
+        return Err(Message::new("UNSUPPORTED_PDF_TYPE"));
+    }
+    if settings.skip_already_stamped {
+        let bytes = settings
+            .retry
+            .read(input_path)
+            .map_err(|e| Message::new("PDF_READ_FAILED").arg(e.to_string()))?;
+        let doc = Document::load_mem(&bytes)
+            .map_err(|e| Message::new("PDF_READ_FAILED").arg(e.to_string()))?;
+        if has_cornerbrand_marker(&doc) {
+            return Ok(PdfStampResult::Skipped {
+                code: image_engine::CODE_ALREADY_STAMPED,
+                message: Message::new("ALREADY_STAMPED"),
+            });
+        }
+    }
+
+    let (
+        mut doc,
+        PageLog {
+            jitter_offsets,
+            mut warnings,
+            upscale_capped,
+        },
+    ) = if settings.flatten {
+        stamp_flattened_pages(input_path, settings, logo_stream, on_page)?
+    } else {
+        stamp_vector_pages(input_path, settings, logo_stream, on_page)?
+    };
+    if upscale_capped {
+        warnings.push(Message::new("PDF_LOGO_NOT_UPSCALED"));
+    }
+    if settings.flatten && settings.as_layer {
+        warnings.push(Message::new("FLATTENED_NO_LAYER"));
     }
+    if let Some(text) = settings.embed_metadata.as_deref() {
+        metadata::set_pdf_info(&mut doc, text);
+    }
+    if settings.pdf_a {
+        warnings.extend(pdf_a::apply_pdf_a(&mut doc)?);
+    }
+
+    let output = target.pdf_path(input_path, settings.backup_originals, settings.naming)?;
+    let mut bytes = Vec::new();
+    let saved = doc
+        .save_to(&mut bytes)
+        .and_then(|()| settings.retry.write(&output.path, &bytes));
+    if let Err(e) = saved {
+        output.discard();
+        return Err(Message::new("PDF_SAVE_FAILED").arg(e));
+    }
+
+    Ok(PdfStampResult::Stamped(PdfStampOutcome {
+        output_path: output.path,
+        jitter: settings.jitter.map(|jitter| JitterRecord {
+            seed: jitter.seed,
+            offsets: jitter_offsets,
+        }),
+        warnings: settings
+            .clamp_warnings
+            .iter()
+            .cloned()
+            .chain(warnings)
+            .collect(),
+    }))
+}
+
+/// 앞선 실행이 남긴 표시(/Info의 `CornerBrand` 항목, 로고 레이어 OCG, 로고 XObject의 `CornerBrand` 키)를 찾습니다.
+fn has_cornerbrand_marker(doc: &Document) -> bool {
+    let info_marked = doc
+        .trailer
+        .get(b"Info")
+        .and_then(|info| match info {
+            Object::Reference(id) => doc.get_dictionary(*id),
+            other => other.as_dict(),
+        })
+        .is_ok_and(|info| info.has(metadata::METADATA_KEY.as_bytes()));
 
-    let mut doc = Document::load(input_path).map_err(|e| format!("PDF를 읽지 못했습니다: {e}"))?;
+    info_marked
+        || doc.objects.values().any(|object| {
+            let dict = match object {
+                Object::Dictionary(dict) => dict,
+                Object::Stream(stream) => &stream.dict,
+                _ => return false,
+            };
+            let layer = dict.has_type(b"OCG")
+                && dict
+                    .get(b"Name")
+                    .and_then(Object::as_str)
+                    .is_ok_and(|name| name == pdf_layer::WATERMARK_LAYER_NAME.as_bytes());
+            layer || dict.has(metadata::METADATA_KEY.as_bytes())
+        })
+}
+
+/// 로고를 넣지 않고 페이지 수와 첫 페이지 크기(pt)만 읽습니다.
+pub fn probe_pdf(input_path: &Path) -> Result<(u32, (f64, f64)), Message> {
+    let doc = Document::load(input_path)
+        .map_err(|e| Message::new("PDF_READ_FAILED").arg(e.to_string()))?;
+    let pages = doc.get_pages();
+    let (first_number, first_id) = pages
+        .iter()
+        .next()
+        .ok_or_else(|| Message::new("PDF_NO_PAGES"))?;
+    let first_size = resolve_page_size(&doc, *first_id, *first_number)?;
+    Ok((pages.len() as u32, first_size))
+}
+
+/// 원본 페이지 내용은 그대로 두고 로고 이미지 XObject만 덧붙입니다.
+fn stamp_vector_pages(
+    input_path: &Path,
+    settings: &StampSettings,
+    logo_stream: &[u8],
+    on_page: &mut dyn FnMut(u32, u32),
+) -> Result<(Document, PageLog), Message> {
+    let bytes = settings
+        .retry
+        .read(input_path)
+        .map_err(|e| Message::new("PDF_READ_FAILED").arg(e.to_string()))?;
+    let mut doc = Document::load_mem(&bytes)
+        .map_err(|e| Message::new("PDF_READ_FAILED").arg(e.to_string()))?;
     let pages = doc.get_pages();
     if pages.is_empty() {
-        return Err("페이지가 없는 PDF 파일입니다.".to_string());
+        return Err(Message::new("PDF_NO_PAGES"));
     }
 
+    let total_pages = pages.len() as u32;
+    let jitter_key = input_path.to_string_lossy();
+    let mut page_log = PageLog::default();
+    let layer = if settings.as_layer {
+        Some(pdf_layer::add_watermark_layer(&mut doc)?)
+    } else {
+        None
+    };
+    let shared = add_shared_logo(&mut doc, settings, logo_stream, layer)?;
+
     for (page_number, page_id) in pages {
-        let (page_width, page_height) = resolve_page_size(&doc, page_id, page_number)?;
-        let (x, y, draw_width, draw_height) =
-            compute_logo_rect(page_width, page_height, settings, logo_stream)?;
+        if !settings.stamps_page(page_number, total_pages) {
+            on_page(page_number, total_pages);
+            continue;
+        }
+        let outcome = stamp_vector_page(
+            &mut doc,
+            page_id,
+            page_number,
+            settings,
+            &jitter_key,
+            shared,
+        );
+        page_log.record_page(page_number, outcome, settings)?;
+        on_page(page_number, total_pages);
+    }
 
-        let img = lopdf::xobject::image_from(logo_stream.to_vec())
-            .map_err(|e| format!("로고 XObject 생성에 실패했습니다: {e}"))?;
+    Ok((
+        doc,
+        page_log.finish(settings.target_page_count(total_pages))?,
+    ))
+}
 
-        doc.insert_image(
-            page_id,
-            img,
-            (x as f32, y as f32),
-            (draw_width as f32, draw_height as f32),
-        )
-        .map_err(|e| format!("페이지 {page_number}에 로고 삽입 실패: {e}"))?;
+/// 모든 페이지가 함께 참조하는 로고 이미지 XObject와 배너용 그래픽 상태, 로고의 픽셀 크기입니다.
+#[derive(Debug, Clone, Copy)]
+struct SharedLogo {
+    image: ObjectId,
+    graphics_state: Option<ObjectId>,
+    size: (u32, u32),
+}
+
+/// 로고 이미지를 문서에 한 번만 넣습니다. 페이지마다 새로 넣으면 페이지 수만큼 파일이 커집니다.
+/// 크기도 여기서 한 번만 읽어 페이지마다 로고를 다시 디코딩하지 않습니다.
+fn add_shared_logo(
+    doc: &mut Document,
+    settings: &StampSettings,
+    logo_stream: &[u8],
+    layer: Option<ObjectId>,
+) -> Result<SharedLogo, Message> {
+    let size = logo_dimensions(logo_stream)?;
+    let mut img = lopdf::xobject::image_from(logo_stream.to_vec())
+        .map_err(|e| Message::new("LOGO_XOBJECT_FAILED").arg(e.to_string()))?;
+    // 다시 넣기를 막는 표시입니다. 뷰어는 모르는 키를 무시합니다.
+    img.dict.set(metadata::METADATA_KEY, true);
+    if let Some(layer) = layer {
+        // 이미지 XObject의 /OC로 레이어에 묶으면 콘텐츠 스트림을 고치지 않아도 켜고 끌 수 있습니다.
+        img.dict.set("OC", layer);
+    }
+    // 로고는 흰 바탕으로 평탄화돼 있으므로 배너는 곱하기 혼합으로 바탕이 본문을 가리지 않게 합니다.
+    let graphics_state = settings.banner_opacity.map(|opacity| {
+        doc.add_object(dictionary! {
+            "Type" => "ExtGState",
+            "ca" => opacity as f32,
+            "CA" => opacity as f32,
+            "BM" => "Multiply",
+        })
+    });
+    Ok(SharedLogo {
+        image: doc.add_object(img),
+        graphics_state,
+        size,
+    })
+}
+
+fn logo_dimensions(logo_stream: &[u8]) -> Result<(u32, u32), Message> {
+    let (logo_w, logo_h) = image::load_from_memory(logo_stream)
+        .map_err(|e| Message::new("LOGO_SIZE_FAILED").arg(e.to_string()))?
+        .dimensions();
+    if logo_w == 0 || logo_h == 0 {
+        return Err(Message::new("LOGO_SIZE_INVALID"));
+    }
+    Ok((logo_w, logo_h))
+}
+
+/// 페이지 하나에 공유 로고 XObject를 그리고, 흔들림을 적용했다면 그 오프셋을 돌려줍니다.
+fn stamp_vector_page(
+    doc: &mut Document,
+    page_id: ObjectId,
+    page_number: u32,
+    settings: &StampSettings,
+    jitter_key: &str,
+    shared: SharedLogo,
+) -> Result<PageStamp, Message> {
+    let (page_width, page_height) = resolve_page_size(doc, page_id, page_number)?;
+    let (origin_x, origin_y, box_width, box_height) = if settings.anchor_to_content {
+        content_anchor_box(doc, page_id, page_width, page_height)
+    } else {
+        (0.0, 0.0, page_width, page_height)
+    };
+
+    if settings.banner_opacity.is_some() {
+        let matrix = banner_matrix((origin_x, origin_y, box_width, box_height), shared.size);
+        insert_logo_xobject(doc, page_id, shared, matrix).map_err(|e| {
+            Message::new("PAGE_STAMP_FAILED")
+                .arg(page_number.to_string())
+                .arg(e.to_string())
+        })?;
+        return Ok(PageStamp::default());
+    }
+
+    let position = settings.position_for_page(page_number);
+    let rect = compute_logo_rect(box_width, box_height, position, settings, shared.size)?;
+    let (mut x, mut y, draw_width, draw_height) = (rect.x, rect.y, rect.width, rect.height);
+
+    let mut offset = None;
+    if let Some(jitter) = settings.jitter {
+        let delta = jitter.offset(jitter_key, page_number, box_width.min(box_height));
+        let applied;
+        (x, y, applied) = apply_jitter(
+            (x, y),
+            delta,
+            (box_width - draw_width, box_height - draw_height),
+        );
+        offset = Some(applied);
+    }
+    x += origin_x;
+    y += origin_y;
+
+    let matrix = [draw_width, 0.0, 0.0, draw_height, x, y];
+    insert_logo_xobject(doc, page_id, shared, matrix).map_err(|e| {
+        Message::new("PAGE_STAMP_FAILED")
+            .arg(page_number.to_string())
+            .arg(e.to_string())
+    })?;
+
+    Ok(PageStamp {
+        offset,
+        upscale_capped: rect.upscale_capped,
+    })
+}
+
+/// 로고 위치 `(x, y)`를 `(dx, dy)`만큼 옮기되 `0..=max`를 넘지 않게 자르고, 자른 뒤 실제로 옮긴 거리를 함께 돌려줍니다.
+fn apply_jitter(
+    (x, y): (f64, f64),
+    (dx, dy): (i32, i32),
+    (max_x, max_y): (f64, f64),
+) -> (f64, f64, [i32; 2]) {
+    let (max_x, max_y) = (max_x.max(0.0), max_y.max(0.0));
+    let jittered_x = (x + f64::from(dx)).clamp(0.0, max_x);
+    let jittered_y = (y + f64::from(dy)).clamp(0.0, max_y);
+    let applied = [
+        (jittered_x - x.clamp(0.0, max_x)).round() as i32,
+        (jittered_y - y.clamp(0.0, max_y)).round() as i32,
+    ];
+    (jittered_x, jittered_y, applied)
+}
+
+/// 기존 콘텐츠를 `q ... Q`로 감싸고 그 뒤에 로고를 `matrix`(단위 정사각형 → 페이지 좌표)로 그립니다.
+/// 일부 생성기가 `cm`을 되돌리지 않거나 `q`를 닫지 않은 채 끝내도, 로고는 항상 페이지 기본 좌표계에 놓입니다.
+fn insert_logo_xobject(
+    doc: &mut Document,
+    page_id: ObjectId,
+    shared: SharedLogo,
+    matrix: [f64; 6],
+) -> Result<(), lopdf::Error> {
+    let img_name = format!("X{}", shared.image.0);
+    doc.add_xobject(page_id, img_name.as_bytes(), shared.image)?;
+    let graphics_state = match shared.graphics_state {
+        Some(state_id) => {
+            let state_name = format!("GS{}", state_id.0);
+            doc.add_graphics_state(page_id, state_name.as_bytes(), state_id)?;
+            Some(state_name)
+        }
+        None => None,
+    };
+
+    let content = doc.get_and_decode_page_content(page_id)?;
+    let mut depth = 0i64;
+    let mut lowest = 0i64;
+    for operation in &content.operations {
+        match operation.operator.as_str() {
+            "q" => depth += 1,
+            "Q" => {
+                depth -= 1;
+                lowest = lowest.min(depth);
+            }
+            _ => {}
+        }
+    }
+
+    // 짝이 안 맞는 `Q`가 바깥 상태를 꺼내지 않도록 앞에 `q`를 더 두고, 열린 채 끝난 `q`는 모두 닫습니다.
+    let opening = 1 + (-lowest) as usize;
+    let closing = 1 + (depth - lowest).max(0) as usize;
+    let mut operations = Vec::with_capacity(content.operations.len() + opening + closing + 4);
+    operations.extend((0..opening).map(|_| Operation::new("q", vec![])));
+    operations.extend(content.operations);
+    operations.extend((0..closing).map(|_| Operation::new("Q", vec![])));
+    operations.push(Operation::new("q", vec![]));
+    if let Some(state_name) = graphics_state {
+        operations.push(Operation::new(
+            "gs",
+            vec![Object::Name(state_name.into_bytes())],
+        ));
+    }
+    operations.extend([
+        Operation::new(
+            "cm",
+            matrix.iter().map(|value| (*value as f32).into()).collect(),
+        ),
+        Operation::new("Do", vec![Object::Name(img_name.into_bytes())]),
+        Operation::new("Q", vec![]),
+    ]);
+
+    let encoded = Content { operations }.encode()?;
+    doc.change_page_content(page_id, encoded)
+}
+
+/// 로고를 `BANNER_ANGLE_DEGREES`만큼 돌려 상자 가운데에 두는 `cm` 행렬입니다.
+/// 돌린 로고의 외곽 상자가 `BANNER_SPAN` 안에 들어가도록 크기를 정합니다.
+fn banner_matrix(
+    (origin_x, origin_y, box_width, box_height): (f64, f64, f64, f64),
+    (logo_w, logo_h): (u32, u32),
+) -> [f64; 6] {
+    let (sin, cos) = banner::BANNER_ANGLE_DEGREES.to_radians().sin_cos();
+    let (logo_w, logo_h) = (f64::from(logo_w), f64::from(logo_h));
+    let rotated = (logo_w * cos + logo_h * sin, logo_w * sin + logo_h * cos);
+    let scale = banner::fit_scale((box_width, box_height), rotated);
+    let (width, height) = (logo_w * scale, logo_h * scale);
+
+    let (a, b, c, d) = (width * cos, width * sin, -height * sin, height * cos);
+    let center_x = origin_x + box_width / 2.0;
+    let center_y = origin_y + box_height / 2.0;
+    [
+        a,
+        b,
+        c,
+        d,
+        center_x - (a + c) / 2.0,
+        center_y - (b + d) / 2.0,
+    ]
+}
+
+/// 콘텐츠 영역을 페이지 안으로 잘라 (x, y, 폭, 높이)로 돌려줍니다.
+/// 영역을 어림하지 못했거나 너무 작으면 MediaBox 전체를 씁니다.
+fn content_anchor_box(
+    doc: &Document,
+    page_id: ObjectId,
+    page_width: f64,
+    page_height: f64,
+) -> (f64, f64, f64, f64) {
+    let page_box = (0.0, 0.0, page_width, page_height);
+    let Some(bounds) = pdf_content_bounds::estimate(doc, page_id) else {
+        return page_box;
+    };
+    let x0 = bounds.x.clamp(0.0, page_width);
+    let y0 = bounds.y.clamp(0.0, page_height);
+    let x1 = (bounds.x + bounds.width).clamp(0.0, page_width);
+    let y1 = (bounds.y + bounds.height).clamp(0.0, page_height);
+    if x1 - x0 < 1.0 || y1 - y0 < 1.0 {
+        return page_box;
+    }
+    (x0, y0, x1 - x0, y1 - y0)
+}
+
+/// 페이지를 렌더링해 로고를 픽셀로 합성하고, 페이지마다 이미지 한 장인 새 PDF를 만듭니다.
+fn stamp_flattened_pages(
+    input_path: &Path,
+    settings: &StampSettings,
+    logo_stream: &[u8],
+    on_page: &mut dyn FnMut(u32, u32),
+) -> Result<(Document, PageLog), Message> {
+    let mut pages = pdf_flatten::render_pages(input_path, pdf_flatten::FLATTEN_DPI)?;
+    if pages.is_empty() {
+        return Err(Message::new("PDF_NO_PAGES"));
     }
+    let page_log = stamp_raster_pages(&mut pages, input_path, settings, logo_stream, on_page)?;
+    Ok((pdf_flatten::build_image_pdf(&pages)?, page_log))
+}
+
+/// 렌더링한 페이지 이미지에 로고를 그립니다. 로고는 `logo_stream`(바탕색에 합성한 불투명 PNG)을
+/// 그대로 덮어 그리므로, 바탕색은 평면화 모드에서도 로고 둘레의 투명했던 영역에 나타납니다.
+fn stamp_raster_pages(
+    pages: &mut [pdf_flatten::RasterPage],
+    input_path: &Path,
+    settings: &StampSettings,
+    logo_stream: &[u8],
+    on_page: &mut dyn FnMut(u32, u32),
+) -> Result<PageLog, Message> {
+    let logo = image::load_from_memory(logo_stream)
+        .map_err(|e| Message::new("LOGO_DECODE_FAILED").arg(e.to_string()))?
+        .to_rgba8();
+    let banner_logo = settings
+        .banner_opacity
+        .map(|opacity| (banner::rotate_logo(&logo), opacity));
+    let total_pages = pages.len() as u32;
+    let jitter_key = input_path.to_string_lossy();
+    let mut page_log = PageLog::default();
+
+    for (index, page) in pages.iter_mut().enumerate() {
+        let page_number = index as u32 + 1;
+        if !settings.stamps_page(page_number, total_pages) {
+            on_page(page_number, total_pages);
+            continue;
+        }
+        if let Some((rotated, opacity)) = &banner_logo {
+            let (rotated_w, rotated_h) = (f64::from(rotated.width()), f64::from(rotated.height()));
+            let scale = banner::fit_scale((page.width_pt, page.height_pt), (rotated_w, rotated_h));
+            let (draw_width, draw_height) = (rotated_w * scale, rotated_h * scale);
+            let rect = (
+                (page.width_pt - draw_width) / 2.0,
+                (page.height_pt - draw_height) / 2.0,
+                draw_width,
+                draw_height,
+            );
+            pdf_flatten::multiply_raster_page(
+                page,
+                rect,
+                rotated,
+                *opacity,
+                settings.resize_filter,
+            );
+            on_page(page_number, total_pages);
+            continue;
+        }
+        let position = settings.position_for_page(page_number);
+        let outcome = compute_logo_rect(
+            page.width_pt,
+            page.height_pt,
+            position,
+            settings,
+            logo.dimensions(),
+        )
+        .map(|rect| {
+            let (mut x, mut y, draw_width, draw_height) = (rect.x, rect.y, rect.width, rect.height);
+            let mut offset = None;
+            if let Some(jitter) = settings.jitter {
+                let delta =
+                    jitter.offset(&jitter_key, page_number, page.width_pt.min(page.height_pt));
+                let applied;
+                (x, y, applied) = apply_jitter(
+                    (x, y),
+                    delta,
+                    (page.width_pt - draw_width, page.height_pt - draw_height),
+                );
+                offset = Some(applied);
+            }
 
-    let output_path = path_policy::build_output_pdf_path(input_path, output_base_dir)?;
-    doc.save(&output_path)
-        .map_err(|e| format!("결과 PDF를 저장하지 못했습니다: {e}"))?;
+            pdf_flatten::stamp_raster_page(
+                page,
+                (x, y, draw_width, draw_height),
+                &logo,
+                settings.resize_filter,
+            );
+            PageStamp {
+                offset,
+                upscale_capped: rect.upscale_capped,
+            }
+        });
+        page_log.record_page(page_number, outcome, settings)?;
+        on_page(page_number, total_pages);
+    }
 
-    Ok(output_path)
+    page_log.finish(settings.target_page_count(total_pages))
 }
 
-fn build_logo_stream(logo_path: &Path) -> Result<Vec<u8>, String> {
-    let logo_bytes =
-        std::fs::read(logo_path).map_err(|e| format!("로고 리소스를 읽지 못했습니다: {e}"))?;
-    let logo = image::load_from_memory(&logo_bytes)
-        .map_err(|e| format!("로고 이미지 디코딩에 실패했습니다: {e}"))?;
-    let flattened = flatten_alpha_to_white(&logo);
+fn build_logo_stream(
+    logo_path: &Path,
+    logo_style: &image_engine::LogoStyle,
+    size_mode: image_engine::SizeMode,
+    matte: [u8; 3],
+    retry: &RetryPolicy,
+) -> Result<Vec<u8>, Message> {
+    let logo_bytes = retry
+        .read(logo_path)
+        .map_err(|e| Message::new("LOGO_READ_FAILED").arg(e.to_string()))?;
+    let mut logo = svg_logo::decode_logo(logo_path, &logo_bytes, size_mode)
+        .map_err(|e| Message::new("LOGO_DECODE_FAILED").arg(e))?;
+    logo_style.apply(&mut logo)?;
+    let flattened = flatten_alpha(&DynamicImage::ImageRgba8(logo), matte);
 
     let mut png_bytes = Vec::new();
     DynamicImage::ImageRgb8(flattened)
         .write_to(&mut Cursor::new(&mut png_bytes), ImageFormat::Png)
-        .map_err(|e| format!("로고 이미지 변환에 실패했습니다: {e}"))?;
+        .map_err(|e| Message::new("LOGO_CONVERT_FAILED").arg(e.to_string()))?;
 
     Ok(png_bytes)
 }
 
-fn flatten_alpha_to_white(logo: &DynamicImage) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+/// 로고의 투명도를 `matte` 색 위에 합성해 없앱니다.
+fn flatten_alpha(logo: &DynamicImage, matte: [u8; 3]) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
     let rgba = logo.to_rgba8();
     let (width, height) = rgba.dimensions();
     let mut out = ImageBuffer::new(width, height);
 
     for (x, y, px) in rgba.enumerate_pixels() {
         let a = f32::from(px[3]) / 255.0;
-        let r = ((f32::from(px[0]) * a) + (255.0 * (1.0 - a))).round() as u8;
-        let g = ((f32::from(px[1]) * a) + (255.0 * (1.0 - a))).round() as u8;
-        let b = ((f32::from(px[2]) * a) + (255.0 * (1.0 - a))).round() as u8;
-        out.put_pixel(x, y, Rgb([r, g, b]));
+        let blend = |channel: usize| {
+            ((f32::from(px[channel]) * a) + (f32::from(matte[channel]) * (1.0 - a))).round() as u8
+        };
+        out.put_pixel(x, y, Rgb([blend(0), blend(1), blend(2)]));
     }
 
     out
@@ -177,25 +978,47 @@ fn flatten_alpha_to_white(logo: &DynamicImage) -> ImageBuffer<Rgb<u8>, Vec<u8>>
 fn compute_logo_rect(
     page_width: f64,
     page_height: f64,
+    position: CornerPosition,
     settings: &StampSettings,
-    logo_stream: &[u8],
-) -> Result<(f64, f64, f64, f64), String> {
+    (logo_w, logo_h): (u32, u32),
+) -> Result<LogoRect, Message> {
     if page_width <= 0.0 || page_height <= 0.0 {
-        return Err("페이지 크기가 유효하지 않습니다.".to_string());
+        return Err(Message::new("PAGE_SIZE_INVALID"));
     }
-
-    let logo = image::load_from_memory(logo_stream)
-        .map_err(|e| format!("로고 크기 계산에 실패했습니다: {e}"))?;
-    let (logo_w, logo_h) = logo.dimensions();
     if logo_w == 0 || logo_h == 0 {
-        return Err("로고 크기가 유효하지 않습니다.".to_string());
+        return Err(Message::new("LOGO_SIZE_INVALID"));
     }
 
     let short_side = page_width.min(page_height);
-    let margin = short_side * settings.margin_percent / 100.0;
-    let target_max = (short_side * settings.size_ratio).max(1.0);
+    let (basis_x, basis_y) = match settings.margin_mode {
+        image_engine::MarginMode::ShortSide => (short_side, short_side),
+        image_engine::MarginMode::PerAxis => (page_width, page_height),
+    };
+    let (margin_h, margin_v) = settings.margins.for_position(position);
+    let margin_x = basis_x * f64::from(margin_h) / 100.0;
+    let margin_y = basis_y * f64::from(margin_v) / 100.0;
     let logo_max = f64::from(logo_w.max(logo_h));
-    let scale = target_max / logo_max;
+    // 로고 기준 크기는 로고 1px을 1pt로 봅니다.
+    let basis = match settings.size_mode {
+        image_engine::SizeMode::RelativeToPage => short_side,
+        image_engine::SizeMode::RelativeToLogo => logo_max,
+    };
+    let target_max = settings
+        .physical_points
+        .unwrap_or(basis * settings.size_ratio)
+        .max(1.0);
+    let upscale_capped = settings.no_upscale && target_max > logo_max;
+    let mut scale = if upscale_capped {
+        1.0
+    } else {
+        target_max / logo_max
+    };
+    if let Some(fit_box) = settings.fit_box {
+        scale = scale.min(fit_box.max_scale(
+            (page_width, page_height),
+            (f64::from(logo_w), f64::from(logo_h)),
+        ));
+    }
 
     let draw_width = (f64::from(logo_w) * scale).max(1.0);
     let draw_height = (f64::from(logo_h) * scale).max(1.0);
@@ -203,35 +1026,59 @@ fn compute_logo_rect(
     let max_x = (page_width - draw_width).max(0.0);
     let max_y = (page_height - draw_height).max(0.0);
 
-    let left_x = margin.min(max_x);
-    let right_x = (page_width - draw_width - margin).max(0.0).min(max_x);
-    let bottom_y = margin.min(max_y);
-    let top_y = (page_height - draw_height - margin).max(0.0).min(max_y);
+    let left_x = margin_x.min(max_x);
+    let right_x = (page_width - draw_width - margin_x).max(0.0).min(max_x);
+    let bottom_y = margin_y.min(max_y);
+    let top_y = (page_height - draw_height - margin_y).max(0.0).min(max_y);
 
-    let (x, y) = match settings.position {
+    let (x, y) = match position {
         CornerPosition::TopLeft => (left_x, top_y),
         CornerPosition::TopRight => (right_x, top_y),
         CornerPosition::BottomLeft => (left_x, bottom_y),
         CornerPosition::BottomRight => (right_x, bottom_y),
+        CornerPosition::Center => (max_x / 2.0, max_y / 2.0),
+        // 교차점은 위에서 잰 비율이므로 PDF 좌표에서는 세로를 뒤집습니다.
+        position => {
+            let (fx, fy) = position.thirds_point().unwrap_or((0.5, 0.5));
+            (
+                (page_width * fx - draw_width / 2.0).clamp(0.0, max_x),
+                (page_height * (1.0 - fy) - draw_height / 2.0).clamp(0.0, max_y),
+            )
+        }
     };
+    // 기준점은 위에서 잰 비율이고 PDF 좌표는 아래가 원점이므로, 세로로는 로고를 위로 올립니다.
+    let x = (x - settings.anchor.fx * draw_width).clamp(0.0, max_x);
+    let y = (y + settings.anchor.fy * draw_height).clamp(0.0, max_y);
 
-    Ok((x, y, draw_width, draw_height))
+    Ok(LogoRect {
+        x,
+        y,
+        width: draw_width,
+        height: draw_height,
+        upscale_capped,
+    })
 }
 
 fn resolve_page_size(
     doc: &Document,
     page_id: ObjectId,
     page_number: u32,
-) -> Result<(f64, f64), String> {
+) -> Result<(f64, f64), Message> {
     let mut current_id = page_id;
 
     for _ in 0..32 {
-        let dict = get_object_dictionary(doc, current_id)
-            .map_err(|e| format!("페이지 {page_number} 객체를 읽지 못했습니다: {e}"))?;
+        let dict = get_object_dictionary(doc, current_id).map_err(|e| {
+            Message::new("PAGE_OBJECT_READ_FAILED")
+                .arg(page_number.to_string())
+                .arg(e)
+        })?;
 
         if let Ok(media_box) = dict.get(b"MediaBox") {
-            return parse_media_box(doc, media_box)
-                .map_err(|e| format!("페이지 {page_number} MediaBox를 해석하지 못했습니다: {e}"));
+            return parse_media_box(doc, media_box).map_err(|e| {
+                Message::new("MEDIABOX_PARSE_FAILED")
+                    .arg(page_number.to_string())
+                    .arg(e)
+            });
         }
 
         let parent = match dict.get(b"Parent") {
@@ -241,41 +1088,42 @@ fn resolve_page_size(
         current_id = parent;
     }
 
-    Err(format!(
-        "페이지 {page_number}의 MediaBox를 찾지 못했습니다."
-    ))
+    Err(Message::new("MEDIABOX_NOT_FOUND").arg(page_number.to_string()))
 }
 
-fn get_object_dictionary<'a>(
-    doc: &'a Document,
-    object_id: ObjectId,
-) -> Result<&'a Dictionary, String> {
-    let object = doc
-        .get_object(object_id)
-        .map_err(|e| format!("객체 조회 실패({object_id:?}): {e}"))?;
+fn get_object_dictionary(doc: &Document, object_id: ObjectId) -> Result<&Dictionary, Message> {
+    let object = doc.get_object(object_id).map_err(|e| {
+        Message::new("OBJECT_LOOKUP_FAILED")
+            .arg(format!("{object_id:?}"))
+            .arg(e.to_string())
+    })?;
 
     match object {
         Object::Dictionary(dict) => Ok(dict),
         Object::Stream(stream) => Ok(&stream.dict),
-        _ => Err("페이지 객체가 Dictionary/Stream이 아닙니다.".to_string()),
+        _ => Err(Message::new("PAGE_OBJECT_NOT_DICTIONARY")),
     }
 }
 
-fn parse_media_box(doc: &Document, obj: &Object) -> Result<(f64, f64), String> {
+fn parse_media_box(doc: &Document, obj: &Object) -> Result<(f64, f64), Message> {
     let arr_ref = match obj {
         Object::Array(arr) => arr,
         Object::Reference(object_id) => doc
             .get_object(*object_id)
-            .map_err(|e| format!("MediaBox 참조 객체 조회 실패({object_id:?}): {e}"))?
+            .map_err(|e| {
+                Message::new("MEDIABOX_REF_LOOKUP_FAILED")
+                    .arg(format!("{object_id:?}"))
+                    .arg(e.to_string())
+            })?
             .as_array()
-            .map_err(|_| "MediaBox 참조 객체가 배열이 아닙니다.".to_string())?,
-        _ => return Err("MediaBox는 배열이어야 합니다.".to_string()),
+            .map_err(|_| Message::new("MEDIABOX_REF_NOT_ARRAY"))?,
+        _ => return Err(Message::new("MEDIABOX_NOT_ARRAY")),
     };
 
     let arr = arr_ref;
 
     if arr.len() != 4 {
-        return Err("MediaBox 길이가 4가 아닙니다.".to_string());
+        return Err(Message::new("MEDIABOX_WRONG_LENGTH"));
     }
 
     let llx = object_to_f64(&arr[0])?;
@@ -286,26 +1134,27 @@ fn parse_media_box(doc: &Document, obj: &Object) -> Result<(f64, f64), String> {
     let height = ury - lly;
 
     if width <= 0.0 || height <= 0.0 {
-        return Err("MediaBox 너비/높이가 0 이하입니다.".to_string());
+        return Err(Message::new("MEDIABOX_EMPTY"));
     }
 
     Ok((width, height))
 }
 
-fn object_to_f64(obj: &Object) -> Result<f64, String> {
+fn object_to_f64(obj: &Object) -> Result<f64, Message> {
     match obj {
         Object::Integer(v) => Ok(*v as f64),
         Object::Real(v) => Ok((*v).into()),
-        _ => Err("MediaBox 좌표가 숫자가 아닙니다.".to_string()),
+        _ => Err(Message::new("MEDIABOX_NOT_NUMBERS")),
     }
 }
 
-fn failure_result(input_path: String, error: String) -> StampFileResult {
+fn failure_result(input_path: String, error: Message) -> StampFileResult {
     StampFileResult {
         input_path,
         ok: false,
         output_path: None,
         error: Some(error),
+        ..Default::default()
     }
 }
 
@@ -330,42 +1179,37 @@ mod tests {
     }
 
     fn write_minimal_two_page_pdf(path: &Path) {
+        write_minimal_pdf(path, 2);
+    }
+
+    fn write_minimal_pdf(path: &Path, page_count: u32) {
         let mut doc = Document::with_version("1.5");
         let pages_id = doc.new_object_id();
-        let page1_id = doc.new_object_id();
-        let page2_id = doc.new_object_id();
-        let content1_id = doc.add_object(Stream::new(dictionary! {}, Vec::new()));
-        let content2_id = doc.add_object(Stream::new(dictionary! {}, Vec::new()));
-
         let media_box = vec![0.into(), 0.into(), 300.into(), 300.into()];
 
-        doc.objects.insert(
-            page1_id,
-            Object::Dictionary(dictionary! {
-                "Type" => "Page",
-                "Parent" => pages_id,
-                "MediaBox" => media_box.clone(),
-                "Contents" => content1_id,
-                "Resources" => dictionary! {},
-            }),
-        );
-        doc.objects.insert(
-            page2_id,
-            Object::Dictionary(dictionary! {
-                "Type" => "Page",
-                "Parent" => pages_id,
-                "MediaBox" => media_box.clone(),
-                "Contents" => content2_id,
-                "Resources" => dictionary! {},
-            }),
-        );
+        let mut kids = Vec::new();
+        for _ in 0..page_count {
+            let page_id = doc.new_object_id();
+            let content_id = doc.add_object(Stream::new(dictionary! {}, Vec::new()));
+            doc.objects.insert(
+                page_id,
+                Object::Dictionary(dictionary! {
+                    "Type" => "Page",
+                    "Parent" => pages_id,
+                    "MediaBox" => media_box.clone(),
+                    "Contents" => content_id,
+                    "Resources" => dictionary! {},
+                }),
+            );
+            kids.push(page_id.into());
+        }
 
         doc.objects.insert(
             pages_id,
             Object::Dictionary(dictionary! {
                 "Type" => "Pages",
-                "Kids" => vec![page1_id.into(), page2_id.into()],
-                "Count" => 2,
+                "Kids" => kids,
+                "Count" => page_count,
                 "MediaBox" => media_box,
             }),
         );
@@ -379,43 +1223,1128 @@ mod tests {
         doc.save(path).expect("write pdf fixture");
     }
 
+    /// 각 페이지 콘텐츠에서 로고를 그린 `cm` 연산의 (x, y)를 페이지 순서대로 돌려줍니다.
+    fn stamp_origins(doc: &Document) -> Vec<(f64, f64)> {
+        doc.get_pages()
+            .values()
+            .map(|page_id| {
+                let content = doc
+                    .get_and_decode_page_content(*page_id)
+                    .expect("page content");
+                let cm = content
+                    .operations
+                    .iter()
+                    .rfind(|op| op.operator == "cm")
+                    .expect("cm operation");
+                (
+                    object_to_f64(&cm.operands[4]).expect("x"),
+                    object_to_f64(&cm.operands[5]).expect("y"),
+                )
+            })
+            .collect()
+    }
+
     #[test]
-    fn stamp_pdfs_stamps_all_pages_of_two_page_pdf() {
+    fn apply_jitter_records_offset_after_clamping() {
+        assert_eq!(
+            apply_jitter((10.0, 10.0), (3, -4), (100.0, 100.0)),
+            (13.0, 6.0, [3, -4])
+        );
+        // 페이지 밖으로 밀리는 만큼은 잘리고 기록에도 남지 않습니다.
+        assert_eq!(
+            apply_jitter((2.0, 95.0), (-5, 8), (100.0, 100.0)),
+            (0.0, 100.0, [-2, 5])
+        );
+    }
+
+    #[test]
+    fn probe_pdf_reports_page_count_and_first_page_size() {
         let nonce = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .expect("time")
             .as_nanos();
-        let root = std::env::temp_dir().join(format!("cornerbrand-pdf-engine-{nonce}"));
+        let root = std::env::temp_dir().join(format!("cornerbrand-pdf-probe-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+        let pdf = root.join("input.pdf");
+        write_minimal_two_page_pdf(&pdf);
+
+        let (page_count, page_size) = probe_pdf(&pdf).expect("probe");
+        assert_eq!(page_count, 2);
+        assert_eq!(page_size, (300.0, 300.0));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn anchor_to_content_places_logo_inside_content_box() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-pdf-anchor-{nonce}"));
         fs::create_dir_all(&root).expect("temp dir");
 
         let input_pdf = root.join("input.pdf");
         let logo_path = root.join("logo.png");
-        write_minimal_two_page_pdf(&input_pdf);
+        write_minimal_pdf(&input_pdf, 1);
         write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
 
+        // 300x300 페이지 가운데 (100,100)~(200,200)에만 사각형과 텍스트를 그립니다.
+        let mut doc = Document::load(&input_pdf).expect("load fixture");
+        let page_id = *doc.get_pages().values().next().expect("page");
+        doc.change_page_content(
+            page_id,
+            b"q 0 0 1 rg 100 100 100 80 re f Q BT /F1 10 Tf 100 190 Td (Hi) Tj ET".to_vec(),
+        )
+        .expect("content");
+        doc.save(&input_pdf).expect("save fixture");
+
         let settings = StampSettingsInput {
             position: "우하단".to_string(),
             size_preset: "보통".to_string(),
-            size_percent: None,
-            margin_percent: 2.0,
+            anchor_to_content: true,
+            ..Default::default()
         };
-
         let paths = vec![input_pdf.to_string_lossy().to_string()];
         let results = stamp_pdfs(&paths, settings, &logo_path, None);
+        assert!(results[0].ok, "expected success: {:?}", results[0].error);
 
-        assert_eq!(results.len(), 1);
+        let doc = Document::load(results[0].output_path.as_ref().expect("output")).expect("load");
+        let (x, y) = stamp_origins(&doc)[0];
+        assert!((100.0..200.0).contains(&x), "x={x} outside content box");
+        assert!(
+            (100.0..150.0).contains(&y),
+            "y={y} should sit near content bottom"
+        );
+
+        let empty_pdf = root.join("empty.pdf");
+        write_minimal_pdf(&empty_pdf, 1);
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            anchor_to_content: true,
+            ..Default::default()
+        };
+        let paths = vec![empty_pdf.to_string_lossy().to_string()];
+        let results = stamp_pdfs(&paths, settings, &logo_path, None);
         assert!(results[0].ok, "expected success: {:?}", results[0].error);
+        let doc = Document::load(results[0].output_path.as_ref().expect("output")).expect("load");
+        let (x, _) = stamp_origins(&doc)[0];
+        assert!(x > 200.0, "empty page should fall back to MediaBox, x={x}");
 
-        let output_path = PathBuf::from(results[0].output_path.as_ref().expect("output path"));
-        assert!(output_path.exists(), "stamped pdf should exist");
+        let _ = fs::remove_dir_all(&root);
+    }
 
-        let output_doc = Document::load(&output_path).expect("load output pdf");
-        assert_eq!(
-            output_doc.get_pages().len(),
+    #[test]
+    fn banner_mode_centers_rotated_translucent_logo_on_page() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-pdf-banner-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_pdf = root.join("input.pdf");
+        let logo_path = root.join("logo.png");
+        write_minimal_pdf(&input_pdf, 1);
+        write_test_png(&logo_path, 40, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            banner_mode: true,
+            ..Default::default()
+        };
+        let paths = vec![input_pdf.to_string_lossy().to_string()];
+        let results = stamp_pdfs(&paths, settings, &logo_path, None);
+        assert!(results[0].ok, "expected success: {:?}", results[0].error);
+
+        let doc = Document::load(results[0].output_path.as_ref().expect("output")).expect("load");
+        let page_id = *doc.get_pages().values().next().expect("page");
+        let content = doc.get_and_decode_page_content(page_id).expect("content");
+        let cm = content
+            .operations
+            .iter()
+            .rfind(|op| op.operator == "cm")
+            .expect("cm operation");
+        let m: Vec<f64> = cm
+            .operands
+            .iter()
+            .map(|value| object_to_f64(value).expect("number"))
+            .collect();
+
+        // 로고의 가운데가 페이지 가운데에 오고, 가로축이 45° 위로 기울어 있습니다.
+        let center = (m[4] + (m[0] + m[2]) / 2.0, m[5] + (m[1] + m[3]) / 2.0);
+        assert!((center.0 - 150.0).abs() < 0.5 && (center.1 - 150.0).abs() < 0.5);
+        assert!(m[1] > 0.0 && (m[0] - m[1]).abs() < 0.5);
+        let length = m[0].hypot(m[1]);
+        assert!(length > 200.0, "banner should span the page, got {length}");
+        for (u, v) in [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (1.0, 1.0)] {
+            let (x, y) = (m[0] * u + m[2] * v + m[4], m[1] * u + m[3] * v + m[5]);
+            assert!((0.0..=300.0).contains(&x) && (0.0..=300.0).contains(&y));
+        }
+
+        let gs_name = content
+            .operations
+            .iter()
+            .find(|op| op.operator == "gs")
+            .and_then(|op| op.operands[0].as_name().ok())
+            .expect("gs operation")
+            .to_vec();
+        let resources = doc
+            .get_dictionary(page_id)
+            .and_then(|page| page.get(b"Resources"))
+            .and_then(Object::as_dict)
+            .expect("resources");
+        let state_id = resources
+            .get(b"ExtGState")
+            .and_then(Object::as_dict)
+            .and_then(|states| states.get(&gs_name))
+            .and_then(Object::as_reference)
+            .expect("graphics state");
+        let state = doc.get_dictionary(state_id).expect("state dict");
+        let ca = object_to_f64(state.get(b"ca").expect("ca")).expect("ca value");
+        assert!((ca - f64::from(banner::BANNER_OPACITY)).abs() < 1e-6);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn stamp_ignores_ctm_left_behind_by_page_content() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-pdf-ctm-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let clean_pdf = root.join("clean.pdf");
+        let skewed_pdf = root.join("skewed.pdf");
+        let logo_path = root.join("logo.png");
+        write_minimal_pdf(&clean_pdf, 1);
+        write_minimal_pdf(&skewed_pdf, 1);
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+
+        // 열린 `q`와 되돌리지 않은 `cm`을 남긴 채 끝나는 콘텐츠입니다.
+        let mut doc = Document::load(&skewed_pdf).expect("load fixture");
+        let page_id = *doc.get_pages().values().next().expect("page");
+        doc.change_page_content(
+            page_id,
+            b"Q q 2 0 0 2 50 50 cm 0.5 0 0 0.5 10 10 cm".to_vec(),
+        )
+        .expect("content");
+        doc.save(&skewed_pdf).expect("save fixture");
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            ..Default::default()
+        };
+        let paths = vec![
+            clean_pdf.to_string_lossy().to_string(),
+            skewed_pdf.to_string_lossy().to_string(),
+        ];
+        let results = stamp_pdfs(&paths, settings, &logo_path, None);
+        assert!(
+            results.iter().all(|r| r.ok),
+            "expected success: {results:?}"
+        );
+
+        let clean = Document::load(results[0].output_path.as_ref().expect("output")).expect("load");
+        let skewed =
+            Document::load(results[1].output_path.as_ref().expect("output")).expect("load");
+        let (x, y) = stamp_origins(&clean)[0];
+        assert_eq!(stamp_origins(&skewed)[0], (x, y));
+
+        // 실제로 그려지는 영역(CTM 적용 후)이 깨끗한 페이지와 같은 자리여야 합니다.
+        let page_id = *skewed.get_pages().values().next().expect("page");
+        let bounds = pdf_content_bounds::estimate(&skewed, page_id).expect("bounds");
+        assert!((bounds.x - x).abs() < 0.01, "x {} != {x}", bounds.x);
+        assert!((bounds.y - y).abs() < 0.01, "y {} != {y}", bounds.y);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn pdf_as_layer_registers_watermark_ocg() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-pdf-layer-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_pdf = root.join("input.pdf");
+        let logo_path = root.join("logo.png");
+        write_minimal_two_page_pdf(&input_pdf);
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            pdf_as_layer: true,
+            ..Default::default()
+        };
+        let paths = vec![input_pdf.to_string_lossy().to_string()];
+        let results = stamp_pdfs(&paths, settings, &logo_path, None);
+        assert!(results[0].ok, "expected success: {:?}", results[0].error);
+
+        let doc = Document::load(results[0].output_path.as_ref().expect("output")).expect("load");
+        let catalog = doc.catalog().expect("catalog");
+        let properties = catalog
+            .get(b"OCProperties")
+            .and_then(Object::as_dict)
+            .expect("OCProperties");
+        let ocgs = properties
+            .get(b"OCGs")
+            .and_then(Object::as_array)
+            .expect("OCGs");
+        assert_eq!(ocgs.len(), 1);
+        let layer_id = ocgs[0].as_reference().expect("ocg ref");
+        let layer = doc.get_dictionary(layer_id).expect("ocg");
+        assert_eq!(
+            layer.get(b"Name").and_then(Object::as_str).expect("name"),
+            pdf_layer::WATERMARK_LAYER_NAME.as_bytes()
+        );
+
+        for page_id in doc.get_pages().values() {
+            let (resources, _) = doc.get_page_resources(*page_id).expect("resources");
+            let xobjects = resources
+                .expect("resource dict")
+                .get(b"XObject")
+                .and_then(Object::as_dict)
+                .expect("xobjects");
+            let logo_id = xobjects
+                .iter()
+                .next()
+                .and_then(|(_, obj)| obj.as_reference().ok())
+                .expect("logo xobject");
+            let logo = doc
+                .get_object(logo_id)
+                .and_then(Object::as_stream)
+                .expect("stream");
+            assert_eq!(
+                logo.dict.get(b"OC").and_then(Object::as_reference).ok(),
+                Some(layer_id)
+            );
+        }
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn stamp_pdfs_stamps_all_pages_of_two_page_pdf() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-pdf-engine-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_pdf = root.join("input.pdf");
+        let logo_path = root.join("logo.png");
+        write_minimal_two_page_pdf(&input_pdf);
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            size_percent: None,
+            margin_percent: 2.0,
+            ..Default::default()
+        };
+
+        let paths = vec![input_pdf.to_string_lossy().to_string()];
+        let results = stamp_pdfs(&paths, settings, &logo_path, None);
+
+        assert_eq!(results.len(), 1);
+        assert!(results[0].ok, "expected success: {:?}", results[0].error);
+
+        let output_path = PathBuf::from(results[0].output_path.as_ref().expect("output path"));
+        assert!(output_path.exists(), "stamped pdf should exist");
+
+        let output_doc = Document::load(&output_path).expect("load output pdf");
+        assert_eq!(
+            output_doc.get_pages().len(),
             2,
             "output should keep 2 pages"
         );
 
         let _ = fs::remove_dir_all(&root);
     }
+
+    #[test]
+    fn skip_already_stamped_skips_second_run() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-pdf-restamp-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_pdf = root.join("input.pdf");
+        let logo_path = root.join("logo.png");
+        write_minimal_two_page_pdf(&input_pdf);
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            skip_already_stamped: true,
+            ..Default::default()
+        };
+        let first = stamp_pdfs(
+            &[input_pdf.to_string_lossy().to_string()],
+            settings.clone(),
+            &logo_path,
+            None,
+        );
+        assert!(first[0].ok, "expected success: {:?}", first[0].error);
+
+        let stamped = vec![first[0].output_path.clone().expect("output path")];
+        let second = stamp_pdfs(&stamped, settings.clone(), &logo_path, None);
+        assert!(!second[0].ok && second[0].skipped);
+        assert_eq!(
+            second[0].code.as_deref(),
+            Some(image_engine::CODE_ALREADY_STAMPED)
+        );
+
+        let forced = StampSettingsInput {
+            skip_already_stamped: false,
+            ..settings
+        };
+        assert!(stamp_pdfs(&stamped, forced, &logo_path, None)[0].ok);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn logo_xobject_is_shared_across_pages() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-pdf-shared-logo-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_pdf = root.join("input.pdf");
+        let logo_path = root.join("logo.png");
+        write_minimal_pdf(&input_pdf, 5);
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            ..Default::default()
+        };
+        let paths = vec![input_pdf.to_string_lossy().to_string()];
+        let results = stamp_pdfs(&paths, settings, &logo_path, None);
+        assert!(results[0].ok, "expected success: {:?}", results[0].error);
+
+        let doc = Document::load(results[0].output_path.as_ref().expect("output path"))
+            .expect("load output pdf");
+        let images: Vec<ObjectId> = doc
+            .objects
+            .iter()
+            .filter(|(_, object)| {
+                object.as_stream().is_ok_and(|stream| {
+                    stream.dict.get(b"Subtype").and_then(Object::as_name).ok() == Some(b"Image")
+                })
+            })
+            .map(|(id, _)| *id)
+            .collect();
+        assert_eq!(images.len(), 1, "logo should be embedded once");
+
+        for page_id in doc.get_pages().values() {
+            let (resources, _) = doc.get_page_resources(*page_id).expect("resources");
+            let referenced: Vec<ObjectId> = resources
+                .expect("resource dict")
+                .get(b"XObject")
+                .and_then(Object::as_dict)
+                .expect("xobjects")
+                .iter()
+                .filter_map(|(_, obj)| obj.as_reference().ok())
+                .collect();
+            assert_eq!(referenced, images);
+        }
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn pdf_pages_first_and_last_stamp_exactly_one_page() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-pdf-pages-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let logo_path = root.join("logo.png");
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+
+        for (keyword, expected) in [("first", [true, false]), ("last", [false, true])] {
+            let input_pdf = root.join(format!("{keyword}.pdf"));
+            write_minimal_two_page_pdf(&input_pdf);
+            let settings = StampSettingsInput {
+                position: "우하단".to_string(),
+                size_preset: "보통".to_string(),
+                pdf_pages: Some(keyword.to_string()),
+                ..Default::default()
+            };
+            let paths = vec![input_pdf.to_string_lossy().to_string()];
+            let results = stamp_pdfs(&paths, settings, &logo_path, None);
+            assert!(results[0].ok, "{keyword}: {:?}", results[0].error);
+
+            let doc =
+                Document::load(results[0].output_path.as_ref().expect("output")).expect("load");
+            let stamped: Vec<bool> = doc
+                .get_pages()
+                .values()
+                .map(|page_id| {
+                    doc.get_and_decode_page_content(*page_id)
+                        .expect("content")
+                        .operations
+                        .iter()
+                        .any(|op| op.operator == "Do")
+                })
+                .collect();
+            assert_eq!(stamped, expected, "{keyword}");
+        }
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    fn red_extent(image: &RgbaImage) -> (u32, u32) {
+        let xs: Vec<u32> = image
+            .enumerate_pixels()
+            .filter(|(_, _, px)| px[0] > 200 && px[1] < 80 && px[2] < 80)
+            .map(|(x, _, _)| x)
+            .collect();
+        let ys: Vec<u32> = image
+            .enumerate_pixels()
+            .filter(|(_, _, px)| px[0] > 200 && px[1] < 80 && px[2] < 80)
+            .map(|(_, y, _)| y)
+            .collect();
+        let width = xs.iter().max().unwrap() - xs.iter().min().unwrap() + 1;
+        let height = ys.iter().max().unwrap() - ys.iter().min().unwrap() + 1;
+        (width, height)
+    }
+
+    #[test]
+    fn image_and_pdf_apply_same_size_percent_proportionally() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-size-parity-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_png = root.join("input.png");
+        let logo_path = root.join("logo.png");
+        write_test_png(&input_png, 200, 100, [240, 240, 240, 255]);
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+        let logo_stream = build_logo_stream(
+            &logo_path,
+            &image_engine::LogoStyle::default(),
+            image_engine::SizeMode::RelativeToPage,
+            [255, 255, 255],
+            &RetryPolicy::default(),
+        )
+        .expect("logo stream");
+
+        // 범위 안의 값과 범위를 넘는 값 모두 두 엔진에서 같은 비율이어야 합니다.
+        for size_percent in [20.0_f32, 100.0] {
+            let settings = StampSettingsInput {
+                position: "우하단".to_string(),
+                size_preset: "보통".to_string(),
+                size_percent: Some(size_percent),
+                margin_percent: 0.0,
+                ..Default::default()
+            };
+
+            let results = image_engine::stamp_images(
+                &[input_png.to_string_lossy().to_string()],
+                settings.clone(),
+                &logo_path,
+                None,
+            );
+            assert!(results[0].ok, "expected success: {:?}", results[0].error);
+            let stamped = image::open(results[0].output_path.as_ref().expect("output"))
+                .expect("open stamped image")
+                .to_rgba8();
+            let (logo_px, _) = red_extent(&stamped);
+            let image_ratio = f64::from(logo_px) / 100.0;
+
+            let pdf_settings = StampSettings::try_from(settings).expect("pdf settings");
+            let rect = compute_logo_rect(
+                600.0,
+                300.0,
+                pdf_settings.position,
+                &pdf_settings,
+                logo_dimensions(&logo_stream).expect("logo size"),
+            )
+            .expect("rect");
+            let pdf_ratio = rect.width / 300.0;
+
+            assert!(
+                (image_ratio - pdf_ratio).abs() < 0.011,
+                "size {size_percent}: image ratio {image_ratio} vs pdf ratio {pdf_ratio}"
+            );
+        }
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn stamp_pdfs_applies_page_position_override() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-pdf-page-positions-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_pdf = root.join("contract.pdf");
+        let logo_path = root.join("logo.png");
+        write_minimal_pdf(&input_pdf, 3);
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            margin_percent: 2.0,
+            pdf_page_positions: Some(BTreeMap::from([(2, "좌상단".to_string())])),
+            ..Default::default()
+        };
+
+        let paths = vec![input_pdf.to_string_lossy().to_string()];
+        let results = stamp_pdfs(&paths, settings, &logo_path, None);
+        assert!(results[0].ok, "expected success: {:?}", results[0].error);
+
+        let output_doc =
+            Document::load(results[0].output_path.as_ref().expect("output")).expect("load output");
+        let origins = stamp_origins(&output_doc);
+        assert_eq!(origins.len(), 3);
+
+        // 300pt 페이지, 로고 36pt, 여백 6pt: 우하단=(258, 6), 좌상단=(6, 258)
+        assert_eq!(origins[0], origins[2], "pages 1 and 3 keep the base corner");
+        assert!((origins[0].0 - 258.0).abs() < 0.01 && (origins[0].1 - 6.0).abs() < 0.01);
+        assert!((origins[1].0 - 6.0).abs() < 0.01 && (origins[1].1 - 258.0).abs() < 0.01);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn compute_logo_rect_centers_thirds_positions_on_intersections() {
+        let settings = StampSettings::try_from(StampSettingsInput {
+            position: "삼분할 좌상단".to_string(),
+            size_percent: Some(10.0),
+            margin_percent: 5.0,
+            ..Default::default()
+        })
+        .expect("settings");
+
+        // 600x300pt 페이지, 로고 30pt. PDF는 아래가 원점이라 위쪽 교차점의 y는 200pt입니다.
+        let cases = [
+            (CornerPosition::ThirdTopLeft, (200.0, 200.0)),
+            (CornerPosition::ThirdTopRight, (400.0, 200.0)),
+            (CornerPosition::ThirdBottomLeft, (200.0, 100.0)),
+            (CornerPosition::ThirdBottomRight, (400.0, 100.0)),
+        ];
+        for (position, (expected_x, expected_y)) in cases {
+            let rect = compute_logo_rect(600.0, 300.0, position, &settings, (8, 8)).expect("rect");
+            let center_x = rect.x + rect.width / 2.0;
+            let center_y = rect.y + rect.height / 2.0;
+            assert!(
+                (center_x - expected_x).abs() < 0.01 && (center_y - expected_y).abs() < 0.01,
+                "{position:?}: center = ({center_x}, {center_y})"
+            );
+        }
+    }
+
+    #[test]
+    fn compute_logo_rect_uses_per_side_margins_of_the_corner() {
+        let settings = StampSettings::try_from(StampSettingsInput {
+            position: "우하단".to_string(),
+            size_percent: Some(10.0),
+            margin_percent: 5.0,
+            margin_right: Some(0.0),
+            margin_bottom: Some(10.0),
+            ..Default::default()
+        })
+        .expect("settings");
+
+        // 300pt 페이지, 로고 30pt: 오른쪽 0pt, 아래 30pt.
+        let LogoRect {
+            x,
+            y,
+            width: w,
+            height: h,
+            ..
+        } = compute_logo_rect(300.0, 300.0, CornerPosition::BottomRight, &settings, (8, 8))
+            .expect("rect");
+        assert!((w - 30.0).abs() < 0.01 && (h - 30.0).abs() < 0.01);
+        assert!((x - 270.0).abs() < 0.01, "x = {x}");
+        assert!((y - 30.0).abs() < 0.01, "y = {y}");
+
+        // 좌상단은 덮어쓰지 않은 변이라 기본 5%(15pt)를 씁니다.
+        let LogoRect { x, y, .. } =
+            compute_logo_rect(300.0, 300.0, CornerPosition::TopLeft, &settings, (8, 8))
+                .expect("rect");
+        assert!((x - 15.0).abs() < 0.01 && (y - 255.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn fit_box_scales_wide_logo_down_to_box_height() {
+        let settings = StampSettings::try_from(StampSettingsInput {
+            position: "우하단".to_string(),
+            size_percent: Some(50.0),
+            fit_box: Some(image_engine::FitBox {
+                max_width_percent: 80.0,
+                max_height_percent: 10.0,
+            }),
+            ..Default::default()
+        })
+        .expect("settings");
+
+        // 300pt 페이지의 상자는 240x30pt이므로 150pt 대신 높이 30pt에 맞춥니다.
+        let rect = compute_logo_rect(
+            300.0,
+            300.0,
+            CornerPosition::BottomRight,
+            &settings,
+            (100, 40),
+        )
+        .expect("rect");
+        assert!((rect.width - 75.0).abs() < 0.01);
+        assert!((rect.height - 30.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn no_upscale_caps_tiny_logo_at_native_points() {
+        let input = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_percent: Some(10.0),
+            ..Default::default()
+        };
+
+        let stretched = StampSettings::try_from(input.clone()).expect("settings");
+        let rect = compute_logo_rect(
+            300.0,
+            300.0,
+            CornerPosition::BottomRight,
+            &stretched,
+            (8, 4),
+        )
+        .expect("rect");
+        assert!((rect.width - 30.0).abs() < 0.01 && !rect.upscale_capped);
+
+        let capped = StampSettings::try_from(StampSettingsInput {
+            no_upscale: true,
+            ..input
+        })
+        .expect("settings");
+        let rect = compute_logo_rect(300.0, 300.0, CornerPosition::BottomRight, &capped, (8, 4))
+            .expect("rect");
+        assert!(rect.upscale_capped);
+        assert!((rect.width - 8.0).abs() < 0.01 && (rect.height - 4.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn physical_size_draws_logo_in_points() {
+        let settings = StampSettings::try_from(StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            physical_size: Some(image_engine::PhysicalSize {
+                inches: 1.0,
+                dpi: 300.0,
+            }),
+            ..Default::default()
+        })
+        .expect("settings");
+
+        // 인치당 72pt이므로 DPI와 페이지 크기에 관계없이 긴 변이 72pt입니다.
+        for side in [300.0, 1200.0] {
+            let LogoRect {
+                width: w,
+                height: h,
+                ..
+            } = compute_logo_rect(side, side, CornerPosition::BottomRight, &settings, (20, 10))
+                .expect("rect");
+            assert!(
+                (w - 72.0).abs() < 0.01 && (h - 36.0).abs() < 0.01,
+                "{w}x{h}"
+            );
+        }
+    }
+
+    #[test]
+    fn stamp_pdfs_mirrors_corner_on_even_pages() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-pdf-mirror-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_pdf = root.join("booklet.pdf");
+        let logo_path = root.join("logo.png");
+        write_minimal_two_page_pdf(&input_pdf);
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            margin_percent: 2.0,
+            mirror_even_pages: true,
+            ..Default::default()
+        };
+
+        let paths = vec![input_pdf.to_string_lossy().to_string()];
+        let results = stamp_pdfs(&paths, settings, &logo_path, None);
+        assert!(results[0].ok, "expected success: {:?}", results[0].error);
+
+        let output_doc =
+            Document::load(results[0].output_path.as_ref().expect("output")).expect("load output");
+        let origins = stamp_origins(&output_doc);
+
+        // 300pt 페이지, 로고 36pt, 여백 6pt: 1쪽 우하단=(258, 6), 2쪽 좌하단=(6, 6)
+        assert!((origins[0].0 - 258.0).abs() < 0.01 && (origins[0].1 - 6.0).abs() < 0.01);
+        assert!((origins[1].0 - 6.0).abs() < 0.01 && (origins[1].1 - 6.0).abs() < 0.01);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn stamp_pdfs_skips_broken_page_when_continue_on_page_error() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-pdf-page-error-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_pdf = root.join("broken.pdf");
+        let logo_path = root.join("logo.png");
+        write_minimal_pdf(&input_pdf, 3);
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+
+        // 2쪽만 MediaBox가 없도록 페이지 트리의 상속 값과 2쪽 값을 지웁니다.
+        let mut doc = Document::load(&input_pdf).expect("load fixture");
+        let pages = doc.get_pages();
+        let pages_id = doc
+            .get_dictionary(pages[&1])
+            .and_then(|page| page.get(b"Parent"))
+            .and_then(Object::as_reference)
+            .expect("parent");
+        for id in [pages_id, pages[&2]] {
+            doc.get_dictionary_mut(id)
+                .expect("dictionary")
+                .remove(b"MediaBox");
+        }
+        doc.save(&input_pdf).expect("rewrite fixture");
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            ..Default::default()
+        };
+        let paths = vec![input_pdf.to_string_lossy().to_string()];
+
+        let strict = stamp_pdfs(&paths, settings.clone(), &logo_path, None);
+        assert!(!strict[0].ok);
+
+        let results = stamp_pdfs(
+            &paths,
+            StampSettingsInput {
+                continue_on_page_error: true,
+                ..settings
+            },
+            &logo_path,
+            None,
+        );
+        assert!(results[0].ok, "expected success: {:?}", results[0].error);
+        assert_eq!(results[0].warnings.len(), 1);
+        assert!(results[0].warnings[0].to_string().contains("페이지 2"));
+
+        let output_doc =
+            Document::load(results[0].output_path.as_ref().expect("output")).expect("load output");
+        let painted: Vec<usize> = output_doc
+            .get_pages()
+            .values()
+            .map(|page_id| {
+                output_doc
+                    .get_and_decode_page_content(*page_id)
+                    .expect("page content")
+                    .operations
+                    .iter()
+                    .filter(|op| op.operator == "Do")
+                    .count()
+            })
+            .collect();
+        assert_eq!(painted, vec![1, 0, 1]);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn build_logo_stream_inverts_logo_when_enabled() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-pdf-invert-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let logo_path = root.join("logo.png");
+        write_test_png(&logo_path, 4, 4, [255, 255, 255, 255]);
+
+        let settings = StampSettings::try_from(StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            logo_invert: true,
+            ..Default::default()
+        })
+        .expect("settings");
+        let stream = build_logo_stream(
+            &logo_path,
+            &settings.logo_style,
+            settings.size_mode,
+            settings.matte_color,
+            &settings.retry,
+        )
+        .expect("logo stream");
+        let decoded = image::load_from_memory(&stream).expect("decode").to_rgb8();
+        assert_eq!(decoded.get_pixel(0, 0).0, [0, 0, 0]);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn build_logo_stream_flattens_alpha_onto_matte_color() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-pdf-matte-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let logo_path = root.join("logo.png");
+        write_test_png(&logo_path, 4, 4, [255, 255, 255, 128]);
+
+        let settings = StampSettings::try_from(StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            pdf_matte_color: Some([0, 0, 0]),
+            ..Default::default()
+        })
+        .expect("settings");
+        let stream = build_logo_stream(
+            &logo_path,
+            &settings.logo_style,
+            settings.size_mode,
+            settings.matte_color,
+            &settings.retry,
+        )
+        .expect("logo stream");
+        let decoded = image::load_from_memory(&stream).expect("decode").to_rgb8();
+        // 반투명 흰색은 흰 바탕이면 흰색 그대로지만, 검은 바탕에서는 회색이 됩니다.
+        assert_eq!(decoded.get_pixel(0, 0).0, [128, 128, 128]);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn flatten_mode_draws_logo_padding_in_matte_color() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-pdf-flatten-matte-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        // 가운데만 불투명한 로고입니다. 둘레의 투명한 여백은 바탕색으로 채워져야 합니다.
+        let logo_path = root.join("logo.png");
+        let mut logo = RgbaImage::from_pixel(8, 8, image::Rgba([0, 0, 0, 0]));
+        for y in 3..5 {
+            for x in 3..5 {
+                logo.put_pixel(x, y, image::Rgba([255, 0, 0, 255]));
+            }
+        }
+        logo.save(&logo_path).expect("write logo");
+
+        let settings = StampSettings::try_from(StampSettingsInput {
+            position: "좌상단".to_string(),
+            size_percent: Some(50.0),
+            margin_percent: 0.0,
+            pdf_flatten: true,
+            pdf_matte_color: Some([0, 0, 255]),
+            ..Default::default()
+        })
+        .expect("settings");
+        let stream = build_logo_stream(
+            &logo_path,
+            &settings.logo_style,
+            settings.size_mode,
+            settings.matte_color,
+            &settings.retry,
+        )
+        .expect("logo stream");
+
+        let mut pages = vec![pdf_flatten::RasterPage {
+            image: RgbaImage::from_pixel(100, 100, image::Rgba([255, 255, 255, 255])),
+            width_pt: 100.0,
+            height_pt: 100.0,
+        }];
+        stamp_raster_pages(
+            &mut pages,
+            Path::new("input.pdf"),
+            &settings,
+            &stream,
+            &mut |_, _| {},
+        )
+        .expect("stamp raster");
+
+        let page = &pages[0].image;
+        assert_eq!(page.get_pixel(1, 1).0, [0, 0, 255, 255]);
+        assert_eq!(page.get_pixel(25, 25).0, [255, 0, 0, 255]);
+        assert_eq!(page.get_pixel(99, 99).0, [255, 255, 255, 255]);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn stamp_pdfs_writes_metadata_into_info_dictionary() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-pdf-metadata-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_pdf = root.join("input.pdf");
+        let logo_path = root.join("logo.png");
+        write_minimal_pdf(&input_pdf, 1);
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            embed_metadata: Some("run-42".to_string()),
+            ..Default::default()
+        };
+        let paths = vec![input_pdf.to_string_lossy().to_string()];
+        let results = stamp_pdfs(&paths, settings, &logo_path, None);
+        assert!(results[0].ok, "expected success: {:?}", results[0].error);
+
+        let output_doc =
+            Document::load(results[0].output_path.as_ref().expect("output")).expect("load output");
+        let info_id = output_doc
+            .trailer
+            .get(b"Info")
+            .and_then(Object::as_reference)
+            .expect("info ref");
+        let info = output_doc.get_dictionary(info_id).expect("info");
+        let value = info
+            .get(metadata::METADATA_KEY.as_bytes())
+            .and_then(Object::as_str)
+            .expect("metadata entry");
+        assert_eq!(value, b"run-42");
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn pdf_a_output_carries_output_intent_and_xmp_metadata() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-pdf-a-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_pdf = root.join("input.pdf");
+        let logo_path = root.join("logo.png");
+        write_minimal_pdf(&input_pdf, 1);
+        write_test_png(&logo_path, 8, 8, [255, 0, 0, 255]);
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            pdf_a: true,
+            ..Default::default()
+        };
+        let paths = vec![input_pdf.to_string_lossy().to_string()];
+        let results = stamp_pdfs(&paths, settings, &logo_path, None);
+        assert!(results[0].ok, "expected success: {:?}", results[0].error);
+
+        let output_doc =
+            Document::load(results[0].output_path.as_ref().expect("output")).expect("load output");
+        let catalog = output_doc.catalog().expect("catalog");
+
+        let intents = catalog
+            .get(b"OutputIntents")
+            .and_then(Object::as_array)
+            .expect("output intents");
+        let intent = output_doc
+            .get_dictionary(intents[0].as_reference().expect("intent ref"))
+            .expect("intent");
+        assert_eq!(
+            intent.get(b"S").and_then(Object::as_name).expect("subtype"),
+            b"GTS_PDFA1"
+        );
+        let profile_id = intent
+            .get(b"DestOutputProfile")
+            .and_then(Object::as_reference)
+            .expect("profile ref");
+        assert!(output_doc.get_object(profile_id).is_ok());
+
+        let metadata_id = catalog
+            .get(b"Metadata")
+            .and_then(Object::as_reference)
+            .expect("metadata ref");
+        let metadata = output_doc
+            .get_object(metadata_id)
+            .and_then(Object::as_stream)
+            .expect("metadata stream");
+        assert!(!metadata.dict.has(b"Filter"), "XMP must stay uncompressed");
+        let xmp = String::from_utf8_lossy(&metadata.content);
+        assert!(xmp.contains("<pdfaid:part>2</pdfaid:part>"));
+        assert!(xmp.contains("<pdfaid:conformance>B</pdfaid:conformance>"));
+        assert!(output_doc.trailer.get(b"ID").is_ok());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn pdf_selected_as_its_own_logo_fails_with_logo_is_input() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-pdf-logo-is-input-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_pdf = root.join("input.pdf");
+        write_minimal_two_page_pdf(&input_pdf);
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            ..Default::default()
+        };
+        let results = stamp_pdfs(
+            &[input_pdf.to_string_lossy().to_string()],
+            settings,
+            &input_pdf,
+            None,
+        );
+
+        assert_eq!(
+            results[0].code.as_deref(),
+            Some(image_engine::CODE_LOGO_IS_INPUT)
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
 }
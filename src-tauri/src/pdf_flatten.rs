@@ -0,0 +1,290 @@
+use crate::i18n::Message;
+use image::imageops::{replace, resize, FilterType};
+use image::RgbaImage;
+use lopdf::content::{Content, Operation};
+use lopdf::{dictionary, Document, Object, Stream};
+use pdfium_render::prelude::*;
+use std::path::Path;
+
+/// 평탄화 모드에서 페이지를 래스터로 만드는 해상도입니다.
+pub(crate) const FLATTEN_DPI: f32 = 150.0;
+
+/// 렌더링된 페이지 이미지와 원래 페이지 크기(pt)입니다.
+pub(crate) struct RasterPage {
+    pub(crate) image: RgbaImage,
+    pub(crate) width_pt: f64,
+    pub(crate) height_pt: f64,
+}
+
+/// pdfium 라이브러리는 시스템 경로 또는 실행 폴더에서 찾습니다.
+fn load_pdfium() -> Result<Pdfium, Message> {
+    let bindings = Pdfium::bind_to_system_library()
+        .or_else(|_| Pdfium::bind_to_library(Pdfium::pdfium_platform_library_name_at_path("./")))
+        .map_err(|e| Message::new("PDFIUM_LOAD_FAILED").arg(e.to_string()))?;
+    Ok(Pdfium::new(bindings))
+}
+
+/// pdfium으로 모든 페이지를 `dpi` 해상도의 이미지로 렌더링합니다.
+pub(crate) fn render_pages(input_path: &Path, dpi: f32) -> Result<Vec<RasterPage>, Message> {
+    let pdfium = load_pdfium()?;
+    let document = pdfium
+        .load_pdf_from_file(input_path, None)
+        .map_err(|e| Message::new("PDF_RENDER_OPEN_FAILED").arg(e.to_string()))?;
+
+    let render_config = PdfRenderConfig::new().scale_page_by_factor(dpi / 72.0);
+    document
+        .pages()
+        .iter()
+        .enumerate()
+        .map(|(index, page)| render_one(&page, index + 1, &render_config))
+        .collect()
+}
+
+/// 1부터 세는 `page_number` 한 페이지만 렌더링합니다.
+pub(crate) fn render_page(
+    input_path: &Path,
+    page_number: u32,
+    dpi: f32,
+) -> Result<RasterPage, Message> {
+    let pdfium = load_pdfium()?;
+    let document = pdfium
+        .load_pdf_from_file(input_path, None)
+        .map_err(|e| Message::new("PDF_RENDER_OPEN_FAILED").arg(e.to_string()))?;
+    let page = page_number
+        .checked_sub(1)
+        .and_then(|index| PdfPageIndex::try_from(index).ok())
+        .and_then(|index| document.pages().get(index).ok())
+        .ok_or_else(|| Message::new("PAGE_NOT_FOUND").arg(page_number.to_string()))?;
+
+    let render_config = PdfRenderConfig::new().scale_page_by_factor(dpi / 72.0);
+    render_one(&page, page_number as usize, &render_config)
+}
+
+fn render_one(
+    page: &PdfPage,
+    page_number: usize,
+    render_config: &PdfRenderConfig,
+) -> Result<RasterPage, Message> {
+    let bitmap = page.render_with_config(render_config).map_err(|e| {
+        Message::new("PAGE_RENDER_FAILED")
+            .arg(page_number.to_string())
+            .arg(e.to_string())
+    })?;
+    Ok(RasterPage {
+        image: bitmap.as_image().to_rgba8(),
+        width_pt: f64::from(page.width().value),
+        height_pt: f64::from(page.height().value),
+    })
+}
+
+/// PDF 좌표(pt, 좌하단 원점)의 로고 영역을 페이지 이미지 픽셀로 옮겨 로고를 덮어씁니다.
+pub(crate) fn stamp_raster_page(
+    page: &mut RasterPage,
+    (x, y, draw_width, draw_height): (f64, f64, f64, f64),
+    logo: &RgbaImage,
+    filter: FilterType,
+) {
+    let (left_px, top_px, width_px, height_px) = pixel_rect(page, (x, y, draw_width, draw_height));
+    let resized = resize(logo, width_px, height_px, filter);
+    replace(&mut page.image, &resized, left_px, top_px);
+}
+
+/// 배너 로고를 `opacity`만큼 곱하기(multiply)로 합성합니다. 흰 바탕은 페이지를 가리지 않고 투명한 곳은 그대로 둡니다.
+pub(crate) fn multiply_raster_page(
+    page: &mut RasterPage,
+    rect: (f64, f64, f64, f64),
+    logo: &RgbaImage,
+    opacity: f64,
+    filter: FilterType,
+) {
+    let (left_px, top_px, width_px, height_px) = pixel_rect(page, rect);
+    let resized = resize(logo, width_px, height_px, filter);
+    let (page_width, page_height) = page.image.dimensions();
+
+    for (lx, ly, logo_px) in resized.enumerate_pixels() {
+        let (px, py) = (left_px + i64::from(lx), top_px + i64::from(ly));
+        if px < 0 || py < 0 || px >= i64::from(page_width) || py >= i64::from(page_height) {
+            continue;
+        }
+        let alpha = f64::from(logo_px[3]) / 255.0 * opacity;
+        let target = page.image.get_pixel_mut(px as u32, py as u32);
+        for channel in 0..3 {
+            let factor = 1.0 - alpha + alpha * f64::from(logo_px[channel]) / 255.0;
+            target[channel] = (f64::from(target[channel]) * factor).round() as u8;
+        }
+    }
+}
+
+/// PDF 좌표(pt, 좌하단 원점)의 영역을 페이지 이미지의 (왼쪽, 위, 폭, 높이) 픽셀로 바꿉니다.
+fn pixel_rect(
+    page: &RasterPage,
+    (x, y, draw_width, draw_height): (f64, f64, f64, f64),
+) -> (i64, i64, u32, u32) {
+    let scale_x = f64::from(page.image.width()) / page.width_pt;
+    let scale_y = f64::from(page.image.height()) / page.height_pt;
+    (
+        (x * scale_x).round() as i64,
+        ((page.height_pt - y - draw_height) * scale_y).round() as i64,
+        ((draw_width * scale_x).round() as u32).max(1),
+        ((draw_height * scale_y).round() as u32).max(1),
+    )
+}
+
+/// 페이지마다 이미지 한 장만 그리는 새 PDF를 만듭니다. 페이지 크기는 원본과 같습니다.
+pub(crate) fn build_image_pdf(pages: &[RasterPage]) -> Result<Document, Message> {
+    if pages.is_empty() {
+        return Err(Message::new("PDF_NO_PAGES"));
+    }
+
+    let mut doc = Document::with_version("1.5");
+    let pages_id = doc.new_object_id();
+    let mut kids = Vec::with_capacity(pages.len());
+
+    for page in pages {
+        let (width, height) = page.image.dimensions();
+        let rgb: Vec<u8> = page
+            .image
+            .pixels()
+            .flat_map(|px| {
+                let a = u16::from(px[3]);
+                [0, 1, 2].map(|c| ((u16::from(px[c]) * a + 255 * (255 - a)) / 255) as u8)
+            })
+            .collect();
+
+        let mut image_stream = Stream::new(
+            dictionary! {
+                "Type" => "XObject",
+                "Subtype" => "Image",
+                "Width" => i64::from(width),
+                "Height" => i64::from(height),
+                "ColorSpace" => "DeviceRGB",
+                "BitsPerComponent" => 8,
+            },
+            rgb,
+        );
+        image_stream
+            .compress()
+            .map_err(|e| Message::new("PAGE_IMAGE_COMPRESS_FAILED").arg(e.to_string()))?;
+        let image_id = doc.add_object(image_stream);
+
+        let content = Content {
+            operations: vec![
+                Operation::new("q", vec![]),
+                Operation::new(
+                    "cm",
+                    vec![
+                        page.width_pt.into(),
+                        0.into(),
+                        0.into(),
+                        page.height_pt.into(),
+                        0.into(),
+                        0.into(),
+                    ],
+                ),
+                Operation::new("Do", vec![Object::Name(b"Page".to_vec())]),
+                Operation::new("Q", vec![]),
+            ],
+        };
+        let content_bytes = content
+            .encode()
+            .map_err(|e| Message::new("PAGE_CONTENT_FAILED").arg(e.to_string()))?;
+        let content_id = doc.add_object(Stream::new(dictionary! {}, content_bytes));
+
+        let page_id = doc.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "MediaBox" => vec![0.into(), 0.into(), page.width_pt.into(), page.height_pt.into()],
+            "Contents" => content_id,
+            "Resources" => dictionary! {
+                "XObject" => dictionary! { "Page" => image_id },
+            },
+        });
+        kids.push(Object::Reference(page_id));
+    }
+
+    let count = kids.len() as i64;
+    doc.objects.insert(
+        pages_id,
+        Object::Dictionary(dictionary! {
+            "Type" => "Pages",
+            "Kids" => kids,
+            "Count" => count,
+        }),
+    );
+    let catalog_id = doc.add_object(dictionary! {
+        "Type" => "Catalog",
+        "Pages" => pages_id,
+    });
+    doc.trailer.set("Root", catalog_id);
+
+    Ok(doc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raster_page(width_pt: f64, height_pt: f64) -> RasterPage {
+        RasterPage {
+            image: RgbaImage::from_pixel(
+                (width_pt * 2.0) as u32,
+                (height_pt * 2.0) as u32,
+                image::Rgba([255, 255, 255, 255]),
+            ),
+            width_pt,
+            height_pt,
+        }
+    }
+
+    #[test]
+    fn image_pdf_keeps_page_count_and_draws_one_image_per_page() {
+        let mut pages = vec![
+            raster_page(100.0, 50.0),
+            raster_page(60.0, 80.0),
+            raster_page(40.0, 40.0),
+        ];
+        let logo = RgbaImage::from_pixel(4, 4, image::Rgba([255, 0, 0, 255]));
+        stamp_raster_page(
+            &mut pages[0],
+            (90.0, 0.0, 10.0, 10.0),
+            &logo,
+            FilterType::Lanczos3,
+        );
+        // 좌하단 원점의 (90, 0) 영역은 이미지 오른쪽 아래 20x20px입니다.
+        assert_eq!(pages[0].image.get_pixel(199, 99).0, [255, 0, 0, 255]);
+        assert_eq!(pages[0].image.get_pixel(179, 79).0, [255, 255, 255, 255]);
+
+        let doc = build_image_pdf(&pages).expect("image pdf");
+        let doc_pages = doc.get_pages();
+        assert_eq!(doc_pages.len(), 3);
+
+        for page_id in doc_pages.values() {
+            let page = doc.get_dictionary(*page_id).expect("page");
+            let xobjects = page
+                .get(b"Resources")
+                .and_then(Object::as_dict)
+                .and_then(|resources| resources.get(b"XObject"))
+                .and_then(Object::as_dict)
+                .expect("xobjects");
+            assert_eq!(xobjects.len(), 1);
+
+            let image_id = xobjects
+                .get(b"Page")
+                .and_then(Object::as_reference)
+                .expect("image ref");
+            let image = doc
+                .get_object(image_id)
+                .and_then(Object::as_stream)
+                .expect("image");
+            assert_eq!(
+                image.dict.get(b"Subtype").and_then(Object::as_name).ok(),
+                Some(&b"Image"[..])
+            );
+
+            let content = doc.get_page_content(*page_id).expect("content");
+            let ops = Content::decode(&content).expect("decode").operations;
+            let painted: Vec<_> = ops.iter().filter(|op| op.operator == "Do").collect();
+            assert_eq!(painted.len(), 1);
+        }
+    }
+}
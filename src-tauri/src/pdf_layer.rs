@@ -0,0 +1,76 @@
+use crate::i18n::Message;
+use lopdf::{dictionary, Dictionary, Document, Object, ObjectId};
+
+/// PDF 뷰어의 레이어 목록에 보이는 로고 레이어 이름입니다.
+pub(crate) const WATERMARK_LAYER_NAME: &str = "CornerBrand Watermark";
+
+/// 로고용 선택적 콘텐츠 그룹(OCG)을 만들어 카탈로그의 `/OCProperties`에 등록하고 그 ID를 돌려줍니다.
+/// 원본에 이미 레이어가 있으면 그 목록 뒤에 덧붙이며, 기본 상태는 켜짐입니다.
+pub(crate) fn add_watermark_layer(doc: &mut Document) -> Result<ObjectId, Message> {
+    let catalog_id = doc
+        .trailer
+        .get(b"Root")
+        .and_then(Object::as_reference)
+        .map_err(|e| Message::new("PDF_CATALOG_NOT_FOUND").arg(e.to_string()))?;
+    let ocg_id = doc.add_object(dictionary! {
+        "Type" => "OCG",
+        "Name" => Object::string_literal(WATERMARK_LAYER_NAME),
+    });
+
+    let properties_ref = doc
+        .get_dictionary(catalog_id)
+        .map_err(|e| Message::new("PDF_CATALOG_READ_FAILED").arg(e.to_string()))?
+        .get(b"OCProperties")
+        .ok()
+        .cloned();
+    let mut properties = resolve_dictionary(doc, properties_ref.as_ref());
+    push_reference(&mut properties, b"OCGs", ocg_id);
+
+    let config_ref = properties.get(b"D").ok().cloned();
+    let mut config = resolve_dictionary(doc, config_ref.as_ref());
+    push_reference(&mut config, b"Order", ocg_id);
+    push_reference(&mut config, b"ON", ocg_id);
+    store_dictionary(doc, &mut properties, b"D", config_ref.as_ref(), config);
+
+    if let Some(Object::Reference(id)) = properties_ref {
+        doc.objects.insert(id, Object::Dictionary(properties));
+    } else {
+        doc.get_dictionary_mut(catalog_id)
+            .map_err(|e| Message::new("PDF_CATALOG_READ_FAILED").arg(e.to_string()))?
+            .set("OCProperties", properties);
+    }
+
+    Ok(ocg_id)
+}
+
+/// 직접 값이든 참조든 사전을 복사해 옵니다. 없거나 사전이 아니면 빈 사전입니다.
+fn resolve_dictionary(doc: &Document, value: Option<&Object>) -> Dictionary {
+    match value {
+        Some(Object::Dictionary(dict)) => dict.clone(),
+        Some(Object::Reference(id)) => doc.get_dictionary(*id).cloned().unwrap_or_default(),
+        _ => Dictionary::new(),
+    }
+}
+
+/// 원래 참조였던 사전은 그 객체를, 아니면 `parent`의 직접 값을 갱신합니다.
+fn store_dictionary(
+    doc: &mut Document,
+    parent: &mut Dictionary,
+    key: &[u8],
+    original: Option<&Object>,
+    value: Dictionary,
+) {
+    match original {
+        Some(Object::Reference(id)) => {
+            doc.objects.insert(*id, Object::Dictionary(value));
+        }
+        _ => parent.set(key, value),
+    }
+}
+
+fn push_reference(dict: &mut Dictionary, key: &[u8], id: ObjectId) {
+    match dict.get_mut(key) {
+        Ok(Object::Array(items)) => items.push(Object::Reference(id)),
+        _ => dict.set(key, vec![Object::Reference(id)]),
+    }
+}
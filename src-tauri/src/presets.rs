@@ -0,0 +1,148 @@
+use crate::i18n::Message;
+use crate::image_engine::StampSettingsInput;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const PRESETS_FILE_NAME: &str = "cornerbrand_presets.json";
+
+const MAX_PRESET_NAME_CHARS: usize = 64;
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PresetStore {
+    #[serde(default)]
+    presets: BTreeMap<String, StampSettingsInput>,
+}
+
+pub fn save_preset(
+    config_dir: &Path,
+    name: &str,
+    settings: StampSettingsInput,
+    overwrite: bool,
+) -> Result<(), Message> {
+    let name = validate_preset_name(name)?;
+    let mut store = read_store(config_dir)?;
+
+    if !overwrite && store.presets.contains_key(&name) {
+        return Err(Message::new("PRESET_EXISTS").arg(name));
+    }
+
+    store.presets.insert(name, settings);
+    write_store(config_dir, &store)
+}
+
+pub fn list_presets(config_dir: &Path) -> Result<Vec<String>, Message> {
+    let store = read_store(config_dir)?;
+    Ok(store.presets.into_keys().collect())
+}
+
+pub fn load_preset(config_dir: &Path, name: &str) -> Result<StampSettingsInput, Message> {
+    let name = validate_preset_name(name)?;
+    let mut store = read_store(config_dir)?;
+    store
+        .presets
+        .remove(&name)
+        .ok_or_else(|| Message::new("PRESET_NOT_FOUND").arg(name))
+}
+
+fn validate_preset_name(name: &str) -> Result<String, Message> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() {
+        return Err(Message::new("PRESET_NAME_EMPTY"));
+    }
+    if trimmed.chars().count() > MAX_PRESET_NAME_CHARS {
+        return Err(Message::new("PRESET_NAME_TOO_LONG").arg(MAX_PRESET_NAME_CHARS.to_string()));
+    }
+    if trimmed.chars().any(char::is_control) {
+        return Err(Message::new("PRESET_NAME_CONTROL_CHARS"));
+    }
+
+    Ok(trimmed.to_string())
+}
+
+fn presets_path(config_dir: &Path) -> PathBuf {
+    config_dir.join(PRESETS_FILE_NAME)
+}
+
+fn read_store(config_dir: &Path) -> Result<PresetStore, Message> {
+    let path = presets_path(config_dir);
+    if !path.exists() {
+        return Ok(PresetStore::default());
+    }
+
+    let bytes =
+        fs::read(&path).map_err(|e| Message::new("PRESET_READ_FAILED").arg(e.to_string()))?;
+    parse_store(&bytes)
+}
+
+fn write_store(config_dir: &Path, store: &PresetStore) -> Result<(), Message> {
+    fs::create_dir_all(config_dir)
+        .map_err(|e| Message::new("SETTINGS_DIR_CREATE_FAILED").arg(e.to_string()))?;
+    let payload = serialize_store(store)?;
+    fs::write(presets_path(config_dir), payload)
+        .map_err(|e| Message::new("PRESET_SAVE_FAILED").arg(e.to_string()))
+}
+
+fn parse_store(bytes: &[u8]) -> Result<PresetStore, Message> {
+    serde_json::from_slice(bytes).map_err(|e| Message::new("PRESET_MALFORMED").arg(e.to_string()))
+}
+
+fn serialize_store(store: &PresetStore) -> Result<Vec<u8>, Message> {
+    serde_json::to_vec_pretty(store)
+        .map_err(|e| Message::new("PRESET_SERIALIZE_FAILED").arg(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn sample_settings() -> StampSettingsInput {
+        StampSettingsInput {
+            position: "좌상단".to_string(),
+            size_preset: "보통".to_string(),
+            size_percent: Some(24.0),
+            margin_percent: 3.5,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn preset_store_round_trips_through_serializer() {
+        let mut store = PresetStore::default();
+        store.presets.insert("명함".to_string(), sample_settings());
+
+        let bytes = serialize_store(&store).expect("serialize");
+        let parsed = parse_store(&bytes).expect("parse");
+
+        let restored = parsed.presets.get("명함").expect("preset");
+        assert_eq!(restored.position, "좌상단");
+        assert_eq!(restored.size_percent, Some(24.0));
+        assert_eq!(restored.margin_percent, 3.5);
+    }
+
+    #[test]
+    fn save_and_load_preset_rejects_collisions_and_invalid_names() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-presets-{nonce}"));
+
+        save_preset(&root, " 명함 ", sample_settings(), false).expect("save preset");
+        assert_eq!(list_presets(&root).expect("list"), vec!["명함".to_string()]);
+
+        let loaded = load_preset(&root, "명함").expect("load preset");
+        assert_eq!(loaded.position, "좌상단");
+
+        assert!(save_preset(&root, "명함", sample_settings(), false).is_err());
+        assert!(save_preset(&root, "명함", sample_settings(), true).is_ok());
+        assert!(save_preset(&root, "   ", sample_settings(), false).is_err());
+        assert!(save_preset(&root, "a\nb", sample_settings(), false).is_err());
+        assert!(load_preset(&root, "없는 프리셋").is_err());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,401 @@
+use crate::i18n::Message;
+use crate::image_engine::{self, StampSettings, StampSettingsInput};
+use crate::path_policy;
+use crate::pdf_engine;
+use crate::pdf_flatten;
+use crate::svg_logo;
+use image::imageops::{replace, resize, FilterType};
+use image::{ImageFormat, RgbaImage};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const DIVIDER_COLOR: [u8; 4] = [40, 40, 40, 255];
+
+/// 요청별 미리보기 폴더(`<temp>/cornerbrand-preview/<request_id>`)를 모아 두는 폴더 이름입니다.
+pub const PREVIEW_DIR_NAME: &str = "cornerbrand-preview";
+
+/// 앱을 시작할 때 이보다 오래된 미리보기 폴더를 지웁니다.
+pub const STALE_PREVIEW_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// PDF 페이지 미리보기 렌더링 해상도. 화면 썸네일에는 이 정도로 충분하고 큰 페이지도 빨리 끝납니다.
+pub const PDF_PREVIEW_DPI: f32 = 72.0;
+
+pub fn preview_root() -> PathBuf {
+    std::env::temp_dir().join(PREVIEW_DIR_NAME)
+}
+
+/// `preview_root` 아래에서 마지막 수정 후 `max_age`가 지난 하위 폴더를 지우고 지운 개수를 돌려줍니다.
+/// 루트 폴더가 없으면 0입니다. 지우지 못한 폴더는 다음 정리 때 다시 시도합니다.
+pub fn cleanup_previews(preview_root: &Path, max_age: Duration) -> Result<usize, Message> {
+    let entries = match std::fs::read_dir(preview_root) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(Message::new("PREVIEW_DIR_READ_FAILED").arg(e.to_string())),
+    };
+
+    let now = SystemTime::now();
+    let removed = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+        .filter(|entry| {
+            entry
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok())
+                .is_some_and(|age| age > max_age)
+        })
+        .filter(|entry| std::fs::remove_dir_all(entry.path()).is_ok())
+        .count();
+    Ok(removed)
+}
+
+/// 원본(왼쪽)과 로고 삽입 결과(오른쪽)를 구분선과 함께 한 장으로 붙인 PNG 바이트를 돌려줍니다.
+/// 파일은 쓰지 않습니다.
+pub fn compare_preview(
+    input_path: &Path,
+    settings_input: StampSettingsInput,
+    logo_path: &Path,
+) -> Result<Vec<u8>, Message> {
+    if path_policy::detect_supported_image(input_path).is_none() {
+        return Err(Message::new("PREVIEW_IMAGES_ONLY"));
+    }
+
+    let size_mode = image_engine::parse_size_mode(settings_input.size_mode.as_deref())?;
+    let settings = StampSettings::try_from(settings_input)?;
+    let source = image::open(input_path)
+        .map_err(|e| Message::new("IMAGE_READ_FAILED").arg(e.to_string()))?
+        .to_rgba8();
+    if source.width() == 0 || source.height() == 0 {
+        return Err(Message::new("IMAGE_SIZE_INVALID"));
+    }
+    let logo = svg_logo::open_logo(logo_path, size_mode)
+        .map_err(|e| Message::new("LOGO_READ_FAILED").arg(e))?;
+
+    let stamped = image_engine::try_stamp_rgba(&source, &logo, &settings)?;
+    let composite = side_by_side(&source, &stamped);
+
+    let mut png_bytes = Vec::new();
+    composite
+        .write_to(&mut Cursor::new(&mut png_bytes), ImageFormat::Png)
+        .map_err(|e| Message::new("PREVIEW_CREATE_FAILED").arg(e.to_string()))?;
+    Ok(png_bytes)
+}
+
+/// `page_number`(1부터) 페이지에만 로고를 넣은 PDF를 임시 폴더에 저장한 뒤 그 페이지만 `PDF_PREVIEW_DPI`로 렌더링한
+/// PNG 바이트를 돌려줍니다. 임시 파일은 돌려주기 전에 지웁니다.
+pub fn preview_pdf_page(
+    input_path: &Path,
+    page_number: u32,
+    settings_input: StampSettingsInput,
+    logo_path: &Path,
+) -> Result<Vec<u8>, Message> {
+    if !path_policy::is_supported_pdf(input_path) {
+        return Err(Message::new("PREVIEW_PDF_ONLY"));
+    }
+    let (page_count, _) = pdf_engine::probe_pdf(input_path)?;
+    if page_number == 0 || page_number > page_count {
+        return Err(Message::new("PREVIEW_PAGE_OUT_OF_RANGE").arg(page_count.to_string()));
+    }
+
+    let nonce = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let work_dir = preview_root().join(format!("pdf-page-{nonce}"));
+    std::fs::create_dir_all(&work_dir)
+        .map_err(|e| Message::new("PREVIEW_DIR_CREATE_FAILED").arg(e.to_string()))?;
+    let rendered = render_stamped_page(
+        input_path,
+        page_number,
+        settings_input,
+        logo_path,
+        &work_dir.join("preview.pdf"),
+    );
+    let _ = std::fs::remove_dir_all(&work_dir);
+
+    let mut png_bytes = Vec::new();
+    rendered?
+        .image
+        .write_to(&mut Cursor::new(&mut png_bytes), ImageFormat::Png)
+        .map_err(|e| Message::new("PREVIEW_CREATE_FAILED").arg(e.to_string()))?;
+    Ok(png_bytes)
+}
+
+fn render_stamped_page(
+    input_path: &Path,
+    page_number: u32,
+    settings_input: StampSettingsInput,
+    logo_path: &Path,
+    stamped_path: &Path,
+) -> Result<pdf_flatten::RasterPage, Message> {
+    stamp_preview_page(
+        input_path,
+        page_number,
+        settings_input,
+        logo_path,
+        stamped_path,
+    )?;
+    pdf_flatten::render_page(stamped_path, page_number, PDF_PREVIEW_DPI)
+}
+
+/// 렌더링할 페이지에만 로고를 넣어 `stamped_path`에 저장합니다.
+fn stamp_preview_page(
+    input_path: &Path,
+    page_number: u32,
+    settings_input: StampSettingsInput,
+    logo_path: &Path,
+    stamped_path: &Path,
+) -> Result<(), Message> {
+    let result = pdf_engine::stamp_pdf_page_to_path(
+        &input_path.to_string_lossy(),
+        page_number,
+        settings_input,
+        logo_path,
+        stamped_path,
+    );
+    if !result.ok {
+        return Err(result
+            .error
+            .unwrap_or_else(|| Message::new("PDF_STAMP_FAILED")));
+    }
+    Ok(())
+}
+
+/// 오른쪽 이미지를 왼쪽 높이에 맞춰 비율대로 줄이거나 늘린 뒤 나란히 붙입니다.
+fn side_by_side(left: &RgbaImage, right: &RgbaImage) -> RgbaImage {
+    let height = left.height();
+    let right = if right.height() == height {
+        right.clone()
+    } else {
+        let width = (u64::from(right.width()) * u64::from(height) / u64::from(right.height()))
+            .max(1) as u32;
+        resize(right, width, height, FilterType::Lanczos3)
+    };
+
+    let divider = (height / 100).max(2);
+    let mut canvas = RgbaImage::from_pixel(
+        left.width() + divider + right.width(),
+        height,
+        image::Rgba(DIVIDER_COLOR),
+    );
+    replace(&mut canvas, left, 0, 0);
+    replace(&mut canvas, &right, i64::from(left.width() + divider), 0);
+    canvas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn compare_preview_is_about_twice_as_wide_as_input() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-compare-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_path = root.join("input.png");
+        let logo_path = root.join("logo.png");
+        RgbaImage::from_pixel(120, 80, image::Rgba([230, 230, 230, 255]))
+            .save(&input_path)
+            .expect("write input");
+        RgbaImage::from_pixel(8, 8, image::Rgba([255, 0, 0, 255]))
+            .save(&logo_path)
+            .expect("write logo");
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            margin_percent: 2.0,
+            ..Default::default()
+        };
+        let png = compare_preview(&input_path, settings, &logo_path).expect("preview");
+        let preview = image::load_from_memory(&png).expect("decode").to_rgba8();
+
+        assert_eq!(preview.height(), 80);
+        assert!(
+            (240..=250).contains(&preview.width()),
+            "unexpected width {}",
+            preview.width()
+        );
+        assert_eq!(preview.get_pixel(0, 0).0, [230, 230, 230, 255]);
+        assert!(
+            fs::read_dir(&root).expect("list").count() == 2,
+            "preview should not write files"
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn preview_stamps_only_the_requested_page() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-pdf-page-stamp-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_path = root.join("input.pdf");
+        let logo_path = root.join("logo.png");
+        let stamped_path = root.join("stamped.pdf");
+        write_two_page_pdf(&input_path);
+        RgbaImage::from_pixel(8, 8, image::Rgba([255, 0, 0, 255]))
+            .save(&logo_path)
+            .expect("write logo");
+        let settings = || StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            pdf_flatten: true,
+            ..Default::default()
+        };
+
+        assert!(preview_pdf_page(&input_path, 0, settings(), &logo_path).is_err());
+        assert!(preview_pdf_page(&input_path, 3, settings(), &logo_path).is_err());
+        stamp_preview_page(&input_path, 2, settings(), &logo_path, &stamped_path)
+            .expect("stamp preview page");
+
+        let doc = lopdf::Document::load(&stamped_path).expect("load stamped");
+        let pages = doc.get_pages();
+        assert_eq!(pages.len(), 2, "preview keeps page numbering");
+        let has_logo = |page_number: u32| {
+            let (resources, _) = doc
+                .get_page_resources(pages[&page_number])
+                .expect("resources");
+            resources
+                .and_then(|resources| resources.get(b"XObject").ok())
+                .and_then(|xobjects| xobjects.as_dict().ok())
+                .is_some_and(|xobjects| !xobjects.is_empty())
+        };
+        assert!(!has_logo(1));
+        assert!(has_logo(2));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    #[ignore = "pdfium 라이브러리가 있어야 렌더링할 수 있습니다"]
+    fn preview_pdf_page_renders_requested_page_as_png() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-pdf-page-preview-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let input_path = root.join("input.pdf");
+        let logo_path = root.join("logo.png");
+        write_two_page_pdf(&input_path);
+        RgbaImage::from_pixel(8, 8, image::Rgba([255, 0, 0, 255]))
+            .save(&logo_path)
+            .expect("write logo");
+        let settings = || StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            ..Default::default()
+        };
+
+        let png = preview_pdf_page(&input_path, 2, settings(), &logo_path).expect("preview");
+        // 200x100pt 페이지를 72dpi로 그리면 200x100px입니다.
+        let preview = image::load_from_memory(&png).expect("decode");
+        assert!(
+            (195..=205).contains(&preview.width()),
+            "{}",
+            preview.width()
+        );
+        assert!(
+            (95..=105).contains(&preview.height()),
+            "{}",
+            preview.height()
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    fn write_two_page_pdf(path: &Path) {
+        use lopdf::{dictionary, Document, Object, Stream};
+
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let kids: Vec<Object> = (0..2)
+            .map(|_| {
+                let content_id = doc.add_object(Stream::new(dictionary! {}, Vec::new()));
+                doc.add_object(dictionary! {
+                    "Type" => "Page",
+                    "Parent" => pages_id,
+                    "MediaBox" => vec![0.into(), 0.into(), 200.into(), 100.into()],
+                    "Contents" => content_id,
+                    "Resources" => dictionary! {},
+                })
+                .into()
+            })
+            .collect();
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => kids,
+                "Count" => 2,
+            }),
+        );
+        let catalog_id = doc.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+        });
+        doc.trailer.set("Root", catalog_id);
+        doc.save(path).expect("write pdf fixture");
+    }
+
+    // Windows에서는 폴더를 `File::open`으로 열 수 없어 폴더의 수정 시각을 되돌릴 수 없습니다.
+    #[cfg(unix)]
+    #[test]
+    fn cleanup_previews_removes_only_stale_dirs() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-preview-cleanup-{nonce}"));
+        let old = root.join("old-request");
+        let fresh = root.join("fresh-request");
+        fs::create_dir_all(&old).expect("old dir");
+        fs::create_dir_all(&fresh).expect("fresh dir");
+        fs::write(old.join("preview.png"), b"x").expect("old file");
+
+        let two_days_ago = SystemTime::now() - Duration::from_secs(2 * 24 * 60 * 60);
+        fs::File::open(&old)
+            .and_then(|dir| dir.set_modified(two_days_ago))
+            .expect("age old dir");
+
+        assert_eq!(cleanup_previews(&root, STALE_PREVIEW_AGE), Ok(1));
+        assert!(!old.exists());
+        assert!(fresh.exists());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn cleanup_previews_keeps_fresh_dirs_and_ignores_missing_root() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-preview-fresh-{nonce}"));
+        let fresh = root.join("fresh-request");
+        fs::create_dir_all(&fresh).expect("fresh dir");
+
+        assert_eq!(cleanup_previews(&root, STALE_PREVIEW_AGE), Ok(0));
+        assert!(fresh.exists());
+        assert_eq!(
+            cleanup_previews(&root.join("missing"), STALE_PREVIEW_AGE),
+            Ok(0)
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
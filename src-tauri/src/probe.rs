@@ -0,0 +1,123 @@
+use crate::i18n::Message;
+use crate::path_policy::{self, FormatKind};
+use crate::pdf_engine;
+use image::ImageFormat;
+use serde::Serialize;
+use std::path::Path;
+
+/// 로고를 넣기 전에 화면에 보여 줄 파일 정보입니다. 이미지는 헤더만 읽고 전체를 디코딩하지 않습니다.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileProbe {
+    pub kind: FormatKind,
+    /// "JPEG", "PNG", "WEBP", "TIFF", "HEIF", "PDF" 중 하나입니다.
+    pub format: String,
+    pub byte_size: u64,
+    /// 이미지 픽셀 크기. HEIC/HEIF는 헤더만으로 알 수 없어 비어 있습니다.
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub page_count: Option<u32>,
+    /// PDF 첫 페이지 크기(pt)입니다.
+    pub page_width_pt: Option<f64>,
+    pub page_height_pt: Option<f64>,
+}
+
+pub fn probe_file(path: &Path) -> Result<FileProbe, Message> {
+    let byte_size = path
+        .metadata()
+        .map_err(|e| Message::new("FILE_INFO_FAILED").arg(e.to_string()))?
+        .len();
+
+    if path_policy::is_supported_pdf(path) {
+        let (page_count, (page_width, page_height)) = pdf_engine::probe_pdf(path)?;
+        return Ok(FileProbe {
+            kind: FormatKind::Pdf,
+            format: "PDF".to_string(),
+            byte_size,
+            width: None,
+            height: None,
+            page_count: Some(page_count),
+            page_width_pt: Some(page_width),
+            page_height_pt: Some(page_height),
+        });
+    }
+
+    let format_info = path_policy::detect_supported_image(path)
+        .ok_or_else(|| Message::new("UNSUPPORTED_PROBE_TYPE"))?;
+    let (format, dimensions) = if format_info.heif {
+        ("HEIF".to_string(), None)
+    } else {
+        let dimensions = image::image_dimensions(path)
+            .map_err(|e| Message::new("IMAGE_SIZE_READ_FAILED").arg(e.to_string()))?;
+        (format_name(format_info.format), Some(dimensions))
+    };
+
+    Ok(FileProbe {
+        kind: FormatKind::Raster,
+        format,
+        byte_size,
+        width: dimensions.map(|(width, _)| width),
+        height: dimensions.map(|(_, height)| height),
+        page_count: None,
+        page_width_pt: None,
+        page_height_pt: None,
+    })
+}
+
+/// 화면에 보여 줄 형식 이름입니다. `Debug` 출력은 `image` 버전마다 바뀔 수 있어 직접 정합니다.
+fn format_name(format: ImageFormat) -> String {
+    match format {
+        ImageFormat::Jpeg => "JPEG".to_string(),
+        ImageFormat::Png => "PNG".to_string(),
+        ImageFormat::WebP => "WEBP".to_string(),
+        ImageFormat::Tiff => "TIFF".to_string(),
+        other => other
+            .extensions_str()
+            .first()
+            .map_or_else(|| "UNKNOWN".to_string(), |ext| ext.to_ascii_uppercase()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::RgbaImage;
+    use std::fs;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn probe_file_reads_png_header() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-probe-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+
+        let png = root.join("photo.png");
+        RgbaImage::from_pixel(30, 20, image::Rgba([10, 20, 30, 255]))
+            .save(&png)
+            .expect("write png");
+
+        let probe = probe_file(&png).expect("probe");
+        assert_eq!(probe.kind, FormatKind::Raster);
+        assert_eq!(probe.format, "PNG");
+        assert_eq!((probe.width, probe.height), (Some(30), Some(20)));
+        assert_eq!(probe.byte_size, fs::metadata(&png).expect("meta").len());
+        assert!(probe.page_count.is_none());
+
+        assert!(probe_file(&root.join("missing.png")).is_err());
+
+        let jpeg = root.join("photo.jpeg");
+        image::DynamicImage::ImageRgba8(RgbaImage::new(4, 4))
+            .into_rgb8()
+            .save(&jpeg)
+            .expect("write jpeg");
+        assert_eq!(probe_file(&jpeg).expect("probe jpeg").format, "JPEG");
+        assert_eq!(format_name(ImageFormat::WebP), "WEBP");
+        assert_eq!(format_name(ImageFormat::Tiff), "TIFF");
+        assert_eq!(format_name(ImageFormat::Bmp), "BMP");
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
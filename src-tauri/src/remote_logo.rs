@@ -0,0 +1,233 @@
+use crate::i18n::Message;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// 앱 캐시 폴더 안에서 내려받은 로고를 두는 하위 폴더입니다.
+pub const LOGO_CACHE_DIR_NAME: &str = "logos";
+
+const FETCH_TIMEOUT: Duration = Duration::from_secs(15);
+const MAX_LOGO_BYTES: u64 = 20 * 1024 * 1024;
+/// 받아 둔 사본을 서버에 다시 묻지 않고 쓰는 기간입니다. 미리보기처럼 자주 부르는 명령이 매번 내려받지 않게 합니다.
+const CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// 내려받았거나 캐시에서 찾은 로고입니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchedLogo {
+    pub path: PathBuf,
+    /// 내려받지 못해 기한이 지난 사본을 썼을 때 결과에 덧붙일 경고입니다.
+    pub warning: Option<Message>,
+}
+
+/// `url`의 로고를 `cache_dir/logos`에 URL별로 저장하고 그 경로를 돌려줍니다.
+/// `CACHE_TTL` 안에 받아 둔 사본이 있으면 내려받지 않고 그 사본을 씁니다.
+/// 내려받지 못하거나(오프라인, 404 등) 이미지로 읽히지 않으면 기한이 지난 사본을 경고와 함께 쓰고, 사본도 없으면 오류입니다.
+pub fn fetch_logo(url: &str, cache_dir: &Path) -> Result<FetchedLogo, Message> {
+    let url = url.trim();
+    if !(url.starts_with("http://") || url.starts_with("https://")) {
+        return Err(Message::new("LOGO_URL_SCHEME"));
+    }
+
+    let cache_dir = cache_dir.join(LOGO_CACHE_DIR_NAME);
+    let cached = cached_logo(&cache_dir, url);
+    if let Some(path) = cached.as_ref().filter(|path| is_fresh(path)) {
+        return Ok(FetchedLogo {
+            path: path.clone(),
+            warning: None,
+        });
+    }
+    let fetched = download(url).and_then(|bytes| {
+        let format = image::guess_format(&bytes).map_err(|_| Message::new("DOWNLOAD_NOT_IMAGE"))?;
+        image::load_from_memory_with_format(&bytes, format)
+            .map_err(|e| Message::new("DOWNLOAD_DECODE_FAILED").arg(e.to_string()))?;
+        Ok((bytes, format))
+    });
+
+    match fetched {
+        Ok((bytes, format)) => {
+            let extension = format.extensions_str().first().copied().unwrap_or("img");
+            let path = cache_dir.join(format!("{}.{extension}", cache_key(url)));
+            store(&cache_dir, &path, &bytes)?;
+            if let Some(stale) = cached.filter(|stale| *stale != path) {
+                let _ = fs::remove_file(stale);
+            }
+            Ok(FetchedLogo {
+                path,
+                warning: None,
+            })
+        }
+        Err(message) => match cached {
+            Some(path) => Ok(FetchedLogo {
+                path,
+                warning: Some(Message::new("LOGO_CACHE_USED").arg(message)),
+            }),
+            None => Err(Message::new("LOGO_DOWNLOAD_NO_CACHE").arg(message)),
+        },
+    }
+}
+
+/// 수정 시각을 알 수 없거나 미래로 되어 있으면 새로 받도록 기한이 지난 것으로 봅니다.
+fn is_fresh(path: &Path) -> bool {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age < CACHE_TTL)
+}
+
+fn download(url: &str) -> Result<Vec<u8>, Message> {
+    let config = ureq::Agent::config_builder()
+        .timeout_global(Some(FETCH_TIMEOUT))
+        .build();
+    let agent = ureq::Agent::new_with_config(config);
+    let mut response = agent.get(url).call().map_err(|e| match e {
+        ureq::Error::StatusCode(status) => Message::new("LOGO_HTTP_STATUS").arg(status.to_string()),
+        other => Message::new("LOGO_CONNECT_FAILED").arg(other.to_string()),
+    })?;
+    response
+        .body_mut()
+        .with_config()
+        .limit(MAX_LOGO_BYTES)
+        .read_to_vec()
+        .map_err(|e| Message::new("LOGO_DOWNLOAD_FAILED").arg(e.to_string()))
+}
+
+/// 같은 폴더에 임시 파일로 쓴 뒤 이름을 바꿔, 받다 만 파일이 캐시에 남지 않게 합니다.
+fn store(cache_dir: &Path, path: &Path, bytes: &[u8]) -> Result<(), Message> {
+    fs::create_dir_all(cache_dir)
+        .map_err(|e| Message::new("LOGO_CACHE_DIR_FAILED").arg(e.to_string()))?;
+    let partial = path.with_extension("part");
+    fs::write(&partial, bytes)
+        .and_then(|()| fs::rename(&partial, path))
+        .map_err(|e| {
+            let _ = fs::remove_file(&partial);
+            Message::new("LOGO_CACHE_SAVE_FAILED").arg(e.to_string())
+        })
+}
+
+/// 확장자는 받은 이미지 형식을 따르므로 키가 같은 파일을 찾습니다.
+fn cached_logo(cache_dir: &Path, url: &str) -> Option<PathBuf> {
+    let key = cache_key(url);
+    fs::read_dir(cache_dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| {
+            path.file_stem().is_some_and(|stem| stem == key.as_str())
+                && path.extension().is_some_and(|ext| ext != "part")
+        })
+}
+
+/// 실행마다 같은 값이 나오도록 FNV-1a로 URL을 파일 이름으로 바꿉니다.
+fn cache_key(url: &str) -> String {
+    let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+    for byte in url.as_bytes() {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{DynamicImage, ImageFormat, RgbaImage};
+    use std::io::{Cursor, Read, Write};
+    use std::net::TcpListener;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    /// 요청 `count`개에 답하고 닫히는 HTTP 서버입니다. 경로별 `(상태, 본문)`을 돌려줍니다.
+    fn serve(
+        count: usize,
+        routes: Vec<(&'static str, u16, Vec<u8>)>,
+    ) -> (String, std::thread::JoinHandle<()>) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+        let base = format!("http://{}", listener.local_addr().expect("addr"));
+        let handle = std::thread::spawn(move || {
+            for stream in listener.incoming().take(count) {
+                let mut stream = stream.expect("accept");
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    let n = stream.read(&mut buf).expect("read request");
+                    if n == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buf[..n]);
+                }
+                let request = String::from_utf8_lossy(&request);
+                let path = request.split_whitespace().nth(1).unwrap_or("/");
+                let (status, body) = routes
+                    .iter()
+                    .find(|(route, _, _)| *route == path)
+                    .map(|(_, status, body)| (*status, body.clone()))
+                    .unwrap_or((404, Vec::new()));
+                let head = format!(
+                    "HTTP/1.1 {status} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                );
+                stream.write_all(head.as_bytes()).expect("write head");
+                stream.write_all(&body).expect("write body");
+            }
+        });
+        (base, handle)
+    }
+
+    #[test]
+    fn downloads_and_caches_logo_then_falls_back_with_warning_when_offline() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let cache = std::env::temp_dir().join(format!("cornerbrand-remote-logo-{nonce}"));
+
+        let mut png = Vec::new();
+        DynamicImage::ImageRgba8(RgbaImage::new(6, 4))
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .expect("encode logo");
+        let (base, server) = serve(
+            3,
+            vec![
+                ("/logo.png", 200, png),
+                ("/notes.txt", 200, b"not an image".to_vec()),
+            ],
+        );
+        let logo_url = format!("{base}/logo.png");
+
+        let fetched = fetch_logo(&logo_url, &cache).expect("download logo");
+        assert_eq!(fetched.warning, None);
+        let path = fetched.path;
+        assert_eq!(path.extension().and_then(|e| e.to_str()), Some("png"));
+        assert_eq!(
+            image::open(&path)
+                .expect("cached logo")
+                .to_rgba8()
+                .dimensions(),
+            (6, 4)
+        );
+
+        let missing = fetch_logo(&format!("{base}/missing.png"), &cache).expect_err("404");
+        assert!(missing.to_string().contains("HTTP 404"), "{missing}");
+        assert!(fetch_logo(&format!("{base}/notes.txt"), &cache).is_err());
+        server.join().expect("server");
+
+        // 기한 안의 사본은 서버에 묻지 않고 그대로 씁니다.
+        let fresh = fetch_logo(&logo_url, &cache).expect("fresh cached copy");
+        assert_eq!(fresh.path, path);
+        assert_eq!(fresh.warning, None);
+
+        // 기한이 지난 사본은 다시 받으려다 실패하면 경고와 함께 씁니다.
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(SystemTime::now() - CACHE_TTL * 2))
+            .expect("age cached logo");
+        let stale = fetch_logo(&logo_url, &cache).expect("stale cached copy");
+        assert_eq!(stale.path, path);
+        let warning = stale.warning.expect("stale warning");
+        assert!(warning.to_string().contains("사본을 썼습니다"), "{warning}");
+        assert!(fetch_logo("ftp://example.com/logo.png", &cache).is_err());
+
+        let _ = fs::remove_dir_all(&cache);
+    }
+}
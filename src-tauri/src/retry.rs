@@ -0,0 +1,239 @@
+use crate::cancellation::CancellationToken;
+use crate::i18n::Message;
+use std::ffi::OsString;
+use std::io::{self, ErrorKind};
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+const DEFAULT_ATTEMPTS: u32 = 3;
+const DEFAULT_BACKOFF: Duration = Duration::from_millis(100);
+
+/// 네트워크 드라이브처럼 가끔 실패하는 디스크 작업을 다시 시도하는 방식입니다.
+/// 디코딩/검증 오류가 아니라 I/O 오류에만 쓰며, 대기 시간은 시도마다 두 배로 늘어납니다.
+#[derive(Debug, Clone)]
+pub(crate) struct RetryPolicy {
+    attempts: u32,
+    backoff: Duration,
+    /// 취소되면 결과 파일을 더 쓰지 않습니다. 제한 시간을 넘겨 버려진 작업이 나중에 결과를 남기지 않게 합니다.
+    cancel: Option<CancellationToken>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: DEFAULT_ATTEMPTS,
+            backoff: DEFAULT_BACKOFF,
+            cancel: None,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// `attempts`는 첫 시도를 포함한 횟수입니다. 0이나 1이면 다시 시도하지 않습니다.
+    pub(crate) fn from_input(attempts: Option<u32>, backoff_ms: Option<u64>) -> Self {
+        Self {
+            attempts: attempts.unwrap_or(DEFAULT_ATTEMPTS).max(1),
+            backoff: backoff_ms.map_or(DEFAULT_BACKOFF, Duration::from_millis),
+            cancel: None,
+        }
+    }
+
+    pub(crate) fn with_cancel(self, cancel: Option<CancellationToken>) -> Self {
+        Self { cancel, ..self }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    }
+
+    pub(crate) fn run<T>(&self, mut operation: impl FnMut() -> io::Result<T>) -> io::Result<T> {
+        let mut delay = self.backoff;
+        let mut attempt = 1;
+        loop {
+            match operation() {
+                Err(error)
+                    if attempt < self.attempts && is_transient(&error) && !self.is_cancelled() =>
+                {
+                    thread::sleep(delay);
+                    delay = delay.saturating_mul(2);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    pub(crate) fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.run(|| std::fs::read(path))
+    }
+
+    /// 같은 폴더의 임시 파일에 다 쓴 뒤 `path`로 이름을 바꿔, 다른 프로세스가 쓰다 만 파일을 읽지 않게 합니다.
+    /// 취소되었으면 쓰기 전과 이름을 바꾸기 직전에 멈추고 임시 파일을 지웁니다.
+    pub(crate) fn write(&self, path: &Path, bytes: &[u8]) -> io::Result<()> {
+        self.run(|| write_replacing(path, bytes, &|| self.is_cancelled()))
+    }
+}
+
+/// `path`와 같은 폴더에 숨은 임시 파일을 써서 `rename`으로 덮어씁니다. 같은 볼륨 안의 이름 바꾸기는
+/// 원자적이라, `path`에는 이전 내용(자리만 잡아 둔 빈 파일 포함) 아니면 완성된 내용만 보입니다.
+fn write_replacing(path: &Path, bytes: &[u8], cancelled: &dyn Fn() -> bool) -> io::Result<()> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| io::Error::from(ErrorKind::InvalidInput))?;
+    let mut temp_name = OsString::from(".");
+    temp_name.push(file_name);
+    temp_name.push(format!(".{}.cornerbrand-tmp", std::process::id()));
+    let temp_path = path.with_file_name(temp_name);
+
+    let cancelled_error = || io::Error::other(Message::new("WRITE_CANCELLED"));
+    if cancelled() {
+        return Err(cancelled_error());
+    }
+    let written = std::fs::write(&temp_path, bytes).and_then(|()| {
+        if cancelled() {
+            return Err(cancelled_error());
+        }
+        std::fs::rename(&temp_path, path)
+    });
+    if written.is_err() {
+        let _ = std::fs::remove_file(&temp_path);
+    }
+    written
+}
+
+/// Windows `ERROR_SHARING_VIOLATION`, `ERROR_LOCK_VIOLATION`. 백신·동기화 프로그램이 잠깐 파일을 잡고 있을 때 납니다.
+#[cfg(windows)]
+const SHARING_VIOLATIONS: [i32; 2] = [32, 33];
+
+/// 잠시 뒤 다시 하면 풀릴 수 있는 오류만 다시 시도합니다. 디스크 가득 참, 읽기 전용, 잘못된 이름 등
+/// 그 밖의 오류는 다시 해도 결과가 같으므로 곧바로 돌려줍니다.
+fn is_transient(error: &io::Error) -> bool {
+    if matches!(
+        error.kind(),
+        ErrorKind::Interrupted | ErrorKind::WouldBlock | ErrorKind::TimedOut
+    ) {
+        return true;
+    }
+    #[cfg(windows)]
+    if error
+        .raw_os_error()
+        .is_some_and(|code| SHARING_VIOLATIONS.contains(&code))
+    {
+        return true;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn run_retries_transient_error_until_write_succeeds() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-retry-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+        let path = root.join("out.bin");
+
+        let policy = RetryPolicy::from_input(Some(3), Some(1));
+        let mut calls = 0;
+        policy
+            .run(|| {
+                calls += 1;
+                if calls == 1 {
+                    return Err(io::Error::new(ErrorKind::TimedOut, "flaky share"));
+                }
+                fs::write(&path, b"stamped")
+            })
+            .expect("retried write");
+        assert_eq!(calls, 2);
+        assert_eq!(fs::read(&path).expect("read"), b"stamped");
+
+        let mut calls = 0;
+        let result: io::Result<()> = policy.run(|| {
+            calls += 1;
+            Err(io::Error::from(ErrorKind::NotFound))
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 1, "permanent errors must not be retried");
+
+        for kind in [
+            ErrorKind::StorageFull,
+            ErrorKind::ReadOnlyFilesystem,
+            ErrorKind::IsADirectory,
+            ErrorKind::InvalidFilename,
+        ] {
+            let mut calls = 0;
+            let result: io::Result<()> = policy.run(|| {
+                calls += 1;
+                Err(io::Error::from(kind))
+            });
+            assert!(result.is_err());
+            assert_eq!(calls, 1, "{kind:?} must not be retried");
+        }
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn cancelled_policy_writes_nothing() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-retry-cancel-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+        let path = root.join("out.png");
+
+        let token = CancellationToken::new();
+        let policy = RetryPolicy::default().with_cancel(Some(token.clone()));
+        token.cancel();
+        assert!(policy.write(&path, b"late").is_err());
+        assert_eq!(fs::read_dir(&root).expect("read dir").count(), 0);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn write_replaces_target_without_leaving_temp_files() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-atomic-write-{nonce}"));
+        fs::create_dir_all(&root).expect("temp dir");
+        let path = root.join("out.png");
+        // 결과 이름을 선점한 빈 파일 자리에 쓰는 경우와 같습니다.
+        fs::write(&path, b"").expect("reserved file");
+
+        let png = {
+            let mut bytes = Vec::new();
+            image::DynamicImage::ImageRgba8(image::RgbaImage::new(3, 2))
+                .write_to(
+                    &mut std::io::Cursor::new(&mut bytes),
+                    image::ImageFormat::Png,
+                )
+                .expect("encode");
+            bytes
+        };
+        RetryPolicy::default().write(&path, &png).expect("write");
+
+        let decoded = image::open(&path).expect("complete image");
+        assert_eq!((decoded.width(), decoded.height()), (3, 2));
+        let names: Vec<_> = fs::read_dir(&root)
+            .expect("read dir")
+            .map(|entry| entry.expect("entry").file_name())
+            .collect();
+        assert_eq!(names, vec![OsString::from("out.png")]);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,110 @@
+use crate::image_engine::StampFileResult;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const LOG_FILE_NAME: &str = "cornerbrand.log";
+
+/// 로그 파일이 이 크기를 넘으면 `.1`로 밀어내고 새로 씁니다. 이전 파일은 하나만 남깁니다.
+const MAX_LOG_BYTES: u64 = 1024 * 1024;
+
+/// 여러 배치가 동시에 돌아도 줄이 섞이거나 회전이 겹치지 않도록 프로세스 전체에서 잠급니다.
+static LOG_LOCK: Mutex<()> = Mutex::new(());
+
+/// 처리한 파일마다 한 줄씩 남기는 실행 기록입니다. 기록 실패는 배치를 멈추지 않습니다.
+pub struct RunLog {
+    path: PathBuf,
+    max_bytes: u64,
+}
+
+impl RunLog {
+    pub fn new(log_dir: &Path) -> Self {
+        Self {
+            path: log_dir.join(LOG_FILE_NAME),
+            max_bytes: MAX_LOG_BYTES,
+        }
+    }
+
+    /// `시각(unix 초)\t입력\tok\t코드\t소요(ms)` 형식으로 한 줄을 덧붙입니다.
+    pub fn append(&self, result: &StampFileResult, duration: Duration) {
+        let line = format!(
+            "{}\t{}\t{}\t{}\t{}\n",
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            result.input_path,
+            result.ok,
+            result.code.as_deref().unwrap_or("-"),
+            duration.as_millis()
+        );
+
+        let _guard = LOG_LOCK
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(parent) = self.path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        self.rotate_if_needed();
+
+        if let Ok(mut file) = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+        {
+            let _ = file.write_all(line.as_bytes());
+        }
+    }
+
+    fn rotate_if_needed(&self) {
+        let size = fs::metadata(&self.path).map(|m| m.len()).unwrap_or(0);
+        if size < self.max_bytes {
+            return;
+        }
+
+        let mut rotated = self.path.clone().into_os_string();
+        rotated.push(".1");
+        let _ = fs::rename(&self.path, PathBuf::from(rotated));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_rotates_when_it_exceeds_the_cap() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-run-log-{nonce}"));
+        let log = RunLog {
+            max_bytes: 64,
+            ..RunLog::new(&root)
+        };
+        let result = StampFileResult {
+            input_path: "/photos/a-rather-long-input-name.png".to_string(),
+            ok: true,
+            ..Default::default()
+        };
+
+        for _ in 0..3 {
+            log.append(&result, Duration::from_millis(5));
+        }
+
+        let current = fs::read_to_string(root.join(LOG_FILE_NAME)).expect("current log");
+        let previous =
+            fs::read_to_string(root.join(format!("{LOG_FILE_NAME}.1"))).expect("rotated");
+        assert_eq!(current.lines().count(), 1);
+        assert!(previous.lines().count() >= 1);
+        assert!(
+            current.contains("\ttrue\t-\t5\n"),
+            "unexpected line: {current}"
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
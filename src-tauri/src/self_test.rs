@@ -0,0 +1,186 @@
+use crate::image_engine::{self, StampSettingsInput};
+use crate::pdf_engine;
+use image::{Rgba, RgbaImage};
+use lopdf::{dictionary, Document, Object, Stream};
+use serde::Serialize;
+use std::path::Path;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// 지원 문의 때 "이 PC에서 엔진이 도는지"를 빠르게 확인하는 결과입니다.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfTestReport {
+    pub passed: bool,
+    pub checks: Vec<SelfTestCheck>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfTestCheck {
+    /// "image" 또는 "pdf"입니다.
+    pub subsystem: String,
+    pub ok: bool,
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+/// 임시 폴더에 작은 이미지·로고·한 페이지 PDF를 만들어 실제 엔진으로 처리해 보고, 결과를 다시 읽어 확인합니다.
+/// 만든 파일은 끝나면 모두 지웁니다.
+pub fn self_test() -> SelfTestReport {
+    let nonce = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let root = std::env::temp_dir().join(format!("cornerbrand-self-test-{nonce}"));
+
+    let checks = match std::fs::create_dir_all(&root) {
+        Ok(()) => vec![
+            run_check("image", || check_image_engine(&root)),
+            run_check("pdf", || check_pdf_engine(&root)),
+        ],
+        Err(e) => ["image", "pdf"]
+            .into_iter()
+            .map(|subsystem| SelfTestCheck {
+                subsystem: subsystem.to_string(),
+                ok: false,
+                error: Some(format!("임시 폴더를 만들지 못했습니다: {e}")),
+                duration_ms: 0,
+            })
+            .collect(),
+    };
+    let _ = std::fs::remove_dir_all(&root);
+
+    SelfTestReport {
+        passed: checks.iter().all(|check| check.ok),
+        checks,
+    }
+}
+
+fn run_check(subsystem: &str, check: impl FnOnce() -> Result<(), String>) -> SelfTestCheck {
+    let started = Instant::now();
+    let outcome = check();
+    SelfTestCheck {
+        subsystem: subsystem.to_string(),
+        ok: outcome.is_ok(),
+        error: outcome.err(),
+        duration_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+    }
+}
+
+fn settings() -> StampSettingsInput {
+    StampSettingsInput {
+        position: "우하단".to_string(),
+        size_preset: "보통".to_string(),
+        ..Default::default()
+    }
+}
+
+fn write_logo(root: &Path) -> Result<std::path::PathBuf, String> {
+    let logo_path = root.join("logo.png");
+    RgbaImage::from_pixel(8, 8, Rgba([255, 0, 0, 255]))
+        .save(&logo_path)
+        .map_err(|e| format!("테스트 로고를 만들지 못했습니다: {e}"))?;
+    Ok(logo_path)
+}
+
+fn check_image_engine(root: &Path) -> Result<(), String> {
+    let logo_path = write_logo(root)?;
+    let input_path = root.join("sample.png");
+    RgbaImage::from_pixel(64, 48, Rgba([240, 240, 240, 255]))
+        .save(&input_path)
+        .map_err(|e| format!("테스트 이미지를 만들지 못했습니다: {e}"))?;
+
+    let paths = vec![input_path.to_string_lossy().to_string()];
+    let result = image_engine::stamp_images(&paths, settings(), &logo_path, None)
+        .into_iter()
+        .next()
+        .ok_or_else(|| "이미지 엔진이 결과를 돌려주지 않았습니다.".to_string())?;
+    let output_path = match (result.ok, result.output_path) {
+        (true, Some(path)) => path,
+        _ => {
+            return Err(result
+                .error
+                .map_or_else(|| "이미지 처리 실패".to_string(), |e| e.to_string()))
+        }
+    };
+
+    let output = image::open(&output_path)
+        .map_err(|e| format!("결과 이미지를 다시 읽지 못했습니다: {e}"))?;
+    if (output.width(), output.height()) != (64, 48) {
+        return Err("결과 이미지 크기가 원본과 다릅니다.".to_string());
+    }
+    Ok(())
+}
+
+fn check_pdf_engine(root: &Path) -> Result<(), String> {
+    let logo_path = write_logo(root)?;
+    let input_path = root.join("sample.pdf");
+    write_one_page_pdf(&input_path)?;
+
+    let paths = vec![input_path.to_string_lossy().to_string()];
+    let result = pdf_engine::stamp_pdfs(&paths, settings(), &logo_path, None)
+        .into_iter()
+        .next()
+        .ok_or_else(|| "PDF 엔진이 결과를 돌려주지 않았습니다.".to_string())?;
+    let output_path = match (result.ok, result.output_path) {
+        (true, Some(path)) => path,
+        _ => {
+            return Err(result
+                .error
+                .map_or_else(|| "PDF 처리 실패".to_string(), |e| e.to_string()))
+        }
+    };
+
+    let output = Document::load(&output_path)
+        .map_err(|e| format!("결과 PDF를 다시 읽지 못했습니다: {e}"))?;
+    if output.get_pages().len() != 1 {
+        return Err("결과 PDF의 페이지 수가 원본과 다릅니다.".to_string());
+    }
+    Ok(())
+}
+
+fn write_one_page_pdf(path: &Path) -> Result<(), String> {
+    let mut doc = Document::with_version("1.5");
+    let pages_id = doc.new_object_id();
+    let content_id = doc.add_object(Stream::new(dictionary! {}, Vec::new()));
+    let page_id = doc.add_object(dictionary! {
+        "Type" => "Page",
+        "Parent" => pages_id,
+        "MediaBox" => vec![0.into(), 0.into(), 200.into(), 200.into()],
+        "Contents" => content_id,
+        "Resources" => dictionary! {},
+    });
+    doc.objects.insert(
+        pages_id,
+        Object::Dictionary(dictionary! {
+            "Type" => "Pages",
+            "Kids" => vec![page_id.into()],
+            "Count" => 1,
+        }),
+    );
+    let catalog_id = doc.add_object(dictionary! {
+        "Type" => "Catalog",
+        "Pages" => pages_id,
+    });
+    doc.trailer.set("Root", catalog_id);
+    doc.save(path)
+        .map(|_| ())
+        .map_err(|e| format!("테스트 PDF를 만들지 못했습니다: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn self_test_passes_on_healthy_build() {
+        let report = self_test();
+        assert!(report.passed, "self test failed: {:?}", report.checks);
+        let subsystems: Vec<&str> = report
+            .checks
+            .iter()
+            .map(|check| check.subsystem.as_str())
+            .collect();
+        assert_eq!(subsystems, ["image", "pdf"]);
+    }
+}
@@ -0,0 +1,202 @@
+use crate::i18n::Message;
+use crate::path_policy;
+use ab_glyph::{Font, FontVec, PxScale, ScaleFont};
+use image::imageops::{self, FilterType};
+use image::{Rgba, RgbaImage};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+const DEFAULT_COLUMNS: u32 = 4;
+const DEFAULT_ROWS: u32 = 5;
+const DEFAULT_CELL_SIZE: u32 = 256;
+const CELL_PADDING: u32 = 8;
+const LABEL_PX: f32 = 14.0;
+const BACKGROUND: Rgba<u8> = Rgba([255, 255, 255, 255]);
+const LABEL_COLOR: [u8; 3] = [40, 40, 40];
+
+/// 결과 이미지를 바둑판으로 모은 확인용 시트(`sheet_1.png`, `sheet_2.png`, ...)의 모양입니다.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SheetOptions {
+    /// 한 줄의 칸 수(기본 4).
+    pub columns: Option<u32>,
+    /// 시트 한 장의 줄 수(기본 5). 칸이 모자라면 다음 시트로 넘어갑니다.
+    pub rows: Option<u32>,
+    /// 썸네일이 들어가는 정사각형 칸의 한 변(px, 기본 256).
+    pub cell_size: Option<u32>,
+    /// 칸 아래에 파일 이름을 쓸 TTF/OTF 글꼴 경로. 비우면 이름 없이 썸네일만 놓습니다.
+    pub label_font: Option<String>,
+}
+
+impl SheetOptions {
+    fn columns(&self) -> u32 {
+        self.columns.filter(|n| *n > 0).unwrap_or(DEFAULT_COLUMNS)
+    }
+
+    fn rows(&self) -> u32 {
+        self.rows.filter(|n| *n > 0).unwrap_or(DEFAULT_ROWS)
+    }
+
+    fn cell_size(&self) -> u32 {
+        self.cell_size
+            .filter(|n| *n > 0)
+            .unwrap_or(DEFAULT_CELL_SIZE)
+    }
+}
+
+/// `label_font`을 읽습니다. 지정하지 않았으면 `None`입니다.
+fn load_font(options: &SheetOptions) -> Result<Option<FontVec>, Message> {
+    let Some(path) = options
+        .label_font
+        .as_deref()
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+    else {
+        return Ok(None);
+    };
+    let bytes = std::fs::read(path)
+        .map_err(|e| Message::new("SHEET_FONT_READ_FAILED").arg(e.to_string()))?;
+    FontVec::try_from_vec(bytes)
+        .map(Some)
+        .map_err(|_| Message::new("SHEET_FONT_INVALID"))
+}
+
+/// `(이름, 이미지)`를 입력 순서대로 칸에 채운 시트들을 만듭니다. 이미지는 비율을 지켜 칸 안에 줄여 가운데 놓습니다.
+fn build_sheets(
+    cells: &[(String, RgbaImage)],
+    options: &SheetOptions,
+    font: Option<&FontVec>,
+) -> Vec<RgbaImage> {
+    let columns = options.columns();
+    let per_sheet = (columns * options.rows()) as usize;
+    let cell_size = options.cell_size();
+    let label_height = if font.is_some() {
+        LABEL_PX.ceil() as u32 + CELL_PADDING
+    } else {
+        0
+    };
+    let pitch_x = cell_size + CELL_PADDING;
+    let pitch_y = cell_size + label_height + CELL_PADDING;
+
+    cells
+        .chunks(per_sheet)
+        .map(|chunk| {
+            let used_columns = (chunk.len() as u32).min(columns);
+            let used_rows = (chunk.len() as u32).div_ceil(columns);
+            let mut sheet = RgbaImage::from_pixel(
+                used_columns * pitch_x + CELL_PADDING,
+                used_rows * pitch_y + CELL_PADDING,
+                BACKGROUND,
+            );
+            for (index, (label, image)) in chunk.iter().enumerate() {
+                let x = CELL_PADDING + (index as u32 % columns) * pitch_x;
+                let y = CELL_PADDING + (index as u32 / columns) * pitch_y;
+                let thumbnail = thumbnail(image, cell_size);
+                let offset_x = (cell_size - thumbnail.width()) / 2;
+                let offset_y = (cell_size - thumbnail.height()) / 2;
+                imageops::overlay(
+                    &mut sheet,
+                    &thumbnail,
+                    i64::from(x + offset_x),
+                    i64::from(y + offset_y),
+                );
+                if let Some(font) = font {
+                    draw_label(
+                        &mut sheet,
+                        font,
+                        label,
+                        x,
+                        y + cell_size + CELL_PADDING / 2,
+                        cell_size,
+                    );
+                }
+            }
+            sheet
+        })
+        .collect()
+}
+
+/// `outputs`를 읽어 `dir/sheet_1.png`부터 차례로 씁니다. 같은 이름이 있으면 덮어쓰지 않고 `sheet_1(1).png`처럼 씁니다.
+/// 이미지로 읽지 못하는 파일(PDF 등)은 빼며, 남는 이미지가 없으면 아무것도 쓰지 않습니다. 메모리를 아끼려고 읽자마자 칸 크기로 줄입니다.
+pub fn write_sheets(
+    dir: &Path,
+    outputs: &[&Path],
+    options: &SheetOptions,
+) -> Result<Vec<PathBuf>, Message> {
+    let font = load_font(options)?;
+    let cell_size = options.cell_size();
+    let cells: Vec<(String, RgbaImage)> = outputs
+        .iter()
+        .filter_map(|path| {
+            let image = image::open(path).ok()?.to_rgba8();
+            Some((label_for(path), thumbnail(&image, cell_size)))
+        })
+        .collect();
+
+    build_sheets(&cells, options, font.as_ref())
+        .into_iter()
+        .enumerate()
+        .map(|(index, sheet)| {
+            let path = path_policy::reserve_sheet_path(dir, index + 1)?;
+            sheet.save(&path).map_err(|e| {
+                let _ = std::fs::remove_file(&path);
+                Message::new("SHEET_SAVE_FAILED").arg(e.to_string())
+            })?;
+            Ok(path)
+        })
+        .collect()
+}
+
+fn label_for(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn thumbnail(image: &RgbaImage, cell_size: u32) -> RgbaImage {
+    let (width, height) = image.dimensions();
+    if width <= cell_size && height <= cell_size {
+        return image.clone();
+    }
+    let scale = f64::from(cell_size) / f64::from(width.max(height));
+    let thumb_width = ((f64::from(width) * scale).round() as u32).clamp(1, cell_size);
+    let thumb_height = ((f64::from(height) * scale).round() as u32).clamp(1, cell_size);
+    imageops::resize(image, thumb_width, thumb_height, FilterType::Triangle)
+}
+
+/// 한 줄로 왼쪽부터 쓰고, 칸 너비를 넘는 글자는 그리지 않습니다.
+fn draw_label(sheet: &mut RgbaImage, font: &FontVec, label: &str, x: u32, y: u32, max_width: u32) {
+    let scaled = font.as_scaled(PxScale::from(LABEL_PX));
+    let mut caret = 0.0_f32;
+    for ch in label.chars() {
+        let glyph_id = scaled.glyph_id(ch);
+        let advance = scaled.h_advance(glyph_id);
+        if caret + advance > max_width as f32 {
+            break;
+        }
+        let glyph = glyph_id.with_scale_and_position(
+            PxScale::from(LABEL_PX),
+            ab_glyph::point(x as f32 + caret, y as f32 + scaled.ascent()),
+        );
+        if let Some(outlined) = font.outline_glyph(glyph) {
+            let bounds = outlined.px_bounds();
+            outlined.draw(|gx, gy, coverage| {
+                let px = bounds.min.x as i64 + i64::from(gx);
+                let py = bounds.min.y as i64 + i64::from(gy);
+                if px < 0
+                    || py < 0
+                    || px >= i64::from(sheet.width())
+                    || py >= i64::from(sheet.height())
+                {
+                    return;
+                }
+                let pixel = sheet.get_pixel_mut(px as u32, py as u32);
+                for (channel, ink) in pixel.0.iter_mut().zip(LABEL_COLOR) {
+                    *channel = (f32::from(*channel) * (1.0 - coverage) + f32::from(ink) * coverage)
+                        .round() as u8;
+                }
+            });
+        }
+        caret += advance;
+    }
+}
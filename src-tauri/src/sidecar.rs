@@ -0,0 +1,71 @@
+use crate::i18n::Message;
+use crate::image_engine::{StampFileResult, StampSettingsInput};
+use crate::retry::RetryPolicy;
+use serde::Serialize;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 사이드카 JSON 형식 버전. 필드 의미가 바뀌거나 빠지면 올립니다.
+pub const SIDECAR_SCHEMA_VERSION: u32 = 1;
+
+/// 결과 파일 하나에 대한 기록입니다. 배치 보고서와 달리 결과 옆에 파일마다 하나씩 둡니다.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Sidecar<'a> {
+    schema_version: u32,
+    timestamp: u64,
+    input_path: &'a str,
+    output_path: &'a str,
+    settings: &'a StampSettingsInput,
+    warnings: &'a [Message],
+}
+
+/// 결과 `photo_cornerbrand.png`의 사이드카 `photo_cornerbrand.png.json` 경로입니다.
+pub fn sidecar_path(output_path: &Path) -> PathBuf {
+    let mut name = OsString::from(output_path.as_os_str());
+    name.push(".json");
+    PathBuf::from(name)
+}
+
+/// 결과 하나의 사이드카 JSON입니다. `output_path`에는 결과가 놓인 곳(ZIP 모드라면 압축 안의 항목 이름)을 적습니다.
+pub fn sidecar_payload(
+    result: &StampFileResult,
+    output_path: &str,
+    settings: &StampSettingsInput,
+) -> Result<Vec<u8>, Message> {
+    let sidecar = Sidecar {
+        schema_version: SIDECAR_SCHEMA_VERSION,
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        input_path: &result.input_path,
+        output_path,
+        settings,
+        warnings: &result.warnings,
+    };
+    serde_json::to_vec_pretty(&sidecar).map_err(|e| Message::from(e.to_string()))
+}
+
+/// 성공한 결과 옆에 사이드카를 씁니다. 쓰지 못하면 결과는 그대로 두고 경고만 덧붙입니다.
+/// 결과 파일과 같은 재시도 설정(`ioRetryAttempts`)으로 씁니다. 이어서 처리(`RESUMED`)한 파일은 이전 실행이 이미
+/// 사이드카를 남겼으므로 배치가 이 함수를 부르지 않습니다. ZIP 모드는 `sidecar_payload`로 압축 안에 넣습니다.
+pub fn write_sidecar(result: &mut StampFileResult, settings: &StampSettingsInput) {
+    if !result.ok || result.skipped {
+        return;
+    }
+    let Some(output_path) = result.output_path.as_deref() else {
+        return;
+    };
+    let written = sidecar_payload(result, output_path, settings).and_then(|payload| {
+        RetryPolicy::from_input(settings.io_retry_attempts, settings.io_retry_backoff_ms)
+            .write(&sidecar_path(Path::new(output_path)), &payload)
+            .map_err(Message::from)
+    });
+    if let Err(e) = written {
+        result
+            .warnings
+            .push(Message::new("SIDECAR_WRITE_FAILED").arg(e));
+    }
+}
@@ -0,0 +1,184 @@
+use crate::i18n::Message;
+use crate::image_engine::SizeMode;
+use image::RgbaImage;
+use resvg::{tiny_skia, usvg};
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+
+/// 크기를 모를 때(PDF, 미리보기 등) SVG 로고를 그리는 긴 변 길이(px)입니다. 인쇄에도 충분히 선명합니다.
+pub(crate) const SVG_RENDER_LONG_SIDE: u32 = 2048;
+/// 한 변이 이보다 크게 그리지는 않습니다. 아주 큰 캔버스에서 메모리가 폭증하지 않게 합니다.
+const SVG_MAX_LONG_SIDE: u32 = 16384;
+
+/// 확장자(`.svg`/`.svgz`)나 내용 앞부분으로 SVG 로고인지 판단합니다.
+pub(crate) fn is_svg(path: &Path, bytes: &[u8]) -> bool {
+    let by_extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("svg") || e.eq_ignore_ascii_case("svgz"));
+    if by_extension {
+        return true;
+    }
+    let head = String::from_utf8_lossy(&bytes[..bytes.len().min(1024)]);
+    let head = head.trim_start_matches('\u{feff}').trim_start();
+    head.starts_with("<svg") || (head.starts_with("<?xml") && head.contains("<svg"))
+}
+
+/// 로고 파일 내용을 RGBA로 풉니다. SVG면 `SVG_RENDER_LONG_SIDE`로 그리되, 로고 원본 크기 기준이면
+/// SVG에 적힌 크기를 원본 크기로 보고 그 크기로 그립니다.
+pub(crate) fn decode_logo(
+    path: &Path,
+    bytes: &[u8],
+    size_mode: SizeMode,
+) -> Result<RgbaImage, Message> {
+    if is_svg(path, bytes) {
+        return match size_mode {
+            SizeMode::RelativeToPage => render(bytes, SVG_RENDER_LONG_SIDE),
+            SizeMode::RelativeToLogo => render_native(bytes),
+        };
+    }
+    image::load_from_memory(bytes)
+        .map(|logo| logo.to_rgba8())
+        .map_err(|e| Message::from(e.to_string()))
+}
+
+/// 로고 파일을 읽어 `decode_logo`로 풉니다.
+pub(crate) fn open_logo(path: &Path, size_mode: SizeMode) -> Result<RgbaImage, Message> {
+    let bytes = std::fs::read(path).map_err(|e| e.to_string())?;
+    decode_logo(path, &bytes, size_mode)
+}
+
+/// SVG를 긴 변이 `long_side`(px)가 되도록 비율을 유지해 그립니다.
+pub(crate) fn render(bytes: &[u8], long_side: u32) -> Result<RgbaImage, Message> {
+    let tree = parse(bytes)?;
+    let size = tree.size();
+    let scale = long_side.clamp(1, SVG_MAX_LONG_SIDE) as f32 / size.width().max(size.height());
+    rasterize(&tree, scale)
+}
+
+/// SVG에 적힌 크기(1단위 = 1px) 그대로 그립니다.
+fn render_native(bytes: &[u8]) -> Result<RgbaImage, Message> {
+    let tree = parse(bytes)?;
+    let size = tree.size();
+    let scale = (SVG_MAX_LONG_SIDE as f32 / size.width().max(size.height())).min(1.0);
+    rasterize(&tree, scale)
+}
+
+fn parse(bytes: &[u8]) -> Result<usvg::Tree, Message> {
+    let options = usvg::Options {
+        fontdb: system_fonts(),
+        ..usvg::Options::default()
+    };
+    usvg::Tree::from_data(bytes, &options)
+        .map_err(|e| Message::new("SVG_PARSE_FAILED").arg(e.to_string()))
+}
+
+/// 글자가 든 SVG 로고가 빈 그림이 되지 않도록 시스템 글꼴을 씁니다.
+/// 글꼴을 읽는 데 시간이 걸리므로 처음 한 번만 읽어 둡니다.
+fn system_fonts() -> Arc<usvg::fontdb::Database> {
+    static FONTS: OnceLock<Arc<usvg::fontdb::Database>> = OnceLock::new();
+    FONTS
+        .get_or_init(|| {
+            let mut fontdb = usvg::fontdb::Database::new();
+            fontdb.load_system_fonts();
+            fill_missing_generic_families(&mut fontdb);
+            Arc::new(fontdb)
+        })
+        .clone()
+}
+
+/// `sans-serif` 등 일반 글꼴 이름의 기본값(Arial 등)이 없는 PC에서는 설치된 첫 글꼴로 대신합니다.
+/// usvg는 찾지 못한 글꼴을 `serif`로 대신하므로, 없는 글꼴을 지정한 로고도 이 글꼴로 그려집니다.
+fn fill_missing_generic_families(fontdb: &mut usvg::fontdb::Database) {
+    use usvg::fontdb::{Family, Query};
+
+    let Some(fallback) = fontdb
+        .faces()
+        .find_map(|face| face.families.first().map(|(name, _)| name.clone()))
+    else {
+        return;
+    };
+    let missing = |fontdb: &usvg::fontdb::Database, family: Family| {
+        fontdb
+            .query(&Query {
+                families: &[family],
+                ..Query::default()
+            })
+            .is_none()
+    };
+    if missing(fontdb, Family::SansSerif) {
+        fontdb.set_sans_serif_family(fallback.clone());
+    }
+    if missing(fontdb, Family::Serif) {
+        fontdb.set_serif_family(fallback.clone());
+    }
+    if missing(fontdb, Family::Monospace) {
+        fontdb.set_monospace_family(fallback);
+    }
+}
+
+fn rasterize(tree: &usvg::Tree, scale: f32) -> Result<RgbaImage, Message> {
+    let size = tree.size();
+    let width = ((size.width() * scale).round() as u32).max(1);
+    let height = ((size.height() * scale).round() as u32).max(1);
+
+    let mut pixmap =
+        tiny_skia::Pixmap::new(width, height).ok_or_else(|| Message::new("SVG_SIZE_INVALID"))?;
+    resvg::render(
+        tree,
+        tiny_skia::Transform::from_scale(scale, scale),
+        &mut pixmap.as_mut(),
+    );
+
+    // tiny-skia는 미리 곱한 알파로 저장하므로 일반 RGBA로 되돌립니다.
+    let raw = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|px| {
+            let px = px.demultiply();
+            [px.red(), px.green(), px.blue(), px.alpha()]
+        })
+        .collect();
+    RgbaImage::from_raw(width, height, raw).ok_or_else(|| Message::new("SVG_RENDER_FAILED"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARE: &[u8] = br##"<?xml version="1.0"?>
+<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10" viewBox="0 0 20 10">
+  <rect width="20" height="10" fill="#ff0000"/>
+</svg>"##;
+
+    #[test]
+    fn render_keeps_aspect_ratio_at_requested_long_side() {
+        let logo = render(SQUARE, 200).expect("render");
+        assert_eq!(logo.dimensions(), (200, 100));
+        assert_eq!(logo.get_pixel(100, 50).0, [255, 0, 0, 255]);
+
+        assert!(is_svg(Path::new("logo.bin"), SQUARE));
+        assert!(is_svg(Path::new("logo.SVG"), b""));
+        assert!(!is_svg(Path::new("logo.png"), b"\x89PNG"));
+
+        let native = decode_logo(Path::new("logo.svg"), SQUARE, SizeMode::RelativeToLogo)
+            .expect("native render");
+        assert_eq!(native.dimensions(), (20, 10));
+    }
+
+    #[test]
+    fn text_is_drawn_with_system_fonts() {
+        if system_fonts().is_empty() {
+            // 글꼴이 하나도 없는 환경에서는 글자를 그릴 수 없습니다.
+            return;
+        }
+        let text = br##"<svg xmlns="http://www.w3.org/2000/svg" width="120" height="40">
+  <text x="4" y="30" font-family="No Such Font, sans-serif" font-size="28" fill="#000">LOGO</text>
+</svg>"##;
+        let logo = render(text, 240).expect("render text");
+        assert!(
+            logo.pixels().any(|px| px.0[3] > 0),
+            "글자가 그려져야 합니다"
+        );
+    }
+}
@@ -0,0 +1,397 @@
+use crate::i18n::Message;
+use image::{DynamicImage, ImageBuffer, Rgba};
+use std::io::{Cursor, Seek, Write};
+use tiff::decoder::ifd::Value;
+use tiff::decoder::{Decoder, DecodingResult};
+use tiff::encoder::compression::DeflateLevel;
+use tiff::encoder::{colortype, Compression, Rational, TiffEncoder, TiffKindStandard};
+use tiff::tags::{CompressionMethod, Tag};
+use tiff::ColorType;
+
+/// `PhotometricInterpretation` 값 0(WhiteIsZero). 팩스 문서에 흔하며 회색조를 뒤집어 읽어야 합니다.
+const PHOTOMETRIC_WHITE_IS_ZERO: u16 = 0;
+
+/// 여러 쪽 TIFF를 어떻게 처리할지입니다.
+pub(crate) enum TiffLayout {
+    /// 한 쪽짜리이거나 구조를 읽지 못했습니다. 일반 이미지 경로로 처리합니다.
+    Single,
+    /// 여러 쪽이지만 이 모듈이 다루지 못하는 색 형식(CMYK, 팔레트 등)의 쪽이 있습니다.
+    /// 일반 이미지 경로로 첫 쪽만 처리합니다.
+    Unsupported {
+        pages: usize,
+        color: String,
+    },
+    Pages(TiffPages),
+}
+
+/// 풀어 둔 여러 쪽 TIFF입니다. 다시 쓸 때 원본의 압축 방식을 따릅니다.
+pub(crate) struct TiffPages {
+    pub pages: Vec<TiffPage>,
+    compression: Compression,
+}
+
+/// 한 쪽의 픽셀과 다시 쓸 때 지킬 원본 정보입니다. 픽셀은 8비트면 `ImageRgba8`, 16비트면 `ImageRgba16`입니다.
+pub(crate) struct TiffPage {
+    pub image: DynamicImage,
+    /// 원본이 회색조(1비트 팩스 포함)였는지. 로고를 넣은 뒤에도 회색이면 회색조로 씁니다.
+    gray: bool,
+    /// 원본에 알파 채널이 있었는지. 없었고 결과도 불투명하면 알파 없이 씁니다.
+    alpha: bool,
+    resolution: Option<Resolution>,
+}
+
+/// 인쇄 크기를 지키기 위해 그대로 옮겨 쓰는 해상도 태그입니다.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Resolution {
+    x: (u32, u32),
+    y: (u32, u32),
+    unit: u16,
+}
+
+/// 디렉터리(페이지)가 둘 이상이면 모든 쪽을 풉니다. 디코더 기본 제한(메모리 등)을 그대로 씁니다.
+pub(crate) fn decode_pages(bytes: &[u8]) -> Result<TiffLayout, Message> {
+    let Ok(mut decoder) = Decoder::new(Cursor::new(bytes)) else {
+        return Ok(TiffLayout::Single);
+    };
+    if !decoder.more_images() {
+        return Ok(TiffLayout::Single);
+    }
+    let compression = output_compression(
+        decoder
+            .find_tag_unsigned::<u16>(Tag::Compression)
+            .ok()
+            .flatten(),
+    );
+
+    let mut pages = Vec::new();
+    loop {
+        let number = pages.len() + 1;
+        let color = decoder.colortype().map_err(|e| {
+            Message::new("TIFF_PAGE_READ_FAILED")
+                .arg(number.to_string())
+                .arg(e.to_string())
+        })?;
+        if !is_supported(color) {
+            let mut count = number;
+            while decoder.more_images() && decoder.next_image().is_ok() {
+                count += 1;
+            }
+            return Ok(TiffLayout::Unsupported {
+                pages: count,
+                color: format!("{color:?}"),
+            });
+        }
+        let page = decode_page(&mut decoder, color).map_err(|e| {
+            Message::new("TIFF_PAGE_READ_FAILED")
+                .arg(number.to_string())
+                .arg(e)
+        })?;
+        pages.push(page);
+        if !decoder.more_images() {
+            return Ok(TiffLayout::Pages(TiffPages { pages, compression }));
+        }
+        decoder.next_image().map_err(|e| {
+            Message::new("TIFF_PAGE_NOT_FOUND")
+                .arg((number + 1).to_string())
+                .arg(e.to_string())
+        })?;
+    }
+}
+
+/// 쪽들을 순서대로 담은 여러 쪽 TIFF로 씁니다. 쪽마다 원본 색 형식·비트 깊이·해상도를 따르며,
+/// 1비트 쪽은 8비트 회색조로 씁니다(인코더가 1비트를 쓰지 못합니다).
+pub(crate) fn encode_pages(document: &TiffPages) -> Result<Vec<u8>, Message> {
+    let mut bytes = Cursor::new(Vec::new());
+    let mut encoder = TiffEncoder::new(&mut bytes)
+        .map_err(|e| Message::new("TIFF_ENCODE_FAILED").arg(e.to_string()))?
+        .with_compression(document.compression);
+    for page in &document.pages {
+        encode_page(&mut encoder, page)
+            .map_err(|e| Message::new("TIFF_ENCODE_FAILED").arg(e.to_string()))?;
+    }
+    Ok(bytes.into_inner())
+}
+
+fn is_supported(color: ColorType) -> bool {
+    matches!(
+        color,
+        ColorType::Gray(1 | 8 | 16)
+            | ColorType::GrayA(8 | 16)
+            | ColorType::RGB(8 | 16)
+            | ColorType::RGBA(8 | 16)
+    )
+}
+
+/// 인코더가 쓸 수 있는 압축이면 원본과 같게, 아니면(팩스, JPEG 등) Deflate로 씁니다.
+fn output_compression(source: Option<u16>) -> Compression {
+    match source.and_then(CompressionMethod::from_u16) {
+        Some(CompressionMethod::None) => Compression::Uncompressed,
+        Some(CompressionMethod::LZW) => Compression::Lzw,
+        Some(CompressionMethod::PackBits) => Compression::Packbits,
+        _ => Compression::Deflate(DeflateLevel::Balanced),
+    }
+}
+
+fn decode_page(
+    decoder: &mut Decoder<Cursor<&[u8]>>,
+    color: ColorType,
+) -> Result<TiffPage, Message> {
+    let (width, height) = decoder.dimensions().map_err(|e| e.to_string())?;
+    let white_is_zero = decoder
+        .find_tag_unsigned::<u16>(Tag::PhotometricInterpretation)
+        .ok()
+        .flatten()
+        == Some(PHOTOMETRIC_WHITE_IS_ZERO);
+    let resolution = read_resolution(decoder);
+    let (gray, alpha, channels) = match color {
+        ColorType::Gray(_) => (true, false, 1),
+        ColorType::GrayA(_) => (true, true, 2),
+        ColorType::RGB(_) => (false, false, 3),
+        _ => (false, true, 4),
+    };
+
+    let image = match decoder.read_image().map_err(|e| e.to_string())? {
+        DecodingResult::U8(data) => {
+            let data = if matches!(color, ColorType::Gray(1)) {
+                unpack_bilevel(&data, width, height)
+            } else {
+                data
+            };
+            let raw = expand_to_rgba(&data, channels, u8::MAX, white_is_zero);
+            ImageBuffer::<Rgba<u8>, _>::from_raw(width, height, raw).map(DynamicImage::ImageRgba8)
+        }
+        DecodingResult::U16(data) => {
+            let raw = expand_to_rgba(&data, channels, u16::MAX, white_is_zero);
+            ImageBuffer::<Rgba<u16>, _>::from_raw(width, height, raw).map(DynamicImage::ImageRgba16)
+        }
+        _ => return Err(Message::new("TIFF_COLOR_UNSUPPORTED").arg(format!("{color:?}"))),
+    }
+    .ok_or_else(|| Message::new("TIFF_PAGE_DATA_SHORT"))?;
+
+    Ok(TiffPage {
+        image,
+        gray,
+        alpha,
+        resolution,
+    })
+}
+
+/// 채널 수가 `channels`인 샘플을 RGBA로 늘립니다. `invert`이면(WhiteIsZero) 회색 값을 뒤집습니다.
+fn expand_to_rgba<T>(samples: &[T], channels: usize, max: T, invert: bool) -> Vec<T>
+where
+    T: Copy + std::ops::Sub<Output = T>,
+{
+    let gray = |v: T| if invert { max - v } else { v };
+    samples
+        .chunks_exact(channels)
+        .flat_map(|px| match *px {
+            [v] => [gray(v), gray(v), gray(v), max],
+            [v, a] => [gray(v), gray(v), gray(v), a],
+            [r, g, b] => [r, g, b, max],
+            [r, g, b, a, ..] => [r, g, b, a],
+            [] => unreachable!("chunks_exact never yields empty chunks"),
+        })
+        .collect()
+}
+
+fn read_resolution(decoder: &mut Decoder<Cursor<&[u8]>>) -> Option<Resolution> {
+    let mut rational = |tag| match decoder.find_tag(tag).ok().flatten()? {
+        Value::Rational(n, d) => Some((n, d)),
+        other => other.into_u32().ok().map(|n| (n, 1)),
+    };
+    let x = rational(Tag::XResolution)?;
+    let y = rational(Tag::YResolution).unwrap_or(x);
+    let unit = decoder
+        .find_tag_unsigned::<u16>(Tag::ResolutionUnit)
+        .ok()
+        .flatten()
+        .unwrap_or(2);
+    Some(Resolution { x, y, unit })
+}
+
+fn encode_page<W: Write + Seek>(
+    encoder: &mut TiffEncoder<W, TiffKindStandard>,
+    page: &TiffPage,
+) -> tiff::TiffResult<()> {
+    let (width, height) = (page.image.width(), page.image.height());
+    match &page.image {
+        DynamicImage::ImageRgba16(image) => {
+            let alpha = page.alpha || image.pixels().any(|px| px[3] != u16::MAX);
+            let gray = page.gray && image.pixels().all(|px| px[0] == px[1] && px[1] == px[2]);
+            let samples = select_channels(image.as_raw(), gray, alpha);
+            match (gray, alpha) {
+                (true, false) => write_page::<colortype::Gray16, W>(encoder, page, &samples),
+                (false, false) => write_page::<colortype::RGB16, W>(encoder, page, &samples),
+                _ => write_page::<colortype::RGBA16, W>(encoder, page, &samples),
+            }
+        }
+        other => {
+            let image = match other {
+                DynamicImage::ImageRgba8(image) => std::borrow::Cow::Borrowed(image),
+                other => std::borrow::Cow::Owned(other.to_rgba8()),
+            };
+            debug_assert_eq!(image.dimensions(), (width, height));
+            let alpha = page.alpha || image.pixels().any(|px| px[3] != u8::MAX);
+            let gray = page.gray && image.pixels().all(|px| px[0] == px[1] && px[1] == px[2]);
+            let samples = select_channels(image.as_raw(), gray, alpha);
+            match (gray, alpha) {
+                (true, false) => write_page::<colortype::Gray8, W>(encoder, page, &samples),
+                (false, false) => write_page::<colortype::RGB8, W>(encoder, page, &samples),
+                _ => write_page::<colortype::RGBA8, W>(encoder, page, &samples),
+            }
+        }
+    }
+}
+
+/// RGBA 샘플에서 쓸 채널만 남깁니다. 회색조는 R, 알파가 없으면 RGB입니다. 회색+알파는 RGBA로 씁니다.
+fn select_channels<T: Copy>(rgba: &[T], gray: bool, alpha: bool) -> Vec<T> {
+    match (gray, alpha) {
+        (true, false) => rgba.chunks_exact(4).map(|px| px[0]).collect(),
+        (false, false) => rgba
+            .chunks_exact(4)
+            .flat_map(|px| [px[0], px[1], px[2]])
+            .collect(),
+        _ => rgba.to_vec(),
+    }
+}
+
+fn write_page<C: colortype::ColorType, W: Write + Seek>(
+    encoder: &mut TiffEncoder<W, TiffKindStandard>,
+    page: &TiffPage,
+    samples: &[C::Inner],
+) -> tiff::TiffResult<()>
+where
+    [C::Inner]: tiff::encoder::TiffValue,
+{
+    let mut image = encoder.new_image::<C>(page.image.width(), page.image.height())?;
+    if let Some(resolution) = page.resolution {
+        let rational = |(n, d): (u32, u32)| Rational { n, d };
+        let tags = image.encoder();
+        tags.write_tag(Tag::XResolution, rational(resolution.x))?;
+        tags.write_tag(Tag::YResolution, rational(resolution.y))?;
+        tags.write_tag(Tag::ResolutionUnit, resolution.unit)?;
+    }
+    image.write_data(samples)
+}
+
+/// 1비트 페이지는 행마다 바이트 경계에 맞춰 비트가 채워져 있습니다. 픽셀당 한 바이트(0/255)로 풉니다.
+fn unpack_bilevel(data: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let row_bytes = (width as usize).div_ceil(8);
+    (0..height as usize)
+        .flat_map(|y| {
+            (0..width as usize).map(move |x| {
+                let byte = data.get(y * row_bytes + x / 8).copied().unwrap_or(0);
+                if byte & (0x80 >> (x % 8)) != 0 {
+                    255
+                } else {
+                    0
+                }
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+impl TiffPages {
+    /// RGBA 쪽들로 무압축 문서를 만듭니다.
+    pub(crate) fn from_rgba(pages: Vec<image::RgbaImage>) -> Self {
+        TiffPages {
+            pages: pages
+                .into_iter()
+                .map(|image| TiffPage {
+                    image: DynamicImage::ImageRgba8(image),
+                    gray: false,
+                    alpha: true,
+                    resolution: None,
+                })
+                .collect(),
+            compression: Compression::Uncompressed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::RgbaImage;
+
+    fn decoded(bytes: &[u8]) -> TiffPages {
+        match decode_pages(bytes).expect("decode") {
+            TiffLayout::Pages(document) => document,
+            _ => panic!("expected a multi-page tiff"),
+        }
+    }
+
+    #[test]
+    fn encoded_pages_round_trip_in_order() {
+        let pages = vec![
+            RgbaImage::from_pixel(4, 3, Rgba([10, 20, 30, 255])),
+            RgbaImage::from_pixel(2, 5, Rgba([200, 100, 50, 128])),
+        ];
+        let bytes = encode_pages(&TiffPages::from_rgba(pages.clone())).expect("encode");
+        let single = encode_pages(&TiffPages::from_rgba(pages[..1].to_vec())).expect("single");
+
+        assert!(matches!(decode_pages(&single), Ok(TiffLayout::Single)));
+        let round_trip: Vec<_> = decoded(&bytes)
+            .pages
+            .into_iter()
+            .map(|page| page.image.to_rgba8())
+            .collect();
+        assert_eq!(round_trip, pages);
+    }
+
+    #[test]
+    fn re_encoding_keeps_color_type_depth_resolution_and_compression() {
+        let mut source = Cursor::new(Vec::new());
+        let mut encoder = TiffEncoder::new(&mut source)
+            .expect("encoder")
+            .with_compression(Compression::Lzw);
+        let mut gray = encoder.new_image::<colortype::Gray8>(4, 2).expect("page 1");
+        gray.resolution(tiff::tags::ResolutionUnit::Inch, Rational { n: 300, d: 1 });
+        gray.write_data(&[40u8; 8]).expect("write page 1");
+        encoder
+            .write_image::<colortype::RGB16>(2, 2, &[1000u16; 12])
+            .expect("write page 2");
+
+        let bytes = encode_pages(&decoded(source.get_ref())).expect("encode");
+
+        let mut decoder = Decoder::new(Cursor::new(bytes.as_slice())).expect("decoder");
+        assert_eq!(decoder.colortype().expect("color"), ColorType::Gray(8));
+        assert_eq!(
+            decoder
+                .find_tag_unsigned::<u16>(Tag::Compression)
+                .expect("tag"),
+            Some(CompressionMethod::LZW.to_u16())
+        );
+        assert_eq!(
+            read_resolution(&mut decoder),
+            Some(Resolution {
+                x: (300, 1),
+                y: (300, 1),
+                unit: 2,
+            })
+        );
+        decoder.next_image().expect("page 2");
+        assert_eq!(decoder.colortype().expect("color"), ColorType::RGB(16));
+        assert!(matches!(
+            decoder.read_image().expect("pixels"),
+            DecodingResult::U16(data) if data == vec![1000; 12]
+        ));
+    }
+
+    #[test]
+    fn unhandled_color_types_fall_back_to_single_page() {
+        let mut source = Cursor::new(Vec::new());
+        let mut encoder = TiffEncoder::new(&mut source).expect("encoder");
+        for _ in 0..2 {
+            encoder
+                .write_image::<colortype::CMYK8>(2, 2, &[0u8; 16])
+                .expect("write page");
+        }
+
+        match decode_pages(source.get_ref()).expect("decode") {
+            TiffLayout::Unsupported { pages, .. } => assert_eq!(pages, 2),
+            _ => panic!("expected unsupported layout"),
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use crate::i18n::Message;
+use crate::image_engine::StampFileResult;
+use crate::{color, path_policy, webp_codec};
+use lopdf::Document;
+use std::path::Path;
+
+pub const CODE_VERIFY_FAILED: &str = "VERIFY_FAILED";
+
+/// 성공한 결과의 출력 파일(웹용 사본 포함)을 다시 읽어 보고, 읽지 못하면 `VERIFY_FAILED` 실패로 바꿉니다.
+pub fn verify_result(result: &mut StampFileResult) {
+    if !result.ok || result.skipped {
+        return;
+    }
+    let outputs = [&result.output_path, &result.web_output_path];
+    let failure = outputs
+        .into_iter()
+        .flatten()
+        .find_map(|output| verify_output(Path::new(output)).err());
+
+    if let Some(message) = failure {
+        result.ok = false;
+        result.error = Some(Message::new("VERIFY_FAILED").arg(message));
+        result.code = Some(CODE_VERIFY_FAILED.to_string());
+    }
+}
+
+/// PDF는 `Document::load`로, 이미지는 입력과 같은 디코더로 끝까지 읽습니다.
+pub fn verify_output(path: &Path) -> Result<(), Message> {
+    if path_policy::is_supported_pdf(path) {
+        let doc =
+            Document::load(path).map_err(|e| Message::new("PDF_READ_FAILED").arg(e.to_string()))?;
+        if doc.get_pages().is_empty() {
+            return Err(Message::new("VERIFY_PDF_NO_PAGES"));
+        }
+        return Ok(());
+    }
+
+    let bytes =
+        std::fs::read(path).map_err(|e| Message::new("FILE_READ_FAILED").arg(e.to_string()))?;
+    if webp_codec::is_animated(&bytes) {
+        webp_codec::decode_animation(&bytes).map(|_| ())
+    } else {
+        color::decode_image(&bytes, false).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+    use std::fs;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn truncated_output_is_flagged_as_verify_failed() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-verify-{nonce}"));
+        fs::create_dir_all(&root).expect("create root");
+
+        let output = root.join("photo.png");
+        RgbaImage::from_pixel(32, 32, Rgba([10, 20, 30, 255]))
+            .save(&output)
+            .expect("write png");
+        let mut result = StampFileResult {
+            input_path: "photo.png".to_string(),
+            ok: true,
+            output_path: Some(output.to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        verify_result(&mut result);
+        assert!(result.ok);
+
+        let bytes = fs::read(&output).expect("read png");
+        fs::write(&output, &bytes[..bytes.len() / 2]).expect("corrupt png");
+        verify_result(&mut result);
+        assert!(!result.ok);
+        assert_eq!(result.code.as_deref(), Some(CODE_VERIFY_FAILED));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,191 @@
+use crate::batch;
+use crate::i18n::Message;
+use crate::image_engine::{StampFileResult, StampSettingsInput};
+use crate::path_policy;
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// 복사 중인 파일을 읽지 않도록 마지막 변경 이후 이 시간만큼 기다린 뒤 처리합니다.
+const SETTLE_DELAY: Duration = Duration::from_millis(400);
+
+static ACTIVE_WATCHES: OnceLock<Mutex<HashMap<String, FolderWatch>>> = OnceLock::new();
+
+pub struct FolderWatch {
+    stop: Arc<AtomicBool>,
+    handle: JoinHandle<()>,
+}
+
+impl FolderWatch {
+    pub fn stop(self) {
+        self.stop.store(true, Ordering::SeqCst);
+        let _ = self.handle.join();
+    }
+}
+
+/// `dir`에 새로 들어오는 지원 파일을 감시해 도착하는 대로 로고를 삽입합니다.
+/// 하위 폴더(기본 출력 폴더 포함)는 감시하지 않습니다.
+pub fn start_watch(
+    dir: &Path,
+    settings: StampSettingsInput,
+    logo_path: PathBuf,
+    output_base_dir: Option<PathBuf>,
+    mut on_processed: impl FnMut(StampFileResult) + Send + 'static,
+) -> Result<FolderWatch, Message> {
+    if !dir.is_dir() {
+        return Err(Message::new("WATCH_DIR_MISSING"));
+    }
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)
+        .map_err(|e| Message::new("WATCH_START_FAILED").arg(e.to_string()))?;
+    watcher
+        .watch(dir, RecursiveMode::NonRecursive)
+        .map_err(|e| Message::new("WATCH_START_FAILED").arg(e.to_string()))?;
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let stop_flag = Arc::clone(&stop);
+
+    let handle = std::thread::spawn(move || {
+        // watcher가 살아 있어야 이벤트가 계속 들어옵니다.
+        let _watcher = watcher;
+        let mut pending = HashMap::<PathBuf, Instant>::new();
+        let mut processed = HashSet::<PathBuf>::new();
+
+        while !stop_flag.load(Ordering::SeqCst) {
+            match rx.recv_timeout(POLL_INTERVAL) {
+                Ok(Ok(event)) => {
+                    if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                        for path in event.paths {
+                            if is_watchable_file(&path) && !processed.contains(&path) {
+                                pending.insert(path, Instant::now());
+                            }
+                        }
+                    }
+                }
+                Ok(Err(_)) | Err(mpsc::RecvTimeoutError::Timeout) => {}
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            }
+
+            let ready: Vec<PathBuf> = pending
+                .iter()
+                .filter(|(_, seen)| seen.elapsed() >= SETTLE_DELAY)
+                .map(|(path, _)| path.clone())
+                .collect();
+
+            for path in ready {
+                pending.remove(&path);
+                processed.insert(path.clone());
+                let input = path.to_string_lossy().to_string();
+                let result = batch::stamp_single(
+                    &input,
+                    settings.clone(),
+                    &logo_path,
+                    output_base_dir.as_deref(),
+                );
+                on_processed(result);
+            }
+        }
+    });
+
+    Ok(FolderWatch { stop, handle })
+}
+
+/// 감시를 `watch_id`로 등록합니다. 같은 id가 이미 있으면 이전 감시를 멈춥니다.
+pub fn register(watch_id: String, watch: FolderWatch) {
+    let previous = registry()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(watch_id, watch);
+    if let Some(previous) = previous {
+        previous.stop();
+    }
+}
+
+pub fn stop(watch_id: &str) -> bool {
+    let watch = registry()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .remove(watch_id);
+    match watch {
+        Some(watch) => {
+            watch.stop();
+            true
+        }
+        None => false,
+    }
+}
+
+fn registry() -> &'static Mutex<HashMap<String, FolderWatch>> {
+    ACTIVE_WATCHES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn is_watchable_file(path: &Path) -> bool {
+    path.is_file()
+        && (path_policy::detect_supported_image(path).is_some()
+            || path_policy::is_supported_pdf(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::RgbaImage;
+    use std::fs;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn watch_stamps_new_file_and_reports_it() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("cornerbrand-watch-{nonce}"));
+        let watched = root.join("inbox");
+        fs::create_dir_all(&watched).expect("watched dir");
+
+        let logo_path = root.join("logo.png");
+        RgbaImage::from_pixel(8, 8, image::Rgba([255, 0, 0, 255]))
+            .save(&logo_path)
+            .expect("write logo");
+
+        let settings = StampSettingsInput {
+            position: "우하단".to_string(),
+            size_preset: "보통".to_string(),
+            margin_percent: 2.0,
+            ..Default::default()
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let watch = start_watch(&watched, settings, logo_path, None, move |result| {
+            let _ = tx.send(result);
+        })
+        .expect("start watch");
+
+        let input_path = watched.join("arrived.png");
+        RgbaImage::from_pixel(32, 32, image::Rgba([200, 200, 200, 255]))
+            .save(&input_path)
+            .expect("write input");
+
+        let result = rx
+            .recv_timeout(Duration::from_secs(10))
+            .expect("watch should report the new file");
+        watch.stop();
+
+        assert_eq!(result.input_path, input_path.to_string_lossy());
+        assert!(result.ok, "expected success: {:?}", result.error);
+        let output = PathBuf::from(result.output_path.expect("output path"));
+        assert!(output.exists(), "stamped output should exist");
+        assert!(
+            rx.try_recv().is_err(),
+            "the output written into the sub folder should not be picked up again"
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
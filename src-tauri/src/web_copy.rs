@@ -0,0 +1,106 @@
+use crate::i18n::Message;
+use crate::path_policy;
+use crate::retry::RetryPolicy;
+use crate::webp_codec::{self, WebpOptions};
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat};
+use serde::{Deserialize, Serialize};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+const DEFAULT_WEB_QUALITY: u8 = 85;
+
+/// 보관용 결과와 별도로 만들 웹용 축소본 설정입니다. 이미지에만 적용됩니다.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebCopySettings {
+    /// 긴 변의 최대 픽셀 수. 이보다 작은 이미지는 키우지 않습니다.
+    pub max_dimension: u32,
+    /// "jpeg", "png", "webp". 비우면 결과 파일과 같은 형식입니다.
+    #[serde(default)]
+    pub format: Option<String>,
+    /// JPEG/WebP 품질(1~100, 기본 85). PNG에는 쓰지 않습니다.
+    #[serde(default)]
+    pub quality: Option<u8>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct WebCopy {
+    max_dimension: u32,
+    format: Option<ImageFormat>,
+    quality: u8,
+}
+
+impl TryFrom<&WebCopySettings> for WebCopy {
+    type Error = Message;
+
+    fn try_from(value: &WebCopySettings) -> Result<Self, Self::Error> {
+        if value.max_dimension == 0 {
+            return Err(Message::new("WEB_COPY_MAX_INVALID"));
+        }
+        let format = match value.format.as_deref().map(str::trim) {
+            None | Some("") => None,
+            Some("jpeg") | Some("jpg") => Some(ImageFormat::Jpeg),
+            Some("png") => Some(ImageFormat::Png),
+            Some("webp") => Some(ImageFormat::WebP),
+            Some(_) => return Err(Message::new("INVALID_WEB_COPY_FORMAT")),
+        };
+        let quality = value.quality.unwrap_or(DEFAULT_WEB_QUALITY);
+        if !(1..=100).contains(&quality) {
+            return Err(Message::new("WEB_COPY_QUALITY_INVALID"));
+        }
+        Ok(Self {
+            max_dimension: value.max_dimension,
+            format,
+            quality,
+        })
+    }
+}
+
+impl WebCopy {
+    /// 로고를 넣은 이미지를 줄여 결과 폴더의 `web` 하위 폴더에 `<이름>_web.<확장자>`로 저장합니다.
+    pub(crate) fn write(
+        &self,
+        image: &DynamicImage,
+        input_path: &Path,
+        main_output: &Path,
+        output_format: ImageFormat,
+        retry: &RetryPolicy,
+    ) -> Result<PathBuf, Message> {
+        let format = self.format.unwrap_or(output_format);
+        let (width, height) = (image.width(), image.height());
+        let resized = if width.max(height) > self.max_dimension {
+            image.resize(self.max_dimension, self.max_dimension, FilterType::Lanczos3)
+        } else {
+            image.clone()
+        };
+
+        let bytes = self.encode(&resized, format)?;
+        let path = path_policy::reserve_web_copy_path(input_path, main_output, format)?;
+        if let Err(e) = retry.write(&path, &bytes) {
+            let _ = std::fs::remove_file(&path);
+            return Err(Message::new("WEB_COPY_SAVE_FAILED").arg(e));
+        }
+        Ok(path)
+    }
+
+    fn encode(&self, image: &DynamicImage, format: ImageFormat) -> Result<Vec<u8>, Message> {
+        let mut bytes = Vec::new();
+        match format {
+            ImageFormat::Jpeg => image
+                .to_rgb8()
+                .write_with_encoder(JpegEncoder::new_with_quality(&mut bytes, self.quality))
+                .map_err(|e| Message::new("WEB_COPY_ENCODE_FAILED").arg(e.to_string()))?,
+            ImageFormat::WebP => {
+                let options = WebpOptions::from_input(Some(false), Some(f32::from(self.quality)))?;
+                bytes = webp_codec::encode(&image.to_rgba8(), options)?;
+            }
+            format => image
+                .to_rgba8()
+                .write_to(&mut Cursor::new(&mut bytes), format)
+                .map_err(|e| Message::new("WEB_COPY_ENCODE_FAILED").arg(e.to_string()))?,
+        }
+        Ok(bytes)
+    }
+}
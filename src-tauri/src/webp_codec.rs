@@ -0,0 +1,119 @@
+use crate::i18n::Message;
+use image::RgbaImage;
+use webp::{AnimDecoder, AnimEncoder, AnimFrame, BitstreamFeatures, Encoder, WebPConfig};
+
+const DEFAULT_WEBP_QUALITY: f32 = 90.0;
+
+/// WebP 결과를 어떻게 압축할지 정합니다. 품질만 지정하면 손실 압축으로 봅니다.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct WebpOptions {
+    lossless: bool,
+    quality: f32,
+}
+
+impl WebpOptions {
+    pub(crate) fn from_input(
+        lossless: Option<bool>,
+        quality: Option<f32>,
+    ) -> Result<Self, Message> {
+        if let Some(quality) = quality {
+            if !quality.is_finite() || !(0.0..=100.0).contains(&quality) {
+                return Err(Message::new("WEBP_QUALITY_INVALID"));
+            }
+        }
+        Ok(Self {
+            lossless: lossless.unwrap_or(quality.is_none()),
+            quality: quality.unwrap_or(DEFAULT_WEBP_QUALITY),
+        })
+    }
+
+    fn config(self) -> Result<WebPConfig, Message> {
+        let mut config = WebPConfig::new().map_err(|_| Message::new("WEBP_CONFIG_FAILED"))?;
+        config.lossless = i32::from(self.lossless);
+        config.alpha_compression = i32::from(!self.lossless);
+        config.quality = self.quality;
+        Ok(config)
+    }
+}
+
+impl Default for WebpOptions {
+    fn default() -> Self {
+        Self {
+            lossless: true,
+            quality: DEFAULT_WEBP_QUALITY,
+        }
+    }
+}
+
+/// 프레임마다 (이미지, 표시 시작 시각 ms)를 담은 애니메이션 WebP입니다.
+pub(crate) struct AnimatedWebp {
+    pub frames: Vec<(RgbaImage, i32)>,
+    pub loop_count: u32,
+}
+
+pub(crate) fn is_animated(bytes: &[u8]) -> bool {
+    BitstreamFeatures::new(bytes).is_some_and(|features| features.has_animation())
+}
+
+pub(crate) fn encode(image: &RgbaImage, options: WebpOptions) -> Result<Vec<u8>, Message> {
+    let config = options.config()?;
+    let encoded = Encoder::from_rgba(image.as_raw(), image.width(), image.height())
+        .encode_advanced(&config)
+        .map_err(|e| Message::new("WEBP_ENCODE_FAILED").arg(format!("{e:?}")))?;
+    Ok(encoded.to_vec())
+}
+
+pub(crate) fn decode_animation(bytes: &[u8]) -> Result<AnimatedWebp, Message> {
+    let decoded = AnimDecoder::new(bytes)
+        .decode()
+        .map_err(|e| Message::new("ANIMATED_WEBP_READ_FAILED").arg(e))?;
+
+    // 디코더는 각 프레임이 끝나는 시각을 주므로, 앞 프레임의 끝을 시작 시각으로 씁니다.
+    let mut frames = Vec::with_capacity(decoded.len());
+    let mut start = 0;
+    for index in 0..decoded.len() {
+        let Some(frame) = decoded.get_frame(index) else {
+            continue;
+        };
+        let image = match image::DynamicImage::from(&frame) {
+            image::DynamicImage::ImageRgba8(image) => image,
+            other => other.into_rgba8(),
+        };
+        frames.push((image, start));
+        start = frame.get_time_ms();
+    }
+    if frames.is_empty() {
+        return Err(Message::new("ANIMATED_WEBP_NO_FRAMES"));
+    }
+
+    Ok(AnimatedWebp {
+        frames,
+        loop_count: decoded.loop_count,
+    })
+}
+
+pub(crate) fn encode_animation(
+    animation: &AnimatedWebp,
+    options: WebpOptions,
+) -> Result<Vec<u8>, Message> {
+    let (first, _) = animation
+        .frames
+        .first()
+        .ok_or_else(|| Message::new("ANIMATED_WEBP_NO_FRAMES"))?;
+    let config = options.config()?;
+
+    let mut encoder = AnimEncoder::new(first.width(), first.height(), &config);
+    encoder.set_loop_count(i32::try_from(animation.loop_count).unwrap_or(0));
+    for (image, start) in &animation.frames {
+        encoder.add_frame(AnimFrame::from_rgba(
+            image.as_raw(),
+            image.width(),
+            image.height(),
+            *start,
+        ));
+    }
+    let encoded = encoder
+        .try_encode()
+        .map_err(|e| Message::new("ANIMATED_WEBP_ENCODE_FAILED").arg(format!("{e:?}")))?;
+    Ok(encoded.to_vec())
+}
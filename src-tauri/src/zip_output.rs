@@ -0,0 +1,130 @@
+use crate::i18n::Message;
+use crate::path_policy::OUTPUT_DIR_NAME;
+use std::fs::{self, File, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Component, Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+pub const REPORT_ENTRY_NAME: &str = "cornerbrand_report.json";
+pub const MANIFEST_ENTRY_NAME: &str = "cornerbrand_manifest.json";
+
+/// 결과를 압축하기 전에 잠시 쓰는 폴더입니다. 같은 볼륨에 두려고 ZIP 파일 옆에 만듭니다.
+pub fn staging_dir(zip_path: &Path) -> PathBuf {
+    let nonce = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or_default();
+    let parent = zip_path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    parent.join(format!(".cornerbrand-zip-{nonce}"))
+}
+
+/// 입력 파일들이 든 폴더의 공통 상위 폴더입니다. 압축 안의 폴더 구조는 여기서부터 셉니다.
+pub fn common_input_root(inputs: &[String]) -> PathBuf {
+    let mut dirs = inputs.iter().map(|input| input_dir(input));
+    let Some(mut root) = dirs.next() else {
+        return PathBuf::new();
+    };
+    for dir in dirs {
+        while !dir.starts_with(&root) {
+            if !root.pop() {
+                return PathBuf::new();
+            }
+        }
+    }
+    root
+}
+
+/// 입력 파일 폴더를 `root` 기준 상대 경로로 바꿉니다. `..` 같은 이동은 빼서 임시 폴더 밖으로 나가지 않게 합니다.
+pub fn relative_input_dir(input: &str, root: &Path) -> PathBuf {
+    let dir = input_dir(input);
+    dir.strip_prefix(root)
+        .unwrap_or(&dir)
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part),
+            _ => None,
+        })
+        .collect()
+}
+
+/// 임시 폴더 안의 결과 파일을 압축 항목 이름(`/` 구분)으로 바꿉니다. 출력 폴더 이름은 뺍니다.
+pub fn entry_name(output: &Path, staging: &Path) -> Option<String> {
+    let relative = output.strip_prefix(staging).ok()?;
+    let parts: Vec<String> = relative
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(part) if part != OUTPUT_DIR_NAME => {
+                Some(part.to_string_lossy().to_string())
+            }
+            _ => None,
+        })
+        .collect();
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+/// ZIP 파일을 `create_new`로 만듭니다. 정확히 지정한 경로라 이미 있는 파일은 덮어쓰지 않고 오류로 돌려줍니다.
+pub fn create_zip(zip_path: &Path) -> Result<File, Message> {
+    if let Some(parent) = zip_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .map_err(|e| Message::new("ZIP_DIR_CREATE_FAILED").arg(e.to_string()))?;
+    }
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(zip_path)
+        .map_err(|e| match e.kind() {
+            ErrorKind::AlreadyExists => {
+                Message::new("ZIP_EXISTS").arg(zip_path.display().to_string())
+            }
+            _ => Message::new("ZIP_CREATE_FAILED").arg(e.to_string()),
+        })
+}
+
+/// `create_zip`으로 만든 파일에 `(항목 이름, 파일)`을 순서대로 담고, 그 뒤에 보고서·매니페스트·사이드카처럼
+/// 메모리에서 만든 `(항목 이름, 내용)`을 순서대로 추가합니다.
+pub fn write_zip(
+    file: File,
+    entries: &[(String, PathBuf)],
+    documents: &[(String, Vec<u8>)],
+) -> Result<(), Message> {
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default();
+
+    for (name, path) in entries {
+        let bytes = fs::read(path)
+            .map_err(|e| Message::new("ZIP_READ_OUTPUT_FAILED").arg(e.to_string()))?;
+        add_entry(&mut zip, name, &bytes, options)?;
+    }
+    for (name, bytes) in documents {
+        add_entry(&mut zip, name, bytes, options)?;
+    }
+
+    zip.finish()
+        .map_err(|e| Message::new("ZIP_FINISH_FAILED").arg(e.to_string()))?;
+    Ok(())
+}
+
+fn add_entry(
+    zip: &mut ZipWriter<File>,
+    name: &str,
+    bytes: &[u8],
+    options: SimpleFileOptions,
+) -> Result<(), Message> {
+    zip.start_file(name, options)
+        .and_then(|()| zip.write_all(bytes).map_err(Into::into))
+        .map_err(|e| {
+            Message::new("ZIP_ENTRY_FAILED")
+                .arg(name)
+                .arg(e.to_string())
+        })
+}
+
+fn input_dir(input: &str) -> PathBuf {
+    let path = std::path::absolute(input).unwrap_or_else(|_| PathBuf::from(input));
+    path.parent().map(Path::to_path_buf).unwrap_or_default()
+}
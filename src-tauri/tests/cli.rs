@@ -0,0 +1,57 @@
+use image::{Rgba, RgbaImage};
+use std::fs;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[test]
+fn cli_stamps_fixture_and_prints_json_results() {
+    let nonce = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("time")
+        .as_nanos();
+    let root = std::env::temp_dir().join(format!("cornerbrand-cli-{nonce}"));
+    let output_dir = root.join("out");
+    fs::create_dir_all(&output_dir).expect("temp dir");
+
+    let input = root.join("photo.png");
+    let logo = root.join("logo.png");
+    RgbaImage::from_pixel(64, 48, Rgba([240, 240, 240, 255]))
+        .save(&input)
+        .expect("input");
+    RgbaImage::from_pixel(8, 8, Rgba([255, 0, 0, 255]))
+        .save(&logo)
+        .expect("logo");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cornerbrand-cli"))
+        .arg("--paths")
+        .arg(&input)
+        .arg(root.join("missing.png"))
+        .arg("--logo")
+        .arg(&logo)
+        .args(["--position", "좌상단", "--size", "20", "--margin", "0"])
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .output()
+        .expect("run cli");
+
+    // 없는 파일이 하나 있으므로 실패 코드로 끝나지만 결과는 모두 출력합니다.
+    assert_eq!(output.status.code(), Some(1));
+    let results: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json stdout");
+    let results = results.as_array().expect("array");
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["ok"], true);
+    assert_eq!(results[1]["ok"], false);
+
+    let stamped = results[0]["outputPath"].as_str().expect("output path");
+    assert!(stamped.starts_with(output_dir.to_str().expect("utf-8")));
+    let stamped = image::open(stamped).expect("open output").to_rgba8();
+    assert_eq!(stamped.get_pixel(0, 0).0, [255, 0, 0, 255]);
+
+    let usage = Command::new(env!("CARGO_BIN_EXE_cornerbrand-cli"))
+        .arg("--paths")
+        .output()
+        .expect("run cli");
+    assert_eq!(usage.status.code(), Some(2));
+
+    let _ = fs::remove_dir_all(&root);
+}
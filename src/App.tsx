@@ -27,7 +27,7 @@ type BatchProgressEvent = {
   total: number;
   done: number;
   inputPath: string;
-  ok: boolean;
+  status: "ok" | "failed" | "skipped" | "cancelled";
 };
 
 type UpdateDownloadState = {